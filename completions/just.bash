@@ -20,19 +20,39 @@ _just() {
 
     case "${cmd}" in
         just)
-            opts=" -q -u -v -e -l -h -V -f -d -s  --dry-run --highlight --no-dotenv --no-highlight --quiet --clear-shell-args --unsorted --verbose --choose --dump --edit --evaluate --init --list --summary --variables --help --version --chooser --color --list-heading --list-prefix --justfile --set --shell --shell-arg --working-directory --completions --show  <ARGUMENTS>... "
+            opts=" -k -g -q -u -v -e -h -V -f -d -l -s  --check --dry-run --groups --highlight --keep-going --no-dependencies --no-dotenv --no-function-cache --no-highlight --global-justfile --quiet --safe --clear-shell-args --timestamps --timings --unsorted --verbose --yes --choose --dump --edit --evaluate --fmt --graph --init --stats --summary --variables --help --version --cache-dir --changed-since --chooser --color --dotenv-file --echo-truncate --error-context --focus --format --list-heading --list-prefix --log-json --justfile --report --search-boundary --search-from --set --shebang --shell --shell-arg --stats-file --tee --timestamp-format --working-directory --changed --command --complete --completions --list --set-env --show  <ARGUMENTS>... "
                 if [[ ${cur} == -* ]] ; then
                     COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
                     return 0
-                elif [[ ${COMP_CWORD} -eq 1 ]]; then
-                    local recipes=$(just --summary --color never 2> /dev/null)
-                    if [[ $? -eq 0 ]]; then
-                        COMPREPLY=( $(compgen -W "${recipes}" -- "${cur}") )
-                        return 0
-                    fi
+                fi
+
+                local completions
+                if completions=$(just --complete "${COMP_WORDS[@]:1:COMP_CWORD-1}" 2> /dev/null); then
+                    case "${completions%%$'\n'*}" in
+                        recipe)
+                            COMPREPLY=( $(compgen -W "$(tail -n +2 <<< "${completions}")" -- "${cur}") )
+                            return 0
+                            ;;
+                        path)
+                            COMPREPLY=( $(compgen -f -- "${cur}") )
+                            return 0
+                            ;;
+                        argument)
+                            COMPREPLY=()
+                            return 0
+                            ;;
+                    esac
                 fi
             case "${prev}" in
                 
+                --cache-dir)
+                    COMPREPLY=($(compgen -f "${cur}"))
+                    return 0
+                    ;;
+                --changed-since)
+                    COMPREPLY=($(compgen -f "${cur}"))
+                    return 0
+                    ;;
                 --chooser)
                     COMPREPLY=($(compgen -f "${cur}"))
                     return 0
@@ -41,6 +61,26 @@ _just() {
                     COMPREPLY=($(compgen -W "auto always never" -- "${cur}"))
                     return 0
                     ;;
+                --dotenv-file)
+                    COMPREPLY=($(compgen -f "${cur}"))
+                    return 0
+                    ;;
+                --echo-truncate)
+                    COMPREPLY=($(compgen -f "${cur}"))
+                    return 0
+                    ;;
+                --error-context)
+                    COMPREPLY=($(compgen -f "${cur}"))
+                    return 0
+                    ;;
+                --focus)
+                    COMPREPLY=($(compgen -f "${cur}"))
+                    return 0
+                    ;;
+                --format)
+                    COMPREPLY=($(compgen -W "json yaml dot" -- "${cur}"))
+                    return 0
+                    ;;
                 --list-heading)
                     COMPREPLY=($(compgen -f "${cur}"))
                     return 0
@@ -49,6 +89,10 @@ _just() {
                     COMPREPLY=($(compgen -f "${cur}"))
                     return 0
                     ;;
+                --log-json)
+                    COMPREPLY=($(compgen -f "${cur}"))
+                    return 0
+                    ;;
                 --justfile)
                     COMPREPLY=($(compgen -f "${cur}"))
                     return 0
@@ -57,10 +101,26 @@ _just() {
                     COMPREPLY=($(compgen -f "${cur}"))
                     return 0
                     ;;
+                --report)
+                    COMPREPLY=($(compgen -f "${cur}"))
+                    return 0
+                    ;;
+                --search-boundary)
+                    COMPREPLY=($(compgen -W "none worktree repository" -- "${cur}"))
+                    return 0
+                    ;;
+                --search-from)
+                    COMPREPLY=($(compgen -f "${cur}"))
+                    return 0
+                    ;;
                 --set)
                     COMPREPLY=($(compgen -f "${cur}"))
                     return 0
                     ;;
+                --shebang)
+                    COMPREPLY=($(compgen -f "${cur}"))
+                    return 0
+                    ;;
                 --shell)
                     COMPREPLY=($(compgen -f "${cur}"))
                     return 0
@@ -69,6 +129,18 @@ _just() {
                     COMPREPLY=($(compgen -f "${cur}"))
                     return 0
                     ;;
+                --stats-file)
+                    COMPREPLY=($(compgen -f "${cur}"))
+                    return 0
+                    ;;
+                --tee)
+                    COMPREPLY=($(compgen -f "${cur}"))
+                    return 0
+                    ;;
+                --timestamp-format)
+                    COMPREPLY=($(compgen -f "${cur}"))
+                    return 0
+                    ;;
                 --working-directory)
                     COMPREPLY=($(compgen -f "${cur}"))
                     return 0
@@ -77,10 +149,34 @@ _just() {
                     COMPREPLY=($(compgen -f "${cur}"))
                     return 0
                     ;;
+                --changed)
+                    COMPREPLY=($(compgen -f "${cur}"))
+                    return 0
+                    ;;
+                --command)
+                    COMPREPLY=($(compgen -f "${cur}"))
+                    return 0
+                    ;;
+                --complete)
+                    COMPREPLY=($(compgen -f "${cur}"))
+                    return 0
+                    ;;
                 --completions)
                     COMPREPLY=($(compgen -W "zsh bash fish powershell elvish" -- "${cur}"))
                     return 0
                     ;;
+                --list)
+                    COMPREPLY=($(compgen -f "${cur}"))
+                    return 0
+                    ;;
+                    -l)
+                    COMPREPLY=($(compgen -f "${cur}"))
+                    return 0
+                    ;;
+                --set-env)
+                    COMPREPLY=($(compgen -f "${cur}"))
+                    return 0
+                    ;;
                 --show)
                     COMPREPLY=($(compgen -f "${cur}"))
                     return 0
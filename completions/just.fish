@@ -1,39 +1,82 @@
 function __fish_just_complete_recipes
-    just --summary 2> /dev/null | tr " " "\n" || echo ""
+    set -l completions (just --complete (commandline -opc)[2..-1] 2> /dev/null)
+    if test "$completions[1]" = recipe
+        printf "%s\n" $completions[2..-1]
+    end
+end
+
+function __fish_just_complete_path
+    set -l completions (just --complete (commandline -opc)[2..-1] 2> /dev/null)
+    test "$completions[1]" = path
 end
 
 # don't suggest files right off
 complete -c just -n "__fish_is_first_arg" --no-files
 
+# complete paths for `[path]` parameters
+complete -c just -n "__fish_just_complete_path" --force-files
+
 # complete recipes
 complete -c just -a '(__fish_just_complete_recipes)'
 
 # autogenerated completions
+complete -c just -n "__fish_use_subcommand" -l cache-dir -d 'Cache recipe metadata for `--list`, `--summary`, and completions in <DIR>'
+complete -c just -n "__fish_use_subcommand" -l changed-since -d 'Compare against <REF> with `--changed`, instead of `HEAD`'
 complete -c just -n "__fish_use_subcommand" -l chooser -d 'Override binary invoked by `--choose`'
 complete -c just -n "__fish_use_subcommand" -l color -d 'Print colorful output' -r -f -a "auto always never"
+complete -c just -n "__fish_use_subcommand" -l dotenv-file -d 'Load environment variables from <FILE>, ignoring dotenv settings'
+complete -c just -n "__fish_use_subcommand" -l echo-truncate -d 'Truncate echoed recipe lines longer than <N> characters, or disable truncation if <N> is 0'
+complete -c just -n "__fish_use_subcommand" -l error-context -d 'Print <N> lines of source before and after errors'
+complete -c just -n "__fish_use_subcommand" -l focus -d 'Print only <RECIPE>, its dependents, and its dependencies with `--graph`'
+complete -c just -n "__fish_use_subcommand" -l format -d 'Print `--dump` or `--evaluate` output as `json` or `yaml`, or `--graph` output as `dot`' -r -f -a "json yaml dot"
 complete -c just -n "__fish_use_subcommand" -l list-heading -d 'Print <TEXT> before list'
 complete -c just -n "__fish_use_subcommand" -l list-prefix -d 'Print <TEXT> before each list item'
-complete -c just -n "__fish_use_subcommand" -s f -l justfile -d 'Use <JUSTFILE> as justfile'
+complete -c just -n "__fish_use_subcommand" -l log-json -d 'Write a JSON line to <PATH> for each recipe, line, and backtick run'
+complete -c just -n "__fish_use_subcommand" -s f -l justfile -d 'Use <JUSTFILE> as justfile, or `-` for stdin'
+complete -c just -n "__fish_use_subcommand" -l report -d 'Write a report of recipe runs to <PATH> in <FORMAT>. Supported formats: junit'
+complete -c just -n "__fish_use_subcommand" -l search-boundary -d 'Stop searching for a justfile at the root of a git submodule or worktree, with `worktree`, or of any git repository, with `repository`' -r -f -a "none worktree repository"
+complete -c just -n "__fish_use_subcommand" -l search-from -d 'Search for the justfile upwards from <DIR>, instead of the current directory'
 complete -c just -n "__fish_use_subcommand" -l set -d 'Override <VARIABLE> with <VALUE>'
+complete -c just -n "__fish_use_subcommand" -l shebang -d 'Run named recipes as scripts with <INTERPRETER>'
 complete -c just -n "__fish_use_subcommand" -l shell -d 'Invoke <SHELL> to run recipes'
 complete -c just -n "__fish_use_subcommand" -l shell-arg -d 'Invoke shell with <SHELL-ARG> as an argument'
-complete -c just -n "__fish_use_subcommand" -s d -l working-directory -d 'Use <WORKING-DIRECTORY> as working directory. --justfile must also be set'
+complete -c just -n "__fish_use_subcommand" -l stats-file -d 'Record recipe run statistics in <FILE>'
+complete -c just -n "__fish_use_subcommand" -l tee -d 'Copy the output of each recipe to a timestamped log file in <DIR>'
+complete -c just -n "__fish_use_subcommand" -l timestamp-format -d 'Use `strftime`-style <FORMAT> for `--timestamps`'
+complete -c just -n "__fish_use_subcommand" -s d -l working-directory -d 'Use <WORKING-DIRECTORY> as working directory. --justfile or --global-justfile must also be set'
+complete -c just -n "__fish_use_subcommand" -l changed -d 'Run recipes matching glob <PATTERN> whose `[inputs]` have changed according to git'
+complete -c just -n "__fish_use_subcommand" -l command -d 'Run <COMMAND> in the working directory with the justfile\'s exported variables and dotenv file'
+complete -c just -n "__fish_use_subcommand" -l complete -d 'Print the kind of word that may follow <WORDS>, and candidates for it, for completion scripts'
 complete -c just -n "__fish_use_subcommand" -l completions -d 'Print shell completion script for <SHELL>' -r -f -a "zsh bash fish powershell elvish"
-complete -c just -n "__fish_use_subcommand" -s s -l show -d 'Show information about <RECIPE>'
+complete -c just -n "__fish_use_subcommand" -s l -l list -d 'List available recipes and their arguments, or <RECIPE> and its dependencies'
+complete -c just -n "__fish_use_subcommand" -l set-env -d 'Set <KEY> to <VALUE> in the dotenv file, creating it if needed'
+complete -c just -n "__fish_use_subcommand" -s s -l show -d 'Show information about <RECIPE>, and any other recipes given as arguments, which may be globs'
+complete -c just -n "__fish_use_subcommand" -l check -d 'Check formatting with `--fmt` without rewriting the justfile'
 complete -c just -n "__fish_use_subcommand" -l dry-run -d 'Print what just would do without doing it'
+complete -c just -n "__fish_use_subcommand" -l groups -d 'List recipes under headings for their `[group]` attributes with `--list`'
 complete -c just -n "__fish_use_subcommand" -l highlight -d 'Highlight echoed recipe lines in bold'
+complete -c just -n "__fish_use_subcommand" -s k -l keep-going -d 'Run remaining recipes given on the command line after one fails'
+complete -c just -n "__fish_use_subcommand" -l no-dependencies -d 'Run recipes given on the command line without running their dependencies'
 complete -c just -n "__fish_use_subcommand" -l no-dotenv -d 'Don\'t load `.env` file'
+complete -c just -n "__fish_use_subcommand" -l no-function-cache -d 'Call functions which probe the system every time, instead of caching their results'
 complete -c just -n "__fish_use_subcommand" -l no-highlight -d 'Don\'t highlight echoed recipe lines in bold'
+complete -c just -n "__fish_use_subcommand" -s g -l global-justfile -d 'Use global justfile'
 complete -c just -n "__fish_use_subcommand" -s q -l quiet -d 'Suppress all output'
+complete -c just -n "__fish_use_subcommand" -l safe -d 'Refuse to evaluate backticks, read environment variables, or run recipes'
 complete -c just -n "__fish_use_subcommand" -l clear-shell-args -d 'Clear shell arguments'
-complete -c just -n "__fish_use_subcommand" -s u -l unsorted -d 'Return list and summary entries in source order'
+complete -c just -n "__fish_use_subcommand" -l timestamps -d 'Prefix echoed recipe lines with the time they were run'
+complete -c just -n "__fish_use_subcommand" -l timings -d 'Print how long each recipe took to run'
+complete -c just -n "__fish_use_subcommand" -s u -l unsorted -d 'List recipes and variables in source order instead of by name'
 complete -c just -n "__fish_use_subcommand" -s v -l verbose -d 'Use verbose output'
+complete -c just -n "__fish_use_subcommand" -l yes -d 'Run `[confirm]` recipes without asking for confirmation'
 complete -c just -n "__fish_use_subcommand" -l choose -d 'Select one or more recipes to run using a binary. If `--chooser` is not passed the chooser defaults to the value of $JUST_CHOOSER, falling back to `fzf`'
 complete -c just -n "__fish_use_subcommand" -l dump -d 'Print entire justfile'
 complete -c just -n "__fish_use_subcommand" -s e -l edit -d 'Edit justfile with editor given by $VISUAL or $EDITOR, falling back to `vim`'
-complete -c just -n "__fish_use_subcommand" -l evaluate -d 'Evaluate and print all variables. If positional arguments are present, only print the variables whose names are given as arguments.'
+complete -c just -n "__fish_use_subcommand" -l evaluate -d 'Evaluate and print all variables, or only those whose names are given as arguments. If a single name is given, print only its value.'
+complete -c just -n "__fish_use_subcommand" -l fmt -d 'Format justfile in place, preserving comments and blank lines'
+complete -c just -n "__fish_use_subcommand" -l graph -d 'Print recipe dependency graph'
 complete -c just -n "__fish_use_subcommand" -l init -d 'Initialize new justfile in project root'
-complete -c just -n "__fish_use_subcommand" -s l -l list -d 'List available recipes and their arguments'
+complete -c just -n "__fish_use_subcommand" -l stats -d 'Print recipe run counts and durations recorded with `--stats-file`'
 complete -c just -n "__fish_use_subcommand" -l summary -d 'List names of available recipes'
 complete -c just -n "__fish_use_subcommand" -l variables -d 'List names of variables'
 complete -c just -n "__fish_use_subcommand" -s h -l help -d 'Print help information'
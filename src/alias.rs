@@ -1,10 +1,14 @@
 use crate::common::*;
 
-/// An alias, e.g. `name := target`
+/// An alias, e.g. `name := target`, or, with fixed arguments which are
+/// passed to the target before any given on the command line,
+/// `name := target arguments...`
 #[derive(Debug, PartialEq)]
 pub(crate) struct Alias<'src, T = Rc<Recipe<'src>>> {
-  pub(crate) name:   Name<'src>,
-  pub(crate) target: T,
+  pub(crate) arguments:  Vec<AliasArgument<'src>>,
+  pub(crate) attributes: BTreeSet<Attribute<'src>>,
+  pub(crate) name:       Name<'src>,
+  pub(crate) target:     T,
 }
 
 impl<'src> Alias<'src, Name<'src>> {
@@ -12,10 +16,18 @@ impl<'src> Alias<'src, Name<'src>> {
     self.name.line
   }
 
-  pub(crate) fn resolve(self, target: Rc<Recipe<'src>>) -> Alias<'src> {
-    assert_eq!(self.target.lexeme(), target.name.lexeme());
-
+  /// Resolve to `target`, which is the recipe named by this alias's target,
+  /// or, if the target is another alias, the recipe it resolves to. In that
+  /// case, `arguments` are the arguments of every alias along the way, in the
+  /// order they are passed to the recipe.
+  pub(crate) fn resolve(
+    self,
+    target: Rc<Recipe<'src>>,
+    arguments: Vec<AliasArgument<'src>>,
+  ) -> Alias<'src> {
     Alias {
+      arguments,
+      attributes: self.attributes,
       name: self.name,
       target,
     }
@@ -24,7 +36,26 @@ impl<'src> Alias<'src, Name<'src>> {
 
 impl Alias<'_> {
   pub(crate) fn is_private(&self) -> bool {
-    self.name.lexeme().starts_with('_')
+    self.name.lexeme().starts_with('_') || self.attributes.contains(&Attribute::Private)
+  }
+
+  /// The values of the alias's fixed arguments
+  pub(crate) fn argument_values(&self) -> Vec<&str> {
+    self
+      .arguments
+      .iter()
+      .map(|argument| argument.value.as_str())
+      .collect()
+  }
+}
+
+impl<'src, T> Alias<'src, T> {
+  fn fmt_arguments(&self, f: &mut Formatter) -> fmt::Result {
+    for argument in &self.arguments {
+      write!(f, " {}", argument)?;
+    }
+
+    Ok(())
   }
 }
 
@@ -36,12 +67,18 @@ impl<'src, T> Keyed<'src> for Alias<'src, T> {
 
 impl<'src> Display for Alias<'src, Name<'src>> {
   fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    if !self.attributes.is_empty() {
+      writeln!(f, "{}", Attribute::list(&self.attributes))?;
+    }
+
     write!(
       f,
       "alias {} := {}",
       self.name.lexeme(),
       self.target.lexeme()
-    )
+    )?;
+
+    self.fmt_arguments(f)
   }
 }
 
@@ -52,6 +89,8 @@ impl<'src> Display for Alias<'src> {
       "alias {} := {}",
       self.name.lexeme(),
       self.target.name.lexeme()
-    )
+    )?;
+
+    self.fmt_arguments(f)
   }
 }
@@ -0,0 +1,17 @@
+use crate::common::*;
+
+/// A fixed argument passed to the target of an alias, e.g. `--release` in
+/// `alias t := test --release`
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) struct AliasArgument<'src> {
+  /// The argument as written in the justfile, including any quotes
+  pub(crate) lexeme: &'src str,
+  /// The argument as it is passed to the recipe
+  pub(crate) value:  String,
+}
+
+impl Display for AliasArgument<'_> {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(f, "{}", self.lexeme)
+  }
+}
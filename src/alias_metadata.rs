@@ -0,0 +1,47 @@
+use crate::common::*;
+
+/// The parts of an `Alias` needed to list it
+#[derive(Debug, PartialEq)]
+pub(crate) struct AliasMetadata {
+  pub(crate) name:      String,
+  pub(crate) target:    String,
+  /// Fixed arguments, as written in the justfile
+  pub(crate) arguments: Vec<String>,
+}
+
+impl AliasMetadata {
+  /// What the alias runs, e.g. `test --release`
+  pub(crate) fn invocation(&self) -> String {
+    iter::once(self.target.as_str())
+      .chain(self.arguments.iter().map(String::as_str))
+      .collect::<Vec<&str>>()
+      .join(" ")
+  }
+
+  /// The parameters of `recipe`, the alias's target, which aren't filled by
+  /// its fixed arguments. A variadic parameter can always take more
+  /// arguments, so it is never skipped.
+  pub(crate) fn parameters<'a>(&self, recipe: &'a RecipeMetadata) -> &'a [ParameterMetadata] {
+    let singular = recipe
+      .parameters
+      .iter()
+      .filter(|parameter| parameter.prefix.is_none())
+      .count();
+
+    &recipe.parameters[cmp::min(self.arguments.len(), singular)..]
+  }
+}
+
+impl<'src> From<&Alias<'src>> for AliasMetadata {
+  fn from(alias: &Alias<'src>) -> Self {
+    Self {
+      name:      alias.name.lexeme().to_owned(),
+      target:    alias.target.name().to_owned(),
+      arguments: alias
+        .arguments
+        .iter()
+        .map(|argument| argument.lexeme.to_owned())
+        .collect(),
+    }
+  }
+}
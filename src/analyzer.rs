@@ -19,18 +19,22 @@ impl<'src> Analyzer<'src> {
     mut self,
     module: Module<'src>,
   ) -> CompilationResult<'src, Justfile<'src>> {
+    let integers = Self::integer_variables(&module.items);
+
     for item in module.items {
       match item {
         Item::Alias(alias) => {
           self.analyze_alias(&alias)?;
           self.aliases.insert(alias);
         },
-        Item::Assignment(assignment) => {
+        Item::Assignment(mut assignment) => {
           self.analyze_assignment(&assignment)?;
+          assignment.value.resolve_additions(&integers);
           self.assignments.insert(assignment);
         },
-        Item::Recipe(recipe) => {
+        Item::Recipe(mut recipe) => {
           self.analyze_recipe(&recipe)?;
+          Self::resolve_recipe_additions(&mut recipe, &integers);
           self.recipes.insert(recipe);
         },
         Item::Set(set) => {
@@ -56,30 +60,123 @@ impl<'src> Analyzer<'src> {
       }
     }
 
+    let mut targets = BTreeMap::new();
+    for alias in self.aliases.values() {
+      targets.insert(
+        alias.name.lexeme(),
+        Self::alias_target(&recipes, &self.aliases, alias)?,
+      );
+    }
+
     let mut aliases = Table::new();
     while let Some(alias) = self.aliases.pop() {
-      aliases.insert(Self::resolve_alias(&recipes, alias)?);
+      let (target, arguments) = targets.remove(alias.name.lexeme()).unwrap();
+      aliases.insert(Self::resolve_alias(&recipes, alias, target, arguments)?);
     }
 
     let mut settings = Settings::new();
 
     for (_, set) in self.sets {
       match set.value {
+        Setting::AllowDownloads(allow_downloads) => {
+          settings.allow_downloads = allow_downloads;
+        },
+        Setting::BoolExpression(expression) => {
+          if let Some(variable) = expression.variables().next() {
+            return Err(variable.error(SettingVariable {
+              setting:  set.name.lexeme(),
+              variable: variable.lexeme(),
+            }));
+          }
+          settings.expressions.push((set.name, expression));
+        },
+        Setting::BacktickEnv(backtick_env) => {
+          settings.backtick_env = backtick_env;
+        },
+        Setting::Default(default) => {
+          let (recipe, fixed) = match recipes.get(default.cooked.as_str()) {
+            Some(recipe) => (recipe, 0),
+            None => match aliases.get(default.cooked.as_str()) {
+              Some(alias) => (&alias.target, alias.arguments.len()),
+              None =>
+                return Err(set.name.error(UnknownDefaultRecipe {
+                  recipe: default.cooked,
+                })),
+            },
+          };
+
+          let min_arguments = recipe.min_arguments().saturating_sub(fixed);
+          if min_arguments > 0 {
+            return Err(set.name.error(DefaultRecipeRequiresArguments {
+              recipe: recipe.name(),
+              min_arguments,
+            }));
+          }
+
+          settings.default = Some(default.cooked);
+        },
+        Setting::DotenvFiles(dotenv_files) => {
+          settings.dotenv_files = Some(dotenv_files);
+        },
         Setting::DotenvLoad(dotenv_load) => {
           settings.dotenv_load = Some(dotenv_load);
         },
+        Setting::EchoTruncate(echo_truncate) => {
+          settings.echo_truncate = Some(echo_truncate);
+        },
+        Setting::ExitMessage(exit_message) => {
+          settings.exit_message = exit_message;
+        },
+        Setting::ExitMessageTemplate(exit_message_template) => {
+          settings.exit_message_template = Some(exit_message_template.cooked);
+        },
         Setting::Export(export) => {
           settings.export = export;
         },
+        Setting::ListHeading(list_heading) => {
+          settings.list_heading = Some(list_heading.cooked);
+        },
+        Setting::ListPrefix(list_prefix) => {
+          settings.list_prefix = Some(list_prefix.cooked);
+        },
+        Setting::MetadataSideEffects(metadata_side_effects) => {
+          settings.metadata_side_effects = metadata_side_effects;
+        },
+        Setting::NoExitMessage(no_exit_message) => {
+          settings.no_exit_message = no_exit_message;
+        },
+        Setting::Pager(pager) => {
+          settings.pager = pager;
+        },
         Setting::Shell(shell) => {
           assert!(settings.shell.is_none());
           settings.shell = Some(shell);
         },
+        Setting::StrictInterpolation(strict_interpolation) => {
+          settings.strict_interpolation = strict_interpolation;
+        },
+        Setting::Tempdir(tempdir) => {
+          settings.tempdir = Some(tempdir.cooked);
+        },
+        Setting::Timestamps(timestamps) => {
+          settings.timestamps = timestamps;
+        },
+        Setting::UnknownRecipe(unknown_recipe) => {
+          settings.unknown_recipe = unknown_recipe;
+        },
+        Setting::WindowsPowershell(windows_powershell) => {
+          settings.windows_powershell = windows_powershell;
+        },
+        Setting::WindowsShell(windows_shell) => {
+          assert!(settings.windows_shell.is_none());
+          settings.windows_shell = Some(windows_shell);
+        },
       }
     }
 
     Ok(Justfile {
       warnings: module.warnings,
+      overrides: module.overrides,
       aliases,
       assignments,
       recipes,
@@ -87,6 +184,51 @@ impl<'src> Analyzer<'src> {
     })
   }
 
+  /// Names of variables whose values are integers, so that `+` adds them.
+  /// A variable's value may be a sum of other such variables, so this is
+  /// repeated until no more are found.
+  fn integer_variables(items: &[Item<'src>]) -> BTreeSet<&'src str> {
+    let mut integers = BTreeSet::new();
+
+    loop {
+      let found = integers.len();
+
+      for item in items {
+        if let Item::Assignment(assignment) = item {
+          if assignment.value.is_integer_valued(&integers) {
+            integers.insert(assignment.name.lexeme());
+          }
+        }
+      }
+
+      if integers.len() == found {
+        return integers;
+      }
+    }
+  }
+
+  fn resolve_recipe_additions(recipe: &mut UnresolvedRecipe<'src>, integers: &BTreeSet<&str>) {
+    for parameter in &mut recipe.parameters {
+      if let Some(default) = &mut parameter.default {
+        default.resolve_additions(integers);
+      }
+    }
+
+    for dependency in &mut recipe.dependencies {
+      for argument in &mut dependency.arguments {
+        argument.resolve_additions(integers);
+      }
+    }
+
+    for line in &mut recipe.body {
+      for fragment in &mut line.fragments {
+        if let Fragment::Interpolation { expression } = fragment {
+          expression.resolve_additions(integers);
+        }
+      }
+    }
+  }
+
   fn analyze_recipe(&self, recipe: &UnresolvedRecipe<'src>) -> CompilationResult<'src, ()> {
     if let Some(original) = self.recipes.get(recipe.name.lexeme()) {
       return Err(recipe.name.token().error(DuplicateRecipe {
@@ -172,9 +314,45 @@ impl<'src> Analyzer<'src> {
     Ok(())
   }
 
+  /// Follow `alias` through any aliases it targets, returning the name of the
+  /// recipe it ultimately refers to, and the fixed arguments of every alias
+  /// along the way, in the order they are passed to the recipe
+  fn alias_target(
+    recipes: &Table<'src, Rc<Recipe<'src>>>,
+    aliases: &Table<'src, Alias<'src, Name<'src>>>,
+    alias: &Alias<'src, Name<'src>>,
+  ) -> CompilationResult<'src, (&'src str, Vec<AliasArgument<'src>>)> {
+    let mut circle = vec![alias.name.lexeme()];
+    let mut target = alias.target.lexeme();
+    let mut arguments = alias.arguments.clone();
+
+    while !recipes.contains_key(target) {
+      if circle.contains(&target) {
+        circle.push(target);
+        return Err(alias.name.token().error(CircularAlias {
+          alias: alias.name.lexeme(),
+          circle,
+        }));
+      }
+
+      match aliases.get(target) {
+        Some(next) => {
+          circle.push(target);
+          target = next.target.lexeme();
+          arguments.splice(0..0, next.arguments.iter().cloned());
+        },
+        None => break,
+      }
+    }
+
+    Ok((target, arguments))
+  }
+
   fn resolve_alias(
     recipes: &Table<'src, Rc<Recipe<'src>>>,
     alias: Alias<'src, Name<'src>>,
+    target: &'src str,
+    arguments: Vec<AliasArgument<'src>>,
   ) -> CompilationResult<'src, Alias<'src>> {
     let token = alias.name.token();
     // Make sure the alias doesn't conflict with any recipe
@@ -186,11 +364,23 @@ impl<'src> Analyzer<'src> {
     }
 
     // Make sure the target recipe exists
-    match recipes.get(alias.target.lexeme()) {
-      Some(target) => Ok(alias.resolve(Rc::clone(target))),
+    match recipes.get(target) {
+      Some(recipe) => {
+        // Make sure the recipe can take the alias's fixed arguments
+        if arguments.len() > recipe.max_arguments() {
+          return Err(token.error(AliasArgumentCountMismatch {
+            alias:  alias.name.lexeme(),
+            recipe: recipe.name(),
+            found:  arguments.len(),
+            max:    recipe.max_arguments(),
+          }));
+        }
+
+        Ok(alias.resolve(Rc::clone(recipe), arguments))
+      },
       None => Err(token.error(UnknownAliasTarget {
-        alias:  alias.name.lexeme(),
-        target: alias.target.lexeme(),
+        alias: alias.name.lexeme(),
+        target,
       })),
     }
   }
@@ -200,6 +390,46 @@ impl<'src> Analyzer<'src> {
 mod tests {
   use super::*;
 
+  analysis_error! {
+    name: setting_variable,
+    input: "x := 'true'\nset export := x == 'true'",
+    offset: 26,
+    line: 1,
+    column: 14,
+    width: 1,
+    kind: SettingVariable { setting: "export", variable: "x" },
+  }
+
+  analysis_error! {
+    name: unknown_default_recipe,
+    input: "set default := 'build'\ntest:",
+    offset: 4,
+    line: 0,
+    column: 4,
+    width: 7,
+    kind: UnknownDefaultRecipe { recipe: "build".to_owned() },
+  }
+
+  analysis_error! {
+    name: default_recipe_requires_arguments,
+    input: "set default := 'build'\nbuild target:",
+    offset: 4,
+    line: 0,
+    column: 4,
+    width: 7,
+    kind: DefaultRecipeRequiresArguments { recipe: "build", min_arguments: 1 },
+  }
+
+  analysis_error! {
+    name: default_recipe_alias_requires_arguments,
+    input: "set default := 'b'\nalias b := build\nbuild target:",
+    offset: 4,
+    line: 0,
+    column: 4,
+    width: 7,
+    kind: DefaultRecipeRequiresArguments { recipe: "build", min_arguments: 1 },
+  }
+
   analysis_error! {
     name: duplicate_alias,
     input: "alias foo := bar\nalias foo := baz",
@@ -220,6 +450,46 @@ mod tests {
     kind: UnknownAliasTarget {alias: "foo", target: "bar"},
   }
 
+  analysis_error! {
+    name: unknown_alias_target_through_alias,
+    input: "alias a := b\nalias b := c\n",
+    offset: 6,
+    line: 0,
+    column: 6,
+    width: 1,
+    kind: UnknownAliasTarget {alias: "a", target: "c"},
+  }
+
+  analysis_error! {
+    name: circular_alias,
+    input: "alias a := b\nalias b := a\n",
+    offset: 6,
+    line: 0,
+    column: 6,
+    width: 1,
+    kind: CircularAlias {alias: "a", circle: vec!["a", "b", "a"]},
+  }
+
+  analysis_error! {
+    name: alias_argument_count_mismatch,
+    input: "alias b := a -v\nalias c := b x\na x:\n",
+    offset: 22,
+    line: 1,
+    column: 6,
+    width: 1,
+    kind: AliasArgumentCountMismatch {alias: "c", recipe: "a", found: 2, max: 1},
+  }
+
+  analysis_error! {
+    name: self_alias,
+    input: "alias a := a\n",
+    offset: 6,
+    line: 0,
+    column: 6,
+    width: 1,
+    kind: CircularAlias {alias: "a", circle: vec!["a", "a"]},
+  }
+
   analysis_error! {
     name: alias_shadows_recipe_before,
     input: "bar: \n  echo bar\nalias foo := bar\nfoo:\n  echo foo",
@@ -0,0 +1,384 @@
+//! Running recipes from Rust, for build scripts and test harnesses.
+//!
+//! This module is only available with the `api` feature. The contents of this
+//! module are not bound by any stability guarantees. Breaking changes may be
+//! introduced at any time.
+//!
+//! The main entry point into this module is the `run` function, which runs a
+//! recipe in a justfile in-process, as if it had been run with `just --justfile
+//! PATH RECIPE ARGUMENTS...`, and returns a `RunOutput` with the recipe's exit
+//! status, along with its output and the lines that just echoed, which are
+//! captured instead of being written to stdout and stderr.
+//!
+//! Environment variables which configure the command line, like
+//! `JUST_DRY_RUN`, are ignored, so that recipes run the same way regardless of
+//! the environment of the calling process.
+
+use crate::common::*;
+
+use clap::ErrorKind;
+
+/// Options for `run`, which correspond to command line flags and options
+#[derive(Debug, Clone, PartialEq)]
+pub struct Options {
+  /// Override variables in the justfile, like `--set NAME VALUE`
+  pub overrides:         BTreeMap<String, String>,
+  /// Use this working directory, instead of the directory containing the
+  /// justfile, like `--working-directory`
+  pub working_directory: Option<PathBuf>,
+  /// Print what would be run instead of running it, like `--dry-run`
+  pub dry_run:           bool,
+  /// Load environment files, unless false, like `--no-dotenv`
+  pub load_dotenv:       bool,
+  /// Suppress all output, like `--quiet`
+  pub quiet:             bool,
+  /// Echo all recipe lines, like `--verbose`
+  pub verbose:           bool,
+}
+
+impl Default for Options {
+  fn default() -> Self {
+    Self {
+      overrides:         BTreeMap::new(),
+      working_directory: None,
+      dry_run:           false,
+      load_dotenv:       true,
+      quiet:             false,
+      verbose:           false,
+    }
+  }
+}
+
+/// The result of running a recipe with `run`
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunOutput {
+  /// Standard output of the recipe's commands
+  pub stdout: String,
+  /// Standard error of the recipe's commands, interleaved with lines echoed
+  /// by just and any error message
+  pub stderr: String,
+  /// The exit code that `just` would have exited with
+  pub status: i32,
+}
+
+impl RunOutput {
+  /// Whether the recipe ran successfully
+  pub fn success(&self) -> bool {
+    self.status == EXIT_SUCCESS
+  }
+}
+
+/// Run `recipe` in `justfile`, with `arguments` as its arguments, capturing
+/// its output
+pub fn run(justfile: &Path, recipe: &str, arguments: &[&str], options: &Options) -> RunOutput {
+  let capture = Capture::default();
+
+  let status = match run_captured(&capture, justfile, recipe, arguments, options) {
+    Ok(()) => EXIT_SUCCESS,
+    Err(code) => code,
+  };
+
+  let (stdout, stderr) = capture.text();
+
+  RunOutput {
+    stdout,
+    stderr,
+    status,
+  }
+}
+
+fn run_captured(
+  capture: &Capture,
+  justfile: &Path,
+  recipe: &str,
+  arguments: &[&str],
+  options: &Options,
+) -> Result<(), i32> {
+  let mut args = vec![
+    OsString::from("just"),
+    "--color".into(),
+    "never".into(),
+    "--justfile".into(),
+    justfile.into(),
+  ];
+
+  if let Some(working_directory) = &options.working_directory {
+    args.push("--working-directory".into());
+    args.push(working_directory.into());
+  }
+
+  for (flag, enabled) in &[
+    ("--dry-run", options.dry_run),
+    ("--no-dotenv", !options.load_dotenv),
+    ("--quiet", options.quiet),
+    ("--verbose", options.verbose),
+  ] {
+    if *enabled {
+      args.push(flag.into());
+    }
+  }
+
+  for (name, value) in &options.overrides {
+    args.push("--set".into());
+    args.push(name.into());
+    args.push(value.into());
+  }
+
+  args.push("--".into());
+  args.push(recipe.into());
+  args.extend(arguments.iter().map(OsString::from));
+
+  let matches = Config::app().get_matches_from_safe(args).map_err(|error| {
+    if error.kind != ErrorKind::HelpDisplayed && error.kind != ErrorKind::VersionDisplayed {
+      capture.eprintln(&error.message);
+    }
+    EXIT_FAILURE
+  })?;
+
+  let config = Config::from_matches(&matches).map_err(|error| capture.error(error, 0))?;
+
+  let (arguments, overrides) = match &config.subcommand {
+    Subcommand::Run {
+      arguments,
+      overrides,
+    } => (arguments, overrides),
+    _ =>
+      return Err(capture.error(
+        ConfigError::internal("recipe arguments parsed as subcommand"),
+        0,
+      )),
+  };
+
+  let search = Search::find(
+    &config.search_config,
+    config.search_boundary,
+    &config.invocation_directory,
+  )
+  .eprint_to(Some(capture), config.color, config.error_context)?;
+
+  let (src, local) = config.load(&search, Some(capture))?;
+
+  let justfile = config.prepare(&src, local.as_deref(), &search, Some(capture))?;
+
+  justfile
+    .run(
+      &config,
+      &search,
+      overrides,
+      arguments,
+      Some(capture.clone()),
+    )
+    .map_err(|error| {
+      if config.verbosity.quiet() {
+        error.code()
+      } else {
+        capture.error(error, config.error_context)
+      }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn run(justfile: &str, recipe: &str, arguments: &[&str], options: &Options) -> RunOutput {
+    let tempdir = tempfile::tempdir().unwrap();
+    let path = tempdir.path().join("justfile");
+    fs::write(&path, unindent(justfile)).unwrap();
+    super::run(&path, recipe, arguments, options)
+  }
+
+  #[test]
+  fn output_is_captured() {
+    let output = run(
+      "
+        foo x:
+          echo {{x}}
+          @echo error 1>&2
+      ",
+      "foo",
+      &["bar"],
+      &Options::default(),
+    );
+
+    assert_eq!(output, RunOutput {
+      stdout: "bar\n".to_owned(),
+      stderr: "echo bar\nerror\n".to_owned(),
+      status: EXIT_SUCCESS,
+    });
+    assert!(output.success());
+  }
+
+  #[test]
+  fn dependencies_are_run() {
+    let output = run(
+      "
+        foo: bar
+          @echo foo
+        bar:
+          @echo bar
+      ",
+      "foo",
+      &[],
+      &Options::default(),
+    );
+
+    assert_eq!(output.stdout, "bar\nfoo\n");
+  }
+
+  #[test]
+  fn filter_output_is_captured() {
+    let output = run(
+      "
+        [filter('tr a-z A-Z')]
+        foo:
+          @echo foo
+      ",
+      "foo",
+      &[],
+      &Options::default(),
+    );
+
+    assert_eq!(output.stdout, "FOO\n");
+    assert!(output.success());
+  }
+
+  #[test]
+  fn failure() {
+    let output = run(
+      "
+        foo:
+          @exit 3
+      ",
+      "foo",
+      &[],
+      &Options::default(),
+    );
+
+    assert_eq!(output, RunOutput {
+      stdout: String::new(),
+      stderr: "error: Recipe `foo` failed on line 2 with exit code 3\n".to_owned(),
+      status: 3,
+    });
+    assert!(!output.success());
+  }
+
+  #[test]
+  fn unknown_recipe() {
+    let output = run("foo:", "bar", &[], &Options::default());
+
+    assert_eq!(
+      output.stderr,
+      "error: Justfile does not contain recipe `bar`.\n"
+    );
+    assert_eq!(output.status, EXIT_FAILURE);
+  }
+
+  #[test]
+  fn compilation_error() {
+    let output = run("foo: bar", "foo", &[], &Options::default());
+
+    assert_eq!(
+      output.stderr,
+      "error: Recipe `foo` has unknown dependency `bar`\n  |\n1 | foo: bar\n  |      ^^^\n"
+    );
+    assert_eq!(output.status, EXIT_FAILURE);
+  }
+
+  #[test]
+  fn recipe_named_like_a_flag() {
+    let output = run("foo:", "--help", &[], &Options::default());
+
+    assert_eq!(
+      output.stderr,
+      "error: Justfile does not contain recipe `--help`.\n"
+    );
+  }
+
+  #[test]
+  fn options() {
+    let output = run(
+      "
+        x := 'a'
+
+        foo:
+          echo {{x}}
+      ",
+      "foo",
+      &[],
+      &Options {
+        overrides: vec![("x".to_owned(), "b".to_owned())].into_iter().collect(),
+        dry_run: true,
+        ..Options::default()
+      },
+    );
+
+    assert_eq!(output.stdout, "");
+    assert_eq!(output.stderr, "echo b\n");
+    assert!(output.success());
+  }
+
+  #[test]
+  fn quiet() {
+    let output = run(
+      "
+        foo:
+          echo foo
+          @exit 1
+      ",
+      "foo",
+      &[],
+      &Options {
+        quiet: true,
+        ..Options::default()
+      },
+    );
+
+    assert_eq!(output.stdout, "");
+    assert_eq!(output.stderr, "");
+    assert_eq!(output.status, 1);
+  }
+
+  #[test]
+  fn working_directory() {
+    let tempdir = tempfile::tempdir().unwrap();
+
+    let output = run("foo:\n  @pwd", "foo", &[], &Options {
+      working_directory: Some(tempdir.path().to_owned()),
+      ..Options::default()
+    });
+
+    assert_eq!(
+      PathBuf::from(output.stdout.trim_end())
+        .canonicalize()
+        .unwrap(),
+      tempdir.path().canonicalize().unwrap()
+    );
+  }
+
+  #[test]
+  fn local_justfile() {
+    let tempdir = tempfile::tempdir().unwrap();
+    let path = tempdir.path().join("justfile");
+    fs::write(&path, "foo:\n  @echo justfile").unwrap();
+    fs::write(tempdir.path().join("justfile.local"), "foo:\n  @echo local").unwrap();
+
+    let output = super::run(&path, "foo", &[], &Options::default());
+
+    assert_eq!(output.stdout, "local\n");
+    assert!(output.success());
+  }
+
+  #[test]
+  fn missing_justfile() {
+    let tempdir = tempfile::tempdir().unwrap();
+    let path = tempdir.path().join("justfile");
+
+    let output = super::run(&path, "foo", &[], &Options::default());
+
+    assert!(output
+      .stderr
+      .starts_with("error: Failed to read justfile at `"));
+    assert_eq!(output.status, EXIT_FAILURE);
+  }
+}
@@ -72,7 +72,19 @@ impl<'src: 'run, 'run> AssignmentResolver<'src, 'run> {
         } else if self.assignments.contains_key(variable) {
           self.resolve_assignment(variable)
         } else {
-          Err(name.token().error(UndefinedVariable { variable }))
+          Err(
+            name.token().error(UndefinedVariable {
+              variable,
+              suggestion: Suggestion::closest(
+                variable,
+                self
+                  .assignments
+                  .keys()
+                  .map(|name| Suggestion::Variable { name })
+                  .chain(Suggestion::function(variable)),
+              ),
+            }),
+          )
         }
       },
       Expression::Call { thunk } => match thunk {
@@ -82,8 +94,31 @@ impl<'src: 'run, 'run> AssignmentResolver<'src, 'run> {
           self.resolve_expression(a)?;
           self.resolve_expression(b)
         },
+        Thunk::Ternary {
+          args: [a, b, c], ..
+        } => {
+          self.resolve_expression(a)?;
+          self.resolve_expression(b)?;
+          self.resolve_expression(c)
+        },
+        Thunk::UnaryOpt { args: (a, b), .. } => {
+          self.resolve_expression(a)?;
+          if let Some(b) = b {
+            self.resolve_expression(b)?;
+          }
+          Ok(())
+        },
+        Thunk::Nary { args, .. } => {
+          for arg in args {
+            self.resolve_expression(arg)?;
+          }
+          Ok(())
+        },
       },
-      Expression::Concatination { lhs, rhs } => {
+      Expression::Arithmetic { lhs, rhs, .. }
+      | Expression::Coalesce { lhs, rhs, .. }
+      | Expression::Comparison { lhs, rhs, .. }
+      | Expression::Concatination { lhs, rhs, .. } => {
         self.resolve_expression(lhs)?;
         self.resolve_expression(rhs)
       },
@@ -99,7 +134,10 @@ impl<'src: 'run, 'run> AssignmentResolver<'src, 'run> {
         self.resolve_expression(then)?;
         self.resolve_expression(otherwise)
       },
-      Expression::StringLiteral { .. } | Expression::Backtick { .. } => Ok(()),
+      Expression::StringLiteral { .. }
+      | Expression::Backtick { .. }
+      | Expression::Boolean { .. }
+      | Expression::Integer { .. } => Ok(()),
       Expression::Group { contents } => self.resolve_expression(contents),
     }
   }
@@ -136,7 +174,27 @@ mod tests {
     line:   0,
     column: 5,
     width:  2,
-    kind:   UndefinedVariable{variable: "yy"},
+    kind:   UndefinedVariable{variable: "yy", suggestion: None},
+  }
+
+  analysis_error! {
+    name:   unknown_expression_variable_suggests_variable,
+    input:  "version := '1'\nx := verison",
+    offset: 20,
+    line:   1,
+    column: 5,
+    width:  7,
+    kind:   UndefinedVariable{variable: "verison", suggestion: Some(Suggestion::Variable{name: "version"})},
+  }
+
+  analysis_error! {
+    name:   unknown_expression_variable_suggests_function,
+    input:  "x := arch",
+    offset: 5,
+    line:   0,
+    column: 5,
+    width:  4,
+    kind:   UndefinedVariable{variable: "arch", suggestion: Some(Suggestion::Function{name: "arch"})},
   }
 
   analysis_error! {
@@ -146,7 +204,7 @@ mod tests {
     line:   0,
     column: 13,
     width:  2,
-    kind:   UndefinedVariable{variable: "yy"},
+    kind:   UndefinedVariable{variable: "yy", suggestion: None},
   }
 
   analysis_error! {
@@ -156,7 +214,7 @@ mod tests {
     line:   0,
     column: 24,
     width:  2,
-    kind:   UndefinedVariable{variable: "yy"},
+    kind:   UndefinedVariable{variable: "yy", suggestion: None},
   }
 
   analysis_error! {
@@ -166,6 +224,6 @@ mod tests {
     line:   0,
     column: 31,
     width:  2,
-    kind:   UndefinedVariable{variable: "yy"},
+    kind:   UndefinedVariable{variable: "yy", suggestion: None},
   }
 }
@@ -0,0 +1,193 @@
+use crate::common::*;
+
+/// A recipe attribute, e.g. `[remember-args]` or `[doc("builds it")]`
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, EnumString)]
+#[strum(serialize_all = "kebab_case")]
+pub(crate) enum Attribute<'src> {
+  /// The values which the named parameter may take, e.g.
+  /// `[choices(env: 'staging', 'prod')]`
+  #[strum(disabled)]
+  Choices(Name<'src>, Vec<StringLiteral<'src>>),
+  /// Run the named recipe when this recipe exits, even if it fails or is
+  /// interrupted
+  #[strum(disabled)]
+  Cleanup(StringLiteral<'src>),
+  /// Ask for confirmation before running, with an optional custom prompt
+  #[strum(disabled)]
+  Confirm(Option<StringLiteral<'src>>),
+  /// Documentation shown by `--list`, overriding the recipe's doc comment
+  #[strum(disabled)]
+  Doc(StringLiteral<'src>),
+  /// Pipe the recipe's stdout and stderr through a shell command
+  #[strum(disabled)]
+  Filter(StringLiteral<'src>),
+  /// Run every time the recipe is invoked or depended on, even if it already
+  /// ran with the same arguments
+  Fresh,
+  /// Files which the recipe reads, given as a glob relative to the working
+  /// directory, which `--changed` compares against changed files
+  #[strum(disabled)]
+  Inputs(StringLiteral<'src>),
+  /// A group that the recipe is listed under with `--list --groups`
+  #[strum(disabled)]
+  Group(StringLiteral<'src>),
+  /// Set the soft limit of a resource, e.g. `nofile`, for the recipe's
+  /// processes
+  #[strum(disabled)]
+  Limit(StringLiteral<'src>, StringLiteral<'src>),
+  /// Run the recipe's processes with a niceness from -20 to 19
+  #[strum(disabled)]
+  Nice(StringLiteral<'src>),
+  /// Environment variables which must be set for the recipe to run, each
+  /// with an optional description, shown if it is missing
+  #[strum(disabled)]
+  NeedsEnv(Vec<(StringLiteral<'src>, Option<StringLiteral<'src>>)>),
+  /// Run in the invocation directory instead of the working directory
+  NoCd,
+  /// Don't print an error message if the recipe fails
+  NoExitMessage,
+  /// Run at most once per distinct list of arguments, which is the default
+  Once,
+  /// The named parameter takes a filesystem path, which shell completion
+  /// scripts will complete
+  #[strum(disabled)]
+  Path(StringLiteral<'src>),
+  /// Hide from `--list` and `--summary`, like a leading underscore
+  Private,
+  /// Never echo recipe lines, unless overridden by `--verbose`
+  Quiet,
+  /// Reuse arguments from the previous successful run if none are given
+  RememberArgs,
+  /// Executables which must be on `PATH` for the recipe to run
+  #[strum(disabled)]
+  Requires(Vec<StringLiteral<'src>>),
+  /// Copy the recipe's stdout and stderr to a file, as well as the terminal
+  #[strum(disabled)]
+  Tee(StringLiteral<'src>),
+  /// Run the recipe's processes with an octal file mode creation mask
+  #[strum(disabled)]
+  Umask(StringLiteral<'src>),
+  /// Always echo recipe lines, unless overridden by `--quiet`
+  Verbose,
+}
+
+impl<'src> Attribute<'src> {
+  /// Look up an attribute that takes no argument by name
+  pub(crate) fn from_name(name: Name) -> Option<Attribute<'src>> {
+    name.lexeme().parse().ok()
+  }
+
+  pub(crate) fn name(&self) -> &'static str {
+    match self {
+      Self::Choices(..) => "choices",
+      Self::Cleanup(_) => "cleanup",
+      Self::Confirm(_) => "confirm",
+      Self::Doc(_) => "doc",
+      Self::Filter(_) => "filter",
+      Self::Fresh => "fresh",
+      Self::Group(_) => "group",
+      Self::Inputs(_) => "inputs",
+      Self::Limit(..) => "limit",
+      Self::NeedsEnv(_) => "needs-env",
+      Self::Nice(_) => "nice",
+      Self::NoCd => "no-cd",
+      Self::NoExitMessage => "no-exit-message",
+      Self::Once => "once",
+      Self::Path(_) => "path",
+      Self::Private => "private",
+      Self::Quiet => "quiet",
+      Self::RememberArgs => "remember-args",
+      Self::Requires(_) => "requires",
+      Self::Tee(_) => "tee",
+      Self::Umask(_) => "umask",
+      Self::Verbose => "verbose",
+    }
+  }
+
+  /// Whether this attribute may be given more than once, with different
+  /// arguments
+  pub(crate) fn repeatable(&self) -> bool {
+    matches!(
+      self,
+      Self::Choices(..)
+        | Self::Group(_)
+        | Self::Inputs(_)
+        | Self::Limit(..)
+        | Self::NeedsEnv(_)
+        | Self::Path(_)
+        | Self::Requires(_)
+    )
+  }
+
+  /// Whether this attribute may be applied to aliases, as well as recipes
+  pub(crate) fn applies_to_aliases(&self) -> bool {
+    matches!(self, Self::Private)
+  }
+
+  /// Render `attributes` as a single attribute line, e.g. `[private, doc('x')]`
+  pub(crate) fn list(attributes: &BTreeSet<Attribute<'src>>) -> String {
+    let attributes = attributes
+      .iter()
+      .map(ToString::to_string)
+      .collect::<Vec<String>>();
+
+    format!("[{}]", attributes.join(", "))
+  }
+}
+
+impl<'src> Display for Attribute<'src> {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    match self {
+      Self::Cleanup(argument)
+      | Self::Confirm(Some(argument))
+      | Self::Doc(argument)
+      | Self::Filter(argument)
+      | Self::Group(argument)
+      | Self::Inputs(argument)
+      | Self::Nice(argument)
+      | Self::Path(argument)
+      | Self::Tee(argument)
+      | Self::Umask(argument) => write!(f, "{}({})", self.name(), argument),
+      Self::Limit(resource, value) => write!(f, "{}({}, {})", self.name(), resource, value),
+      Self::Confirm(None) => write!(f, "{}", self.name()),
+      Self::Choices(parameter, choices) => {
+        let choices = choices
+          .iter()
+          .map(ToString::to_string)
+          .collect::<Vec<String>>();
+        write!(
+          f,
+          "{}({}: {})",
+          self.name(),
+          parameter.lexeme(),
+          choices.join(", ")
+        )
+      },
+      Self::Requires(executables) => {
+        let executables = executables
+          .iter()
+          .map(ToString::to_string)
+          .collect::<Vec<String>>();
+        write!(f, "{}({})", self.name(), executables.join(", "))
+      },
+      Self::NeedsEnv(variables) => {
+        let variables = variables
+          .iter()
+          .map(|(name, description)| match description {
+            Some(description) => format!("{}: {}", name, description),
+            None => name.to_string(),
+          })
+          .collect::<Vec<String>>();
+        write!(f, "{}({})", self.name(), variables.join(", "))
+      },
+      Self::Fresh
+      | Self::NoCd
+      | Self::NoExitMessage
+      | Self::Once
+      | Self::Private
+      | Self::Quiet
+      | Self::RememberArgs
+      | Self::Verbose => write!(f, "{}", self.name()),
+    }
+  }
+}
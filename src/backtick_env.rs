@@ -0,0 +1,30 @@
+use crate::common::*;
+
+/// The environment that backticks are run with, set with `set backtick-env`
+#[derive(Debug, PartialEq, Clone, Copy, EnumString, IntoStaticStr)]
+#[strum(serialize_all = "kebab_case")]
+pub(crate) enum BacktickEnv {
+  /// A clean environment
+  None,
+  /// Only dotenv variables and exported justfile variables
+  Exports,
+  /// Just's own environment, plus dotenv variables and exported justfile
+  /// variables
+  Full,
+}
+
+impl BacktickEnv {
+  pub(crate) const VALUES: &'static [&'static str] = &["none", "exports", "full"];
+}
+
+impl Default for BacktickEnv {
+  fn default() -> Self {
+    Self::Full
+  }
+}
+
+impl Display for BacktickEnv {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(f, "{}", <&str>::from(self))
+  }
+}
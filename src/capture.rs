@@ -0,0 +1,49 @@
+use crate::common::*;
+
+/// Buffers that the output of recipes, and the lines that just echoes, are
+/// written to instead of just's stdout and stderr, when recipes are run with
+/// `api::run`
+#[derive(Clone, Default)]
+pub(crate) struct Capture {
+  stdout: Arc<Mutex<Vec<u8>>>,
+  stderr: Arc<Mutex<Vec<u8>>>,
+}
+
+impl Capture {
+  pub(crate) fn stdout(&self) -> Sink {
+    Arc::clone(&self.stdout) as Sink
+  }
+
+  pub(crate) fn stderr(&self) -> Sink {
+    Arc::clone(&self.stderr) as Sink
+  }
+
+  /// Write `line` to the captured stderr
+  pub(crate) fn eprintln(&self, line: impl Display) {
+    if let Ok(mut stderr) = self.stderr.lock() {
+      writeln!(stderr, "{}", line).ok();
+    }
+  }
+
+  /// Write `error` to the captured stderr, like `ErrorResultExt::eprint`,
+  /// returning its exit code
+  pub(crate) fn error<E: Error>(&self, error: E, context: usize) -> i32 {
+    if error.print_message() {
+      self.eprintln(error.render(Color::never(), context));
+    }
+    error.code()
+  }
+
+  /// The captured stdout and stderr, with invalid UTF-8 replaced
+  #[cfg(feature = "api")]
+  pub(crate) fn text(&self) -> (String, String) {
+    let text = |buffer: &Mutex<Vec<u8>>| {
+      buffer
+        .lock()
+        .map(|buffer| String::from_utf8_lossy(&buffer).into_owned())
+        .unwrap_or_default()
+    };
+
+    (text(&self.stdout), text(&self.stderr))
+  }
+}
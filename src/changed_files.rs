@@ -0,0 +1,70 @@
+use crate::common::*;
+
+/// Files which git reports as changed since a commit, including uncommitted
+/// and untracked files, relative to the working directory
+pub(crate) struct ChangedFiles {
+  files: Vec<String>,
+}
+
+impl ChangedFiles {
+  /// Load files changed since the merge base of `since` and `HEAD`, so that
+  /// when `since` is a branch, changes made on that branch after the current
+  /// branch was created from it are not included
+  pub(crate) fn load(working_directory: &Path, since: &str) -> Result<Self, String> {
+    let base = Self::git(working_directory, &["merge-base", since, "HEAD"])?;
+
+    let mut files = Self::git(working_directory, &[
+      "diff",
+      "--name-only",
+      "--relative",
+      base.trim(),
+    ])?
+    .lines()
+    .map(str::to_owned)
+    .collect::<Vec<String>>();
+
+    files.extend(
+      Self::git(working_directory, &[
+        "ls-files",
+        "--others",
+        "--exclude-standard",
+      ])?
+      .lines()
+      .map(str::to_owned),
+    );
+
+    Ok(Self { files })
+  }
+
+  fn git(working_directory: &Path, args: &[&str]) -> Result<String, String> {
+    let output = Command::new("git")
+      .args(args)
+      .current_dir(working_directory)
+      .output()
+      .map_err(|io_error| format!("Failed to invoke `git`: {}", io_error))?;
+
+    if !output.status.success() {
+      return Err(format!(
+        "`git {}` failed: {}",
+        args.join(" "),
+        String::from_utf8_lossy(&output.stderr).trim()
+      ));
+    }
+
+    String::from_utf8(output.stdout).map_err(|error| {
+      format!(
+        "`git {}` output was not valid UTF-8: {}",
+        args.join(" "),
+        error
+      )
+    })
+  }
+
+  /// Whether any changed file matches any of `globs`
+  pub(crate) fn any_match(&self, globs: &[Glob]) -> bool {
+    self
+      .files
+      .iter()
+      .any(|file| globs.iter().any(|glob| glob.is_match(file)))
+  }
+}
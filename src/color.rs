@@ -103,6 +103,10 @@ impl Color {
     self.restyle(Style::new().fg(Green))
   }
 
+  pub(crate) fn timestamp(self) -> Self {
+    self.restyle(Style::new().dimmed())
+  }
+
   pub(crate) fn active(&self) -> bool {
     match self.use_color {
       UseColor::Always => true,
@@ -12,9 +12,7 @@ impl CommandExt for Command {
       self.env(name, value);
     }
 
-    if let Some(parent) = scope.parent() {
-      self.export_scope(settings, parent);
-    }
+    self.export_scope(settings, scope);
   }
 
   fn export_scope(&mut self, settings: &Settings, scope: &Scope) {
@@ -1,5 +1,6 @@
 // stdlib
 pub(crate) use std::{
+  cell::RefCell,
   cmp,
   collections::{BTreeMap, BTreeSet},
   env,
@@ -8,58 +9,74 @@ pub(crate) use std::{
   fs,
   io::{self, Cursor, Write},
   iter::{self, FromIterator},
+  num,
   ops::{Index, Range, RangeInclusive},
   path::{Path, PathBuf},
   process::{self, Command, Stdio},
   rc::Rc,
   str::{self, Chars},
-  sync::{Mutex, MutexGuard},
+  sync::{Arc, Mutex, MutexGuard},
+  time::{Duration, Instant},
   usize, vec,
 };
 
 // dependencies
 pub(crate) use derivative::Derivative;
 pub(crate) use edit_distance::edit_distance;
-pub(crate) use libc::EXIT_FAILURE;
+pub(crate) use libc::{EXIT_FAILURE, EXIT_SUCCESS};
 pub(crate) use log::{info, warn};
+pub(crate) use rand::RngCore;
+pub(crate) use regex::Regex;
 pub(crate) use snafu::{ResultExt, Snafu};
 pub(crate) use strum::{Display, EnumString, IntoStaticStr};
 pub(crate) use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+pub(crate) use unicode_xid::UnicodeXID;
 
 // modules
 pub(crate) use crate::{config_error, setting};
 
 // functions
 pub(crate) use crate::{
-  default::default, empty::empty, load_dotenv::load_dotenv, output::output, unindent::unindent,
+  default::default, empty::empty, filter::filter, load_dotenv::load_dotenv, output::output,
+  tee::tee, unindent::unindent,
 };
 
 // traits
 pub(crate) use crate::{
   command_ext::CommandExt, error::Error, error_result_ext::ErrorResultExt, keyed::Keyed,
-  ordinal::Ordinal, platform_interface::PlatformInterface, range_ext::RangeExt,
+  ordinal::Ordinal, platform_interface::PlatformInterface, range_ext::RangeExt, sortable::Sortable,
 };
 
 // structs and enums
 pub(crate) use crate::{
-  alias::Alias, analyzer::Analyzer, assignment::Assignment,
-  assignment_resolver::AssignmentResolver, binding::Binding, color::Color,
-  compilation_error::CompilationError, compilation_error_kind::CompilationErrorKind,
-  compiler::Compiler, config::Config, config_error::ConfigError, count::Count,
-  delimiter::Delimiter, dependency::Dependency, enclosure::Enclosure, evaluator::Evaluator,
-  expression::Expression, fragment::Fragment, function::Function,
-  function_context::FunctionContext, interrupt_guard::InterruptGuard,
-  interrupt_handler::InterruptHandler, item::Item, justfile::Justfile, keyword::Keyword,
-  lexer::Lexer, line::Line, list::List, load_error::LoadError, module::Module, name::Name,
-  output_error::OutputError, parameter::Parameter, parameter_kind::ParameterKind, parser::Parser,
-  platform::Platform, position::Position, positional::Positional, recipe::Recipe,
-  recipe_context::RecipeContext, recipe_resolver::RecipeResolver, runtime_error::RuntimeError,
-  scope::Scope, search::Search, search_config::SearchConfig, search_error::SearchError, set::Set,
-  setting::Setting, settings::Settings, shebang::Shebang, show_whitespace::ShowWhitespace,
-  string_kind::StringKind, string_literal::StringLiteral, subcommand::Subcommand,
+  alias::Alias, alias_argument::AliasArgument, alias_metadata::AliasMetadata, analyzer::Analyzer,
+  assignment::Assignment, assignment_resolver::AssignmentResolver, attribute::Attribute,
+  backtick_env::BacktickEnv, binding::Binding, capture::Capture, changed_files::ChangedFiles,
+  color::Color, compilation_error::CompilationError, compilation_error_kind::CompilationErrorKind,
+  compiler::Compiler, config::Config, config_error::ConfigError, count::Count, datetime::DateTime,
+  delimiter::Delimiter, dependency::Dependency, doc_interpolator::DocInterpolator,
+  dotenv_error::DotenvError, dotenv_file::DotenvFile, dotenv_parser::DotenvParser,
+  download_cache::DownloadCache, dump::Dump, enclosure::Enclosure, evaluator::Evaluator,
+  exit_message::ExitMessage, expression::Expression, fallback::Fallback, fragment::Fragment,
+  function::Function, function_cache::FunctionCache, function_context::FunctionContext, glob::Glob,
+  graph::Graph, interrupt_guard::InterruptGuard, interrupt_handler::InterruptHandler, item::Item,
+  justfile::Justfile, keyword::Keyword, lexer::Lexer, line::Line, list::List,
+  load_error::LoadError, local_override::LocalOverride, metadata::Metadata, module::Module,
+  name::Name, operator::Operator, output_error::OutputError, output_format::OutputFormat,
+  output_value::OutputValue, pager::Pager, parameter::Parameter, parameter_kind::ParameterKind,
+  parameter_metadata::ParameterMetadata, parser::Parser, platform::Platform, position::Position,
+  positional::Positional, process_limits::ProcessLimits, recipe::Recipe,
+  recipe_context::RecipeContext, recipe_metadata::RecipeMetadata, recipe_resolver::RecipeResolver,
+  remembered_arguments::RememberedArguments, report::Report, report_format::ReportFormat,
+  resource::Resource, run_log::RunLog, runtime_error::RuntimeError, scope::Scope, search::Search,
+  search_boundary::SearchBoundary, search_config::SearchConfig, search_error::SearchError,
+  set::Set, setting::Setting, settings::Settings, sha256::Sha256, shebang::Shebang,
+  show_whitespace::ShowWhitespace, sort_order::SortOrder, source_formatter::SourceFormatter,
+  stats::Stats, string_kind::StringKind, string_literal::StringLiteral, subcommand::Subcommand,
   suggestion::Suggestion, table::Table, thunk::Thunk, token::Token, token_kind::TokenKind,
-  unresolved_dependency::UnresolvedDependency, unresolved_recipe::UnresolvedRecipe,
-  use_color::UseColor, variables::Variables, verbosity::Verbosity, warning::Warning,
+  unknown_recipe::UnknownRecipe, unresolved_dependency::UnresolvedDependency,
+  unresolved_recipe::UnresolvedRecipe, use_color::UseColor, variables::Variables,
+  verbosity::Verbosity, warning::Warning,
 };
 
 // type aliases
@@ -67,6 +84,7 @@ pub(crate) type CompilationResult<'a, T> = Result<T, CompilationError<'a>>;
 pub(crate) type ConfigResult<T> = Result<T, ConfigError>;
 pub(crate) type RunResult<'a, T> = Result<T, RuntimeError<'a>>;
 pub(crate) type SearchResult<T> = Result<T, SearchError>;
+pub(crate) type Sink = Arc<Mutex<dyn Write + Send>>;
 
 // modules used in tests
 #[cfg(test)]
@@ -8,6 +8,28 @@ pub(crate) struct CompilationError<'src> {
 
 impl Error for CompilationError<'_> {}
 
+impl CompilationError<'_> {
+  /// Draw `circle` as a chain of dependencies, highlighting the dependency
+  /// which closes it
+  fn write_circle(f: &mut Formatter, circle: &[&str]) -> Result<(), fmt::Error> {
+    let error = Color::fmt(f).error();
+
+    writeln!(f, "{}", circle[0])?;
+
+    for (i, name) in circle.iter().enumerate().skip(1) {
+      let edge = format!("└── {}", name);
+      write!(f, "{:indent$}", "", indent = (i - 1) * 4)?;
+      if i == circle.len() - 1 {
+        writeln!(f, "{}", error.paint(&edge))?;
+      } else {
+        writeln!(f, "{}", edge)?;
+      }
+    }
+
+    Ok(())
+  }
+}
+
 impl Display for CompilationError<'_> {
   fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
     use CompilationErrorKind::*;
@@ -16,6 +38,26 @@ impl Display for CompilationError<'_> {
     write!(f, "{}", message.prefix())?;
 
     match &self.kind {
+      AliasArgumentCountMismatch {
+        alias,
+        recipe,
+        found,
+        max,
+      } => {
+        writeln!(
+          f,
+          "Alias `{}` passes {} {} to recipe `{}`, which takes at most {} {}",
+          alias,
+          found,
+          Count("argument", *found),
+          recipe,
+          max,
+          Count("argument", *max),
+        )?;
+      },
+      AliasInvalidAttribute { alias, attribute } => {
+        writeln!(f, "Alias `{}` has invalid attribute `{}`", alias, attribute,)?;
+      },
       AliasShadowsRecipe { alias, recipe_line } => {
         writeln!(
           f,
@@ -26,9 +68,38 @@ impl Display for CompilationError<'_> {
           recipe_line.ordinal(),
         )?;
       },
+      AttributesConflict { first, second } => {
+        writeln!(
+          f,
+          "Attributes `{}` and `{}` may not be used together",
+          first, second
+        )?;
+      },
       BacktickShebang => {
         writeln!(f, "Backticks may not start with `#!`")?;
       },
+      CircularAlias { alias, circle } =>
+        if circle.len() == 2 {
+          writeln!(f, "Alias `{}` is an alias for itself", alias)?;
+        } else {
+          writeln!(
+            f,
+            "Alias `{}` has circular target `{}`",
+            alias,
+            circle.join(" -> ")
+          )?;
+        },
+      CircularRecipeCleanup { recipe, circle } =>
+        if circle.len() == 2 {
+          writeln!(f, "Recipe `{}` is its own cleanup recipe", recipe)?;
+        } else {
+          writeln!(
+            f,
+            "Recipe `{}` has circular cleanup `{}`",
+            recipe,
+            circle.join(" -> ")
+          )?;
+        },
       CircularRecipeDependency { recipe, ref circle } =>
         if circle.len() == 2 {
           writeln!(f, "Recipe `{}` depends on itself", recipe)?;
@@ -100,6 +171,15 @@ impl Display for CompilationError<'_> {
           self.token.line.ordinal(),
         )?;
       },
+      DuplicateAttribute { attribute, first } => {
+        writeln!(
+          f,
+          "Attribute `{}` first used on line {} is duplicated on line {}",
+          attribute,
+          first.ordinal(),
+          self.token.line.ordinal(),
+        )?;
+      },
       DuplicateRecipe { recipe, first } => {
         writeln!(
           f,
@@ -118,6 +198,27 @@ impl Display for CompilationError<'_> {
           self.token.line.ordinal(),
         )?;
       },
+      CleanupArgumentCountMismatch { cleanup, min } => {
+        writeln!(
+          f,
+          "Cleanup recipe `{}` is run without arguments but takes at least {} {}",
+          cleanup,
+          min,
+          Count("argument", *min),
+        )?;
+      },
+      DefaultRecipeRequiresArguments {
+        recipe,
+        min_arguments,
+      } => {
+        writeln!(
+          f,
+          "Recipe `{}` cannot be used as default recipe since it requires at least {} {}.",
+          recipe,
+          min_arguments,
+          Count("argument", *min_arguments),
+        )?;
+      },
       DependencyArgumentCountMismatch {
         dependency,
         found,
@@ -161,9 +262,26 @@ impl Display for CompilationError<'_> {
           parameter
         )?;
       },
+      SettingVariable { setting, variable } => {
+        writeln!(
+          f,
+          "Setting `{}` refers to variable `{}`, but settings are evaluated before variables",
+          setting, variable
+        )?;
+      },
       ParameterFollowsVariadicParameter { parameter } => {
         writeln!(f, "Parameter `{}` follows variadic parameter", parameter)?;
       },
+      NonIntegerOperand { operator, operand } => {
+        writeln!(
+          f,
+          "Operand `{}` of `{}` is not an integer",
+          operand, operator
+        )?;
+      },
+      NumberTooLarge { number } => {
+        writeln!(f, "Number `{}` is too large", number)?;
+      },
       MixedLeadingWhitespace { whitespace } => {
         writeln!(
           f,
@@ -186,7 +304,12 @@ impl Display for CompilationError<'_> {
           function,
           found,
           Count("argument", *found),
-          expected
+          match expected.end - expected.start {
+            _ if expected.end == usize::MAX => format!("{} or more", expected.start),
+            1 => expected.start.to_string(),
+            2 => format!("{} or {}", expected.start, expected.start + 1),
+            _ => format!("{} to {}", expected.start, expected.end - 1),
+          },
         )?;
       },
       InconsistentLeadingWhitespace { expected, found } => {
@@ -201,6 +324,19 @@ impl Display for CompilationError<'_> {
       UnknownAliasTarget { alias, target } => {
         writeln!(f, "Alias `{}` has an unknown target `{}`", alias, target)?;
       },
+      UnknownAttribute { attribute } => {
+        writeln!(f, "Unknown attribute `{}`", attribute)?;
+      },
+      UnknownCleanupRecipe { recipe, unknown } => {
+        writeln!(
+          f,
+          "Recipe `{}` has unknown cleanup recipe `{}`",
+          recipe, unknown
+        )?;
+      },
+      UnknownDefaultRecipe { recipe } => {
+        writeln!(f, "Default recipe `{}` does not exist", recipe)?;
+      },
       UnknownDependency { recipe, unknown } => {
         writeln!(
           f,
@@ -208,11 +344,61 @@ impl Display for CompilationError<'_> {
           recipe, unknown
         )?;
       },
-      UndefinedVariable { variable } => {
+      UndefinedVariable {
+        variable,
+        suggestion,
+      } => {
         writeln!(f, "Variable `{}` not defined", variable)?;
+        if let Some(suggestion) = suggestion {
+          writeln!(f, "{}", suggestion)?;
+        }
       },
-      UnknownFunction { function } => {
+      UnknownFunction {
+        function,
+        suggestion,
+      } => {
         writeln!(f, "Call to unknown function `{}`", function)?;
+        if let Some(suggestion) = suggestion {
+          writeln!(f, "{}", suggestion)?;
+        }
+      },
+      UnknownChoicesParameter { recipe, parameter } => {
+        writeln!(
+          f,
+          "Recipe `{}` has no parameter `{}`, named by its `choices` attribute",
+          recipe, parameter
+        )?;
+      },
+      UnknownPathParameter { recipe, parameter } => {
+        writeln!(
+          f,
+          "Recipe `{}` has no parameter `{}`, named by its `path` attribute",
+          recipe, parameter
+        )?;
+      },
+      InvalidAttributeArgument {
+        attribute,
+        argument,
+        expected,
+      } => {
+        writeln!(
+          f,
+          "Invalid argument `{}` for attribute `{}`, expected {}",
+          argument, attribute, expected,
+        )?;
+      },
+      InvalidSettingValue {
+        setting,
+        value,
+        expected,
+      } => {
+        writeln!(
+          f,
+          "Invalid value `{}` for setting `{}`, expected {}",
+          value,
+          setting,
+          List::or_ticked(expected),
+        )?;
       },
       UnknownSetting { setting } => {
         writeln!(f, "Unknown setting `{}`", setting)?;
@@ -220,6 +406,31 @@ impl Display for CompilationError<'_> {
       UnexpectedCharacter { expected } => {
         writeln!(f, "Expected character `{}`", expected)?;
       },
+      CombiningCharacter { name, character } => {
+        writeln!(
+          f,
+          "Name `{}` contains combining character U+{:04X}, write accented letters as single \
+           characters instead",
+          name, *character as u32,
+        )?;
+      },
+      ConfusableCharacter {
+        character,
+        lookalike,
+      } => {
+        writeln!(
+          f,
+          "Character `{}` (U+{:04X}) looks like `{}`, but is not valid here",
+          character, *character as u32, lookalike,
+        )?;
+      },
+      InvisibleCharacter { character } => {
+        writeln!(
+          f,
+          "Invisible character U+{:04X} is not valid here",
+          *character as u32
+        )?;
+      },
       UnknownStartOfToken => {
         writeln!(f, "Unknown start of token:")?;
       },
@@ -263,6 +474,12 @@ impl Display for CompilationError<'_> {
 
     write!(f, "{}", message.suffix())?;
 
+    if let CircularRecipeDependency { circle, .. } = &self.kind {
+      if circle.len() > 2 {
+        Self::write_circle(f, circle)?;
+      }
+    }
+
     self.token.write_context(f, Color::fmt(f).error())
   }
 }
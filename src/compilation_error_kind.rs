@@ -2,11 +2,33 @@ use crate::common::*;
 
 #[derive(Debug, PartialEq)]
 pub(crate) enum CompilationErrorKind<'src> {
+  AliasArgumentCountMismatch {
+    alias:  &'src str,
+    recipe: &'src str,
+    found:  usize,
+    max:    usize,
+  },
+  AliasInvalidAttribute {
+    alias:     &'src str,
+    attribute: &'src str,
+  },
   AliasShadowsRecipe {
     alias:       &'src str,
     recipe_line: usize,
   },
   BacktickShebang,
+  AttributesConflict {
+    first:  &'src str,
+    second: &'src str,
+  },
+  CircularAlias {
+    alias:  &'src str,
+    circle: Vec<&'src str>,
+  },
+  CircularRecipeCleanup {
+    recipe: &'src str,
+    circle: Vec<String>,
+  },
   CircularRecipeDependency {
     recipe: &'src str,
     circle: Vec<&'src str>,
@@ -15,6 +37,22 @@ pub(crate) enum CompilationErrorKind<'src> {
     variable: &'src str,
     circle:   Vec<&'src str>,
   },
+  CleanupArgumentCountMismatch {
+    cleanup: String,
+    min:     usize,
+  },
+  CombiningCharacter {
+    name:      &'src str,
+    character: char,
+  },
+  ConfusableCharacter {
+    character: char,
+    lookalike: char,
+  },
+  DefaultRecipeRequiresArguments {
+    recipe:        &'src str,
+    min_arguments: usize,
+  },
   DependencyArgumentCountMismatch {
     dependency: &'src str,
     found:      usize,
@@ -26,6 +64,10 @@ pub(crate) enum CompilationErrorKind<'src> {
     alias: &'src str,
     first: usize,
   },
+  DuplicateAttribute {
+    attribute: &'src str,
+    first:     usize,
+  },
   DuplicateParameter {
     recipe:    &'src str,
     parameter: &'src str,
@@ -46,10 +88,13 @@ pub(crate) enum CompilationErrorKind<'src> {
     found:    &'src str,
   },
   ExtraLeadingWhitespace,
+  InvisibleCharacter {
+    character: char,
+  },
   FunctionArgumentCountMismatch {
     function: &'src str,
     found:    usize,
-    expected: usize,
+    expected: Range<usize>,
   },
   InconsistentLeadingWhitespace {
     expected: &'src str,
@@ -58,12 +103,29 @@ pub(crate) enum CompilationErrorKind<'src> {
   Internal {
     message: String,
   },
+  InvalidAttributeArgument {
+    attribute: &'src str,
+    argument:  &'src str,
+    expected:  String,
+  },
+  InvalidSettingValue {
+    setting:  &'src str,
+    value:    &'src str,
+    expected: Vec<&'static str>,
+  },
   InvalidEscapeSequence {
     character: char,
   },
   MixedLeadingWhitespace {
     whitespace: &'src str,
   },
+  NonIntegerOperand {
+    operator: Operator,
+    operand:  String,
+  },
+  NumberTooLarge {
+    number: &'src str,
+  },
   ParameterFollowsVariadicParameter {
     parameter: &'src str,
   },
@@ -73,9 +135,14 @@ pub(crate) enum CompilationErrorKind<'src> {
   RequiredParameterFollowsDefaultParameter {
     parameter: &'src str,
   },
-  UndefinedVariable {
+  SettingVariable {
+    setting:  &'src str,
     variable: &'src str,
   },
+  UndefinedVariable {
+    variable:   &'src str,
+    suggestion: Option<Suggestion<'src>>,
+  },
   UnexpectedToken {
     expected: Vec<TokenKind>,
     found:    TokenKind,
@@ -84,12 +151,31 @@ pub(crate) enum CompilationErrorKind<'src> {
     alias:  &'src str,
     target: &'src str,
   },
+  UnknownAttribute {
+    attribute: &'src str,
+  },
+  UnknownCleanupRecipe {
+    recipe:  &'src str,
+    unknown: String,
+  },
+  UnknownChoicesParameter {
+    recipe:    &'src str,
+    parameter: &'src str,
+  },
+  UnknownDefaultRecipe {
+    recipe: String,
+  },
   UnknownDependency {
     recipe:  &'src str,
     unknown: &'src str,
   },
   UnknownFunction {
-    function: &'src str,
+    function:   &'src str,
+    suggestion: Option<Suggestion<'src>>,
+  },
+  UnknownPathParameter {
+    recipe:    &'src str,
+    parameter: String,
   },
   UnknownStartOfToken,
   UnexpectedCharacter {
@@ -10,4 +10,16 @@ impl Compiler {
 
     Analyzer::analyze(ast)
   }
+
+  /// Compile `src` with the items in `local` layered over it
+  pub(crate) fn compile_with_local<'src>(
+    src: &'src str,
+    local: &'src str,
+  ) -> CompilationResult<'src, Justfile<'src>> {
+    let ast = Parser::parse(&Lexer::lex(src)?)?;
+
+    let local = Parser::parse(&Lexer::lex(local)?)?;
+
+    Analyzer::analyze(ast.overlay(local))
+  }
 }
@@ -2,6 +2,8 @@ use crate::common::*;
 
 use clap::{App, AppSettings, Arg, ArgGroup, ArgMatches, ArgSettings};
 
+use std::io::Read;
+
 // These three strings should be kept in sync:
 pub(crate) const CHOOSER_DEFAULT: &str = "fzf";
 pub(crate) const CHOOSER_ENVIRONMENT_KEY: &str = "JUST_CHOOSER";
@@ -9,49 +11,84 @@ pub(crate) const CHOOSE_HELP: &str = "Select one or more recipes to run using a
                                       `--chooser` is not passed the chooser defaults to the value \
                                       of $JUST_CHOOSER, falling back to `fzf`";
 
+pub(crate) const STATS_FILE_ENVIRONMENT_KEY: &str = "JUST_STATS_FILE";
+
 pub(crate) const DEFAULT_SHELL: &str = "sh";
 pub(crate) const DEFAULT_SHELL_ARG: &str = "-cu";
+pub(crate) const DEFAULT_TIMESTAMP_FORMAT: &str = "%H:%M:%S";
 pub(crate) const INIT_JUSTFILE: &str = "default:\n\techo 'Hello, world!'\n";
 
 #[derive(Debug, PartialEq)]
 pub(crate) struct Config {
+  pub(crate) cache_dir:            Option<PathBuf>,
   pub(crate) color:                Color,
+  pub(crate) dotenv_file:          Option<PathBuf>,
   pub(crate) dry_run:              bool,
+  pub(crate) echo_truncate:        Option<usize>,
+  pub(crate) error_context:        usize,
+  pub(crate) function_cache:       bool,
   pub(crate) highlight:            bool,
   pub(crate) invocation_directory: PathBuf,
-  pub(crate) list_heading:         String,
-  pub(crate) list_prefix:          String,
+  pub(crate) keep_going:           bool,
+  pub(crate) list_heading:         Option<String>,
+  pub(crate) list_prefix:          Option<String>,
   pub(crate) load_dotenv:          bool,
+  pub(crate) log_json:             Option<PathBuf>,
+  pub(crate) no_dependencies:      bool,
+  pub(crate) report:               Option<(ReportFormat, PathBuf)>,
+  pub(crate) safe:                 bool,
+  pub(crate) search_boundary:      SearchBoundary,
   pub(crate) search_config:        SearchConfig,
+  pub(crate) shebang:              Option<String>,
   pub(crate) shell:                String,
   pub(crate) shell_args:           Vec<String>,
   pub(crate) shell_present:        bool,
+  pub(crate) sort_order:           SortOrder,
+  pub(crate) stats_file:           Option<PathBuf>,
   pub(crate) subcommand:           Subcommand,
-  pub(crate) unsorted:             bool,
+  pub(crate) tee:                  Option<PathBuf>,
+  pub(crate) timestamp_format:     String,
+  pub(crate) timestamps:           bool,
+  pub(crate) timings:              bool,
   pub(crate) verbosity:            Verbosity,
+  pub(crate) yes:                  bool,
 }
 
 mod cmd {
+  pub(crate) const CHANGED: &str = "CHANGED";
   pub(crate) const CHOOSE: &str = "CHOOSE";
+  pub(crate) const COMMAND: &str = "COMMAND";
+  pub(crate) const COMPLETE: &str = "COMPLETE";
   pub(crate) const COMPLETIONS: &str = "COMPLETIONS";
   pub(crate) const DUMP: &str = "DUMP";
   pub(crate) const EDIT: &str = "EDIT";
   pub(crate) const EVALUATE: &str = "EVALUATE";
+  pub(crate) const FMT: &str = "FMT";
+  pub(crate) const GRAPH: &str = "GRAPH";
   pub(crate) const INIT: &str = "INIT";
   pub(crate) const LIST: &str = "LIST";
+  pub(crate) const SET_ENV: &str = "SET-ENV";
   pub(crate) const SHOW: &str = "SHOW";
+  pub(crate) const STATS: &str = "STATS";
   pub(crate) const SUMMARY: &str = "SUMMARY";
   pub(crate) const VARIABLES: &str = "VARIABLES";
 
   pub(crate) const ALL: &[&str] = &[
+    CHANGED,
     CHOOSE,
+    COMMAND,
+    COMPLETE,
     COMPLETIONS,
     DUMP,
     EDIT,
     INIT,
     EVALUATE,
+    FMT,
+    GRAPH,
     LIST,
+    SET_ENV,
     SHOW,
+    STATS,
     SUMMARY,
     VARIABLES,
   ];
@@ -60,9 +97,11 @@ mod cmd {
     COMPLETIONS,
     DUMP,
     EDIT,
+    FMT,
+    GRAPH,
     INIT,
-    LIST,
-    SHOW,
+    SET_ENV,
+    STATS,
     SUMMARY,
     VARIABLES,
   ];
@@ -70,28 +109,110 @@ mod cmd {
 
 mod arg {
   pub(crate) const ARGUMENTS: &str = "ARGUMENTS";
+  pub(crate) const CACHE_DIR: &str = "CACHE-DIR";
+  pub(crate) const CHANGED_SINCE: &str = "CHANGED-SINCE";
+  pub(crate) const CHECK: &str = "CHECK";
   pub(crate) const CHOOSER: &str = "CHOOSER";
   pub(crate) const CLEAR_SHELL_ARGS: &str = "CLEAR-SHELL-ARGS";
   pub(crate) const COLOR: &str = "COLOR";
+  pub(crate) const DOTENV_FILE: &str = "DOTENV-FILE";
   pub(crate) const DRY_RUN: &str = "DRY-RUN";
+  pub(crate) const ECHO_TRUNCATE: &str = "ECHO-TRUNCATE";
+  pub(crate) const ERROR_CONTEXT: &str = "ERROR-CONTEXT";
+  pub(crate) const FOCUS: &str = "FOCUS";
+  pub(crate) const FORMAT: &str = "FORMAT";
+  pub(crate) const GLOBAL_JUSTFILE: &str = "GLOBAL-JUSTFILE";
+  pub(crate) const GROUPS: &str = "GROUPS";
   pub(crate) const HIGHLIGHT: &str = "HIGHLIGHT";
+  pub(crate) const KEEP_GOING: &str = "KEEP-GOING";
   pub(crate) const LIST_HEADING: &str = "LIST-HEADING";
   pub(crate) const LIST_PREFIX: &str = "LIST-PREFIX";
+  pub(crate) const LOG_JSON: &str = "LOG-JSON";
   pub(crate) const JUSTFILE: &str = "JUSTFILE";
+  pub(crate) const JUSTFILE_SOURCE: &str = "JUSTFILE-SOURCE";
+  pub(crate) const NO_DEPENDENCIES: &str = "NO-DEPENDENCIES";
   pub(crate) const NO_DOTENV: &str = "NO-DOTENV";
+  pub(crate) const NO_FUNCTION_CACHE: &str = "NO-FUNCTION-CACHE";
   pub(crate) const NO_HIGHLIGHT: &str = "NO-HIGHLIGHT";
   pub(crate) const QUIET: &str = "QUIET";
+  pub(crate) const REPORT: &str = "REPORT";
+  pub(crate) const SAFE: &str = "SAFE";
+  pub(crate) const SEARCH_BOUNDARY: &str = "SEARCH-BOUNDARY";
+  pub(crate) const SEARCH_FROM: &str = "SEARCH-FROM";
   pub(crate) const SET: &str = "SET";
+  pub(crate) const SHEBANG: &str = "SHEBANG";
   pub(crate) const SHELL: &str = "SHELL";
   pub(crate) const SHELL_ARG: &str = "SHELL-ARG";
+  pub(crate) const STATS_FILE: &str = "STATS-FILE";
+  pub(crate) const TEE: &str = "TEE";
+  pub(crate) const TIMESTAMP_FORMAT: &str = "TIMESTAMP-FORMAT";
+  pub(crate) const TIMESTAMPS: &str = "TIMESTAMPS";
+  pub(crate) const TIMINGS: &str = "TIMINGS";
   pub(crate) const UNSORTED: &str = "UNSORTED";
   pub(crate) const VERBOSE: &str = "VERBOSE";
   pub(crate) const WORKING_DIRECTORY: &str = "WORKING-DIRECTORY";
+  pub(crate) const YES: &str = "YES";
+
+  /// Flags which may be set with environment variables, see
+  /// `Config::environment_arguments`
+  pub(crate) const FLAGS: &[&str] = &[
+    CHECK,
+    CLEAR_SHELL_ARGS,
+    DRY_RUN,
+    GLOBAL_JUSTFILE,
+    GROUPS,
+    HIGHLIGHT,
+    KEEP_GOING,
+    NO_DEPENDENCIES,
+    NO_DOTENV,
+    NO_FUNCTION_CACHE,
+    NO_HIGHLIGHT,
+    QUIET,
+    SAFE,
+    TIMESTAMPS,
+    TIMINGS,
+    UNSORTED,
+    VERBOSE,
+    YES,
+  ];
+
+  /// Options which may be set with environment variables, see
+  /// `Config::environment_arguments`
+  pub(crate) const OPTIONS: &[&str] = &[
+    CACHE_DIR,
+    CHANGED_SINCE,
+    CHOOSER,
+    COLOR,
+    DOTENV_FILE,
+    ECHO_TRUNCATE,
+    ERROR_CONTEXT,
+    FOCUS,
+    FORMAT,
+    JUSTFILE,
+    LIST_HEADING,
+    LIST_PREFIX,
+    LOG_JSON,
+    REPORT,
+    SEARCH_BOUNDARY,
+    SEARCH_FROM,
+    SHEBANG,
+    SHELL,
+    SHELL_ARG,
+    STATS_FILE,
+    TEE,
+    TIMESTAMP_FORMAT,
+    WORKING_DIRECTORY,
+  ];
 
   pub(crate) const COLOR_ALWAYS: &str = "always";
   pub(crate) const COLOR_AUTO: &str = "auto";
   pub(crate) const COLOR_NEVER: &str = "never";
   pub(crate) const COLOR_VALUES: &[&str] = &[COLOR_AUTO, COLOR_ALWAYS, COLOR_NEVER];
+
+  pub(crate) const FORMAT_DOT: &str = "dot";
+  pub(crate) const FORMAT_JSON: &str = "json";
+  pub(crate) const FORMAT_YAML: &str = "yaml";
+  pub(crate) const FORMAT_VALUES: &[&str] = &[FORMAT_JSON, FORMAT_YAML, FORMAT_DOT];
 }
 
 impl Config {
@@ -101,6 +222,27 @@ impl Config {
       .version_message("Print version information")
       .setting(AppSettings::ColoredHelp)
       .setting(AppSettings::TrailingVarArg)
+      .arg(
+        Arg::with_name(arg::CACHE_DIR)
+          .long("cache-dir")
+          .takes_value(true)
+          .value_name("DIR")
+          .help("Cache recipe metadata for `--list`, `--summary`, and completions in <DIR>"),
+      )
+      .arg(
+        Arg::with_name(arg::CHANGED_SINCE)
+          .long("changed-since")
+          .takes_value(true)
+          .value_name("REF")
+          .requires(cmd::CHANGED)
+          .help("Compare against <REF> with `--changed`, instead of `HEAD`"),
+      )
+      .arg(
+        Arg::with_name(arg::CHECK)
+          .long("check")
+          .requires(cmd::FMT)
+          .help("Check formatting with `--fmt` without rewriting the justfile"),
+      )
       .arg(
         Arg::with_name(arg::CHOOSER)
           .long("chooser")
@@ -115,18 +257,75 @@ impl Config {
           .default_value(arg::COLOR_AUTO)
           .help("Print colorful output"),
       )
+      .arg(
+        Arg::with_name(arg::DOTENV_FILE)
+          .long("dotenv-file")
+          .takes_value(true)
+          .value_name("FILE")
+          .conflicts_with(arg::NO_DOTENV)
+          .help("Load environment variables from <FILE>, ignoring dotenv settings"),
+      )
       .arg(
         Arg::with_name(arg::DRY_RUN)
           .long("dry-run")
           .help("Print what just would do without doing it")
           .conflicts_with(arg::QUIET),
       )
+      .arg(
+        Arg::with_name(arg::ECHO_TRUNCATE)
+          .long("echo-truncate")
+          .takes_value(true)
+          .value_name("N")
+          .help(
+            "Truncate echoed recipe lines longer than <N> characters, or disable truncation if \
+             <N> is 0",
+          ),
+      )
+      .arg(
+        Arg::with_name(arg::ERROR_CONTEXT)
+          .long("error-context")
+          .takes_value(true)
+          .value_name("N")
+          .default_value("0")
+          .help("Print <N> lines of source before and after errors"),
+      )
+      .arg(
+        Arg::with_name(arg::FOCUS)
+          .long("focus")
+          .takes_value(true)
+          .value_name("RECIPE")
+          .requires(cmd::GRAPH)
+          .help("Print only <RECIPE>, its dependents, and its dependencies with `--graph`"),
+      )
+      .arg(
+        Arg::with_name(arg::GROUPS)
+          .long("groups")
+          .requires(cmd::LIST)
+          .help("List recipes under headings for their `[group]` attributes with `--list`"),
+      )
+      .arg(
+        Arg::with_name(arg::FORMAT)
+          .long("format")
+          .takes_value(true)
+          .possible_values(arg::FORMAT_VALUES)
+          .requires("FORMATTED")
+          .help(
+            "Print `--dump` or `--evaluate` output as `json` or `yaml`, or `--graph` output as \
+             `dot`",
+          ),
+      )
       .arg(
         Arg::with_name(arg::HIGHLIGHT)
           .long("highlight")
           .help("Highlight echoed recipe lines in bold")
           .overrides_with(arg::NO_HIGHLIGHT),
       )
+      .arg(
+        Arg::with_name(arg::KEEP_GOING)
+          .short("k")
+          .long("keep-going")
+          .help("Run remaining recipes given on the command line after one fails"),
+      )
       .arg(
         Arg::with_name(arg::LIST_HEADING)
           .long("list-heading")
@@ -141,23 +340,48 @@ impl Config {
           .value_name("TEXT")
           .takes_value(true),
       )
+      .arg(
+        Arg::with_name(arg::LOG_JSON)
+          .long("log-json")
+          .takes_value(true)
+          .value_name("PATH")
+          .help("Write a JSON line to <PATH> for each recipe, line, and backtick run"),
+      )
+      .arg(
+        Arg::with_name(arg::NO_DEPENDENCIES)
+          .long("no-dependencies")
+          .help("Run recipes given on the command line without running their dependencies"),
+      )
       .arg(
         Arg::with_name(arg::NO_DOTENV)
           .long("no-dotenv")
           .help("Don't load `.env` file"),
       )
+      .arg(
+        Arg::with_name(arg::NO_FUNCTION_CACHE)
+          .long("no-function-cache")
+          .help(
+            "Call functions which probe the system every time, instead of caching their results",
+          ),
+      )
       .arg(
         Arg::with_name(arg::NO_HIGHLIGHT)
           .long("no-highlight")
           .help("Don't highlight echoed recipe lines in bold")
           .overrides_with(arg::HIGHLIGHT),
       )
+      .arg(
+        Arg::with_name(arg::GLOBAL_JUSTFILE)
+          .short("g")
+          .long("global-justfile")
+          .help("Use global justfile"),
+      )
       .arg(
         Arg::with_name(arg::JUSTFILE)
           .short("f")
           .long("justfile")
           .takes_value(true)
-          .help("Use <JUSTFILE> as justfile"),
+          .help("Use <JUSTFILE> as justfile, or `-` for stdin"),
       )
       .arg(
         Arg::with_name(arg::QUIET)
@@ -166,6 +390,37 @@ impl Config {
           .help("Suppress all output")
           .conflicts_with(arg::DRY_RUN),
       )
+      .arg(
+        Arg::with_name(arg::SAFE)
+          .long("safe")
+          .help("Refuse to evaluate backticks, read environment variables, or run recipes"),
+      )
+      .arg(
+        Arg::with_name(arg::REPORT)
+          .long("report")
+          .takes_value(true)
+          .value_name("FORMAT=PATH")
+          .help("Write a report of recipe runs to <PATH> in <FORMAT>. Supported formats: junit"),
+      )
+      .arg(
+        Arg::with_name(arg::SEARCH_BOUNDARY)
+          .long("search-boundary")
+          .takes_value(true)
+          .value_name("BOUNDARY")
+          .possible_values(SearchBoundary::VALUES)
+          .help(
+            "Stop searching for a justfile at the root of a git submodule or worktree, with \
+             `worktree`, or of any git repository, with `repository`",
+          ),
+      )
+      .arg(
+        Arg::with_name(arg::SEARCH_FROM)
+          .long("search-from")
+          .takes_value(true)
+          .value_name("DIR")
+          .conflicts_with_all(&[arg::JUSTFILE, arg::GLOBAL_JUSTFILE])
+          .help("Search for the justfile upwards from <DIR>, instead of the current directory"),
+      )
       .arg(
         Arg::with_name(arg::SET)
           .long("set")
@@ -175,6 +430,14 @@ impl Config {
           .multiple(true)
           .help("Override <VARIABLE> with <VALUE>"),
       )
+      .arg(
+        Arg::with_name(arg::SHEBANG)
+          .long("shebang")
+          .alias("recipe-shell")
+          .takes_value(true)
+          .value_name("INTERPRETER")
+          .help("Run named recipes as scripts with <INTERPRETER>"),
+      )
       .arg(
         Arg::with_name(arg::SHELL)
           .long("shell")
@@ -199,11 +462,43 @@ impl Config {
           .overrides_with(arg::SHELL_ARG)
           .help("Clear shell arguments"),
       )
+      .arg(
+        Arg::with_name(arg::STATS_FILE)
+          .long("stats-file")
+          .takes_value(true)
+          .value_name("FILE")
+          .help("Record recipe run statistics in <FILE>"),
+      )
+      .arg(
+        Arg::with_name(arg::TEE)
+          .long("tee")
+          .takes_value(true)
+          .value_name("DIR")
+          .help("Copy the output of each recipe to a timestamped log file in <DIR>"),
+      )
+      .arg(
+        Arg::with_name(arg::TIMESTAMP_FORMAT)
+          .long("timestamp-format")
+          .takes_value(true)
+          .value_name("FORMAT")
+          .default_value(DEFAULT_TIMESTAMP_FORMAT)
+          .help("Use `strftime`-style <FORMAT> for `--timestamps`"),
+      )
+      .arg(
+        Arg::with_name(arg::TIMESTAMPS)
+          .long("timestamps")
+          .help("Prefix echoed recipe lines with the time they were run"),
+      )
+      .arg(
+        Arg::with_name(arg::TIMINGS)
+          .long("timings")
+          .help("Print how long each recipe took to run"),
+      )
       .arg(
         Arg::with_name(arg::UNSORTED)
           .long("unsorted")
           .short("u")
-          .help("Return list and summary entries in source order"),
+          .help("List recipes and variables in source order instead of by name"),
       )
       .arg(
         Arg::with_name(arg::VERBOSE)
@@ -212,20 +507,64 @@ impl Config {
           .multiple(true)
           .help("Use verbose output"),
       )
+      .arg(
+        Arg::with_name(arg::YES)
+          .long("yes")
+          .help("Run `[confirm]` recipes without asking for confirmation"),
+      )
       .arg(
         Arg::with_name(arg::WORKING_DIRECTORY)
           .short("d")
           .long("working-directory")
           .takes_value(true)
-          .help("Use <WORKING-DIRECTORY> as working directory. --justfile must also be set")
-          .requires(arg::JUSTFILE),
+          .help(
+            "Use <WORKING-DIRECTORY> as working directory. --justfile or --global-justfile must \
+             also be set",
+          )
+          .requires(arg::JUSTFILE_SOURCE),
       )
       .arg(
         Arg::with_name(arg::ARGUMENTS)
           .multiple(true)
           .help("Overrides and recipe(s) to run, defaulting to the first recipe in the justfile"),
       )
+      .arg(
+        Arg::with_name(cmd::CHANGED)
+          .long("changed")
+          .takes_value(true)
+          .value_name("PATTERN")
+          .help(
+            "Run recipes matching glob <PATTERN> whose `[inputs]` have changed according to git",
+          ),
+      )
       .arg(Arg::with_name(cmd::CHOOSE).long("choose").help(CHOOSE_HELP))
+      .arg(
+        Arg::with_name(cmd::COMMAND)
+          .long("command")
+          .alias("exec")
+          .takes_value(true)
+          .multiple(true)
+          .allow_hyphen_values(true)
+          .value_name("COMMAND")
+          .help(
+            "Run <COMMAND> in the working directory with the justfile's exported variables and \
+             dotenv file",
+          ),
+      )
+      .arg(
+        Arg::with_name(cmd::COMPLETE)
+          .long("complete")
+          .takes_value(true)
+          .multiple(true)
+          .min_values(0)
+          .allow_hyphen_values(true)
+          .value_name("WORDS")
+          .hidden(true)
+          .help(
+            "Print the kind of word that may follow <WORDS>, and candidates for it, for \
+             completion scripts",
+          ),
+      )
       .arg(
         Arg::with_name(cmd::COMPLETIONS)
           .long("completions")
@@ -247,9 +586,19 @@ impl Config {
           .help("Edit justfile with editor given by $VISUAL or $EDITOR, falling back to `vim`"),
       )
       .arg(Arg::with_name(cmd::EVALUATE).long("evaluate").help(
-        "Evaluate and print all variables. If positional arguments are present, only print the \
-         variables whose names are given as arguments.",
+        "Evaluate and print all variables, or only those whose names are given as arguments. If a \
+         single name is given, print only its value.",
       ))
+      .arg(
+        Arg::with_name(cmd::FMT)
+          .long("fmt")
+          .help("Format justfile in place, preserving comments and blank lines"),
+      )
+      .arg(
+        Arg::with_name(cmd::GRAPH)
+          .long("graph")
+          .help("Print recipe dependency graph"),
+      )
       .arg(
         Arg::with_name(cmd::INIT)
           .long("init")
@@ -259,7 +608,19 @@ impl Config {
         Arg::with_name(cmd::LIST)
           .short("l")
           .long("list")
-          .help("List available recipes and their arguments"),
+          .takes_value(true)
+          .min_values(0)
+          .max_values(1)
+          .value_name("RECIPE")
+          .help("List available recipes and their arguments, or <RECIPE> and its dependencies"),
+      )
+      .arg(
+        Arg::with_name(cmd::SET_ENV)
+          .long("set-env")
+          .takes_value(true)
+          .multiple(true)
+          .value_name("KEY=VALUE")
+          .help("Set <KEY> to <VALUE> in the dotenv file, creating it if needed"),
       )
       .arg(
         Arg::with_name(cmd::SHOW)
@@ -267,7 +628,15 @@ impl Config {
           .long("show")
           .takes_value(true)
           .value_name("RECIPE")
-          .help("Show information about <RECIPE>"),
+          .help(
+            "Show information about <RECIPE>, and any other recipes given as arguments, which may \
+             be globs",
+          ),
+      )
+      .arg(
+        Arg::with_name(cmd::STATS)
+          .long("stats")
+          .help("Print recipe run counts and durations recorded with `--stats-file`"),
       )
       .arg(
         Arg::with_name(cmd::SUMMARY)
@@ -279,7 +648,11 @@ impl Config {
           .long("variables")
           .help("List names of variables"),
       )
-      .group(ArgGroup::with_name("SUBCOMMAND").args(cmd::ALL));
+      .group(ArgGroup::with_name("SUBCOMMAND").args(cmd::ALL))
+      .group(ArgGroup::with_name("FORMATTED").args(&[cmd::DUMP, cmd::EVALUATE, cmd::GRAPH]))
+      .group(
+        ArgGroup::with_name(arg::JUSTFILE_SOURCE).args(&[arg::JUSTFILE, arg::GLOBAL_JUSTFILE]),
+      );
 
     if cfg!(feature = "help4help2man") {
       app.version(env!("CARGO_PKG_VERSION")).about(concat!(
@@ -310,8 +683,124 @@ impl Config {
     }
   }
 
+  fn format_from_value(subcommand: &str, value: &str) -> ConfigResult<OutputFormat> {
+    match value {
+      arg::FORMAT_JSON => Ok(OutputFormat::Json),
+      arg::FORMAT_YAML => Ok(OutputFormat::Yaml),
+      arg::FORMAT_DOT => Err(ConfigError::Format {
+        subcommand: subcommand.to_owned(),
+        format:     value.to_owned(),
+      }),
+      _ => Err(ConfigError::Internal {
+        message: format!("Invalid argument `{}` to --format.", value),
+      }),
+    }
+  }
+
+  fn dot_from_value(value: Option<&str>) -> ConfigResult<bool> {
+    match value {
+      None => Ok(false),
+      Some(arg::FORMAT_DOT) => Ok(true),
+      Some(value) => Err(ConfigError::Format {
+        subcommand: String::from("--graph"),
+        format:     value.to_owned(),
+      }),
+    }
+  }
+
+  fn report_from_value(value: &str) -> ConfigResult<(ReportFormat, PathBuf)> {
+    let mut pieces = value.splitn(2, '=');
+
+    match (pieces.next().map(str::parse), pieces.next()) {
+      (Some(Ok(format)), Some(path)) if !path.is_empty() => Ok((format, PathBuf::from(path))),
+      _ => Err(ConfigError::Report {
+        value: value.to_owned(),
+      }),
+    }
+  }
+
+  /// The environment variable which sets the flag or option named `name`,
+  /// e.g. `JUST_DRY_RUN` for `--dry-run`
+  pub(crate) fn environment_variable(name: &str) -> String {
+    format!("JUST_{}", name.replace('-', "_"))
+  }
+
+  /// Add flags and options from `JUST_*` environment variables, looked up
+  /// with `lookup`, to the command line `args`, for those which `args` does
+  /// not already contain. Options are set to the value of their variable.
+  /// Flags are set if their variable is a number of times to pass the flag,
+  /// `true`, or anything else other than `false`, `0`, or the empty string.
+  ///
+  /// Variables whose flag or option conflicts with `args`, or is otherwise
+  /// invalid, are ignored, so the command line always takes precedence.
+  pub(crate) fn environment_arguments(
+    args: Vec<OsString>,
+    lookup: impl Fn(&str) -> Option<OsString>,
+  ) -> Vec<OsString> {
+    let valid = |args: &[OsString]| {
+      Self::app()
+        .get_matches_from_safe(args)
+        .ok()
+        .filter(|matches| Self::from_matches(matches).is_ok())
+    };
+
+    let matches = match valid(&args) {
+      Some(matches) if !args.is_empty() => matches,
+      _ => return args,
+    };
+
+    let mut environment = Vec::new();
+
+    for (name, flag) in arg::FLAGS
+      .iter()
+      .map(|name| (name, true))
+      .chain(arg::OPTIONS.iter().map(|name| (name, false)))
+    {
+      if matches.occurrences_of(name) > 0 {
+        continue;
+      }
+
+      let value = match lookup(&Self::environment_variable(name)) {
+        Some(value) => value,
+        None => continue,
+      };
+
+      let long = format!("--{}", name.to_lowercase());
+
+      let arguments = if flag {
+        let count = match value.to_str() {
+          Some("") | Some("0") | Some("false") => 0,
+          Some(value) => value.parse().unwrap_or(1),
+          None => 1,
+        };
+        iter::repeat(OsString::from(&long)).take(count).collect()
+      } else {
+        let mut argument = OsString::from(format!("{}=", long));
+        argument.push(value);
+        vec![argument]
+      };
+
+      let mut candidate = environment.clone();
+      candidate.extend(arguments);
+
+      let mut combined = args[..1].to_vec();
+      combined.extend(candidate.iter().cloned());
+      combined.extend(args[1..].iter().cloned());
+
+      if valid(&combined).is_some() {
+        environment = candidate;
+      }
+    }
+
+    let mut combined = args[..1].to_vec();
+    combined.extend(environment);
+    combined.extend(args[1..].iter().cloned());
+    combined
+  }
+
   pub(crate) fn from_matches(matches: &ArgMatches) -> ConfigResult<Self> {
-    let invocation_directory = env::current_dir().context(config_error::CurrentDir)?;
+    let invocation_directory =
+      Search::strip_verbatim_prefix(env::current_dir().context(config_error::CurrentDir)?);
 
     let verbosity = if matches.is_present(arg::QUIET) {
       Verbosity::Quiet
@@ -325,6 +814,41 @@ impl Config {
         .expect("`--color` had no value"),
     )?;
 
+    let echo_truncate = matches
+      .value_of(arg::ECHO_TRUNCATE)
+      .map(|value| {
+        value
+          .parse::<usize>()
+          .context(config_error::EchoTruncate { value })
+      })
+      .transpose()?;
+
+    let error_context = {
+      let value = matches
+        .value_of(arg::ERROR_CONTEXT)
+        .expect("`--error-context` had no value");
+
+      value
+        .parse::<usize>()
+        .context(config_error::ErrorContext { value })?
+    };
+
+    let timestamp_format = matches
+      .value_of(arg::TIMESTAMP_FORMAT)
+      .expect("`--timestamp-format` had no value");
+
+    if let Err(message) = DateTime::now_utc().and_then(|now| now.format(timestamp_format)) {
+      return Err(ConfigError::TimestampFormat {
+        format: timestamp_format.to_owned(),
+        message,
+      });
+    }
+
+    let report = matches
+      .value_of(arg::REPORT)
+      .map(Self::report_from_value)
+      .transpose()?;
+
     let set_count = matches.occurrences_of(arg::SET);
     let mut overrides = BTreeMap::new();
     if set_count > 0 {
@@ -346,14 +870,23 @@ impl Config {
     let search_config = {
       let justfile = matches.value_of(arg::JUSTFILE).map(PathBuf::from);
       let working_directory = matches.value_of(arg::WORKING_DIRECTORY).map(PathBuf::from);
+      let global = matches.is_present(arg::GLOBAL_JUSTFILE);
+
+      let search_from = matches.value_of(arg::SEARCH_FROM).map(PathBuf::from);
 
       if let Some(search_directory) = positional.search_directory.map(PathBuf::from) {
-        if justfile.is_some() || working_directory.is_some() {
+        if justfile.is_some() || working_directory.is_some() || global || search_from.is_some() {
           return Err(ConfigError::SearchDirConflict);
         }
         SearchConfig::FromSearchDirectory { search_directory }
+      } else if let Some(search_directory) = search_from {
+        SearchConfig::FromSearchDirectory { search_directory }
+      } else if global {
+        SearchConfig::GlobalJustfile { working_directory }
       } else {
         match (justfile, working_directory) {
+          (Some(justfile), working_directory) if justfile == Path::new("-") =>
+            SearchConfig::FromStdin { working_directory },
           (None, None) => SearchConfig::FromInvocationDirectory,
           (Some(justfile), None) => SearchConfig::WithJustfile { justfile },
           (Some(justfile), Some(working_directory)) =>
@@ -396,34 +929,117 @@ impl Config {
       }
     }
 
-    let subcommand = if matches.is_present(cmd::CHOOSE) {
+    let subcommand = if let Some(pattern) = matches.value_of(cmd::CHANGED) {
+      if !positional.arguments.is_empty() {
+        return Err(ConfigError::SubcommandArguments {
+          subcommand: "--changed".to_owned(),
+          arguments:  positional.arguments,
+        });
+      }
+
+      Subcommand::Changed {
+        pattern: pattern.to_owned(),
+        since: matches
+          .value_of(arg::CHANGED_SINCE)
+          .unwrap_or("HEAD")
+          .to_owned(),
+        overrides,
+      }
+    } else if matches.is_present(cmd::CHOOSE) {
       Subcommand::Choose {
         chooser: matches.value_of(arg::CHOOSER).map(str::to_owned),
         overrides,
       }
+    } else if let Some(command) = matches.values_of(cmd::COMMAND) {
+      Subcommand::Command {
+        command: command.map(str::to_owned).collect(),
+        overrides,
+      }
+    } else if matches.is_present(cmd::COMPLETE) {
+      Subcommand::Complete {
+        words: matches
+          .values_of(cmd::COMPLETE)
+          .map(|words| words.map(str::to_owned).collect())
+          .unwrap_or_default(),
+      }
     } else if let Some(shell) = matches.value_of(cmd::COMPLETIONS) {
       Subcommand::Completions {
         shell: shell.to_owned(),
       }
     } else if matches.is_present(cmd::EDIT) {
       Subcommand::Edit
+    } else if matches.is_present(cmd::STATS) {
+      Subcommand::Stats
     } else if matches.is_present(cmd::SUMMARY) {
       Subcommand::Summary
     } else if matches.is_present(cmd::DUMP) {
-      Subcommand::Dump
+      Subcommand::Dump {
+        format: matches
+          .value_of(arg::FORMAT)
+          .map(|value| Self::format_from_value("--dump", value))
+          .transpose()?,
+      }
+    } else if matches.is_present(cmd::GRAPH) {
+      Subcommand::Graph {
+        focus: matches.value_of(arg::FOCUS).map(str::to_owned),
+        dot:   Self::dot_from_value(matches.value_of(arg::FORMAT))?,
+      }
     } else if matches.is_present(cmd::INIT) {
       Subcommand::Init
     } else if matches.is_present(cmd::LIST) {
-      Subcommand::List
+      if !positional.arguments.is_empty() {
+        return Err(ConfigError::SubcommandArguments {
+          subcommand: "--list".to_owned(),
+          arguments:  positional.arguments,
+        });
+      }
+
+      Subcommand::List {
+        recipe: matches.value_of(cmd::LIST).map(str::to_owned),
+        groups: matches.is_present(arg::GROUPS),
+        overrides,
+      }
+    } else if let Some(values) = matches.values_of(cmd::SET_ENV) {
+      let mut variables = Vec::new();
+
+      for value in values {
+        match value.split_once('=') {
+          Some((key, value)) if DotenvFile::is_key(key) =>
+            variables.push((key.to_owned(), value.to_owned())),
+          _ =>
+            return Err(ConfigError::SetEnv {
+              value: value.to_owned(),
+            }),
+        }
+      }
+
+      Subcommand::SetEnv { variables }
     } else if let Some(name) = matches.value_of(cmd::SHOW) {
+      if !overrides.is_empty() {
+        return Err(ConfigError::SubcommandOverrides {
+          subcommand: "--show".to_owned(),
+          overrides,
+        });
+      }
+
       Subcommand::Show {
-        name: name.to_owned(),
+        names: iter::once(name.to_owned())
+          .chain(positional.arguments)
+          .collect(),
       }
     } else if matches.is_present(cmd::EVALUATE) {
       Subcommand::Evaluate {
+        format: matches
+          .value_of(arg::FORMAT)
+          .map(|value| Self::format_from_value("--evaluate", value))
+          .transpose()?,
         variables: positional.arguments,
         overrides,
       }
+    } else if matches.is_present(cmd::FMT) {
+      Subcommand::Format {
+        check: matches.is_present(arg::CHECK),
+      }
     } else if matches.is_present(cmd::VARIABLES) {
       Subcommand::Variables
     } else {
@@ -433,6 +1049,21 @@ impl Config {
       }
     };
 
+    if let SearchConfig::FromStdin { .. } = search_config {
+      let writes = match subcommand {
+        Subcommand::Edit => Some("--edit"),
+        Subcommand::Format { check: false } => Some("--fmt"),
+        Subcommand::Init => Some("--init"),
+        _ => None,
+      };
+
+      if let Some(subcommand) = writes {
+        return Err(ConfigError::StdinJustfile {
+          subcommand: subcommand.to_owned(),
+        });
+      }
+    }
+
     let shell_args = if matches.is_present(arg::CLEAR_SHELL_ARGS) {
       Vec::new()
     } else {
@@ -448,26 +1079,48 @@ impl Config {
       || matches.occurrences_of(arg::SHELL_ARG) > 0;
 
     Ok(Self {
+      cache_dir: matches.value_of(arg::CACHE_DIR).map(PathBuf::from),
+      dotenv_file: matches.value_of_os(arg::DOTENV_FILE).map(PathBuf::from),
       dry_run: matches.is_present(arg::DRY_RUN),
+      echo_truncate,
+      error_context,
+      function_cache: !matches.is_present(arg::NO_FUNCTION_CACHE),
       highlight: !matches.is_present(arg::NO_HIGHLIGHT),
       shell: matches.value_of(arg::SHELL).unwrap().to_owned(),
       load_dotenv: !matches.is_present(arg::NO_DOTENV),
-      unsorted: matches.is_present(arg::UNSORTED),
-      list_heading: matches
-        .value_of(arg::LIST_HEADING)
-        .unwrap_or("Available recipes:\n")
-        .to_owned(),
-      list_prefix: matches
-        .value_of(arg::LIST_PREFIX)
-        .unwrap_or("    ")
-        .to_owned(),
+      log_json: matches.value_of_os(arg::LOG_JSON).map(PathBuf::from),
+      no_dependencies: matches.is_present(arg::NO_DEPENDENCIES),
+      report,
+      safe: matches.is_present(arg::SAFE),
+      search_boundary: matches
+        .value_of(arg::SEARCH_BOUNDARY)
+        .map(|value| {
+          value
+            .parse()
+            .expect("`--search-boundary` had invalid value")
+        })
+        .unwrap_or_default(),
+      shebang: matches.value_of(arg::SHEBANG).map(str::to_owned),
+      sort_order: SortOrder::from_unsorted(matches.is_present(arg::UNSORTED)),
+      keep_going: matches.is_present(arg::KEEP_GOING),
+      list_heading: matches.value_of(arg::LIST_HEADING).map(str::to_owned),
+      list_prefix: matches.value_of(arg::LIST_PREFIX).map(str::to_owned),
       color,
       invocation_directory,
       search_config,
       shell_args,
       shell_present,
+      stats_file: matches
+        .value_of_os(arg::STATS_FILE)
+        .map(PathBuf::from)
+        .or_else(|| env::var_os(STATS_FILE_ENVIRONMENT_KEY).map(PathBuf::from)),
       subcommand,
+      tee: matches.value_of_os(arg::TEE).map(PathBuf::from),
+      timestamp_format: timestamp_format.to_owned(),
+      timestamps: matches.is_present(arg::TIMESTAMPS),
+      timings: matches.is_present(arg::TIMINGS),
       verbosity,
+      yes: matches.is_present(arg::YES),
     })
   }
 
@@ -482,51 +1135,158 @@ impl Config {
       return Subcommand::completions(self.verbosity, &shell);
     }
 
-    let search =
-      Search::find(&self.search_config, &self.invocation_directory).eprint(self.color)?;
+    if let Complete { words } = &self.subcommand {
+      return self.complete(words);
+    }
+
+    let search = Search::find(
+      &self.search_config,
+      self.search_boundary,
+      &self.invocation_directory,
+    )
+    .eprint(self.color, self.error_context)?;
 
     if self.subcommand == Edit {
       return self.edit(&search);
     }
 
-    let src = fs::read_to_string(&search.justfile)
-      .map_err(|io_error| LoadError {
-        io_error,
-        path: &search.justfile,
-      })
-      .eprint(self.color)?;
+    if self.subcommand == Stats {
+      return self.stats(&search);
+    }
 
-    let justfile = Compiler::compile(&src).eprint(self.color)?;
+    let (src, local) = self.load(&search, None)?;
 
-    if self.verbosity.loud() {
-      for warning in &justfile.warnings {
-        if self.color.stderr().active() {
-          eprintln!("{:#}", warning);
-        } else {
-          eprintln!("{}", warning);
+    let metadata_src = Metadata::source(&src, local.as_deref());
+
+    match (&self.subcommand, &self.cache_dir) {
+      (
+        List {
+          recipe: None,
+          groups,
+          ..
+        },
+        Some(cache_dir),
+      ) =>
+        if let Some(metadata) = Metadata::load(cache_dir, &metadata_src) {
+          // interpolations in doc comments need the compiled justfile
+          if !metadata.has_doc_interpolations() {
+            self.list(&metadata, *groups);
+            return Ok(());
+          }
+        },
+      (Summary, Some(cache_dir)) if !self.verbosity.loquacious() => {
+        if let Some(metadata) = Metadata::load(cache_dir, &metadata_src) {
+          self.summary(&metadata);
+          return Ok(());
         }
-      }
+      },
+      _ => {},
     }
 
+    let justfile = self.prepare(&src, local.as_deref(), &search, None)?;
+
     match &self.subcommand {
+      Changed {
+        pattern,
+        since,
+        overrides,
+      } => self.changed(justfile, &search, pattern, since, overrides)?,
       Choose { overrides, chooser } =>
         self.choose(justfile, &search, overrides, chooser.as_deref())?,
-      Dump => Self::dump(justfile),
+      Command { overrides, .. } => self.run(justfile, &search, overrides, &[])?,
+      Dump { format } => self.dump(justfile, *format),
       Evaluate { overrides, .. } => self.run(justfile, &search, overrides, &[])?,
-      List => self.list(justfile),
+      Format { check } => self.format(&src, &search, *check)?,
+      Graph { focus, dot } => self.graph(&justfile, focus.as_deref(), *dot)?,
+      List {
+        recipe: None,
+        groups,
+        overrides,
+      } => {
+        let mut metadata = self.metadata(&metadata_src, &justfile);
+        let mut interpolator = DocInterpolator::new(&justfile, &self, &search, overrides);
+        for recipe in &mut metadata.recipes {
+          if let (false, Some(doc)) = (recipe.private, &mut recipe.doc) {
+            *doc = interpolator.interpolate(doc);
+          }
+        }
+        self.list(&metadata, *groups);
+      },
+      List {
+        recipe: Some(name),
+        overrides,
+        ..
+      } => self.list_recipe(&name, &justfile, &search, overrides)?,
       Run {
         arguments,
         overrides,
       } => self.run(justfile, &search, overrides, arguments)?,
-      Show { ref name } => self.show(&name, justfile)?,
-      Summary => self.summary(justfile),
-      Variables => Self::variables(justfile),
-      Completions { .. } | Edit | Init => unreachable!(),
+      SetEnv { variables } => self.set_env(&justfile, &search, variables)?,
+      Show { ref names } => self.show(names, justfile)?,
+      Summary if self.verbosity.loquacious() => self.summary_tree(&justfile),
+      Summary => self.summary(&self.metadata(&metadata_src, &justfile)),
+      Variables => self.variables(&justfile),
+      Complete { .. } | Completions { .. } | Edit | Init | Stats => unreachable!(),
     }
 
     Ok(())
   }
 
+  fn changed(
+    &self,
+    justfile: Justfile,
+    search: &Search,
+    pattern: &str,
+    since: &str,
+    overrides: &BTreeMap<String, String>,
+  ) -> Result<(), i32> {
+    let glob = Glob::new(pattern);
+
+    let candidates = justfile
+      .public_recipes(self.sort_order)
+      .into_iter()
+      .filter(|recipe| recipe.min_arguments() == 0 && glob.is_match(recipe.name()))
+      .collect::<Vec<&Recipe<Dependency>>>();
+
+    if candidates.is_empty() {
+      if self.verbosity.loud() {
+        eprintln!(
+          "Justfile contains no recipes matching `{}` which can be run without arguments.",
+          pattern
+        );
+      }
+      return Err(EXIT_FAILURE);
+    }
+
+    let changed = match ChangedFiles::load(&search.working_directory, since) {
+      Ok(changed) => changed,
+      Err(message) => {
+        if self.verbosity.loud() {
+          eprintln!("Failed to get files changed since `{}`: {}", since, message);
+        }
+        return Err(EXIT_FAILURE);
+      },
+    };
+
+    let recipes = candidates
+      .into_iter()
+      .filter(|recipe| changed.any_match(&recipe.inputs()))
+      .map(|recipe| recipe.name().to_owned())
+      .collect::<Vec<String>>();
+
+    if recipes.is_empty() {
+      if self.verbosity.loud() {
+        eprintln!(
+          "No recipes matching `{}` have inputs changed since `{}`.",
+          pattern, since
+        );
+      }
+      return Ok(());
+    }
+
+    self.run(justfile, search, overrides, &recipes)
+  }
+
   fn choose(
     &self,
     justfile: Justfile,
@@ -535,7 +1295,7 @@ impl Config {
     chooser: Option<&str>,
   ) -> Result<(), i32> {
     let recipes = justfile
-      .public_recipes(self.unsorted)
+      .public_recipes(self.sort_order)
       .iter()
       .filter(|recipe| recipe.min_arguments() == 0)
       .cloned()
@@ -632,31 +1392,229 @@ impl Config {
     self.run(justfile, search, overrides, &recipes)
   }
 
-  fn dump(justfile: Justfile) {
-    println!("{}", justfile);
+  /// Load the justfile found by `search`, and the local justfile layered over
+  /// it, if any. Errors are written to `capture`, if output is being
+  /// captured, and otherwise to stderr.
+  pub(crate) fn load(
+    &self,
+    search: &Search,
+    capture: Option<&Capture>,
+  ) -> Result<(String, Option<String>), i32> {
+    let src = if search.is_stdin() {
+      let mut src = String::new();
+      io::stdin().read_to_string(&mut src).map(|_| src)
+    } else {
+      fs::read_to_string(&search.justfile)
+    }
+    .map_err(|io_error| LoadError {
+      io_error,
+      path: &search.justfile,
+    })
+    .eprint_to(capture, self.color, self.error_context)?;
+
+    let local_justfile = search.local_justfile();
+
+    let local = local_justfile
+      .as_deref()
+      .map(|path| fs::read_to_string(path).map_err(|io_error| LoadError { io_error, path }))
+      .transpose()
+      .eprint_to(capture, self.color, self.error_context)?;
+
+    Ok((src, local))
   }
 
-  pub(crate) fn edit(&self, search: &Search) -> Result<(), i32> {
-    let editor = env::var_os("VISUAL")
-      .or_else(|| env::var_os("EDITOR"))
-      .unwrap_or_else(|| "vim".into());
+  fn compile<'src>(
+    &self,
+    src: &'src str,
+    local: Option<&'src str>,
+    capture: Option<&Capture>,
+  ) -> Result<Justfile<'src>, i32> {
+    match local {
+      Some(local) => Compiler::compile_with_local(src, local),
+      None => Compiler::compile(src),
+    }
+    .eprint_to(capture, self.color, self.error_context)
+  }
 
-    let error = Command::new(&editor)
-      .current_dir(&search.working_directory)
-      .arg(&search.justfile)
-      .status();
+  /// Compile a justfile loaded with `load` and evaluate its settings, so that
+  /// it's ready to run, and report its warnings and, if loquacious, the
+  /// recipes and variables overridden by the local justfile. Output is
+  /// written to `capture`, if output is being captured, and otherwise to
+  /// stderr.
+  pub(crate) fn prepare<'src>(
+    &self,
+    src: &'src str,
+    local: Option<&'src str>,
+    search: &Search,
+    capture: Option<&Capture>,
+  ) -> Result<Justfile<'src>, i32> {
+    let mut justfile = self.compile(src, local, capture)?;
 
-    match error {
-      Ok(status) =>
-        if status.success() {
-          Ok(())
-        } else {
-          if self.verbosity.loud() {
-            eprintln!("Editor `{}` failed: {}", editor.to_string_lossy(), status);
-          }
-          Err(status.code().unwrap_or(EXIT_FAILURE))
-        },
-      Err(error) => {
+    justfile
+      .evaluate_settings(self, search)
+      .eprint_to(capture, self.color, self.error_context)?;
+
+    if self.verbosity.loud() {
+      for warning in &justfile.warnings {
+        self.eprint_diagnostic(warning, capture);
+      }
+    }
+
+    if self.verbosity.loquacious() {
+      for local_override in &justfile.overrides {
+        self.eprint_diagnostic(local_override, capture);
+      }
+    }
+
+    Ok(justfile)
+  }
+
+  fn eprint_diagnostic(&self, diagnostic: &impl Display, capture: Option<&Capture>) {
+    match capture {
+      Some(capture) => capture.eprintln(format_args!("{:1$}", diagnostic, self.error_context)),
+      None if self.color.stderr().active() => eprintln!("{:#1$}", diagnostic, self.error_context),
+      None => eprintln!("{:1$}", diagnostic, self.error_context),
+    }
+  }
+
+  /// Print the kind of word that should follow `words`, the words on the
+  /// command line after `just`, for use by shell completion scripts:
+  ///
+  /// - `recipe`: a recipe name
+  /// - `argument`: an argument to a recipe
+  /// - `path`: an argument to a recipe parameter with a `[path]` attribute
+  ///
+  /// This is followed by candidates for that word: the names of public
+  /// recipes and aliases for `recipe`, and the parameter the word is an
+  /// argument to for `argument`. The justfile is located using the flags and
+  /// options in `words`, so that completions respect `--justfile` and
+  /// friends, and recipes are read from cached metadata when there is a cache
+  /// directory. Fails if `words` is not a valid command line, for example
+  /// because it ends with an option that is missing its value, so that
+  /// completion scripts can fall back to completing that value.
+  fn complete(&self, words: &[String]) -> Result<(), i32> {
+    // options with default values, like `--color`, parse without a value, but
+    // the next word is still that option's value
+    if let Some(last) = words.last() {
+      if arg::OPTIONS
+        .iter()
+        .any(|name| *last == format!("--{}", name.to_lowercase()))
+      {
+        return Err(EXIT_FAILURE);
+      }
+    }
+
+    let args = Self::environment_arguments(
+      iter::once(env!("CARGO_PKG_NAME"))
+        .chain(words.iter().map(String::as_str))
+        .map(OsString::from)
+        .collect(),
+      |key| env::var_os(key),
+    );
+
+    let config = Self::app()
+      .get_matches_from_safe(args)
+      .ok()
+      .and_then(|matches| Self::from_matches(&matches).ok())
+      .ok_or(EXIT_FAILURE)?;
+
+    let arguments = match &config.subcommand {
+      Subcommand::Run { arguments, .. } => arguments,
+      _ => return Ok(()),
+    };
+
+    let search = Search::find(
+      &config.search_config,
+      config.search_boundary,
+      &self.invocation_directory,
+    )
+    .eprint(self.color, self.error_context)?;
+
+    if search.is_stdin() {
+      return Err(EXIT_FAILURE);
+    }
+
+    let (src, local) = self.load(&search, None)?;
+
+    let metadata_src = Metadata::source(&src, local.as_deref());
+
+    let cached = config
+      .cache_dir
+      .as_ref()
+      .and_then(|cache_dir| Metadata::load(cache_dir, &metadata_src));
+
+    let metadata = match cached {
+      Some(metadata) => metadata,
+      None => config.metadata(&metadata_src, &self.compile(&src, local.as_deref(), None)?),
+    };
+
+    let mut current: Option<(&RecipeMetadata, usize)> = None;
+
+    for argument in arguments {
+      current = match current {
+        Some((recipe, arguments)) if arguments < recipe.max_arguments() =>
+          Some((recipe, arguments + 1)),
+        _ => metadata.get_recipe(argument).map(|recipe| (recipe, 0)),
+      };
+    }
+
+    match current {
+      Some((recipe, arguments)) if arguments < recipe.max_arguments() => {
+        let parameter = &recipe.parameters[cmp::min(arguments, recipe.parameters.len() - 1)];
+        if parameter.path {
+          println!("path");
+        } else {
+          println!("argument");
+          println!("{}", parameter);
+        }
+      },
+      _ => {
+        println!("recipe");
+
+        let names = metadata
+          .public_recipes(SortOrder::Name)
+          .into_iter()
+          .map(|recipe| recipe.name.as_str())
+          .chain(metadata.aliases.iter().map(|alias| alias.name.as_str()))
+          .collect::<BTreeSet<&str>>();
+
+        for name in names {
+          println!("{}", name);
+        }
+      },
+    }
+
+    Ok(())
+  }
+
+  fn dump(&self, justfile: Justfile, format: Option<OutputFormat>) {
+    match format {
+      Some(format) => print!("{}", format.value(&Dump::value(&justfile, self.sort_order))),
+      None => println!("{}", justfile),
+    }
+  }
+
+  pub(crate) fn edit(&self, search: &Search) -> Result<(), i32> {
+    let editor = env::var_os("VISUAL")
+      .or_else(|| env::var_os("EDITOR"))
+      .unwrap_or_else(|| "vim".into());
+
+    let error = Command::new(&editor)
+      .current_dir(&search.working_directory)
+      .arg(&search.justfile)
+      .status();
+
+    match error {
+      Ok(status) =>
+        if status.success() {
+          Ok(())
+        } else {
+          if self.verbosity.loud() {
+            eprintln!("Editor `{}` failed: {}", editor.to_string_lossy(), status);
+          }
+          Err(status.code().unwrap_or(EXIT_FAILURE))
+        },
+      Err(error) => {
         if self.verbosity.loud() {
           eprintln!(
             "Editor `{}` invocation failed: {}",
@@ -669,16 +1627,53 @@ impl Config {
     }
   }
 
+  fn format(&self, src: &str, search: &Search, check: bool) -> Result<(), i32> {
+    let formatted = SourceFormatter::format(src).eprint(self.color, self.error_context)?;
+
+    if formatted == src {
+      return Ok(());
+    }
+
+    if check {
+      if self.verbosity.loud() {
+        eprintln!("Justfile `{}` is not formatted", search.justfile.display());
+      }
+      return Err(EXIT_FAILURE);
+    }
+
+    if let Err(error) = fs::write(&search.justfile, formatted) {
+      if self.verbosity.loud() {
+        eprintln!(
+          "Failed to write justfile to `{}`: {}",
+          search.justfile.display(),
+          error
+        );
+      }
+      return Err(EXIT_FAILURE);
+    }
+
+    if self.verbosity.loud() {
+      eprintln!("Wrote justfile to `{}`", search.justfile.display());
+    }
+
+    Ok(())
+  }
+
   pub(crate) fn init(&self) -> Result<(), i32> {
-    let search =
-      Search::init(&self.search_config, &self.invocation_directory).eprint(self.color)?;
+    let search = Search::init(&self.search_config, &self.invocation_directory)
+      .eprint(self.color, self.error_context)?;
 
     if search.justfile.exists() {
       if self.verbosity.loud() {
         eprintln!("Justfile `{}` already exists", search.justfile.display());
       }
       Err(EXIT_FAILURE)
-    } else if let Err(err) = fs::write(&search.justfile, INIT_JUSTFILE) {
+    } else if let Err(err) = search
+      .justfile
+      .parent()
+      .map_or(Ok(()), fs::create_dir_all)
+      .and_then(|()| fs::write(&search.justfile, INIT_JUSTFILE))
+    {
       if self.verbosity.loud() {
         eprintln!(
           "Failed to write justfile to `{}`: {}",
@@ -695,33 +1690,38 @@ impl Config {
     }
   }
 
-  fn list(&self, justfile: Justfile) {
+  fn list(&self, metadata: &Metadata, groups: bool) {
     // Construct a target to alias map.
-    let mut recipe_aliases: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
-    for alias in justfile.aliases.values() {
-      if alias.is_private() {
-        continue;
-      }
-
-      if !recipe_aliases.contains_key(alias.target.name.lexeme()) {
-        recipe_aliases.insert(alias.target.name.lexeme(), vec![alias.name.lexeme()]);
-      } else {
-        let aliases = recipe_aliases.get_mut(alias.target.name.lexeme()).unwrap();
-        aliases.push(alias.name.lexeme());
-      }
+    let mut recipe_aliases: BTreeMap<&str, Vec<&AliasMetadata>> = BTreeMap::new();
+    for alias in &metadata.aliases {
+      recipe_aliases
+        .entry(alias.target.as_str())
+        .or_default()
+        .push(alias);
     }
 
     let mut line_widths: BTreeMap<&str, usize> = BTreeMap::new();
 
-    for (name, recipe) in &justfile.recipes {
+    for recipe in &metadata.recipes {
       if recipe.private {
         continue;
       }
 
-      for name in iter::once(name).chain(recipe_aliases.get(name).unwrap_or(&Vec::new())) {
-        let mut line_width = UnicodeWidthStr::width(*name);
+      let aliases = recipe_aliases
+        .get(recipe.name.as_str())
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+
+      for (name, parameters) in iter::once((recipe.name.as_str(), recipe.parameters.as_slice()))
+        .chain(
+          aliases
+            .iter()
+            .map(|alias| (alias.name.as_str(), alias.parameters(recipe))),
+        )
+      {
+        let mut line_width = UnicodeWidthStr::width(name);
 
-        for parameter in &recipe.parameters {
+        for parameter in parameters {
           line_width += UnicodeWidthStr::width(format!(" {}", parameter).as_str());
         }
 
@@ -733,17 +1733,232 @@ impl Config {
 
     let max_line_width = cmp::min(line_widths.values().cloned().max().unwrap_or(0), 30);
 
+    // the command line takes precedence over the justfile's settings
+    let heading = self
+      .list_heading
+      .as_deref()
+      .or_else(|| metadata.list_heading.as_deref())
+      .unwrap_or("Available recipes:\n");
+
+    let prefix = self
+      .list_prefix
+      .as_deref()
+      .or_else(|| metadata.list_prefix.as_deref())
+      .unwrap_or("    ");
+
+    let mut text = heading.to_owned();
+
+    if !groups {
+      for recipe in metadata.public_recipes(self.sort_order) {
+        self.list_entry(
+          &mut text,
+          recipe,
+          prefix,
+          &recipe_aliases,
+          &line_widths,
+          max_line_width,
+        );
+      }
+      Pager::print(&text, metadata.pager);
+      return;
+    }
+
+    for (i, (group, recipes)) in metadata.groups(self.sort_order).into_iter().enumerate() {
+      if i > 0 {
+        text.push('\n');
+      }
+
+      if let Some(group) = group {
+        text.push_str(&format!("{}[{}]\n", prefix, group));
+      }
+
+      for recipe in recipes {
+        self.list_entry(
+          &mut text,
+          recipe,
+          prefix,
+          &recipe_aliases,
+          &line_widths,
+          max_line_width,
+        );
+      }
+    }
+
+    Pager::print(&text, metadata.pager);
+  }
+
+  /// Append the `--list` line for `recipe` to `text`, followed by one for
+  /// each of its aliases
+  fn list_entry(
+    &self,
+    text: &mut String,
+    recipe: &RecipeMetadata,
+    prefix: &str,
+    recipe_aliases: &BTreeMap<&str, Vec<&AliasMetadata>>,
+    line_widths: &BTreeMap<&str, usize>,
+    max_line_width: usize,
+  ) {
     let doc_color = self.color.stdout().doc();
-    print!("{}", self.list_heading);
 
-    for recipe in justfile.public_recipes(self.unsorted) {
-      let name = recipe.name();
+    let aliases = recipe_aliases
+      .get(recipe.name.as_str())
+      .map(Vec::as_slice)
+      .unwrap_or_default();
 
-      for (i, name) in iter::once(&name)
-        .chain(recipe_aliases.get(name).unwrap_or(&Vec::new()))
-        .enumerate()
-      {
-        print!("{}{}", self.list_prefix, name);
+    for (name, parameters, alias) in
+      iter::once((recipe.name.as_str(), recipe.parameters.as_slice(), None)).chain(
+        aliases
+          .iter()
+          .map(|alias| (alias.name.as_str(), alias.parameters(recipe), Some(alias))),
+      )
+    {
+      text.push_str(&format!("{}{}", prefix, name));
+      for parameter in parameters {
+        if self.color.stdout().active() {
+          text.push_str(&format!(" {:#}", parameter));
+        } else {
+          text.push_str(&format!(" {}", parameter));
+        }
+      }
+
+      // Declaring this outside of the nested loops will probably be more efficient,
+      // but it creates all sorts of lifetime issues with variables inside the loops.
+      // If this is inlined like the docs say, it shouldn't make any difference.
+      let doc_text = |doc| {
+        format!(
+          " {:padding$}{} {}",
+          "",
+          doc_color.paint("#"),
+          doc_color.paint(doc),
+          padding =
+            max_line_width.saturating_sub(line_widths.get(name).cloned().unwrap_or(max_line_width))
+        )
+      };
+
+      match (alias, &recipe.doc) {
+        (None, Some(doc)) => text.push_str(&doc_text(doc)),
+        (None, None) => (),
+        (Some(alias), _) => {
+          let alias_doc = format!("alias for `{}`", alias.invocation());
+          text.push_str(&doc_text(&alias_doc));
+        },
+      }
+      text.push('\n');
+    }
+  }
+
+  /// List `name`, its documentation and parameters, and its transitive
+  /// dependencies, each indented beneath the recipe that depends on it
+  fn graph(&self, justfile: &Justfile, focus: Option<&str>, dot: bool) -> Result<(), i32> {
+    let focus = match focus {
+      Some(name) => match justfile.get_recipe(name) {
+        Some(recipe) => Some(recipe),
+        None => {
+          if self.verbosity.loud() {
+            eprintln!("Justfile does not contain recipe `{}`.", name);
+            if let Some(suggestion) = justfile.suggest(name) {
+              eprintln!("{}", suggestion);
+            }
+          }
+          return Err(EXIT_FAILURE);
+        },
+      },
+      None => None,
+    };
+
+    if dot {
+      print!("{}", Graph::dot(justfile, focus, self.sort_order));
+      return Ok(());
+    }
+
+    let graph = Graph::new(justfile, focus, self.sort_order);
+
+    if self.color.stdout().active() {
+      print!("{:#}", graph);
+    } else {
+      print!("{}", graph);
+    }
+
+    Ok(())
+  }
+
+  fn list_recipe(
+    &self,
+    name: &str,
+    justfile: &Justfile,
+    search: &Search,
+    overrides: &BTreeMap<String, String>,
+  ) -> Result<(), i32> {
+    let recipe = if let Some(recipe) = justfile.get_recipe(name) {
+      recipe
+    } else {
+      if self.verbosity.loud() {
+        eprintln!("Justfile does not contain recipe `{}`.", name);
+        if let Some(suggestion) = justfile.suggest(name) {
+          eprintln!("{}", suggestion);
+        }
+      }
+      return Err(EXIT_FAILURE);
+    };
+
+    let mut parameters = String::new();
+    for parameter in &recipe.parameters {
+      parameters.push_str(&format!(" {}", parameter));
+    }
+
+    let mut interpolator = DocInterpolator::new(justfile, self, search, overrides);
+
+    let mut lines = vec![(
+      0,
+      recipe.name().to_owned(),
+      parameters,
+      recipe.doc.map(|doc| interpolator.interpolate(doc)),
+    )];
+
+    let mut stack = recipe
+      .dependencies
+      .iter()
+      .rev()
+      .map(|dependency| (1, dependency))
+      .collect::<Vec<(usize, &Dependency)>>();
+
+    while let Some((depth, dependency)) = stack.pop() {
+      lines.push((
+        depth,
+        dependency.to_string(),
+        String::new(),
+        dependency
+          .recipe
+          .doc
+          .map(|doc| interpolator.interpolate(doc)),
+      ));
+      stack.extend(
+        dependency
+          .recipe
+          .dependencies
+          .iter()
+          .rev()
+          .map(|dependency| (depth + 1, dependency)),
+      );
+    }
+
+    let width = |depth: usize, text: &str, parameters: &str| {
+      depth * 4 + UnicodeWidthStr::width(text) + UnicodeWidthStr::width(parameters)
+    };
+
+    let max_line_width = lines
+      .iter()
+      .map(|(depth, text, parameters, _)| width(*depth, text, parameters))
+      .filter(|width| *width <= 30)
+      .max()
+      .unwrap_or(0);
+
+    let doc_color = self.color.stdout().doc();
+
+    for (i, (depth, text, parameters, doc)) in lines.iter().enumerate() {
+      print!("{:indent$}{}", "", text, indent = depth * 4);
+
+      if i == 0 {
         for parameter in &recipe.parameters {
           if self.color.stdout().active() {
             print!(" {:#}", parameter);
@@ -751,32 +1966,37 @@ impl Config {
             print!(" {}", parameter);
           }
         }
+      }
 
-        // Declaring this outside of the nested loops will probably be more efficient,
-        // but it creates all sorts of lifetime issues with variables inside the loops.
-        // If this is inlined like the docs say, it shouldn't make any difference.
-        let print_doc = |doc| {
-          print!(
-            " {:padding$}{} {}",
-            "",
-            doc_color.paint("#"),
-            doc_color.paint(doc),
-            padding = max_line_width
-              .saturating_sub(line_widths.get(name).cloned().unwrap_or(max_line_width))
-          );
-        };
+      if let Some(doc) = doc {
+        print!(
+          " {:padding$}{} {}",
+          "",
+          doc_color.paint("#"),
+          doc_color.paint(doc),
+          padding = max_line_width.saturating_sub(width(*depth, text, parameters))
+        );
+      }
 
-        match (i, recipe.doc) {
-          (0, Some(doc)) => print_doc(doc),
-          (0, None) => (),
-          _ => {
-            let alias_doc = format!("alias for `{}`", recipe.name);
-            print_doc(&alias_doc);
-          },
-        }
-        println!();
+      println!();
+    }
+
+    Ok(())
+  }
+
+  /// Get metadata for `--list` and `--summary`, caching it if a cache
+  /// directory was given. Metadata isn't cached if any settings are
+  /// expressions, since their values may depend on the environment.
+  fn metadata(&self, src: &str, justfile: &Justfile) -> Metadata {
+    let metadata = Metadata::from(justfile);
+
+    if let Some(cache_dir) = &self.cache_dir {
+      if justfile.settings.expressions.is_empty() {
+        metadata.store(cache_dir, src);
       }
     }
+
+    metadata
   }
 
   fn run(
@@ -790,59 +2010,167 @@ impl Config {
       warn!("Failed to set CTRL-C handler: {}", error)
     }
 
-    let result = justfile.run(&self, search, overrides, arguments);
+    let result = justfile.run(&self, search, overrides, arguments, None);
 
     if !self.verbosity.quiet() {
-      result.eprint(self.color)
+      result.eprint(self.color, self.error_context)
     } else {
       result.map_err(|err| err.code())
     }
   }
 
-  fn show(&self, name: &str, justfile: Justfile) -> Result<(), i32> {
-    if let Some(alias) = justfile.get_alias(name) {
-      let recipe = justfile.get_recipe(alias.target.name.lexeme()).unwrap();
-      println!("{}", alias);
-      println!("{}", recipe);
-      Ok(())
-    } else if let Some(recipe) = justfile.get_recipe(name) {
-      println!("{}", recipe);
-      Ok(())
-    } else {
+  fn set_env(
+    &self,
+    justfile: &Justfile,
+    search: &Search,
+    variables: &[(String, String)],
+  ) -> Result<(), i32> {
+    let path = DotenvFile::path(self, &justfile.settings, &search.working_directory);
+
+    if let Err(io_error) = DotenvFile::update(&path, variables) {
       if self.verbosity.loud() {
-        eprintln!("Justfile does not contain recipe `{}`.", name);
-        if let Some(suggestion) = justfile.suggest(name) {
-          eprintln!("{}", suggestion);
+        eprintln!(
+          "Failed to write dotenv file `{}`: {}",
+          path.display(),
+          io_error
+        );
+      }
+      return Err(EXIT_FAILURE);
+    }
+
+    if self.verbosity.loud() {
+      eprintln!(
+        "Set {} in `{}`",
+        List::and_ticked(variables.iter().map(|(key, _)| key)),
+        path.display()
+      );
+    }
+
+    Ok(())
+  }
+
+  /// Print the recipes and aliases named by `names`, in order, separated by
+  /// blank lines. Names containing glob metacharacters, which recipe names
+  /// can't, match all public recipes. Nothing is printed unless every name
+  /// matches something, and recipes are only printed once.
+  fn show(&self, names: &[String], justfile: Justfile) -> Result<(), i32> {
+    let mut shown = BTreeSet::new();
+    let mut items = Vec::new();
+
+    for name in names {
+      if let Some(alias) = justfile.get_alias(name) {
+        let recipe = justfile.get_recipe(alias.target.name.lexeme()).unwrap();
+        shown.insert(recipe.name());
+        items.push(format!("{}\n{}", alias, recipe));
+      } else if let Some(recipe) = justfile.get_recipe(name) {
+        if shown.insert(recipe.name()) {
+          items.push(recipe.to_string());
+        }
+      } else if Glob::is_pattern(name) {
+        let glob = Glob::new(name);
+
+        let matches = justfile
+          .public_recipes(self.sort_order)
+          .into_iter()
+          .filter(|recipe| glob.is_match(recipe.name()))
+          .collect::<Vec<&Recipe<Dependency>>>();
+
+        if matches.is_empty() {
+          if self.verbosity.loud() {
+            eprintln!("Justfile contains no recipes matching `{}`.", name);
+          }
+          return Err(EXIT_FAILURE);
+        }
+
+        for recipe in matches {
+          if shown.insert(recipe.name()) {
+            items.push(recipe.to_string());
+          }
+        }
+      } else {
+        if self.verbosity.loud() {
+          eprintln!("Justfile does not contain recipe `{}`.", name);
+          if let Some(suggestion) = justfile.suggest(name) {
+            eprintln!("{}", suggestion);
+          }
         }
+        return Err(EXIT_FAILURE);
       }
-      Err(EXIT_FAILURE)
     }
+
+    println!("{}", items.join("\n\n"));
+
+    Ok(())
   }
 
-  fn summary(&self, justfile: Justfile) {
-    if justfile.count() == 0 {
+  fn stats(&self, search: &Search) -> Result<(), i32> {
+    let path = if let Some(path) = &self.stats_file {
+      path
+    } else {
+      if self.verbosity.loud() {
+        eprintln!(
+          "`--stats` requires a statistics file, given with `--stats-file` or ${}",
+          STATS_FILE_ENVIRONMENT_KEY
+        );
+      }
+      return Err(EXIT_FAILURE);
+    };
+
+    match Stats::load(path, &search.justfile) {
+      Ok(stats) => {
+        print!("{}", stats);
+        Ok(())
+      },
+      Err(io_error) => {
+        if self.verbosity.loud() {
+          eprintln!(
+            "Failed to read statistics file `{}`: {}",
+            path.display(),
+            io_error
+          );
+        }
+        Err(EXIT_FAILURE)
+      },
+    }
+  }
+
+  fn summary(&self, metadata: &Metadata) {
+    if metadata.recipes.is_empty() {
       if self.verbosity.loud() {
         eprintln!("Justfile contains no recipes.");
       }
     } else {
-      let summary = justfile
-        .public_recipes(self.unsorted)
+      let summary = metadata
+        .public_recipes(self.sort_order)
         .iter()
-        .map(|recipe| recipe.name())
+        .map(|recipe| recipe.name.as_str())
         .collect::<Vec<&str>>()
         .join(" ");
       println!("{}", summary);
     }
   }
 
-  fn variables(justfile: Justfile) {
-    for (i, (_, assignment)) in justfile.assignments.iter().enumerate() {
-      if i > 0 {
-        print!(" ");
+  /// Print the dependency trees of the public recipes, for
+  /// `--summary --verbose`
+  fn summary_tree(&self, justfile: &Justfile) {
+    if justfile.count() == 0 {
+      if self.verbosity.loud() {
+        eprintln!("Justfile contains no recipes.");
       }
-      print!("{}", assignment.name)
+    } else {
+      print!("{}", Graph::summary(justfile, self.sort_order));
     }
-    println!();
+  }
+
+  fn variables(&self, justfile: &Justfile) {
+    let names = self
+      .sort_order
+      .sorted(justfile.assignments.values())
+      .into_iter()
+      .map(|assignment| assignment.name.lexeme())
+      .collect::<Vec<&str>>();
+
+    println!("{}", names.join(" "));
   }
 }
 
@@ -865,47 +2193,118 @@ USAGE:
     just [FLAGS] [OPTIONS] [--] [ARGUMENTS]...
 
 FLAGS:
-        --choose              Select one or more recipes to run using a binary. If `--chooser` is \
-                                 not passed the chooser
-                              defaults to the value of $JUST_CHOOSER, falling back to `fzf`
-        --clear-shell-args    Clear shell arguments
-        --dry-run             Print what just would do without doing it
-        --dump                Print entire justfile
-    -e, --edit                Edit justfile with editor given by $VISUAL or $EDITOR, falling back \
+        --check                Check formatting with `--fmt` without rewriting the justfile
+        --choose               Select one or more recipes to run using a binary. If `--chooser` is \
+                                 not passed the
+                               chooser defaults to the value of $JUST_CHOOSER, falling back to \
+                                 `fzf`
+        --clear-shell-args     Clear shell arguments
+        --dry-run              Print what just would do without doing it
+        --dump                 Print entire justfile
+    -e, --edit                 Edit justfile with editor given by $VISUAL or $EDITOR, falling back \
                                  to `vim`
-        --evaluate            Evaluate and print all variables. If positional arguments are \
-                                 present, only print the
-                              variables whose names are given as arguments.
-        --highlight           Highlight echoed recipe lines in bold
-        --init                Initialize new justfile in project root
-    -l, --list                List available recipes and their arguments
-        --no-dotenv           Don't load `.env` file
-        --no-highlight        Don't highlight echoed recipe lines in bold
-    -q, --quiet               Suppress all output
-        --summary             List names of available recipes
-    -u, --unsorted            Return list and summary entries in source order
-        --variables           List names of variables
-    -v, --verbose             Use verbose output
+        --evaluate             Evaluate and print all variables, or only those whose names are \
+                                 given as arguments. If a
+                               single name is given, print only its value.
+        --fmt                  Format justfile in place, preserving comments and blank lines
+    -g, --global-justfile      Use global justfile
+        --graph                Print recipe dependency graph
+        --groups               List recipes under headings for their `[group]` attributes with \
+                                 `--list`
+        --highlight            Highlight echoed recipe lines in bold
+        --init                 Initialize new justfile in project root
+    -k, --keep-going           Run remaining recipes given on the command line after one fails
+        --no-dependencies      Run recipes given on the command line without running their \
+                                 dependencies
+        --no-dotenv            Don't load `.env` file
+        --no-function-cache    Call functions which probe the system every time, instead of \
+                                 caching their results
+        --no-highlight         Don't highlight echoed recipe lines in bold
+    -q, --quiet                Suppress all output
+        --safe                 Refuse to evaluate backticks, read environment variables, or run \
+                                 recipes
+        --stats                Print recipe run counts and durations recorded with `--stats-file`
+        --summary              List names of available recipes
+        --timestamps           Prefix echoed recipe lines with the time they were run
+        --timings              Print how long each recipe took to run
+    -u, --unsorted             List recipes and variables in source order instead of by name
+        --variables            List names of variables
+    -v, --verbose              Use verbose output
+        --yes                  Run `[confirm]` recipes without asking for confirmation
 
 OPTIONS:
+        --cache-dir <DIR>
+            Cache recipe metadata for `--list`, `--summary`, and completions in <DIR>
+
+        --changed <PATTERN>
+            Run recipes matching glob <PATTERN> whose `[inputs]` have changed according to git
+
+        --changed-since <REF>                      Compare against <REF> with `--changed`, instead \
+                                 of `HEAD`
         --chooser <CHOOSER>                        Override binary invoked by `--choose`
         --color <COLOR>
             Print colorful output [default: auto]  [possible values: auto, always, never]
 
+        --command <COMMAND>...
+            Run <COMMAND> in the working directory with the justfile's exported variables and \
+                                 dotenv file
+
         --completions <SHELL>
             Print shell completion script for <SHELL> [possible values: zsh, bash, fish, \
                                  powershell, elvish]
 
-    -f, --justfile <JUSTFILE>                      Use <JUSTFILE> as justfile
+        --dotenv-file <FILE>                       Load environment variables from <FILE>, \
+                                 ignoring dotenv settings
+        --echo-truncate <N>
+            Truncate echoed recipe lines longer than <N> characters, or disable truncation if <N> \
+                                 is 0
+
+        --error-context <N>                        Print <N> lines of source before and after \
+                                 errors [default: 0]
+        --focus <RECIPE>
+            Print only <RECIPE>, its dependents, and its dependencies with `--graph`
+
+        --format <FORMAT>
+            Print `--dump` or `--evaluate` output as `json` or `yaml`, or `--graph` output as \
+                                 `dot` [possible values:
+            json, yaml, dot]
+    -f, --justfile <JUSTFILE>                      Use <JUSTFILE> as justfile, or `-` for stdin
+    -l, --list <RECIPE>
+            List available recipes and their arguments, or <RECIPE> and its dependencies
+
         --list-heading <TEXT>                      Print <TEXT> before list
         --list-prefix <TEXT>                       Print <TEXT> before each list item
+        --log-json <PATH>                          Write a JSON line to <PATH> for each recipe, \
+                                 line, and backtick run
+        --report <FORMAT=PATH>
+            Write a report of recipe runs to <PATH> in <FORMAT>. Supported formats: junit
+
+        --search-boundary <BOUNDARY>
+            Stop searching for a justfile at the root of a git submodule or worktree, with \
+                                 `worktree`, or of any git
+            repository, with `repository` [possible values: none, worktree, repository]
+        --search-from <DIR>
+            Search for the justfile upwards from <DIR>, instead of the current directory
+
         --set <VARIABLE> <VALUE>                   Override <VARIABLE> with <VALUE>
+        --set-env <KEY=VALUE>...                   Set <KEY> to <VALUE> in the dotenv file, \
+                                 creating it if needed
+        --shebang <INTERPRETER>                    Run named recipes as scripts with <INTERPRETER>
         --shell <SHELL>                            Invoke <SHELL> to run recipes [default: sh]
         --shell-arg <SHELL-ARG>...                 Invoke shell with <SHELL-ARG> as an argument \
                                  [default: -cu]
-    -s, --show <RECIPE>                            Show information about <RECIPE>
+    -s, --show <RECIPE>
+            Show information about <RECIPE>, and any other recipes given as arguments, which may \
+                                 be globs
+
+        --stats-file <FILE>                        Record recipe run statistics in <FILE>
+        --tee <DIR>                                Copy the output of each recipe to a timestamped \
+                                 log file in <DIR>
+        --timestamp-format <FORMAT>                Use `strftime`-style <FORMAT> for \
+                                 `--timestamps` [default: %H:%M:%S]
     -d, --working-directory <WORKING-DIRECTORY>
-            Use <WORKING-DIRECTORY> as working directory. --justfile must also be set
+            Use <WORKING-DIRECTORY> as working directory. --justfile or --global-justfile must \
+                                 also be set
 
 
 ARGS:
@@ -924,16 +2323,34 @@ ARGS:
     {
       name: $name:ident,
       args: [$($arg:expr),*],
+      $(cache_dir: $cache_dir:expr,)?
       $(color: $color:expr,)?
+      $(dotenv_file: $dotenv_file:expr,)?
       $(dry_run: $dry_run:expr,)?
+      $(echo_truncate: $echo_truncate:expr,)?
+      $(error_context: $error_context:expr,)?
+      $(function_cache: $function_cache:expr,)?
       $(highlight: $highlight:expr,)?
+      $(keep_going: $keep_going:expr,)?
+      $(log_json: $log_json:expr,)?
+      $(no_dependencies: $no_dependencies:expr,)?
+      $(report: $report:expr,)?
+      $(safe: $safe:expr,)?
+      $(search_boundary: $search_boundary:expr,)?
       $(search_config: $search_config:expr,)?
+      $(shebang: $shebang:expr,)?
       $(shell: $shell:expr,)?
       $(shell_args: $shell_args:expr,)?
       $(shell_present: $shell_present:expr,)?
+      $(sort_order: $sort_order:expr,)?
+      $(stats_file: $stats_file:expr,)?
       $(subcommand: $subcommand:expr,)?
-      $(unsorted: $unsorted:expr,)?
+      $(tee: $tee:expr,)?
+      $(timestamp_format: $timestamp_format:expr,)?
+      $(timestamps: $timestamps:expr,)?
+      $(timings: $timings:expr,)?
       $(verbosity: $verbosity:expr,)?
+      $(yes: $yes:expr,)?
     } => {
       #[test]
       fn $name() {
@@ -943,16 +2360,34 @@ ARGS:
         ];
 
         let want = Config {
+          $(cache_dir: $cache_dir,)?
           $(color: $color,)?
+          $(dotenv_file: $dotenv_file,)?
           $(dry_run: $dry_run,)?
+          $(echo_truncate: $echo_truncate,)?
+          $(error_context: $error_context,)?
+          $(function_cache: $function_cache,)?
           $(highlight: $highlight,)?
+          $(keep_going: $keep_going,)?
+          $(log_json: $log_json,)?
+          $(no_dependencies: $no_dependencies,)?
+          $(report: $report,)?
+          $(safe: $safe,)?
+          $(search_boundary: $search_boundary,)?
           $(search_config: $search_config,)?
+          $(shebang: $shebang,)?
           $(shell: $shell.to_string(),)?
           $(shell_args: $shell_args,)?
           $(shell_present: $shell_present,)?
+          $(sort_order: $sort_order,)?
+          $(stats_file: $stats_file,)?
           $(subcommand: $subcommand,)?
-          $(unsorted: $unsorted,)?
+          $(tee: $tee,)?
+          $(timestamp_format: $timestamp_format.to_string(),)?
+          $(timestamps: $timestamps,)?
+          $(timings: $timings,)?
           $(verbosity: $verbosity,)?
+          $(yes: $yes,)?
           ..testing::config(&[])
         };
 
@@ -970,7 +2405,94 @@ ARGS:
     assert_eq!(have, want);
   }
 
-  macro_rules! error {
+  fn environment_arguments(args: &[&str], environment: &[(&str, &str)]) -> Vec<String> {
+    Config::environment_arguments(
+      iter::once("just")
+        .chain(args.iter().cloned())
+        .map(OsString::from)
+        .collect(),
+      |key| {
+        environment
+          .iter()
+          .find(|(name, _)| *name == key)
+          .map(|(_, value)| OsString::from(value))
+      },
+    )
+    .into_iter()
+    .skip(1)
+    .map(|argument| argument.into_string().unwrap())
+    .collect()
+  }
+
+  #[test]
+  fn environment_variable() {
+    assert_eq!(Config::environment_variable(arg::DRY_RUN), "JUST_DRY_RUN");
+    assert_eq!(Config::environment_variable(arg::JUSTFILE), "JUST_JUSTFILE");
+  }
+
+  #[test]
+  fn environment_flags() {
+    assert_eq!(
+      environment_arguments(&["foo"], &[("JUST_DRY_RUN", "true"), ("JUST_YES", "1")]),
+      &["--dry-run", "--yes", "foo"]
+    );
+    assert_eq!(
+      environment_arguments(&[], &[("JUST_DRY_RUN", "false"), ("JUST_YES", "0")]),
+      &[] as &[&str]
+    );
+    assert_eq!(environment_arguments(&[], &[("JUST_VERBOSE", "2")]), &[
+      "--verbose",
+      "--verbose"
+    ]);
+  }
+
+  #[test]
+  fn environment_options() {
+    assert_eq!(
+      environment_arguments(&["foo"], &[
+        ("JUST_COLOR", "never"),
+        ("JUST_SHELL", "bash"),
+        ("JUST_SHELL_ARG", "-c"),
+      ]),
+      &["--color=never", "--shell=bash", "--shell-arg=-c", "foo"]
+    );
+  }
+
+  #[test]
+  fn environment_command_line_precedence() {
+    assert_eq!(
+      environment_arguments(&["--color", "always"], &[("JUST_COLOR", "never")]),
+      &["--color", "always"]
+    );
+    assert_eq!(
+      environment_arguments(&["-g"], &[("JUST_JUSTFILE", "foo")]),
+      &["-g"]
+    );
+    assert_eq!(
+      environment_arguments(&["../build"], &[("JUST_JUSTFILE", "foo")]),
+      &["../build"]
+    );
+  }
+
+  #[test]
+  fn environment_invalid() {
+    assert_eq!(
+      environment_arguments(&[], &[("JUST_COLOR", "purple"), ("JUST_QUIET", "true")]),
+      &["--quiet"]
+    );
+  }
+
+  test! {
+    name: environment_config,
+    args: ["--color=never", "--justfile=foo", "--dry-run"],
+    color: Color::never(),
+    dry_run: true,
+    search_config: SearchConfig::WithJustfile {
+      justfile: PathBuf::from("foo"),
+    },
+  }
+
+  macro_rules! error {
     {
       name: $name:ident,
       args: [$($arg:expr),*],
@@ -1034,6 +2556,18 @@ ARGS:
     args: [],
   }
 
+  test! {
+    name: cache_dir_default,
+    args: [],
+    cache_dir: None,
+  }
+
+  test! {
+    name: cache_dir,
+    args: ["--cache-dir", "foo"],
+    cache_dir: Some(PathBuf::from("foo")),
+  }
+
   test! {
     name: color_default,
     args: [],
@@ -1080,6 +2614,84 @@ ARGS:
     args: ["--dry-run", "--quiet"],
   }
 
+  test! {
+    name: echo_truncate_default,
+    args: [],
+    echo_truncate: None,
+  }
+
+  test! {
+    name: echo_truncate,
+    args: ["--echo-truncate", "80"],
+    echo_truncate: Some(80),
+  }
+
+  error! {
+    name: echo_truncate_invalid,
+    args: ["--echo-truncate", "x"],
+    error: ConfigError::EchoTruncate { value, .. },
+    check: {
+      assert_eq!(value, "x");
+    },
+  }
+
+  test! {
+    name: error_context_default,
+    args: [],
+    error_context: 0,
+  }
+
+  test! {
+    name: error_context,
+    args: ["--error-context", "2"],
+    error_context: 2,
+  }
+
+  error! {
+    name: error_context_invalid,
+    args: ["--error-context", "foo"],
+    error: ConfigError::ErrorContext { value, .. },
+    check: {
+      assert_eq!(value, "foo");
+    },
+  }
+
+  test! {
+    name: report_default,
+    args: [],
+    report: None,
+  }
+
+  test! {
+    name: report_junit,
+    args: ["--report", "junit=report.xml"],
+    report: Some((ReportFormat::Junit, PathBuf::from("report.xml"))),
+  }
+
+  test! {
+    name: report_path_with_equals,
+    args: ["--report", "junit=a=b.xml"],
+    report: Some((ReportFormat::Junit, PathBuf::from("a=b.xml"))),
+  }
+
+  error! {
+    name: report_unknown_format,
+    args: ["--report", "tap=report.tap"],
+    error: ConfigError::Report { value },
+    check: {
+      assert_eq!(value, "tap=report.tap");
+    },
+  }
+
+  error! {
+    name: report_missing_path,
+    args: ["--report", "junit"],
+    error: ConfigError::Report { value },
+    check: {
+      assert_eq!(value, "junit");
+    },
+  }
+
   test! {
     name: highlight_default,
     args: [],
@@ -1116,22 +2728,34 @@ ARGS:
     highlight: false,
   }
 
+  test! {
+    name: safe_default,
+    args: [],
+    safe: false,
+  }
+
+  test! {
+    name: safe,
+    args: ["--safe"],
+    safe: true,
+  }
+
   test! {
     name: unsorted_default,
     args: [],
-    unsorted: false,
+    sort_order: SortOrder::Name,
   }
 
   test! {
     name: unsorted_long,
     args: ["--unsorted"],
-    unsorted: true,
+    sort_order: SortOrder::Source,
   }
 
   test! {
     name: unsorted_short,
     args: ["-u"],
-    unsorted: true,
+    sort_order: SortOrder::Source,
   }
 
   test! {
@@ -1198,200 +2822,657 @@ ARGS:
   }
 
   error! {
-    name: set_bad,
-    args: ["--set", "foo"],
+    name: set_bad,
+    args: ["--set", "foo"],
+  }
+
+  test! {
+    name: shell_default,
+    args: [],
+    shell: "sh",
+    shell_args: vec!["-cu".to_owned()],
+    shell_present: false,
+  }
+
+  test! {
+    name: shell_set,
+    args: ["--shell", "tclsh"],
+    shell: "tclsh",
+    shell_present: true,
+  }
+
+  test! {
+    name: verbosity_default,
+    args: [],
+    verbosity: Verbosity::Taciturn,
+  }
+
+  test! {
+    name: verbosity_long,
+    args: ["--verbose"],
+    verbosity: Verbosity::Loquacious,
+  }
+
+  test! {
+    name: verbosity_loquacious,
+    args: ["-v"],
+    verbosity: Verbosity::Loquacious,
+  }
+
+  test! {
+    name: verbosity_grandiloquent,
+    args: ["-v", "-v"],
+    verbosity: Verbosity::Grandiloquent,
+  }
+
+  test! {
+    name: verbosity_great_grandiloquent,
+    args: ["-v", "-v", "-v"],
+    verbosity: Verbosity::Grandiloquent,
+  }
+
+  test! {
+    name: subcommand_default,
+    args: [],
+    subcommand: Subcommand::Run {
+      arguments: Vec::new(),
+      overrides: map!{},
+    },
+  }
+
+  error! {
+    name: subcommand_conflict_summary,
+    args: ["--list", "--summary"],
+  }
+
+  error! {
+    name: subcommand_conflict_dump,
+    args: ["--list", "--dump"],
+  }
+
+  error! {
+    name: subcommand_conflict_init,
+    args: ["--list", "--init"],
+  }
+
+  error! {
+    name: subcommand_conflict_evaluate,
+    args: ["--list", "--evaluate"],
+  }
+
+  error! {
+    name: subcommand_conflict_show,
+    args: ["--list", "--show"],
+  }
+
+  error! {
+    name: subcommand_conflict_completions,
+    args: ["--list", "--completions"],
+  }
+
+  error! {
+    name: subcommand_conflict_variables,
+    args: ["--list", "--variables"],
+  }
+
+  error! {
+    name: subcommand_conflict_choose,
+    args: ["--list", "--choose"],
+  }
+
+  test! {
+    name: subcommand_complete,
+    args: ["--complete", "--dry-run", "foo", "bar"],
+    subcommand: Subcommand::Complete {
+      words: vec!["--dry-run".to_owned(), "foo".to_owned(), "bar".to_owned()],
+    },
+  }
+
+  test! {
+    name: subcommand_complete_empty,
+    args: ["--complete"],
+    subcommand: Subcommand::Complete { words: Vec::new() },
+  }
+
+  test! {
+    name: subcommand_completions,
+    args: ["--completions", "bash"],
+    subcommand: Subcommand::Completions{shell: "bash".to_owned()},
+  }
+
+  test! {
+    name: subcommand_completions_uppercase,
+    args: ["--completions", "BASH"],
+    subcommand: Subcommand::Completions{shell: "BASH".to_owned()},
+  }
+
+  error! {
+    name: subcommand_completions_invalid,
+    args: ["--completions", "monstersh"],
+  }
+
+  test! {
+    name: subcommand_dump,
+    args: ["--dump"],
+    subcommand: Subcommand::Dump { format: None },
+  }
+
+  test! {
+    name: subcommand_edit,
+    args: ["--edit"],
+    subcommand: Subcommand::Edit,
+  }
+
+  test! {
+    name: subcommand_evaluate,
+    args: ["--evaluate"],
+    subcommand: Subcommand::Evaluate {
+      format: None,
+      overrides: map!{},
+      variables: vec![],
+    },
+  }
+
+  test! {
+    name: subcommand_evaluate_overrides,
+    args: ["--evaluate", "x=y"],
+    subcommand: Subcommand::Evaluate {
+      format: None,
+      overrides: map!{"x": "y"},
+      variables: vec![],
+    },
+  }
+
+  test! {
+    name: subcommand_evaluate_overrides_with_argument,
+    args: ["--evaluate", "x=y", "foo"],
+    subcommand: Subcommand::Evaluate {
+      format: None,
+      overrides: map!{"x": "y"},
+      variables: vec!["foo".to_owned()],
+    },
+  }
+
+  test! {
+    name: subcommand_evaluate_format_json,
+    args: ["--evaluate", "--format", "json"],
+    subcommand: Subcommand::Evaluate {
+      format: Some(OutputFormat::Json),
+      overrides: map!{},
+      variables: vec![],
+    },
+  }
+
+  test! {
+    name: subcommand_evaluate_format_yaml,
+    args: ["--evaluate", "--format", "yaml", "foo"],
+    subcommand: Subcommand::Evaluate {
+      format: Some(OutputFormat::Yaml),
+      overrides: map!{},
+      variables: vec!["foo".to_owned()],
+    },
+  }
+
+  error! {
+    name: format_requires_evaluate,
+    args: ["--format", "json"],
+  }
+
+  test! {
+    name: subcommand_dump_format_json,
+    args: ["--dump", "--format", "json"],
+    subcommand: Subcommand::Dump { format: Some(OutputFormat::Json) },
+  }
+
+  error! {
+    name: format_with_other_subcommand,
+    args: ["--list", "--format", "json"],
+  }
+
+  error! {
+    name: format_invalid,
+    args: ["--evaluate", "--format", "toml"],
+  }
+
+  test! {
+    name: subcommand_changed,
+    args: ["--changed", "test-*"],
+    subcommand: Subcommand::Changed {
+      pattern: String::from("test-*"),
+      since: String::from("HEAD"),
+      overrides: map!{},
+    },
+  }
+
+  test! {
+    name: subcommand_changed_since,
+    args: ["--changed", "test-*", "--changed-since", "origin/main", "foo=bar"],
+    subcommand: Subcommand::Changed {
+      pattern: String::from("test-*"),
+      since: String::from("origin/main"),
+      overrides: map!{"foo": "bar"},
+    },
+  }
+
+  error! {
+    name: changed_since_without_changed,
+    args: ["--changed-since", "HEAD"],
+  }
+
+  error! {
+    name: changed_arguments,
+    args: ["--changed", "*", "build"],
+    error: ConfigError::SubcommandArguments { subcommand, arguments },
+    check: {
+      assert_eq!(subcommand, "--changed");
+      assert_eq!(arguments, &["build"]);
+    },
+  }
+
+  test! {
+    name: subcommand_command,
+    args: ["--command", "cargo", "test", "--release"],
+    subcommand: Subcommand::Command {
+      command: vec![String::from("cargo"), String::from("test"), String::from("--release")],
+      overrides: map!{},
+    },
+  }
+
+  test! {
+    name: subcommand_command_exec,
+    args: ["--set", "foo", "bar", "--exec", "env"],
+    subcommand: Subcommand::Command {
+      command: vec![String::from("env")],
+      overrides: map!{"foo": "bar"},
+    },
+  }
+
+  error! {
+    name: command_without_command,
+    args: ["--command"],
+  }
+
+  test! {
+    name: command_after_arguments,
+    args: ["build", "--command", "env"],
+    subcommand: Subcommand::Run {
+      arguments: vec![String::from("build"), String::from("--command"), String::from("env")],
+      overrides: map!{},
+    },
+  }
+
+  test! {
+    name: subcommand_fmt,
+    args: ["--fmt"],
+    subcommand: Subcommand::Format { check: false },
+  }
+
+  test! {
+    name: subcommand_fmt_check,
+    args: ["--fmt", "--check"],
+    subcommand: Subcommand::Format { check: true },
+  }
+
+  error! {
+    name: check_without_fmt,
+    args: ["--check"],
+  }
+
+  test! {
+    name: subcommand_graph,
+    args: ["--graph"],
+    subcommand: Subcommand::Graph { focus: None, dot: false },
+  }
+
+  test! {
+    name: subcommand_graph_focus,
+    args: ["--graph", "--focus", "build"],
+    subcommand: Subcommand::Graph { focus: Some(String::from("build")), dot: false },
+  }
+
+  test! {
+    name: subcommand_graph_dot,
+    args: ["--graph", "--format", "dot"],
+    subcommand: Subcommand::Graph { focus: None, dot: true },
+  }
+
+  error! {
+    name: graph_format_json,
+    args: ["--graph", "--format", "json"],
+    error: ConfigError::Format { subcommand, format },
+    check: {
+      assert_eq!(subcommand, "--graph");
+      assert_eq!(format, "json");
+    },
+  }
+
+  error! {
+    name: dump_format_dot,
+    args: ["--dump", "--format", "dot"],
+    error: ConfigError::Format { subcommand, format },
+    check: {
+      assert_eq!(subcommand, "--dump");
+      assert_eq!(format, "dot");
+    },
+  }
+
+  error! {
+    name: focus_without_graph,
+    args: ["--focus", "build"],
+  }
+
+  test! {
+    name: subcommand_list_long,
+    args: ["--list"],
+    subcommand: Subcommand::List {
+      recipe: None,
+      groups: false,
+      overrides: map!(),
+    },
+  }
+
+  test! {
+    name: subcommand_list_short,
+    args: ["-l"],
+    subcommand: Subcommand::List {
+      recipe: None,
+      groups: false,
+      overrides: map!(),
+    },
+  }
+
+  test! {
+    name: subcommand_list_groups,
+    args: ["--list", "--groups"],
+    subcommand: Subcommand::List {
+      recipe: None,
+      groups: true,
+      overrides: map!(),
+    },
+  }
+
+  test! {
+    name: subcommand_list_overrides,
+    args: ["--set", "x", "y", "--list"],
+    subcommand: Subcommand::List {
+      recipe: None,
+      groups: false,
+      overrides: map!{"x": "y"},
+    },
+  }
+
+  error! {
+    name: groups_without_list,
+    args: ["--groups"],
+  }
+
+  test! {
+    name: subcommand_list_recipe,
+    args: ["--list", "build"],
+    subcommand: Subcommand::List {
+      recipe: Some(String::from("build")),
+      groups: false,
+      overrides: map!(),
+    },
+  }
+
+  test! {
+    name: subcommand_list_recipe_short,
+    args: ["-l", "build"],
+    subcommand: Subcommand::List {
+      recipe: Some(String::from("build")),
+      groups: false,
+      overrides: map!(),
+    },
+  }
+
+  test! {
+    name: subcommand_set_env,
+    args: ["--set-env", "FOO=bar", "BAZ=a=b", "EMPTY="],
+    subcommand: Subcommand::SetEnv {
+      variables: vec![
+        (String::from("FOO"), String::from("bar")),
+        (String::from("BAZ"), String::from("a=b")),
+        (String::from("EMPTY"), String::new()),
+      ],
+    },
+  }
+
+  error! {
+    name: subcommand_set_env_no_arg,
+    args: ["--set-env"],
+  }
+
+  error! {
+    name: subcommand_set_env_missing_equals,
+    args: ["--set-env", "FOO"],
+    error: ConfigError::SetEnv { value },
+    check: {
+      assert_eq!(value, "FOO");
+    },
+  }
+
+  error! {
+    name: subcommand_set_env_invalid_key,
+    args: ["--set-env", "1FOO=bar"],
+    error: ConfigError::SetEnv { value },
+    check: {
+      assert_eq!(value, "1FOO=bar");
+    },
   }
 
   test! {
-    name: shell_default,
-    args: [],
-    shell: "sh",
-    shell_args: vec!["-cu".to_owned()],
-    shell_present: false,
+    name: subcommand_show_long,
+    args: ["--show", "build"],
+    subcommand: Subcommand::Show { names: vec![String::from("build")] },
   }
 
   test! {
-    name: shell_set,
-    args: ["--shell", "tclsh"],
-    shell: "tclsh",
-    shell_present: true,
+    name: subcommand_show_short,
+    args: ["-s", "build"],
+    subcommand: Subcommand::Show { names: vec![String::from("build")] },
   }
 
   test! {
-    name: verbosity_default,
-    args: [],
-    verbosity: Verbosity::Taciturn,
+    name: subcommand_show_multiple,
+    args: ["--show", "build", "test", "deploy"],
+    subcommand: Subcommand::Show {
+      names: vec![
+        String::from("build"),
+        String::from("test"),
+        String::from("deploy"),
+      ],
+    },
   }
 
-  test! {
-    name: verbosity_long,
-    args: ["--verbose"],
-    verbosity: Verbosity::Loquacious,
+  error! {
+    name: subcommand_show_no_arg,
+    args: ["--show"],
   }
 
   test! {
-    name: verbosity_loquacious,
-    args: ["-v"],
-    verbosity: Verbosity::Loquacious,
+    name: subcommand_summary,
+    args: ["--summary"],
+    subcommand: Subcommand::Summary,
   }
 
   test! {
-    name: verbosity_grandiloquent,
-    args: ["-v", "-v"],
-    verbosity: Verbosity::Grandiloquent,
+    name: subcommand_stats,
+    args: ["--stats"],
+    subcommand: Subcommand::Stats,
   }
 
   test! {
-    name: verbosity_great_grandiloquent,
-    args: ["-v", "-v", "-v"],
-    verbosity: Verbosity::Grandiloquent,
+    name: yes,
+    args: ["--yes"],
+    yes: true,
   }
 
   test! {
-    name: subcommand_default,
+    name: log_json_default,
     args: [],
-    subcommand: Subcommand::Run {
-      arguments: Vec::new(),
-      overrides: map!{},
-    },
+    log_json: None,
   }
 
-  error! {
-    name: subcommand_conflict_summary,
-    args: ["--list", "--summary"],
+  test! {
+    name: log_json,
+    args: ["--log-json", "log.jsonl"],
+    log_json: Some(PathBuf::from("log.jsonl")),
   }
 
-  error! {
-    name: subcommand_conflict_dump,
-    args: ["--list", "--dump"],
+  test! {
+    name: stats_file,
+    args: ["--stats-file", "foo"],
+    stats_file: Some(PathBuf::from("foo")),
   }
 
-  error! {
-    name: subcommand_conflict_init,
-    args: ["--list", "--init"],
+  test! {
+    name: shebang,
+    args: ["--shebang", "bash -x"],
+    shebang: Some("bash -x".to_owned()),
   }
 
-  error! {
-    name: subcommand_conflict_evaluate,
-    args: ["--list", "--evaluate"],
+  test! {
+    name: shebang_alias,
+    args: ["--recipe-shell", "python3"],
+    shebang: Some("python3".to_owned()),
   }
 
-  error! {
-    name: subcommand_conflict_show,
-    args: ["--list", "--show"],
+  test! {
+    name: tee,
+    args: ["--tee", "logs"],
+    tee: Some(PathBuf::from("logs")),
   }
 
-  error! {
-    name: subcommand_conflict_completions,
-    args: ["--list", "--completions"],
+  test! {
+    name: timestamps_default,
+    args: [],
+    timestamp_format: "%H:%M:%S",
+    timestamps: false,
   }
 
-  error! {
-    name: subcommand_conflict_variables,
-    args: ["--list", "--variables"],
+  test! {
+    name: keep_going_default,
+    args: [],
+    keep_going: false,
   }
 
-  error! {
-    name: subcommand_conflict_choose,
-    args: ["--list", "--choose"],
+  test! {
+    name: keep_going_long,
+    args: ["--keep-going"],
+    keep_going: true,
   }
 
   test! {
-    name: subcommand_completions,
-    args: ["--completions", "bash"],
-    subcommand: Subcommand::Completions{shell: "bash".to_owned()},
+    name: keep_going_short,
+    args: ["-k"],
+    keep_going: true,
   }
 
   test! {
-    name: subcommand_completions_uppercase,
-    args: ["--completions", "BASH"],
-    subcommand: Subcommand::Completions{shell: "BASH".to_owned()},
+    name: no_dependencies_default,
+    args: [],
+    no_dependencies: false,
   }
 
-  error! {
-    name: subcommand_completions_invalid,
-    args: ["--completions", "monstersh"],
+  test! {
+    name: no_dependencies,
+    args: ["--no-dependencies"],
+    no_dependencies: true,
   }
 
   test! {
-    name: subcommand_dump,
-    args: ["--dump"],
-    subcommand: Subcommand::Dump,
+    name: function_cache_default,
+    args: [],
+    function_cache: true,
   }
 
   test! {
-    name: subcommand_edit,
-    args: ["--edit"],
-    subcommand: Subcommand::Edit,
+    name: no_function_cache,
+    args: ["--no-function-cache"],
+    function_cache: false,
   }
 
   test! {
-    name: subcommand_evaluate,
-    args: ["--evaluate"],
-    subcommand: Subcommand::Evaluate {
-      overrides: map!{},
-      variables: vec![],
-    },
+    name: timings_default,
+    args: [],
+    timings: false,
   }
 
   test! {
-    name: subcommand_evaluate_overrides,
-    args: ["--evaluate", "x=y"],
-    subcommand: Subcommand::Evaluate {
-      overrides: map!{"x": "y"},
-      variables: vec![],
-    },
+    name: timings,
+    args: ["--timings"],
+    timings: true,
   }
 
   test! {
-    name: subcommand_evaluate_overrides_with_argument,
-    args: ["--evaluate", "x=y", "foo"],
-    subcommand: Subcommand::Evaluate {
-      overrides: map!{"x": "y"},
-      variables: vec!["foo".to_owned()],
-    },
+    name: timestamps,
+    args: ["--timestamps"],
+    timestamps: true,
   }
 
   test! {
-    name: subcommand_list_long,
-    args: ["--list"],
-    subcommand: Subcommand::List,
+    name: timestamp_format,
+    args: ["--timestamp-format", "%F %T"],
+    timestamp_format: "%F %T",
   }
 
-  test! {
-    name: subcommand_list_short,
-    args: ["-l"],
-    subcommand: Subcommand::List,
+  error! {
+    name: timestamp_format_invalid,
+    args: ["--timestamp-format", "%Q"],
+    error: ConfigError::TimestampFormat { format, message },
+    check: {
+      assert_eq!(format, "%Q");
+      assert_eq!(message, "invalid format specifier `%Q`");
+    },
   }
 
   test! {
-    name: subcommand_show_long,
-    args: ["--show", "build"],
-    subcommand: Subcommand::Show { name: String::from("build") },
+    name: dotenv_file,
+    args: ["--dotenv-file", "foo"],
+    dotenv_file: Some(PathBuf::from("foo")),
+  }
+
+  error! {
+    name: dotenv_file_conflicts_with_no_dotenv,
+    args: ["--dotenv-file", "foo", "--no-dotenv"],
   }
 
   test! {
-    name: subcommand_show_short,
-    args: ["-s", "build"],
-    subcommand: Subcommand::Show { name: String::from("build") },
+    name: arguments,
+    args: ["foo", "bar"],
+    subcommand: Subcommand::Run {
+      arguments: vec![String::from("foo"), String::from("bar")],
+      overrides: map!{},
+    },
   }
 
-  error! {
-    name: subcommand_show_no_arg,
-    args: ["--show"],
+  test! {
+    name: arguments_terminator,
+    args: ["foo", "--", "--list", "-v"],
+    subcommand: Subcommand::Run {
+      arguments: vec![String::from("foo"), String::from("--list"), String::from("-v")],
+      overrides: map!{},
+    },
   }
 
   test! {
-    name: subcommand_summary,
-    args: ["--summary"],
-    subcommand: Subcommand::Summary,
+    name: arguments_terminator_before_recipe,
+    args: ["--", "foo", "--list"],
+    subcommand: Subcommand::Run {
+      arguments: vec![String::from("foo"), String::from("--list")],
+      overrides: map!{},
+    },
   }
 
   test! {
-    name: arguments,
-    args: ["foo", "bar"],
+    name: arguments_terminator_with_justfile,
+    args: ["--justfile", "bar", "foo", "--", "--justfile"],
+    search_config: SearchConfig::WithJustfile {
+      justfile: PathBuf::from("bar"),
+    },
     subcommand: Subcommand::Run {
-      arguments: vec![String::from("foo"), String::from("bar")],
+      arguments: vec![String::from("foo"), String::from("--justfile")],
       overrides: map!{},
     },
   }
@@ -1560,6 +3641,145 @@ ARGS:
     subcommand: Subcommand::Run { arguments: vec!["build".to_owned()], overrides: BTreeMap::new() },
   }
 
+  test! {
+    name: search_from,
+    args: ["--search-from", "foo/bar"],
+    search_config: SearchConfig::FromSearchDirectory {
+      search_directory: PathBuf::from("foo/bar"),
+    },
+  }
+
+  test! {
+    name: search_from_with_recipe,
+    args: ["--search-from", "..", "build"],
+    search_config: SearchConfig::FromSearchDirectory {
+      search_directory: PathBuf::from(".."),
+    },
+    subcommand: Subcommand::Run { arguments: vec!["build".to_owned()], overrides: BTreeMap::new() },
+  }
+
+  error! {
+    name: search_from_justfile,
+    args: ["--search-from", "foo", "--justfile", "bar"],
+  }
+
+  error! {
+    name: search_from_global_justfile,
+    args: ["--search-from", "foo", "--global-justfile"],
+  }
+
+  error! {
+    name: search_from_search_directory,
+    args: ["--search-from", "foo", "bar/build"],
+    error: ConfigError::SearchDirConflict,
+  }
+
+  test! {
+    name: search_boundary_default,
+    args: [],
+    search_boundary: SearchBoundary::None,
+  }
+
+  test! {
+    name: search_boundary_worktree,
+    args: ["--search-boundary", "worktree"],
+    search_boundary: SearchBoundary::Worktree,
+  }
+
+  test! {
+    name: search_boundary_repository,
+    args: ["--search-boundary", "repository"],
+    search_boundary: SearchBoundary::Repository,
+  }
+
+  error! {
+    name: search_boundary_invalid,
+    args: ["--search-boundary", "submodule"],
+  }
+
+  test! {
+    name: search_config_stdin,
+    args: ["--justfile", "-"],
+    search_config: SearchConfig::FromStdin {
+      working_directory: None,
+    },
+  }
+
+  test! {
+    name: search_config_stdin_and_working_directory,
+    args: ["--working-directory", "foo", "--justfile", "-"],
+    search_config: SearchConfig::FromStdin {
+      working_directory: Some(PathBuf::from("foo")),
+    },
+  }
+
+  test! {
+    name: search_config_stdin_format_check,
+    args: ["--justfile", "-", "--fmt", "--check"],
+    search_config: SearchConfig::FromStdin {
+      working_directory: None,
+    },
+    subcommand: Subcommand::Format { check: true },
+  }
+
+  error! {
+    name: search_config_stdin_edit,
+    args: ["--justfile", "-", "--edit"],
+    error: ConfigError::StdinJustfile { subcommand },
+    check: {
+      assert_eq!(subcommand, "--edit");
+    },
+  }
+
+  error! {
+    name: search_config_stdin_format,
+    args: ["--justfile", "-", "--fmt"],
+    error: ConfigError::StdinJustfile { subcommand },
+    check: {
+      assert_eq!(subcommand, "--fmt");
+    },
+  }
+
+  test! {
+    name: search_config_global_justfile_long,
+    args: ["--global-justfile"],
+    search_config: SearchConfig::GlobalJustfile {
+      working_directory: None,
+    },
+  }
+
+  test! {
+    name: search_config_global_justfile_short,
+    args: ["-g"],
+    search_config: SearchConfig::GlobalJustfile {
+      working_directory: None,
+    },
+  }
+
+  test! {
+    name: search_config_global_justfile_and_working_directory,
+    args: ["-g", "--working-directory", "foo"],
+    search_config: SearchConfig::GlobalJustfile {
+      working_directory: Some(PathBuf::from("foo")),
+    },
+  }
+
+  error! {
+    name: search_config_global_justfile_and_justfile,
+    args: ["-g", "--justfile", "foo"],
+  }
+
+  error! {
+    name: search_config_working_directory_without_justfile,
+    args: ["--working-directory", "foo"],
+  }
+
+  error! {
+    name: search_directory_conflict_global_justfile,
+    args: ["-g", "foo/build"],
+    error: ConfigError::SearchDirConflict,
+  }
+
   error! {
     name: search_directory_conflict_justfile,
     args: ["--justfile", "bar", "foo/build"],
@@ -1584,10 +3804,25 @@ ARGS:
 
   error! {
     name: list_arguments,
-    args: ["--list", "bar"],
+    args: ["--list", "bar", "baz"],
+  }
+
+  error! {
+    name: stats_arguments,
+    args: ["--stats", "bar"],
+    error: ConfigError::SubcommandArguments { subcommand, arguments },
+    check: {
+      assert_eq!(subcommand, "--stats");
+      assert_eq!(arguments, &["bar"]);
+    },
+  }
+
+  error! {
+    name: graph_arguments,
+    args: ["--graph", "bar"],
     error: ConfigError::SubcommandArguments { subcommand, arguments },
     check: {
-      assert_eq!(subcommand, "--list");
+      assert_eq!(subcommand, "--graph");
       assert_eq!(arguments, &["bar"]);
     },
   }
@@ -1623,12 +3858,12 @@ ARGS:
   }
 
   error! {
-    name: show_arguments,
-    args: ["--show", "foo", "bar"],
-    error: ConfigError::SubcommandArguments { subcommand, arguments },
+    name: show_overrides,
+    args: ["--show", "foo", "bar=baz"],
+    error: ConfigError::SubcommandOverrides { subcommand, overrides },
     check: {
       assert_eq!(subcommand, "--show");
-      assert_eq!(arguments, &["bar"]);
+      assert_eq!(overrides, map!{"bar": "baz"});
     },
   }
 
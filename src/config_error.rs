@@ -11,10 +11,45 @@ pub(crate) enum ConfigError {
   Internal { message: String },
   #[snafu(display("Failed to get current directory: {}", source))]
   CurrentDir { source: io::Error },
+  #[snafu(display("`{}` does not support `--format {}`", subcommand, format))]
+  Format {
+    subcommand: String,
+    format:     String,
+  },
+  #[snafu(display("Invalid value `{}` for `--echo-truncate`: {}", value, source))]
+  EchoTruncate {
+    value:  String,
+    source: num::ParseIntError,
+  },
+  #[snafu(display("Invalid value `{}` for `--error-context`: {}", value, source))]
+  ErrorContext {
+    value:  String,
+    source: num::ParseIntError,
+  },
+  #[snafu(display(
+    "Invalid value `{}` for `--report`, expected `FORMAT=PATH`, where FORMAT is {}",
+    value,
+    List::or_ticked(ReportFormat::VALUES)
+  ))]
+  Report { value: String },
   #[snafu(display(
-    "Path-prefixed recipes may not be used with `--working-directory` or `--justfile`."
+    "Path-prefixed recipes may not be used with `--working-directory`, `--justfile`, \
+     `--global-justfile`, or `--search-from`."
   ))]
   SearchDirConflict,
+  #[snafu(display(
+    "Invalid value `{}` for `--set-env`, expected `KEY=VALUE`, where KEY consists of letters, \
+     digits, `_`, and `.`, and does not start with a digit",
+    value
+  ))]
+  SetEnv { value: String },
+  #[snafu(display(
+    "`{}` may not be used with a justfile read from standard input",
+    subcommand
+  ))]
+  StdinJustfile { subcommand: String },
+  #[snafu(display("Invalid `--timestamp-format` `{}`: {}", format, message))]
+  TimestampFormat { format: String, message: String },
   #[snafu(display(
     "`{}` used with unexpected {}: {}",
     subcommand,
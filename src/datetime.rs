@@ -0,0 +1,310 @@
+use crate::common::*;
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const WEEKDAYS: [&str; 7] = [
+  "Sunday",
+  "Monday",
+  "Tuesday",
+  "Wednesday",
+  "Thursday",
+  "Friday",
+  "Saturday",
+];
+
+const MONTHS: [&str; 12] = [
+  "January",
+  "February",
+  "March",
+  "April",
+  "May",
+  "June",
+  "July",
+  "August",
+  "September",
+  "October",
+  "November",
+  "December",
+];
+
+/// A point in time, broken down into calendar fields in either UTC or the
+/// local time zone, which can be formatted with `strftime`-style format
+/// strings
+#[derive(Debug, PartialEq)]
+pub(crate) struct DateTime {
+  /// Seconds since the unix epoch
+  timestamp: i64,
+  /// Seconds east of UTC
+  offset:    i64,
+  year:      i64,
+  /// 1-12
+  month:     i64,
+  /// 1-31
+  day:       i64,
+  hour:      i64,
+  minute:    i64,
+  second:    i64,
+  /// 0-6, starting on Sunday
+  weekday:   i64,
+  /// 0-365
+  yday:      i64,
+}
+
+impl DateTime {
+  pub(crate) fn now_utc() -> Result<Self, String> {
+    Ok(Self::new(Self::now()?, 0))
+  }
+
+  pub(crate) fn now_local() -> Result<Self, String> {
+    let timestamp = Self::now()?;
+    Ok(Self::new(timestamp, Self::local_offset(timestamp)?))
+  }
+
+  fn now() -> Result<i64, String> {
+    SystemTime::now()
+      .duration_since(UNIX_EPOCH)
+      .map(|duration| duration.as_secs() as i64)
+      .map_err(|error| format!("system time is before the unix epoch: {}", error))
+  }
+
+  fn new(timestamp: i64, offset: i64) -> Self {
+    let local = timestamp + offset;
+    let days = local.div_euclid(86400);
+    let seconds = local.rem_euclid(86400);
+    let (year, month, day) = Self::civil_from_days(days);
+
+    Self {
+      hour: seconds / 3600,
+      minute: seconds / 60 % 60,
+      second: seconds % 60,
+      weekday: (days + 4).rem_euclid(7),
+      yday: days - Self::days_from_civil(year, 1, 1),
+      timestamp,
+      offset,
+      year,
+      month,
+      day,
+    }
+  }
+
+  /// Days since the unix epoch of the given date in the proleptic Gregorian
+  /// calendar, from http://howardhinnant.github.io/date_algorithms.html
+  fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year - era * 400;
+    let doy = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+  }
+
+  /// Inverse of `days_from_civil`
+  fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let doe = days - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+  }
+
+  /// Offset of the local time zone from UTC at `timestamp`, in seconds
+  fn local_offset(timestamp: i64) -> Result<i64, String> {
+    let tm = Self::localtime(timestamp)?;
+
+    let local = Self::days_from_civil(
+      i64::from(tm.tm_year) + 1900,
+      i64::from(tm.tm_mon) + 1,
+      i64::from(tm.tm_mday),
+    ) * 86400
+      + i64::from(tm.tm_hour) * 3600
+      + i64::from(tm.tm_min) * 60
+      + i64::from(tm.tm_sec);
+
+    Ok(local - timestamp)
+  }
+
+  #[cfg(unix)]
+  fn localtime(timestamp: i64) -> Result<libc::tm, String> {
+    let time = timestamp as libc::time_t;
+
+    // SAFETY: `libc::tm` is a plain C struct, for which all zeroes is a valid
+    // value, and `localtime_r` only writes through the pointers it is given.
+    unsafe {
+      let mut tm = std::mem::zeroed::<libc::tm>();
+      if libc::localtime_r(&time, &mut tm).is_null() {
+        return Err("failed to get local time".to_owned());
+      }
+      Ok(tm)
+    }
+  }
+
+  #[cfg(windows)]
+  fn localtime(timestamp: i64) -> Result<libc::tm, String> {
+    extern "C" {
+      #[link_name = "_localtime64_s"]
+      fn localtime_s(tm: *mut libc::tm, time: *const libc::time_t) -> libc::c_int;
+    }
+
+    let time = timestamp as libc::time_t;
+
+    // SAFETY: `libc::tm` is a plain C struct, for which all zeroes is a valid
+    // value, and `localtime_s` only writes through the pointers it is given.
+    unsafe {
+      let mut tm = std::mem::zeroed::<libc::tm>();
+      if localtime_s(&mut tm, &time) != 0 {
+        return Err("failed to get local time".to_owned());
+      }
+      Ok(tm)
+    }
+  }
+
+  /// Format `self` according to `format`, which may contain the following
+  /// `strftime` conversion specifications:
+  ///
+  /// - `%Y`, `%C`, `%y`: year, century, and year without century
+  /// - `%m`, `%B`, `%b`, `%h`: month number, name, and abbreviated name
+  /// - `%d`, `%e`, `%j`: day of month, space padded day of month, and day of
+  ///   year
+  /// - `%H`, `%I`, `%M`, `%S`, `%p`: hour, 12-hour clock hour, minute, second,
+  ///   and AM or PM
+  /// - `%A`, `%a`, `%u`, `%w`: weekday name, abbreviated name, and number,
+  ///   starting from Monday as 1 or Sunday as 0
+  /// - `%z`, `%s`: offset from UTC, and seconds since the unix epoch
+  /// - `%F`, `%T`, `%R`, `%D`: `%Y-%m-%d`, `%H:%M:%S`, `%H:%M`, and `%m/%d/%y`
+  /// - `%n`, `%t`, `%%`: newline, tab, and percent sign
+  pub(crate) fn format(&self, format: &str) -> Result<String, String> {
+    let mut output = String::new();
+    let mut chars = format.chars();
+
+    while let Some(c) = chars.next() {
+      if c != '%' {
+        output.push(c);
+        continue;
+      }
+
+      let specifier = chars
+        .next()
+        .ok_or_else(|| "format string ends with `%`".to_owned())?;
+
+      let field = match specifier {
+        'Y' => self.year.to_string(),
+        'C' => format!("{:02}", self.year.div_euclid(100)),
+        'y' => format!("{:02}", self.year.rem_euclid(100)),
+        'm' => format!("{:02}", self.month),
+        'B' => MONTHS[self.month as usize - 1].to_owned(),
+        'b' | 'h' => MONTHS[self.month as usize - 1][..3].to_owned(),
+        'd' => format!("{:02}", self.day),
+        'e' => format!("{:2}", self.day),
+        'j' => format!("{:03}", self.yday + 1),
+        'H' => format!("{:02}", self.hour),
+        'I' => format!("{:02}", (self.hour + 11) % 12 + 1),
+        'M' => format!("{:02}", self.minute),
+        'S' => format!("{:02}", self.second),
+        'p' => if self.hour < 12 { "AM" } else { "PM" }.to_owned(),
+        'A' => WEEKDAYS[self.weekday as usize].to_owned(),
+        'a' => WEEKDAYS[self.weekday as usize][..3].to_owned(),
+        'u' => (if self.weekday == 0 { 7 } else { self.weekday }).to_string(),
+        'w' => self.weekday.to_string(),
+        'z' => format!(
+          "{}{:02}{:02}",
+          if self.offset < 0 { '-' } else { '+' },
+          self.offset.abs() / 3600,
+          self.offset.abs() / 60 % 60
+        ),
+        's' => self.timestamp.to_string(),
+        'F' => self.format("%Y-%m-%d")?,
+        'T' => self.format("%H:%M:%S")?,
+        'R' => self.format("%H:%M")?,
+        'D' => self.format("%m/%d/%y")?,
+        'n' => "\n".to_owned(),
+        't' => "\t".to_owned(),
+        '%' => "%".to_owned(),
+        other => return Err(format!("invalid format specifier `%{}`", other)),
+      };
+
+      output.push_str(&field);
+    }
+
+    Ok(output)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn epoch() {
+    assert_eq!(
+      DateTime::new(0, 0).format("%F %T %A %j %z %s").unwrap(),
+      "1970-01-01 00:00:00 Thursday 001 +0000 0"
+    );
+  }
+
+  #[test]
+  fn leap_day() {
+    assert_eq!(
+      DateTime::new(951_782_400, 0)
+        .format("%Y-%m-%d %a %j")
+        .unwrap(),
+      "2000-02-29 Tue 060"
+    );
+  }
+
+  #[test]
+  fn before_epoch() {
+    assert_eq!(
+      DateTime::new(-1, 0).format("%F %T %w").unwrap(),
+      "1969-12-31 23:59:59 3"
+    );
+  }
+
+  #[test]
+  fn offset() {
+    assert_eq!(
+      DateTime::new(0, -(5 * 3600 + 30 * 60))
+        .format("%F %R %z %I%p %s")
+        .unwrap(),
+      "1969-12-31 18:30 -0530 06PM 0"
+    );
+  }
+
+  #[test]
+  fn names() {
+    assert_eq!(
+      DateTime::new(1_617_235_200, 0)
+        .format("%B %b %h %e %y %C %u %D%n%t%%")
+        .unwrap(),
+      "April Apr Apr  1 21 20 4 04/01/21\n\t%"
+    );
+  }
+
+  #[test]
+  fn round_trip() {
+    for days in (-1_000_000..1_000_000).step_by(997) {
+      let (year, month, day) = DateTime::civil_from_days(days);
+      assert_eq!(DateTime::days_from_civil(year, month, day), days);
+    }
+  }
+
+  #[test]
+  fn invalid_specifier() {
+    assert_eq!(
+      DateTime::new(0, 0).format("%Q"),
+      Err("invalid format specifier `%Q`".to_owned())
+    );
+  }
+
+  #[test]
+  fn trailing_percent() {
+    assert_eq!(
+      DateTime::new(0, 0).format("%Y%"),
+      Err("format string ends with `%`".to_owned())
+    );
+  }
+}
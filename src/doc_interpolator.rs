@@ -0,0 +1,221 @@
+use crate::common::*;
+
+/// Interpolates the values of variables into recipe doc comments for
+/// `--list`, so that `# deploy to {{env}}` is listed as `# deploy to staging`.
+/// Only interpolations consisting of a single variable name are replaced.
+/// Anything else, as well as variables which fail to evaluate, is left as it
+/// is, so listing never fails because of a doc comment. With
+/// `set metadata-side-effects := false`, variables whose values need a
+/// backtick or an impure function are also left as they are.
+pub(crate) struct DocInterpolator<'src, 'run> {
+  config:         &'run Config,
+  dotenv:         Option<BTreeMap<String, String>>,
+  function_cache: FunctionCache,
+  justfile:       &'run Justfile<'src>,
+  overrides:      &'run BTreeMap<String, String>,
+  search:         &'run Search,
+  values:         BTreeMap<String, Option<String>>,
+}
+
+impl<'src, 'run> DocInterpolator<'src, 'run> {
+  pub(crate) fn new(
+    justfile: &'run Justfile<'src>,
+    config: &'run Config,
+    search: &'run Search,
+    overrides: &'run BTreeMap<String, String>,
+  ) -> Self {
+    Self {
+      config,
+      dotenv: None,
+      function_cache: FunctionCache::new(config),
+      justfile,
+      overrides,
+      search,
+      values: BTreeMap::new(),
+    }
+  }
+
+  pub(crate) fn interpolate(&mut self, doc: &str) -> String {
+    let mut interpolated = String::new();
+    let mut rest = doc;
+
+    while let Some(start) = rest.find("{{") {
+      let end = match rest[start + 2..].find("}}") {
+        Some(end) => start + 2 + end,
+        None => break,
+      };
+
+      interpolated.push_str(&rest[..start]);
+
+      match self.value(rest[start + 2..end].trim()) {
+        Some(value) => interpolated.push_str(&value),
+        None => interpolated.push_str(&rest[start..end + 2]),
+      }
+
+      rest = &rest[end + 2..];
+    }
+
+    interpolated.push_str(rest);
+
+    interpolated
+  }
+
+  /// The value of the variable `name`, if it exists and evaluates
+  /// successfully. Values are evaluated at most once.
+  fn value(&mut self, name: &str) -> Option<String> {
+    if let Some(value) = self.values.get(name) {
+      return value.clone();
+    }
+
+    let assignment = self.justfile.assignments.get(name)?;
+
+    let config = self.config;
+    let justfile = self.justfile;
+    let search = self.search;
+
+    let mut scope = Scope::new();
+
+    for (name, value) in self.overrides {
+      if let Some(assignment) = justfile.assignments.get(name) {
+        scope.bind(assignment.export, assignment.name, value.clone());
+      }
+    }
+
+    let value = if justfile.settings.metadata_side_effects
+      || !Evaluator::has_side_effects(assignment, &justfile.assignments, &scope)
+    {
+      let dotenv = self.dotenv.get_or_insert_with(|| {
+        if config.load_dotenv && !config.safe {
+          load_dotenv(config, &justfile.settings, &search.working_directory).unwrap_or_default()
+        } else {
+          BTreeMap::new()
+        }
+      });
+
+      Evaluator::evaluate_single_assignment(
+        assignment,
+        &justfile.assignments,
+        config,
+        dotenv,
+        &self.function_cache,
+        scope,
+        &justfile.settings,
+        search,
+      )
+      .ok()
+    } else {
+      None
+    };
+
+    self.values.insert(name.to_owned(), value.clone());
+
+    value
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  use pretty_assertions::assert_eq;
+  use testing::compile;
+
+  fn interpolate(justfile: &str, doc: &str) -> String {
+    interpolate_with_overrides(justfile, &BTreeMap::new(), doc)
+  }
+
+  fn interpolate_with_overrides(
+    justfile: &str,
+    overrides: &BTreeMap<String, String>,
+    doc: &str,
+  ) -> String {
+    let justfile = compile(justfile);
+    let config = testing::config(&[]);
+    let search = testing::search(&config);
+    DocInterpolator::new(&justfile, &config, &search, overrides).interpolate(doc)
+  }
+
+  #[test]
+  fn variable() {
+    assert_eq!(
+      interpolate("env := 'prod'", "deploy to {{env}}"),
+      "deploy to prod"
+    );
+  }
+
+  #[test]
+  fn whitespace() {
+    assert_eq!(
+      interpolate("env := 'prod'", "deploy to {{ env }} now"),
+      "deploy to prod now"
+    );
+  }
+
+  #[test]
+  fn dependencies() {
+    assert_eq!(interpolate("a := 'x'\nb := a + 'y'", "{{b}}{{a}}"), "xyx");
+  }
+
+  #[test]
+  fn unknown_variable() {
+    assert_eq!(interpolate("", "deploy to {{env}}"), "deploy to {{env}}");
+  }
+
+  #[test]
+  fn expression() {
+    assert_eq!(
+      interpolate("env := 'prod'", "deploy to {{env + 'x'}}"),
+      "deploy to {{env + 'x'}}"
+    );
+  }
+
+  #[test]
+  fn evaluation_error() {
+    assert_eq!(
+      interpolate("env := error('no')", "deploy to {{env}}"),
+      "deploy to {{env}}"
+    );
+  }
+
+  #[test]
+  fn unterminated() {
+    assert_eq!(interpolate("env := 'prod'", "{{env}} {{env"), "prod {{env");
+  }
+
+  #[test]
+  fn overrides() {
+    let overrides = iter::once(("env".to_owned(), "staging".to_owned())).collect();
+    assert_eq!(
+      interpolate_with_overrides(
+        "env := 'prod'\nurl := env + '.com'",
+        &overrides,
+        "{{env}} {{url}}"
+      ),
+      "staging staging.com"
+    );
+  }
+
+  #[test]
+  fn no_metadata_side_effects() {
+    assert_eq!(
+      interpolate(
+        "set metadata-side-effects := false\na := `echo a`\nb := a + 'b'\nc := uuid()\nd := 'd'",
+        "{{a}} {{b}} {{c}} {{d}}"
+      ),
+      "{{a}} {{b}} {{c}} d"
+    );
+  }
+
+  #[test]
+  fn no_metadata_side_effects_overridden() {
+    let overrides = iter::once(("a".to_owned(), "x".to_owned())).collect();
+    assert_eq!(
+      interpolate_with_overrides(
+        "set metadata-side-effects := false\na := `echo a`\nb := a + 'b'",
+        &overrides,
+        "{{b}}"
+      ),
+      "xb"
+    );
+  }
+}
@@ -0,0 +1,83 @@
+use crate::common::*;
+
+/// An error reading or parsing a dotenv file. Lines and columns count from
+/// one.
+#[derive(Debug)]
+pub(crate) enum DotenvError {
+  /// The file could not be read
+  Io(io::Error),
+  /// A line doesn't start with a variable name
+  InvalidKey { line: usize, column: usize },
+  /// An escape sequence other than those that `DotenvParser` supports
+  InvalidEscape {
+    line:      usize,
+    column:    usize,
+    character: char,
+  },
+  /// A variable name isn't followed by `=`
+  MissingEquals {
+    line:   usize,
+    column: usize,
+    key:    String,
+  },
+  /// Something other than a comment follows a value
+  TrailingCharacters {
+    line:      usize,
+    column:    usize,
+    character: char,
+  },
+  /// A quote or `${` isn't closed before the end of the file
+  Unterminated {
+    line:      usize,
+    column:    usize,
+    delimiter: &'static str,
+  },
+}
+
+impl Display for DotenvError {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    use DotenvError::*;
+
+    match self {
+      Io(io_error) => write!(f, "{}", io_error),
+      InvalidKey { line, column } => write!(
+        f,
+        "Expected variable name on line {}, column {}",
+        line, column
+      ),
+      InvalidEscape {
+        line,
+        column,
+        character,
+      } => write!(
+        f,
+        "Invalid escape sequence `\\{}` on line {}, column {}",
+        character, line, column
+      ),
+      MissingEquals { line, column, key } => write!(
+        f,
+        "Expected `=` after `{}` on line {}, column {}",
+        key, line, column
+      ),
+      TrailingCharacters {
+        line,
+        column,
+        character,
+      } => write!(
+        f,
+        "Unexpected `{}` after value on line {}, column {}, values containing whitespace must be \
+         quoted",
+        character, line, column
+      ),
+      Unterminated {
+        line,
+        column,
+        delimiter,
+      } => write!(
+        f,
+        "Unterminated `{}` starting on line {}, column {}",
+        delimiter, line, column
+      ),
+    }
+  }
+}
@@ -0,0 +1,293 @@
+use crate::common::*;
+
+use std::{thread, time::Duration};
+
+/// The lines of a dotenv file, which can be updated with `--set-env` while
+/// preserving comments, blank lines, and the order of existing variables
+#[derive(Debug, PartialEq)]
+pub(crate) struct DotenvFile {
+  lines: Vec<String>,
+}
+
+impl DotenvFile {
+  /// The dotenv file that `--set-env` updates: the file given with
+  /// `--dotenv-file`, the last of the `dotenv-files` setting, or the nearest
+  /// `.env` file, falling back to a new `.env` file in the working directory
+  pub(crate) fn path(config: &Config, settings: &Settings, working_directory: &Path) -> PathBuf {
+    if let Some(path) = &config.dotenv_file {
+      return config.invocation_directory.join(path);
+    }
+
+    if let Some(file) = settings
+      .dotenv_files
+      .as_ref()
+      .and_then(|files| files.last())
+    {
+      return working_directory.join(&file.cooked);
+    }
+
+    working_directory
+      .ancestors()
+      .map(|directory| directory.join(".env"))
+      .find(|path| path.is_file())
+      .unwrap_or_else(|| working_directory.join(".env"))
+  }
+
+  /// Set `variables` in the dotenv file at `path`, creating it if it doesn't
+  /// exist. Concurrent updates are serialized with a lock file next to
+  /// `path`, to which the new contents are written before it is renamed over
+  /// `path`, so readers never see a partially written file.
+  pub(crate) fn update(path: &Path, variables: &[(String, String)]) -> io::Result<()> {
+    let mut lock_path = path.as_os_str().to_owned();
+    lock_path.push(".lock");
+    let lock_path = PathBuf::from(lock_path);
+
+    let mut attempts = 0;
+
+    let mut lock = loop {
+      match fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&lock_path)
+      {
+        Ok(lock) => break lock,
+        Err(io_error) if io_error.kind() == io::ErrorKind::AlreadyExists && attempts < 500 => {
+          attempts += 1;
+          thread::sleep(Duration::from_millis(10));
+        },
+        Err(io_error) if io_error.kind() == io::ErrorKind::AlreadyExists =>
+          return Err(io::Error::new(
+            io_error.kind(),
+            format!(
+              "Timed out waiting for lock file `{}`, which may need to be removed if no other \
+               process is using it",
+              lock_path.display()
+            ),
+          )),
+        Err(io_error) => return Err(io_error),
+      }
+    };
+
+    let result = (|| {
+      let text = match fs::read_to_string(path) {
+        Ok(text) => {
+          // dotenv files often contain secrets, so keep their permissions
+          lock.set_permissions(fs::metadata(path)?.permissions())?;
+          text
+        },
+        Err(io_error) if io_error.kind() == io::ErrorKind::NotFound => String::new(),
+        Err(io_error) => return Err(io_error),
+      };
+
+      let mut dotenv = Self::parse(&text);
+
+      for (key, value) in variables {
+        dotenv.set(key, value);
+      }
+
+      write!(lock, "{}", dotenv)?;
+      lock.sync_all()?;
+      drop(lock);
+
+      fs::rename(&lock_path, path)
+    })();
+
+    if result.is_err() {
+      fs::remove_file(&lock_path).ok();
+    }
+
+    result
+  }
+
+  pub(crate) fn parse(text: &str) -> Self {
+    Self {
+      lines: text.lines().map(str::to_owned).collect(),
+    }
+  }
+
+  /// Whether `key` may be used as a variable name in a dotenv file
+  pub(crate) fn is_key(key: &str) -> bool {
+    key.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+      && key
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.')
+      && key != "export"
+  }
+
+  /// Set `key` to `value`, replacing the value of every existing definition
+  /// of `key`, or adding a new definition at the end of the file if there are
+  /// none
+  pub(crate) fn set(&mut self, key: &str, value: &str) {
+    let value = Self::quote(value);
+
+    let mut found = false;
+
+    for line in &mut self.lines {
+      if let Some(prefix) = Self::definition(line, key) {
+        *line = format!("{}{}", prefix, value);
+        found = true;
+      }
+    }
+
+    if !found {
+      self.lines.push(format!("{}={}", key, value));
+    }
+  }
+
+  /// If `line` defines `key`, return the part of `line` before the value,
+  /// e.g. `export KEY = `
+  fn definition<'line>(line: &'line str, key: &str) -> Option<&'line str> {
+    let rest = line.trim_start();
+
+    let rest = match rest.strip_prefix("export") {
+      Some(after) if after.starts_with(char::is_whitespace) => after.trim_start(),
+      _ => rest,
+    };
+
+    let rest = rest.strip_prefix(key)?.trim_start();
+
+    let value = rest.strip_prefix('=')?.trim_start();
+
+    Some(&line[..line.len() - value.len()])
+  }
+
+  /// Quote `value` so that it is read back unchanged, using single quotes,
+  /// in which nothing is special, if `value` contains anything other than
+  /// characters which are never special
+  fn quote(value: &str) -> String {
+    if value.chars().all(|c| {
+      c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | '/' | ':' | ',' | '+' | '@' | '%')
+    }) {
+      return value.to_owned();
+    }
+
+    let mut quoted = String::from("'");
+
+    for c in value.chars() {
+      match c {
+        '\'' => quoted.push_str(r"'\''"),
+        '\n' => quoted.push_str(r"'\n'"),
+        _ => quoted.push(c),
+      }
+    }
+
+    quoted.push('\'');
+
+    quoted
+  }
+}
+
+impl Display for DotenvFile {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    for line in &self.lines {
+      writeln!(f, "{}", line)?;
+    }
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn case(text: &str, variables: &[(&str, &str)], expected: &str) {
+    let mut dotenv = DotenvFile::parse(text);
+
+    for (key, value) in variables {
+      dotenv.set(key, value);
+    }
+
+    let actual = dotenv.to_string();
+
+    assert_eq!(actual, expected);
+
+    let parsed = DotenvParser::parse(&actual, &|_| None, &BTreeMap::new())
+      .unwrap()
+      .into_iter()
+      .collect::<BTreeMap<String, String>>();
+
+    for (key, value) in variables {
+      assert_eq!(
+        parsed[*key], *value,
+        "`{}` was not read back unchanged",
+        key
+      );
+    }
+  }
+
+  #[test]
+  fn empty() {
+    case("", &[("FOO", "bar")], "FOO=bar\n");
+  }
+
+  #[test]
+  fn append() {
+    case(
+      "# comment\nA=b",
+      &[("FOO", "bar")],
+      "# comment\nA=b\nFOO=bar\n",
+    );
+  }
+
+  #[test]
+  fn replace() {
+    case(
+      "# comment\nFOO=old # trailing\n\nBAR=baz\n",
+      &[("FOO", "new")],
+      "# comment\nFOO=new\n\nBAR=baz\n",
+    );
+  }
+
+  #[test]
+  fn replace_preserves_prefix() {
+    case(
+      "  export FOO = old\nFOO2=x\nFOO=y",
+      &[("FOO", "new")],
+      "  export FOO = new\nFOO2=x\nFOO=new\n",
+    );
+  }
+
+  #[test]
+  fn export_key() {
+    case("export=old", &[("FOO", "x")], "export=old\nFOO=x\n");
+  }
+
+  #[test]
+  fn quoting() {
+    case(
+      "",
+      &[
+        ("EMPTY", ""),
+        ("SPACE", "a b"),
+        ("DOLLAR", "$HOME"),
+        ("QUOTES", r#"it's "quoted""#),
+        ("HASH", "#not-a-comment"),
+        ("BACKSLASH", r"a\b"),
+        ("NEWLINE", "a\nb"),
+        ("URL", "https://example.com/a-b_c.d?e=f"),
+      ],
+      concat!(
+        "EMPTY=\n",
+        "SPACE='a b'\n",
+        "DOLLAR='$HOME'\n",
+        "QUOTES='it'\\''s \"quoted\"'\n",
+        "HASH='#not-a-comment'\n",
+        "BACKSLASH='a\\b'\n",
+        "NEWLINE='a'\\n'b'\n",
+        "URL='https://example.com/a-b_c.d?e=f'\n",
+      ),
+    );
+  }
+
+  #[test]
+  fn is_key() {
+    assert!(DotenvFile::is_key("FOO"));
+    assert!(DotenvFile::is_key("_foo.bar1"));
+    assert!(!DotenvFile::is_key(""));
+    assert!(!DotenvFile::is_key("1FOO"));
+    assert!(!DotenvFile::is_key("FOO BAR"));
+    assert!(!DotenvFile::is_key("FOO-BAR"));
+    assert!(!DotenvFile::is_key("export"));
+  }
+}
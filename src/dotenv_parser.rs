@@ -0,0 +1,492 @@
+use crate::common::*;
+
+/// Parser for dotenv files, following the conventions of dotenv libraries in
+/// other ecosystems:
+///
+/// - Blank lines and lines starting with `#` are ignored
+/// - Definitions may be prefixed with `export`, so that the file can also be
+///   sourced by a shell
+/// - Values may be unquoted, single-quoted, in which nothing is special, or
+///   double-quoted, in which `\` escapes and `$` expansions are recognized.
+///   Quoted values may span multiple lines, and adjacent values are joined, so
+///   `'it'\''s'` is `it's`.
+/// - Unquoted values end at whitespace, after which only a comment may follow
+/// - `$NAME` and `${NAME}` expand to the value of `NAME` in the environment, or
+///   else the value it was given earlier in the file, or else the empty string
+pub(crate) struct DotenvParser<'run> {
+  chars:       Vec<char>,
+  index:       usize,
+  line:        usize,
+  column:      usize,
+  environment: &'run dyn Fn(&str) -> Option<String>,
+  variables:   BTreeMap<String, String>,
+}
+
+impl<'run> DotenvParser<'run> {
+  /// Parse `text`, returning its definitions in the order that they appear.
+  /// Expansions look up variables with `environment`, and then in `loaded`,
+  /// which holds variables loaded from earlier files, and the definitions
+  /// that precede them in `text`.
+  pub(crate) fn parse(
+    text: &str,
+    environment: &'run dyn Fn(&str) -> Option<String>,
+    loaded: &BTreeMap<String, String>,
+  ) -> Result<Vec<(String, String)>, DotenvError> {
+    let mut parser = Self {
+      chars: text.chars().collect(),
+      index: 0,
+      line: 1,
+      column: 1,
+      variables: loaded.clone(),
+      environment,
+    };
+
+    let mut definitions = Vec::new();
+
+    loop {
+      parser.skip_whitespace();
+
+      match parser.peek() {
+        None => break,
+        Some('\n') => {
+          parser.advance();
+        },
+        Some('#') => parser.skip_line(),
+        Some(_) => {
+          let (key, value) = parser.parse_definition()?;
+          parser.variables.insert(key.clone(), value.clone());
+          definitions.push((key, value));
+        },
+      }
+    }
+
+    Ok(definitions)
+  }
+
+  fn peek(&self) -> Option<char> {
+    self.chars.get(self.index).cloned()
+  }
+
+  fn advance(&mut self) -> Option<char> {
+    let c = self.peek()?;
+
+    self.index += 1;
+
+    if c == '\n' {
+      self.line += 1;
+      self.column = 1;
+    } else {
+      self.column += 1;
+    }
+
+    Some(c)
+  }
+
+  /// Skip whitespace, other than newlines
+  fn skip_whitespace(&mut self) {
+    while let Some(' ') | Some('\t') | Some('\r') = self.peek() {
+      self.advance();
+    }
+  }
+
+  /// Skip to the end of the current line
+  fn skip_line(&mut self) {
+    while !matches!(self.peek(), None | Some('\n')) {
+      self.advance();
+    }
+  }
+
+  fn parse_definition(&mut self) -> Result<(String, String), DotenvError> {
+    let mut key = self.parse_key()?;
+
+    self.skip_whitespace();
+
+    // `export` is either a prefix, or a variable named `export`
+    if key == "export" && self.peek() != Some('=') {
+      key = self.parse_key()?;
+      self.skip_whitespace();
+    }
+
+    if self.peek() != Some('=') {
+      return Err(DotenvError::MissingEquals {
+        line: self.line,
+        column: self.column,
+        key,
+      });
+    }
+
+    self.advance();
+
+    self.skip_whitespace();
+
+    let value = self.parse_value()?;
+
+    Ok((key, value))
+  }
+
+  fn parse_key(&mut self) -> Result<String, DotenvError> {
+    match self.peek() {
+      Some(c) if c.is_ascii_alphabetic() || c == '_' => {},
+      _ =>
+        return Err(DotenvError::InvalidKey {
+          line:   self.line,
+          column: self.column,
+        }),
+    }
+
+    let mut key = String::new();
+
+    while let Some(c) = self.peek() {
+      if c.is_ascii_alphanumeric() || c == '_' || c == '.' {
+        key.push(c);
+        self.advance();
+      } else {
+        break;
+      }
+    }
+
+    Ok(key)
+  }
+
+  fn parse_value(&mut self) -> Result<String, DotenvError> {
+    let mut value = String::new();
+    let mut started = false;
+
+    loop {
+      match self.peek() {
+        None | Some('\n') => break,
+        Some(' ') | Some('\t') | Some('\r') => {
+          self.skip_whitespace();
+          match self.peek() {
+            None | Some('\n') => break,
+            Some('#') => {
+              self.skip_line();
+              break;
+            },
+            Some(character) =>
+              return Err(DotenvError::TrailingCharacters {
+                line: self.line,
+                column: self.column,
+                character,
+              }),
+          }
+        },
+        // `#` only starts a comment if it isn't part of a value
+        Some('#') if !started => {
+          self.skip_line();
+          break;
+        },
+        Some('\'') => {
+          let (line, column) = (self.line, self.column);
+          self.advance();
+          loop {
+            match self.advance() {
+              Some('\'') => break,
+              Some(c) => value.push(c),
+              None =>
+                return Err(DotenvError::Unterminated {
+                  delimiter: "'",
+                  line,
+                  column,
+                }),
+            }
+          }
+        },
+        Some('"') => {
+          let (line, column) = (self.line, self.column);
+          self.advance();
+          loop {
+            match self.peek() {
+              Some('"') => {
+                self.advance();
+                break;
+              },
+              Some('\\') => value.push(self.parse_escape()?),
+              Some('$') => self.parse_expansion(&mut value)?,
+              Some(c) => {
+                value.push(c);
+                self.advance();
+              },
+              None =>
+                return Err(DotenvError::Unterminated {
+                  delimiter: "\"",
+                  line,
+                  column,
+                }),
+            }
+          }
+        },
+        Some('\\') => value.push(self.parse_escape()?),
+        Some('$') => self.parse_expansion(&mut value)?,
+        Some(c) => {
+          value.push(c);
+          self.advance();
+        },
+      }
+
+      started = true;
+    }
+
+    Ok(value)
+  }
+
+  /// Parse an escape sequence starting with `\`, returning the character it
+  /// stands for
+  fn parse_escape(&mut self) -> Result<char, DotenvError> {
+    let (line, column) = (self.line, self.column);
+
+    self.advance();
+
+    let c = match self.advance() {
+      Some(c @ '\\') | Some(c @ '\'') | Some(c @ '"') | Some(c @ '$') | Some(c @ ' ') => c,
+      Some('n') => '\n',
+      Some('r') => '\r',
+      Some('t') => '\t',
+      Some(character) =>
+        return Err(DotenvError::InvalidEscape {
+          line,
+          column,
+          character,
+        }),
+      None => '\\',
+    };
+
+    Ok(c)
+  }
+
+  /// Parse an expansion starting with `$`, pushing its value onto `value`.
+  /// A `$` which isn't followed by a name or `{` is left as is.
+  fn parse_expansion(&mut self, value: &mut String) -> Result<(), DotenvError> {
+    let (line, column) = (self.line, self.column);
+
+    self.advance();
+
+    let mut name = String::new();
+
+    match self.peek() {
+      Some('{') => {
+        self.advance();
+        loop {
+          match self.advance() {
+            Some('}') => break,
+            Some(c) if c != '\n' => name.push(c),
+            _ =>
+              return Err(DotenvError::Unterminated {
+                delimiter: "${",
+                line,
+                column,
+              }),
+          }
+        }
+      },
+      Some(c) if c.is_ascii_alphabetic() || c == '_' =>
+        while let Some(c) = self.peek() {
+          if c.is_ascii_alphanumeric() || c == '_' {
+            name.push(c);
+            self.advance();
+          } else {
+            break;
+          }
+        },
+      _ => {
+        value.push('$');
+        return Ok(());
+      },
+    }
+
+    if let Some(expansion) =
+      (self.environment)(&name).or_else(|| self.variables.get(&name).cloned())
+    {
+      value.push_str(&expansion);
+    }
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  use pretty_assertions::assert_eq;
+
+  fn environment(name: &str) -> Option<String> {
+    match name {
+      "HOME" => Some("/home/user".to_owned()),
+      _ => None,
+    }
+  }
+
+  fn parse(text: &str) -> Result<Vec<(String, String)>, String> {
+    DotenvParser::parse(text, &environment, &BTreeMap::new()).map_err(|error| error.to_string())
+  }
+
+  fn case(text: &str, expected: &[(&str, &str)]) {
+    let expected = expected
+      .iter()
+      .map(|(key, value)| ((*key).to_owned(), (*value).to_owned()))
+      .collect::<Vec<(String, String)>>();
+
+    assert_eq!(parse(text), Ok(expected));
+  }
+
+  fn error(text: &str, expected: &str) {
+    assert_eq!(parse(text), Err(expected.to_owned()));
+  }
+
+  #[test]
+  fn empty() {
+    case("", &[]);
+    case("\n\n  \n", &[]);
+  }
+
+  #[test]
+  fn comments() {
+    case("# comment\nA=b # trailing\nC=d#e\nF= # empty", &[
+      ("A", "b"),
+      ("C", "d#e"),
+      ("F", ""),
+    ]);
+  }
+
+  #[test]
+  fn whitespace() {
+    case("  A = b  \r\nB\t=\tc", &[("A", "b"), ("B", "c")]);
+  }
+
+  #[test]
+  fn export() {
+    case("export A=b\nexport  B = c\nexport=d", &[
+      ("A", "b"),
+      ("B", "c"),
+      ("export", "d"),
+    ]);
+  }
+
+  #[test]
+  fn keys() {
+    case("_a.b1=c", &[("_a.b1", "c")]);
+  }
+
+  #[test]
+  fn single_quotes() {
+    case(r#"A='$HOME \n "b" # c'"#, &[("A", r#"$HOME \n "b" # c"#)]);
+  }
+
+  #[test]
+  fn double_quotes() {
+    case(r#"A="a \"b\" \\ \$HOME\tc\nd # e""#, &[(
+      "A",
+      "a \"b\" \\ $HOME\tc\nd # e",
+    )]);
+  }
+
+  #[test]
+  fn multiline() {
+    case("A='a\nb'\nB=\"c\nd\"\nC=e", &[
+      ("A", "a\nb"),
+      ("B", "c\nd"),
+      ("C", "e"),
+    ]);
+  }
+
+  #[test]
+  fn concatenation() {
+    case(r#"A='it'\''s'"b"c"#, &[("A", "it'sbc")]);
+    case(r"A='a'\n'b'", &[("A", "a\nb")]);
+  }
+
+  #[test]
+  fn unquoted_escapes() {
+    case(r"A=a\ b\$c", &[("A", "a b$c")]);
+  }
+
+  #[test]
+  fn expansion() {
+    case(
+      "A=a\nB=${A}b\nC=\"$A-$HOME\"\nD='$A'\nE=$MISSING.x\nF=${B}${B}",
+      &[
+        ("A", "a"),
+        ("B", "ab"),
+        ("C", "a-/home/user"),
+        ("D", "$A"),
+        ("E", ".x"),
+        ("F", "abab"),
+      ],
+    );
+  }
+
+  #[test]
+  fn expansion_prefers_environment() {
+    case("HOME=a\nB=$HOME", &[("HOME", "a"), ("B", "/home/user")]);
+  }
+
+  #[test]
+  fn expansion_of_loaded_variables() {
+    let loaded = vec![("A".to_owned(), "a".to_owned())]
+      .into_iter()
+      .collect::<BTreeMap<String, String>>();
+
+    assert_eq!(
+      DotenvParser::parse("B=$A\nA=b\nC=$A", &environment, &loaded).unwrap(),
+      vec![
+        ("B".to_owned(), "a".to_owned()),
+        ("A".to_owned(), "b".to_owned()),
+        ("C".to_owned(), "b".to_owned()),
+      ]
+    );
+  }
+
+  #[test]
+  fn lone_dollar() {
+    case("A=$\nB=a$ \nC=\"$1\"", &[
+      ("A", "$"),
+      ("B", "a$"),
+      ("C", "$1"),
+    ]);
+  }
+
+  #[test]
+  fn invalid_key() {
+    error("A=b\n1A=b", "Expected variable name on line 2, column 1");
+    error("export 1=b", "Expected variable name on line 1, column 8");
+  }
+
+  #[test]
+  fn missing_equals() {
+    error(
+      "\n  FOO bar",
+      "Expected `=` after `FOO` on line 2, column 7",
+    );
+    error("FOO", "Expected `=` after `FOO` on line 1, column 4");
+  }
+
+  #[test]
+  fn trailing_characters() {
+    error(
+      "A=b c",
+      "Unexpected `c` after value on line 1, column 5, values containing whitespace must be quoted",
+    );
+  }
+
+  #[test]
+  fn invalid_escape() {
+    error(
+      "A=\n B=\"a\\qb\"",
+      "Invalid escape sequence `\\q` on line 2, column 6",
+    );
+  }
+
+  #[test]
+  fn unterminated() {
+    error(
+      "A=b\nB='c\n\nC=d",
+      "Unterminated `'` starting on line 2, column 3",
+    );
+    error("A=\"b", "Unterminated `\"` starting on line 1, column 3");
+    error(
+      "A=${B\nC=d",
+      "Unterminated `${` starting on line 1, column 3",
+    );
+  }
+}
@@ -0,0 +1,163 @@
+use crate::common::*;
+
+/// A content-addressed cache of files fetched with `download()`. Files are
+/// stored as `SHA256/NAME`, where `NAME` is the last segment of the URL's
+/// path, so each file is only downloaded once, and cached files are verified
+/// against their checksum whenever they are used.
+pub(crate) struct DownloadCache {
+  directory: PathBuf,
+}
+
+impl DownloadCache {
+  /// The cache in the `downloads` subdirectory of `--cache-dir`, relative to
+  /// the invocation directory, if given, and otherwise in
+  /// `$XDG_CACHE_HOME/just/downloads`, falling back to `~/.cache/just/
+  /// downloads`, or `%LOCALAPPDATA%\just\downloads` on Windows
+  pub(crate) fn new(config: &Config) -> Result<Self, String> {
+    let nonempty = |name| env::var_os(name).filter(|value| !value.is_empty());
+
+    let directory = if let Some(cache_dir) = &config.cache_dir {
+      config
+        .invocation_directory
+        .join(cache_dir)
+        .join("downloads")
+    } else {
+      let base = if let Some(cache_home) = nonempty("XDG_CACHE_HOME") {
+        PathBuf::from(cache_home)
+      } else if cfg!(windows) {
+        PathBuf::from(
+          nonempty("LOCALAPPDATA")
+            .ok_or("could not find cache directory: `LOCALAPPDATA` is unset")?,
+        )
+      } else {
+        PathBuf::from(nonempty("HOME").ok_or("could not find cache directory: `HOME` is unset")?)
+          .join(".cache")
+      };
+
+      base.join("just").join("downloads")
+    };
+
+    Ok(Self { directory })
+  }
+
+  /// The path that `url`, whose contents have the digest `sha256`, is cached
+  /// at
+  pub(crate) fn path(&self, url: &str, sha256: &str) -> PathBuf {
+    self.directory.join(sha256).join(Self::file_name(url))
+  }
+
+  /// Return the path of the cached copy of `url`, downloading it with `curl`
+  /// if it isn't cached, or if the cached copy doesn't match `sha256`
+  pub(crate) fn fetch(&self, url: &str, sha256: &str, quiet: bool) -> Result<PathBuf, String> {
+    let path = self.path(url, sha256);
+
+    if let Ok(actual) = Sha256::file(&path) {
+      if actual == sha256 {
+        return Ok(path);
+      }
+    }
+
+    let directory = path.parent().unwrap_or(&self.directory);
+
+    fs::create_dir_all(directory).map_err(|io_error| {
+      format!(
+        "failed to create download directory `{}`: {}",
+        directory.display(),
+        io_error
+      )
+    })?;
+
+    // download to a temporary file in the same directory, so that it can be
+    // moved into place once verified, and interrupted or failed downloads
+    // don't leave a partial file behind
+    let download = tempfile::Builder::new()
+      .prefix(".download")
+      .tempfile_in(directory)
+      .map_err(|io_error| format!("failed to create temporary file: {}", io_error))?;
+
+    let mut command = Command::new("curl");
+
+    command
+      .args(&[
+        "--fail",
+        "--silent",
+        "--show-error",
+        "--location",
+        "--output",
+      ])
+      .arg(download.path())
+      .arg(url)
+      .stderr(if quiet {
+        Stdio::null()
+      } else {
+        Stdio::inherit()
+      });
+
+    InterruptHandler::guard(|| output(command)).map_err(|output_error| match output_error {
+      OutputError::Io(io_error) if io_error.kind() == io::ErrorKind::NotFound =>
+        format!("could not find `curl` to download `{}`", url),
+      _ => format!("failed to download `{}`: {}", url, output_error),
+    })?;
+
+    let actual = Sha256::file(download.path())
+      .map_err(|io_error| format!("failed to read download of `{}`: {}", url, io_error))?;
+
+    if actual != sha256 {
+      return Err(format!(
+        "checksum mismatch for `{}`: expected {}, found {}",
+        url, sha256, actual
+      ));
+    }
+
+    download.persist(&path).map_err(|error| {
+      format!(
+        "failed to move download of `{}` to `{}`: {}",
+        url,
+        path.display(),
+        error.error
+      )
+    })?;
+
+    Ok(path)
+  }
+
+  /// The name that `url` is cached under: the last segment of its path, or
+  /// `download` if it has none
+  fn file_name(url: &str) -> &str {
+    let path = url.split(&['?', '#'][..]).next().unwrap_or_default();
+
+    let path = path.split_once("://").map_or(path, |(_, rest)| rest);
+
+    match path.split_once('/') {
+      Some((_, path)) => path
+        .rsplit('/')
+        .find(|segment| {
+          !segment.is_empty() && *segment != "." && *segment != ".." && !segment.contains('\\')
+        })
+        .unwrap_or("download"),
+      None => "download",
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn file_name() {
+    fn case(url: &str, expected: &str) {
+      assert_eq!(DownloadCache::file_name(url), expected);
+    }
+
+    case("https://example.com/tool-1.0.tar.gz", "tool-1.0.tar.gz");
+    case("https://example.com/a/b/tool?version=1#top", "tool");
+    case("https://example.com/a/tool/", "tool");
+    case("https://example.com/a/..", "a");
+    case("https://example.com", "download");
+    case("https://example.com/", "download");
+    case("https://example.com?a/b", "download");
+    case("file:///tmp/tool", "tool");
+    case("https://example.com/tool/..\\..\\x", "tool");
+  }
+}
@@ -0,0 +1,575 @@
+use crate::common::*;
+
+/// Structured output for `--dump --format`. The output starts with a header
+/// that describes how to interpret the rest of it: the schema version, the
+/// version of just that produced it, and the language features and settings
+/// that the justfile uses.
+pub(crate) struct Dump;
+
+impl Dump {
+  /// Features which may appear in `features`, other than `function:NAME`
+  /// features, which are supported for every built-in function
+  const FEATURES: &'static [&'static str] = &[
+    "alias-arguments",
+    "aliases",
+    "arithmetic",
+    "attribute:choices",
+    "attribute:cleanup",
+    "attribute:confirm",
+    "attribute:doc",
+    "attribute:filter",
+    "attribute:fresh",
+    "attribute:group",
+    "attribute:inputs",
+    "attribute:limit",
+    "attribute:needs-env",
+    "attribute:nice",
+    "attribute:no-cd",
+    "attribute:no-exit-message",
+    "attribute:once",
+    "attribute:path",
+    "attribute:private",
+    "attribute:quiet",
+    "attribute:remember-args",
+    "attribute:requires",
+    "attribute:tee",
+    "attribute:umask",
+    "attribute:verbose",
+    "backticks",
+    "booleans",
+    "coalescing",
+    "comparisons",
+    "conditionals",
+    "dependency-arguments",
+    "exported-parameters",
+    "exports",
+    "integers",
+    "setting:allow-downloads",
+    "setting:backtick-env",
+    "setting:default",
+    "setting:dotenv-files",
+    "setting:dotenv-load",
+    "setting:echo-truncate",
+    "setting:exit-message",
+    "setting:exit-message-template",
+    "setting:export",
+    "setting:list-heading",
+    "setting:list-prefix",
+    "setting:metadata-side-effects",
+    "setting:no-exit-message",
+    "setting:pager",
+    "setting:shell",
+    "setting:strict-interpolation",
+    "setting:tempdir",
+    "setting:timestamps",
+    "setting:unknown-recipe",
+    "setting:windows-powershell",
+    "setting:windows-shell",
+    "shebang-recipes",
+    "variadic-parameters",
+  ];
+  /// Version of the structure of the dump. This is incremented whenever
+  /// fields are removed, or the meaning of existing fields changes. Adding
+  /// new fields does not change the schema version.
+  pub(crate) const SCHEMA_VERSION: u64 = 1;
+
+  /// Whether this version of just supports `feature`, using the same names
+  /// as `features`
+  pub(crate) fn supports(feature: &str) -> bool {
+    match feature.strip_prefix("function:") {
+      Some(function) => crate::function::TABLE.contains_key(function),
+      None => Self::FEATURES.contains(&feature),
+    }
+  }
+
+  pub(crate) fn value(justfile: &Justfile, sort_order: SortOrder) -> OutputValue {
+    OutputValue::object(vec![
+      ("schema_version", Self::SCHEMA_VERSION.into()),
+      ("just_version", env!("CARGO_PKG_VERSION").into()),
+      (
+        "features",
+        Self::features(justfile)
+          .into_iter()
+          .collect::<Vec<String>>()
+          .into(),
+      ),
+      ("settings", Self::settings(&justfile.settings)),
+      (
+        "aliases",
+        OutputValue::object(
+          sort_order
+            .sorted(justfile.aliases.values())
+            .into_iter()
+            .map(|alias| (alias.name.lexeme(), alias.target.name().into())),
+        ),
+      ),
+      (
+        "assignments",
+        OutputValue::object(
+          sort_order
+            .sorted(justfile.assignments.values())
+            .into_iter()
+            .map(|assignment| {
+              (
+                assignment.name.lexeme(),
+                OutputValue::object(vec![
+                  ("export", assignment.export.into()),
+                  ("value", assignment.value.to_string().into()),
+                ]),
+              )
+            }),
+        ),
+      ),
+      (
+        "recipes",
+        OutputValue::object(
+          sort_order
+            .sorted(justfile.recipes.values().map(AsRef::as_ref))
+            .into_iter()
+            .map(|recipe| (recipe.name(), Self::recipe(recipe))),
+        ),
+      ),
+    ])
+  }
+
+  /// Features and settings used by `justfile`. Settings are only included if
+  /// they are set to something other than their default value.
+  fn features(justfile: &Justfile) -> BTreeSet<String> {
+    let mut features = BTreeSet::new();
+
+    let settings = &justfile.settings;
+
+    if settings.allow_downloads {
+      features.insert("setting:allow-downloads".to_owned());
+    }
+
+    if settings.backtick_env != BacktickEnv::default() {
+      features.insert("setting:backtick-env".to_owned());
+    }
+
+    if settings.default.is_some() {
+      features.insert("setting:default".to_owned());
+    }
+
+    if settings.dotenv_files.is_some() {
+      features.insert("setting:dotenv-files".to_owned());
+    }
+
+    if settings.dotenv_load.is_some() {
+      features.insert("setting:dotenv-load".to_owned());
+    }
+
+    if settings.echo_truncate.is_some() {
+      features.insert("setting:echo-truncate".to_owned());
+    }
+
+    if settings.exit_message != ExitMessage::default() {
+      features.insert("setting:exit-message".to_owned());
+    }
+
+    if settings.exit_message_template.is_some() {
+      features.insert("setting:exit-message-template".to_owned());
+    }
+
+    if settings.export {
+      features.insert("setting:export".to_owned());
+    }
+
+    if settings.list_heading.is_some() {
+      features.insert("setting:list-heading".to_owned());
+    }
+
+    if settings.list_prefix.is_some() {
+      features.insert("setting:list-prefix".to_owned());
+    }
+
+    if !settings.metadata_side_effects {
+      features.insert("setting:metadata-side-effects".to_owned());
+    }
+
+    if settings.no_exit_message {
+      features.insert("setting:no-exit-message".to_owned());
+    }
+
+    if settings.pager {
+      features.insert("setting:pager".to_owned());
+    }
+
+    if settings.shell.is_some() {
+      features.insert("setting:shell".to_owned());
+    }
+
+    if settings.strict_interpolation {
+      features.insert("setting:strict-interpolation".to_owned());
+    }
+
+    if settings.tempdir.is_some() {
+      features.insert("setting:tempdir".to_owned());
+    }
+
+    if settings.timestamps {
+      features.insert("setting:timestamps".to_owned());
+    }
+
+    if settings.unknown_recipe != UnknownRecipe::default() {
+      features.insert("setting:unknown-recipe".to_owned());
+    }
+
+    if settings.windows_powershell {
+      features.insert("setting:windows-powershell".to_owned());
+    }
+
+    if settings.windows_shell.is_some() {
+      features.insert("setting:windows-shell".to_owned());
+    }
+
+    if justfile.aliases.len() > 0 {
+      features.insert("aliases".to_owned());
+    }
+
+    if justfile
+      .aliases
+      .values()
+      .any(|alias| !alias.arguments.is_empty())
+    {
+      features.insert("alias-arguments".to_owned());
+    }
+
+    for assignment in justfile.assignments.values() {
+      if assignment.export {
+        features.insert("exports".to_owned());
+      }
+      Self::expression_features(&assignment.value, &mut features);
+    }
+
+    for recipe in justfile.recipes.values() {
+      for attribute in &recipe.attributes {
+        features.insert(format!("attribute:{}", attribute.name()));
+      }
+
+      if recipe.shebang {
+        features.insert("shebang-recipes".to_owned());
+      }
+
+      for parameter in &recipe.parameters {
+        if parameter.export {
+          features.insert("exported-parameters".to_owned());
+        }
+
+        if parameter.kind.is_variadic() {
+          features.insert("variadic-parameters".to_owned());
+        }
+
+        if let Some(default) = &parameter.default {
+          Self::expression_features(default, &mut features);
+        }
+      }
+
+      for dependency in &recipe.dependencies {
+        if !dependency.arguments.is_empty() {
+          features.insert("dependency-arguments".to_owned());
+        }
+
+        for argument in &dependency.arguments {
+          Self::expression_features(argument, &mut features);
+        }
+      }
+
+      for line in &recipe.body {
+        for fragment in &line.fragments {
+          if let Fragment::Interpolation { expression } = fragment {
+            Self::expression_features(expression, &mut features);
+          }
+        }
+      }
+    }
+
+    features
+  }
+
+  fn expression_features(expression: &Expression, features: &mut BTreeSet<String>) {
+    match expression {
+      Expression::Backtick { .. } => {
+        features.insert("backticks".to_owned());
+      },
+      Expression::Call { thunk } => {
+        features.insert(format!("function:{}", thunk.name().lexeme()));
+        match thunk {
+          Thunk::Nullary { .. } => {},
+          Thunk::Unary { arg, .. } => Self::expression_features(arg, features),
+          Thunk::Binary { args: [a, b], .. } => {
+            Self::expression_features(a, features);
+            Self::expression_features(b, features);
+          },
+          Thunk::Ternary { args, .. } =>
+            for arg in args {
+              Self::expression_features(arg, features);
+            },
+          Thunk::UnaryOpt { args: (a, b), .. } => {
+            Self::expression_features(a, features);
+            if let Some(b) = b {
+              Self::expression_features(b, features);
+            }
+          },
+          Thunk::Nary { args, .. } =>
+            for arg in args {
+              Self::expression_features(arg, features);
+            },
+        }
+      },
+      Expression::Arithmetic { lhs, rhs, .. } => {
+        features.insert("arithmetic".to_owned());
+        Self::expression_features(lhs, features);
+        Self::expression_features(rhs, features);
+      },
+      Expression::Boolean { .. } => {
+        features.insert("booleans".to_owned());
+      },
+      Expression::Integer { .. } => {
+        features.insert("integers".to_owned());
+      },
+      Expression::Comparison { lhs, rhs, .. } => {
+        features.insert("comparisons".to_owned());
+        Self::expression_features(lhs, features);
+        Self::expression_features(rhs, features);
+      },
+      Expression::Coalesce { lhs, rhs, .. } => {
+        features.insert("coalescing".to_owned());
+        Self::expression_features(lhs, features);
+        Self::expression_features(rhs, features);
+      },
+      Expression::Concatination { lhs, rhs, .. } => {
+        Self::expression_features(lhs, features);
+        Self::expression_features(rhs, features);
+      },
+      Expression::Conditional {
+        lhs,
+        rhs,
+        then,
+        otherwise,
+        ..
+      } => {
+        features.insert("conditionals".to_owned());
+        for expression in &[lhs, rhs, then, otherwise] {
+          Self::expression_features(expression, features);
+        }
+      },
+      Expression::Group { contents } => Self::expression_features(contents, features),
+      Expression::StringLiteral { .. } | Expression::Variable { .. } => {},
+    }
+  }
+
+  fn settings(settings: &Settings) -> OutputValue {
+    OutputValue::object(vec![
+      ("allow_downloads", settings.allow_downloads.into()),
+      ("backtick_env", settings.backtick_env.to_string().into()),
+      ("default", settings.default.clone().into()),
+      (
+        "dotenv_files",
+        settings
+          .dotenv_files
+          .as_ref()
+          .map(|files| {
+            files
+              .iter()
+              .map(|file| file.cooked.clone())
+              .collect::<Vec<String>>()
+          })
+          .into(),
+      ),
+      ("dotenv_load", settings.dotenv_load.into()),
+      (
+        "echo_truncate",
+        settings.echo_truncate.map(|limit| limit as u64).into(),
+      ),
+      ("exit_message", settings.exit_message.to_string().into()),
+      (
+        "exit_message_template",
+        settings.exit_message_template.clone().into(),
+      ),
+      ("export", settings.export.into()),
+      ("list_heading", settings.list_heading.clone().into()),
+      ("list_prefix", settings.list_prefix.clone().into()),
+      (
+        "metadata_side_effects",
+        settings.metadata_side_effects.into(),
+      ),
+      ("no_exit_message", settings.no_exit_message.into()),
+      ("pager", settings.pager.into()),
+      ("shell", Self::shell(&settings.shell)),
+      ("strict_interpolation", settings.strict_interpolation.into()),
+      ("tempdir", settings.tempdir.clone().into()),
+      ("timestamps", settings.timestamps.into()),
+      ("unknown_recipe", settings.unknown_recipe.to_string().into()),
+      ("windows_powershell", settings.windows_powershell.into()),
+      ("windows_shell", Self::shell(&settings.windows_shell)),
+    ])
+  }
+
+  fn shell(shell: &Option<setting::Shell>) -> OutputValue {
+    shell
+      .as_ref()
+      .map(|shell| {
+        iter::once(&shell.command)
+          .chain(&shell.arguments)
+          .map(|argument| argument.cooked.clone())
+          .collect::<Vec<String>>()
+      })
+      .into()
+  }
+
+  fn recipe(recipe: &Recipe) -> OutputValue {
+    OutputValue::object(vec![
+      (
+        "attributes",
+        recipe
+          .attributes
+          .iter()
+          .map(ToString::to_string)
+          .collect::<Vec<String>>()
+          .into(),
+      ),
+      ("doc", recipe.doc.into()),
+      ("private", recipe.private.into()),
+      ("quiet", recipe.quiet.into()),
+      ("shebang", recipe.shebang.into()),
+      (
+        "parameters",
+        OutputValue::Array(
+          recipe
+            .parameters
+            .iter()
+            .map(|parameter| {
+              OutputValue::object(vec![
+                ("name", parameter.name.lexeme().into()),
+                ("kind", Self::parameter_kind(parameter.kind).into()),
+                ("export", parameter.export.into()),
+                (
+                  "default",
+                  parameter.default.as_ref().map(ToString::to_string).into(),
+                ),
+              ])
+            })
+            .collect(),
+        ),
+      ),
+      (
+        "dependencies",
+        OutputValue::Array(
+          recipe
+            .dependencies
+            .iter()
+            .map(|dependency| {
+              OutputValue::object(vec![
+                ("recipe", dependency.recipe.name().into()),
+                (
+                  "arguments",
+                  dependency
+                    .arguments
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<String>>()
+                    .into(),
+                ),
+              ])
+            })
+            .collect(),
+        ),
+      ),
+      (
+        "body",
+        recipe
+          .body
+          .iter()
+          .map(|line| {
+            line
+              .fragments
+              .iter()
+              .map(|fragment| match fragment {
+                Fragment::Text { token } => token.lexeme().to_owned(),
+                Fragment::Interpolation { expression } => format!("{{{{{}}}}}", expression),
+              })
+              .collect::<String>()
+          })
+          .collect::<Vec<String>>()
+          .into(),
+      ),
+    ])
+  }
+
+  fn parameter_kind(kind: ParameterKind) -> &'static str {
+    match kind {
+      ParameterKind::Singular => "singular",
+      ParameterKind::Plus => "plus",
+      ParameterKind::Star => "star",
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  use crate::testing::compile;
+
+  #[test]
+  fn features_are_supported() {
+    let justfile = compile(
+      r#"
+set allow-downloads
+set backtick-env := "none"
+set default := "clean"
+set dotenv-files := [".env"]
+set dotenv-load
+set echo-truncate := 200
+set exit-message := "minimal"
+set exit-message-template := "{recipe} failed"
+set export
+set list-heading := "Recipes:"
+set list-prefix := "  "
+set metadata-side-effects := false
+set no-exit-message
+set pager
+set shell := ["bash", "-c"]
+set strict-interpolation
+set tempdir := "tmp"
+set timestamps
+set unknown-recipe := "suggest-run"
+set windows-powershell
+set windows-shell := ["pwsh.exe", "-c"]
+
+alias b := build
+alias r := build '1' -v
+
+export x := if `echo a` == "a" { uuid() } else { "b" || "c" }
+y := true == ("a" != "b")
+z := 1 + 2
+
+[cleanup('clean'), confirm, doc('foo'), filter('cat'), group('a'), inputs('*'), no-cd]
+[no-exit-message, path('p'), private, quiet, remember-args, requires('sh'), tee('log')]
+build $p +rest: (dep p)
+  #!/bin/sh
+
+[limit('nofile', '64'), needs-env('HOME': 'home'), nice('1'), umask('022'), verbose]
+clean:
+
+[choices(a: 'x', 'y'), fresh]
+dep a:
+
+[once]
+lint:
+"#,
+    );
+
+    let features = Dump::features(&justfile);
+
+    assert_eq!(features.len(), Dump::FEATURES.len() + 1);
+
+    for feature in features {
+      assert!(Dump::supports(&feature), "`{}` is not supported", feature);
+    }
+
+    assert!(!Dump::supports("modules"));
+    assert!(!Dump::supports("function:nope"));
+  }
+}
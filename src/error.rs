@@ -4,4 +4,35 @@ pub(crate) trait Error: Display {
   fn code(&self) -> i32 {
     EXIT_FAILURE
   }
+
+  /// Whether the error should be printed. Errors from failed recipes aren't
+  /// printed if the recipe has the `[no-exit-message]` attribute, the
+  /// `no-exit-message` setting is set, or `exit-message` is `"none"`.
+  fn print_message(&self) -> bool {
+    true
+  }
+
+  /// Whether the error is printed with an `error: ` prefix. Messages from
+  /// `set exit-message-template` are printed as they are, so they can be
+  /// styled freely.
+  fn prefixed(&self) -> bool {
+    true
+  }
+
+  /// The error as it is printed to stderr, prefixed with `error: `, and with
+  /// `context` lines of source before and after it, where applicable
+  fn render(&self, color: Color, context: usize) -> String {
+    if !self.prefixed() {
+      self.to_string()
+    } else if color.stderr().active() {
+      format!(
+        "{}: {:#2$}",
+        color.stderr().error().paint("error"),
+        self,
+        context
+      )
+    } else {
+      format!("error: {:1$}", self, context)
+    }
+  }
 }
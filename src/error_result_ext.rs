@@ -1,22 +1,30 @@
 use crate::common::*;
 
 pub(crate) trait ErrorResultExt<T> {
-  fn eprint(self, color: Color) -> Result<T, i32>;
+  fn eprint(self, color: Color, context: usize) -> Result<T, i32>;
+
+  /// Like `eprint`, but write the error to `capture` if output is being
+  /// captured
+  fn eprint_to(self, capture: Option<&Capture>, color: Color, context: usize) -> Result<T, i32>;
 }
 
 impl<T, E: Error> ErrorResultExt<T> for Result<T, E> {
-  fn eprint(self, color: Color) -> Result<T, i32> {
+  fn eprint(self, color: Color, context: usize) -> Result<T, i32> {
     match self {
       Ok(ok) => Ok(ok),
       Err(error) => {
-        if color.stderr().active() {
-          eprintln!("{}: {:#}", color.stderr().error().paint("error"), error);
-        } else {
-          eprintln!("error: {}", error);
+        if error.print_message() {
+          eprintln!("{}", error.render(color, context));
         }
-
         Err(error.code())
       },
     }
   }
+
+  fn eprint_to(self, capture: Option<&Capture>, color: Color, context: usize) -> Result<T, i32> {
+    match capture {
+      Some(capture) => self.map_err(|error| capture.error(error, context)),
+      None => self.eprint(color, context),
+    }
+  }
 }
@@ -1,12 +1,17 @@
 use crate::common::*;
 
 pub(crate) struct Evaluator<'src: 'run, 'run> {
-  assignments: Option<&'run Table<'src, Assignment<'src>>>,
-  config:      &'run Config,
-  dotenv:      &'run BTreeMap<String, String>,
-  scope:       Scope<'src, 'run>,
-  settings:    &'run Settings<'run>,
-  search:      &'run Search,
+  assignments:    Option<&'run Table<'src, Assignment<'src>>>,
+  config:         &'run Config,
+  dotenv:         &'run BTreeMap<String, String>,
+  /// Scope whose exports backticks and `env_var` see, if not `scope`
+  environment:    Option<&'run Scope<'src, 'run>>,
+  /// Results of functions which probe the system
+  function_cache: &'run FunctionCache,
+  log:            Option<&'run RunLog>,
+  scope:          Scope<'src, 'run>,
+  settings:       &'run Settings<'run>,
+  search:         &'run Search,
 }
 
 impl<'src, 'run> Evaluator<'src, 'run> {
@@ -14,32 +19,135 @@ impl<'src, 'run> Evaluator<'src, 'run> {
     assignments: &'run Table<'src, Assignment<'src>>,
     config: &'run Config,
     dotenv: &'run BTreeMap<String, String>,
+    function_cache: &'run FunctionCache,
     overrides: Scope<'src, 'run>,
     settings: &'run Settings<'run>,
     search: &'run Search,
+    log: Option<&'run RunLog>,
   ) -> RunResult<'src, Scope<'src, 'run>> {
     let mut evaluator = Evaluator {
+      function_cache,
       scope: overrides,
+      environment: None,
       assignments: Some(assignments),
+      log,
       config,
       dotenv,
       settings,
       search,
     };
 
-    for assignment in assignments.values() {
+    // Evaluate assignments in the order they appear in the justfile, so that
+    // backticks see exported variables that were assigned before them
+    let mut ordered = assignments.values().collect::<Vec<&Assignment>>();
+    ordered.sort_by_key(|assignment| assignment.name.offset);
+
+    for assignment in ordered {
       evaluator.evaluate_assignment(assignment)?;
     }
 
     Ok(evaluator.scope)
   }
 
+  /// Evaluate a single assignment, along with only those assignments that
+  /// it depends on. If that runs a backtick or calls an impure function,
+  /// exported assignments before it are also evaluated, so that it sees the
+  /// same environment as it would when running a recipe.
+  pub(crate) fn evaluate_single_assignment(
+    assignment: &'run Assignment<'src>,
+    assignments: &'run Table<'src, Assignment<'src>>,
+    config: &'run Config,
+    dotenv: &'run BTreeMap<String, String>,
+    function_cache: &'run FunctionCache,
+    overrides: Scope<'src, 'run>,
+    settings: &'run Settings<'run>,
+    search: &'run Search,
+  ) -> RunResult<'src, String> {
+    let mut evaluator = Evaluator {
+      function_cache,
+      scope: overrides,
+      environment: None,
+      assignments: Some(assignments),
+      log: None,
+      config,
+      dotenv,
+      settings,
+      search,
+    };
+
+    if Self::has_side_effects(assignment, assignments, &evaluator.scope) {
+      let mut exported = assignments
+        .values()
+        .filter(|exported| {
+          (exported.export || settings.export) && exported.name.offset < assignment.name.offset
+        })
+        .collect::<Vec<&Assignment>>();
+      exported.sort_by_key(|exported| exported.name.offset);
+
+      for exported in exported {
+        evaluator.evaluate_assignment(exported)?;
+      }
+    }
+
+    evaluator.evaluate_assignment(assignment).map(str::to_owned)
+  }
+
+  /// Whether evaluating `assignment`, or any assignment it refers to which
+  /// isn't already bound in `scope`, runs a backtick or calls an impure
+  /// function
+  pub(crate) fn has_side_effects(
+    assignment: &Assignment<'src>,
+    assignments: &Table<'src, Assignment<'src>>,
+    scope: &Scope<'src, 'run>,
+  ) -> bool {
+    if scope.bound(assignment.name.lexeme()) {
+      return false;
+    }
+
+    assignment.value.has_side_effects()
+      || assignment.value.variables().any(|variable| {
+        assignments
+          .get(variable.lexeme())
+          .map_or(false, |assignment| {
+            Self::has_side_effects(assignment, assignments, scope)
+          })
+      })
+  }
+
+  /// Evaluate `expression`, the value of a boolean setting. Settings can't
+  /// refer to variables, and are evaluated before `.env` files are loaded.
+  pub(crate) fn evaluate_setting(
+    expression: &Expression<'src>,
+    config: &'run Config,
+    dotenv: &'run BTreeMap<String, String>,
+    function_cache: &'run FunctionCache,
+    settings: &'run Settings<'run>,
+    search: &'run Search,
+  ) -> RunResult<'src, String> {
+    let mut evaluator = Evaluator {
+      function_cache,
+      scope: Scope::new(),
+      environment: None,
+      assignments: None,
+      log: None,
+      config,
+      dotenv,
+      settings,
+      search,
+    };
+
+    evaluator.evaluate_expression(expression)
+  }
+
   fn evaluate_assignment(&mut self, assignment: &Assignment<'src>) -> RunResult<'src, &str> {
     let name = assignment.name.lexeme();
 
     if !self.scope.bound(name) {
-      let value = self.evaluate_expression(&assignment.value)?;
+      let (value, fallback) = self.evaluate_with_fallback(&assignment.value)?;
       self.scope.bind(assignment.export, assignment.name, value);
+      if let Some(fallback) = fallback {
+        self.scope.bind_fallback(name, fallback);
+      }
     }
 
     Ok(self.scope.value(name).unwrap())
@@ -49,34 +157,47 @@ impl<'src, 'run> Evaluator<'src, 'run> {
     &mut self,
     expression: &Expression<'src>,
   ) -> RunResult<'src, String> {
+    Ok(self.evaluate_with_fallback(expression)?.0)
+  }
+
+  /// Evaluate `expression`, along with the default its value fell back to,
+  /// if its value is empty because of one
+  fn evaluate_with_fallback(
+    &mut self,
+    expression: &Expression<'src>,
+  ) -> RunResult<'src, (String, Option<Fallback<'src>>)> {
     match expression {
       Expression::Variable { name, .. } => {
         let variable = name.lexeme();
-        if let Some(value) = self.scope.value(variable) {
-          Ok(value.to_owned())
+        let value = if let Some(value) = self.scope.value(variable) {
+          value.to_owned()
         } else if let Some(assignment) = self
           .assignments
           .and_then(|assignments| assignments.get(variable))
         {
-          Ok(self.evaluate_assignment(assignment)?.to_owned())
+          self.evaluate_assignment(assignment)?.to_owned()
         } else {
-          Err(RuntimeError::Internal {
+          return Err(RuntimeError::Internal {
             message: format!("attempted to evaluate undefined variable `{}`", variable),
-          })
-        }
+          });
+        };
+        let fallback = self.scope.fallback(variable).cloned();
+        Ok((value, fallback))
       },
       Expression::Call { thunk } => {
         use Thunk::*;
 
-        let context = FunctionContext {
-          dotenv:               self.dotenv,
-          invocation_directory: &self.config.invocation_directory,
-          search:               self.search,
-        };
+        if self.config.safe && crate::function::IMPURE.contains(&thunk.name().lexeme()) {
+          return Err(RuntimeError::UnsafeFunctionCall {
+            function: *thunk.name(),
+          });
+        }
+
+        let mut fallback = None;
 
-        match thunk {
+        let value = match thunk {
           Nullary { name, function, .. } =>
-            function(&context).map_err(|message| RuntimeError::FunctionCall {
+            function(&self.function_context()).map_err(|message| RuntimeError::FunctionCall {
               function: *name,
               message,
             }),
@@ -85,37 +206,154 @@ impl<'src, 'run> Evaluator<'src, 'run> {
             function,
             arg,
             ..
-          } => function(&context, &self.evaluate_expression(arg)?).map_err(|message| {
-            RuntimeError::FunctionCall {
+          } => {
+            let arg = self.evaluate_expression(arg)?;
+            function(&self.function_context(), &arg).map_err(|message| RuntimeError::FunctionCall {
               function: *name,
               message,
-            }
-          }),
+            })
+          },
           Binary {
             name,
             function,
             args: [a, b],
             ..
-          } => function(
-            &context,
-            &self.evaluate_expression(a)?,
-            &self.evaluate_expression(b)?,
-          )
-          .map_err(|message| RuntimeError::FunctionCall {
-            function: *name,
-            message,
-          }),
-        }
+          } => {
+            let a = self.evaluate_expression(a)?;
+            let b = self.evaluate_expression(b)?;
+
+            if name.lexeme() == "env_var_or_default"
+              && b.is_empty()
+              && crate::function::lookup(&self.function_context(), &a) == Ok(None)
+            {
+              fallback = Some(Fallback::EnvVarOrDefault {
+                function: *name,
+                key:      a.clone(),
+              });
+            }
+
+            function(&self.function_context(), &a, &b).map_err(|message| {
+              RuntimeError::FunctionCall {
+                function: *name,
+                message,
+              }
+            })
+          },
+          Ternary {
+            name,
+            function,
+            args: [a, b, c],
+            ..
+          } => {
+            let a = self.evaluate_expression(a)?;
+            let b = self.evaluate_expression(b)?;
+            let c = self.evaluate_expression(c)?;
+            function(&self.function_context(), &a, &b, &c).map_err(|message| {
+              RuntimeError::FunctionCall {
+                function: *name,
+                message,
+              }
+            })
+          },
+          UnaryOpt {
+            name,
+            function,
+            args: (a, b),
+            ..
+          } => {
+            let a = self.evaluate_expression(a)?;
+            let b = match b {
+              Some(b) => Some(self.evaluate_expression(b)?),
+              None => None,
+            };
+            function(&self.function_context(), &a, b.as_deref()).map_err(|message| {
+              RuntimeError::FunctionCall {
+                function: *name,
+                message,
+              }
+            })
+          },
+          Nary {
+            name,
+            function,
+            args,
+            ..
+          } => {
+            let mut evaluated = Vec::new();
+            for arg in args {
+              evaluated.push(self.evaluate_expression(arg)?);
+            }
+            function(&self.function_context(), &evaluated).map_err(|message| {
+              RuntimeError::FunctionCall {
+                function: *name,
+                message,
+              }
+            })
+          },
+        }?;
+
+        Ok((value, fallback))
       },
-      Expression::StringLiteral { string_literal } => Ok(string_literal.cooked.clone()),
+      Expression::StringLiteral { string_literal } => Ok((string_literal.cooked.clone(), None)),
       Expression::Backtick { contents, token } =>
-        if self.config.dry_run {
-          Ok(format!("`{}`", contents))
+        if self.config.dry_run || !self.settings.side_effects(self.config) {
+          Ok((format!("`{}`", contents), None))
+        } else if self.config.safe {
+          Err(RuntimeError::UnsafeBacktick { token: *token })
         } else {
-          Ok(self.run_backtick(contents, token)?)
+          Ok((self.run_backtick(contents, token)?, None))
         },
-      Expression::Concatination { lhs, rhs } =>
-        Ok(self.evaluate_expression(lhs)? + &self.evaluate_expression(rhs)?),
+      Expression::Boolean { value } => Ok((value.to_string(), None)),
+      Expression::Comparison { lhs, rhs, inverted } => {
+        let lhs = self.evaluate_expression(lhs)?;
+        let rhs = self.evaluate_expression(rhs)?;
+        Ok((((lhs == rhs) != *inverted).to_string(), None))
+      },
+      Expression::Coalesce { lhs, rhs, token } => {
+        let lhs = self.evaluate_expression(lhs)?;
+        if lhs.is_empty() {
+          let (rhs, fallback) = self.evaluate_with_fallback(rhs)?;
+          let fallback = match fallback {
+            None if rhs.is_empty() => Some(Fallback::Coalesce { token: *token }),
+            fallback => fallback,
+          };
+          Ok((rhs, fallback))
+        } else {
+          Ok((lhs, None))
+        }
+      },
+      Expression::Arithmetic {
+        lhs,
+        operator,
+        rhs,
+        token,
+      } => {
+        let lhs = self.evaluate_integer(lhs, *operator, *token)?;
+        let rhs = self.evaluate_integer(rhs, *operator, *token)?;
+
+        match operator.apply(lhs, rhs) {
+          Some(value) => Ok((value.to_string(), None)),
+          None if *operator == Operator::Divide && rhs == 0 =>
+            Err(RuntimeError::DivisionByZero { token: *token }),
+          None => Err(RuntimeError::IntegerOverflow {
+            lhs,
+            operator: *operator,
+            rhs,
+            token: *token,
+          }),
+        }
+      },
+      Expression::Concatination { lhs, rhs, .. } => {
+        let (lhs, lhs_fallback) = self.evaluate_with_fallback(lhs)?;
+        let (rhs, rhs_fallback) = self.evaluate_with_fallback(rhs)?;
+        let value = lhs + &rhs;
+        let fallback = if value.is_empty() {
+          lhs_fallback.or(rhs_fallback)
+        } else {
+          None
+        };
+        Ok((value, fallback))
+      },
       Expression::Conditional {
         lhs,
         rhs,
@@ -127,37 +365,64 @@ impl<'src, 'run> Evaluator<'src, 'run> {
         let rhs = self.evaluate_expression(rhs)?;
         let condition = if *inverted { lhs != rhs } else { lhs == rhs };
         if condition {
-          self.evaluate_expression(then)
+          self.evaluate_with_fallback(then)
         } else {
-          self.evaluate_expression(otherwise)
+          self.evaluate_with_fallback(otherwise)
         }
       },
-      Expression::Group { contents } => self.evaluate_expression(contents),
+      Expression::Group { contents } => self.evaluate_with_fallback(contents),
+      Expression::Integer { value } => Ok((value.to_string(), None)),
     }
   }
 
-  fn run_backtick(&self, raw: &str, token: &Token<'src>) -> RunResult<'src, String> {
-    let mut cmd = self.settings.shell_command(self.config);
+  /// Evaluate `expression`, an operand of `operator`, which must evaluate to
+  /// an integer
+  fn evaluate_integer(
+    &mut self,
+    expression: &Expression<'src>,
+    operator: Operator,
+    token: Token<'src>,
+  ) -> RunResult<'src, i64> {
+    let value = self.evaluate_expression(expression)?;
+
+    value.parse().map_err(|_| RuntimeError::NonIntegerOperand {
+      operator,
+      value,
+      token,
+    })
+  }
 
-    cmd.arg(raw);
+  fn function_context(&self) -> FunctionContext<'src, '_> {
+    FunctionContext {
+      config:               self.config,
+      dotenv:               self.dotenv,
+      function_cache:       self.function_cache,
+      invocation_directory: &self.config.invocation_directory,
+      scope:                self.environment.unwrap_or(&self.scope),
+      search:               self.search,
+      settings:             self.settings,
+    }
+  }
 
-    cmd.current_dir(&self.search.working_directory);
+  fn run_backtick(&self, raw: &str, token: &Token<'src>) -> RunResult<'src, String> {
+    let cmd = self.function_context().backtick_command(raw);
 
-    cmd.export(self.settings, self.dotenv, &self.scope);
+    let start = Instant::now();
 
-    cmd.stdin(process::Stdio::inherit());
+    let result = InterruptHandler::guard(|| output(cmd));
 
-    cmd.stderr(if self.config.verbosity.quiet() {
-      process::Stdio::null()
-    } else {
-      process::Stdio::inherit()
-    });
+    if let Some(log) = self.log {
+      let status = match &result {
+        Ok(_) | Err(OutputError::Utf8(_)) => Some(EXIT_SUCCESS),
+        Err(OutputError::Code(code)) => Some(*code),
+        Err(_) => None,
+      };
+      log.backtick(raw, start.elapsed(), status);
+    }
 
-    InterruptHandler::guard(|| {
-      output(cmd).map_err(|output_error| RuntimeError::Backtick {
-        token: *token,
-        output_error,
-      })
+    result.map_err(|output_error| RuntimeError::Backtick {
+      token: *token,
+      output_error,
     })
   }
 
@@ -179,7 +444,16 @@ impl<'src, 'run> Evaluator<'src, 'run> {
           }
         },
         Fragment::Interpolation { expression } => {
-          evaluated += &self.evaluate_expression(expression)?;
+          let (value, fallback) = self.evaluate_with_fallback(expression)?;
+
+          if let (true, Some(fallback)) = (self.settings.strict_interpolation, fallback) {
+            return Err(RuntimeError::EmptyInterpolation {
+              expression: expression.to_string(),
+              fallback,
+            });
+          }
+
+          evaluated += &value;
         },
       }
     }
@@ -187,29 +461,30 @@ impl<'src, 'run> Evaluator<'src, 'run> {
   }
 
   pub(crate) fn evaluate_parameters(
-    config: &'run Config,
+    context: &'run RecipeContext<'src, 'run>,
     dotenv: &'run BTreeMap<String, String>,
     parameters: &[Parameter<'src>],
     arguments: &[&str],
     scope: &'run Scope<'src, 'run>,
-    settings: &'run Settings,
-    search: &'run Search,
   ) -> RunResult<'src, Scope<'src, 'run>> {
     let mut evaluator = Evaluator {
       assignments: None,
+      environment: Some(scope),
       scope: scope.child(),
-      search,
-      settings,
+      search: context.search,
+      settings: context.settings,
+      log: context.log,
+      config: context.config,
+      function_cache: context.function_cache,
       dotenv,
-      config,
     };
 
-    let mut scope = scope.child();
-
     let mut rest = arguments;
     for parameter in parameters {
+      let mut defaulted = false;
       let value = if rest.is_empty() {
         if let Some(ref default) = parameter.default {
+          defaulted = true;
           evaluator.evaluate_expression(default)?
         } else if parameter.kind == ParameterKind::Star {
           String::new()
@@ -227,26 +502,37 @@ impl<'src, 'run> Evaluator<'src, 'run> {
         rest = &rest[1..];
         value
       };
-      scope.bind(parameter.export, parameter.name, value);
+      let empty = value.is_empty();
+      evaluator
+        .scope
+        .bind(parameter.export, parameter.name, value);
+      if defaulted && empty {
+        evaluator
+          .scope
+          .bind_fallback(parameter.name.lexeme(), Fallback::Parameter {
+            name: parameter.name,
+          });
+      }
     }
 
-    Ok(scope)
+    Ok(evaluator.scope)
   }
 
   pub(crate) fn recipe_evaluator(
-    config: &'run Config,
+    context: &'run RecipeContext<'src, 'run>,
     dotenv: &'run BTreeMap<String, String>,
     scope: &'run Scope<'src, 'run>,
-    settings: &'run Settings,
-    search: &'run Search,
   ) -> Evaluator<'src, 'run> {
     Evaluator {
       assignments: None,
+      environment: None,
       scope: Scope::child(scope),
-      search,
-      settings,
+      search: context.search,
+      settings: context.settings,
+      log: context.log,
+      config: context.config,
+      function_cache: context.function_cache,
       dotenv,
-      config,
     }
   }
 }
@@ -275,8 +561,8 @@ mod tests {
   run_error! {
     name: export_assignment_backtick,
     src: r#"
-      export exported_variable := "A"
       b := `echo $exported_variable`
+      export exported_variable := "A"
 
       recipe:
         echo {{b}}
@@ -290,4 +576,32 @@ mod tests {
       assert_eq!(token.lexeme(), "`echo $exported_variable`");
     }
   }
+
+  run_error! {
+    name: safe_backtick,
+    src: "
+      a := `echo foo`
+
+      b:
+    ",
+    args: ["--safe", "b"],
+    error: RuntimeError::UnsafeBacktick { token },
+    check: {
+      assert_eq!(token.lexeme(), "`echo foo`");
+    }
+  }
+
+  run_error! {
+    name: safe_function_call,
+    src: "
+      a := env_var('HOME')
+
+      b:
+    ",
+    args: ["--safe", "b"],
+    error: RuntimeError::UnsafeFunctionCall { function },
+    check: {
+      assert_eq!(function.lexeme(), "env_var");
+    }
+  }
 }
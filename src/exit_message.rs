@@ -0,0 +1,64 @@
+use crate::common::*;
+
+/// How much is printed when a recipe fails, set with `set exit-message`
+#[derive(Debug, PartialEq, Clone, Copy, EnumString, IntoStaticStr)]
+#[strum(serialize_all = "kebab_case")]
+pub(crate) enum ExitMessage {
+  /// The recipe name, line number, and exit code or signal
+  Full,
+  /// Only the recipe name
+  Minimal,
+  /// Nothing, as with `set no-exit-message`
+  None,
+}
+
+impl ExitMessage {
+  pub(crate) const VALUES: &'static [&'static str] = &["full", "minimal", "none"];
+
+  /// Expand the `{recipe}`, `{line}`, and `{code}` placeholders in
+  /// `template`, which is set with `set exit-message-template`. `{line}` is
+  /// empty for shebang recipes, which don't fail on a particular line.
+  pub(crate) fn expand(
+    template: &str,
+    recipe: &str,
+    line_number: Option<usize>,
+    code: i32,
+  ) -> String {
+    template
+      .replace("{recipe}", recipe)
+      .replace(
+        "{line}",
+        &line_number.map(|n| n.to_string()).unwrap_or_default(),
+      )
+      .replace("{code}", &code.to_string())
+  }
+}
+
+impl Default for ExitMessage {
+  fn default() -> Self {
+    Self::Full
+  }
+}
+
+impl Display for ExitMessage {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(f, "{}", <&str>::from(self))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn expand() {
+    assert_eq!(
+      ExitMessage::expand("{recipe}:{line}: exit {code}", "build", Some(3), 2),
+      "build:3: exit 2"
+    );
+    assert_eq!(
+      ExitMessage::expand("{recipe}:{line}: {other}", "build", None, 2),
+      "build:: {other}"
+    );
+  }
+}
@@ -1,24 +1,51 @@
 use crate::common::*;
 
+use std::mem;
+
 /// An expression. Note that the Just language grammar has both an `expression`
-/// production of additions (`a + b`) and values, and a `value` production of
-/// all other value types (for example strings, function calls, and
-/// parenthetical groups).
+/// production of comparisons (`a == b`), coalescings (`a || b`), sums
+/// (`a + b`), products (`a * b`), and values, and a `value` production of all
+/// other value types (for example strings, integers, booleans, function calls,
+/// and parenthetical groups).
 ///
 /// The parser parses both values and expressions into `Expression`s.
 #[derive(PartialEq, Debug)]
 pub(crate) enum Expression<'src> {
+  /// `lhs + rhs`, where one of `lhs` and `rhs` is an integer expression, or
+  /// both are integer-valued variables, `lhs - rhs`, `lhs * rhs`, or
+  /// `lhs / rhs`
+  Arithmetic {
+    lhs:      Box<Expression<'src>>,
+    operator: Operator,
+    rhs:      Box<Expression<'src>>,
+    token:    Token<'src>,
+  },
   /// `contents`
   Backtick {
     contents: String,
     token:    Token<'src>,
   },
+  /// `true` or `false`
+  Boolean { value: bool },
   /// `name(arguments)`
   Call { thunk: Thunk<'src> },
-  /// `lhs + rhs`
+  /// `lhs || rhs`, which is `rhs` if `lhs` is empty, and otherwise `lhs`
+  Coalesce {
+    lhs:   Box<Expression<'src>>,
+    rhs:   Box<Expression<'src>>,
+    token: Token<'src>,
+  },
+  /// `lhs == rhs` or `lhs != rhs`, which is `true` or `false`
+  Comparison {
+    lhs:      Box<Expression<'src>>,
+    rhs:      Box<Expression<'src>>,
+    inverted: bool,
+  },
+  /// `lhs + rhs`, where `lhs` and `rhs` aren't both integers
   Concatination {
-    lhs: Box<Expression<'src>>,
-    rhs: Box<Expression<'src>>,
+    lhs:   Box<Expression<'src>>,
+    rhs:   Box<Expression<'src>>,
+    token: Token<'src>,
   },
   /// `if lhs == rhs { then } else { otherwise }`
   Conditional {
@@ -30,6 +57,8 @@ pub(crate) enum Expression<'src> {
   },
   /// `(contents)`
   Group { contents: Box<Expression<'src>> },
+  /// `123`
+  Integer { value: i64 },
   /// `"string_literal"` or `'string_literal'`
   StringLiteral { string_literal: StringLiteral<'src> },
   /// `variable`
@@ -37,6 +66,111 @@ pub(crate) enum Expression<'src> {
 }
 
 impl<'src> Expression<'src> {
+  /// Whether this expression always evaluates to an integer, which is the
+  /// case for integer literals and arithmetic, in which case `+` adds, rather
+  /// than concatenates
+  pub(crate) fn is_integer(&self) -> bool {
+    match self {
+      Self::Arithmetic { .. } | Self::Integer { .. } => true,
+      Self::Group { contents } => contents.is_integer(),
+      _ => false,
+    }
+  }
+
+  /// Whether this expression evaluates to an integer, given the names of the
+  /// variables which do. This is the case for integer expressions, variables
+  /// in `integers`, and sums of those.
+  pub(crate) fn is_integer_valued(&self, integers: &BTreeSet<&str>) -> bool {
+    match self {
+      Self::Concatination { lhs, rhs, .. } =>
+        lhs.is_integer_valued(integers) && rhs.is_integer_valued(integers),
+      Self::Group { contents } => contents.is_integer_valued(integers),
+      Self::Variable { name } => integers.contains(name.lexeme()),
+      _ => self.is_integer(),
+    }
+  }
+
+  /// Turn each `+` in this expression whose operands both evaluate to
+  /// integers, given the names of the variables which do, into addition
+  pub(crate) fn resolve_additions(&mut self, integers: &BTreeSet<&str>) {
+    match self {
+      Self::Arithmetic { lhs, rhs, .. }
+      | Self::Coalesce { lhs, rhs, .. }
+      | Self::Comparison { lhs, rhs, .. }
+      | Self::Concatination { lhs, rhs, .. } => {
+        lhs.resolve_additions(integers);
+        rhs.resolve_additions(integers);
+      },
+      Self::Conditional {
+        lhs,
+        rhs,
+        then,
+        otherwise,
+        ..
+      } => {
+        lhs.resolve_additions(integers);
+        rhs.resolve_additions(integers);
+        then.resolve_additions(integers);
+        otherwise.resolve_additions(integers);
+      },
+      Self::Call { thunk } =>
+        for argument in thunk.arguments_mut() {
+          argument.resolve_additions(integers);
+        },
+      Self::Group { contents } => contents.resolve_additions(integers),
+      Self::Backtick { .. }
+      | Self::Boolean { .. }
+      | Self::Integer { .. }
+      | Self::StringLiteral { .. }
+      | Self::Variable { .. } => {},
+    }
+
+    if self.is_integer_valued(integers) {
+      if let Self::Concatination { lhs, rhs, token } = self {
+        *self = Self::Arithmetic {
+          lhs:      mem::replace(lhs, Box::new(Self::Integer { value: 0 })),
+          operator: Operator::Add,
+          rhs:      mem::replace(rhs, Box::new(Self::Integer { value: 0 })),
+          token:    *token,
+        };
+      }
+    }
+  }
+
+  /// Whether evaluating this expression, not counting the variables it
+  /// refers to, runs a backtick or calls an impure function
+  pub(crate) fn has_side_effects(&self) -> bool {
+    match self {
+      Self::Arithmetic { lhs, rhs, .. }
+      | Self::Coalesce { lhs, rhs, .. }
+      | Self::Comparison { lhs, rhs, .. }
+      | Self::Concatination { lhs, rhs, .. } => lhs.has_side_effects() || rhs.has_side_effects(),
+      Self::Conditional {
+        lhs,
+        rhs,
+        then,
+        otherwise,
+        ..
+      } =>
+        lhs.has_side_effects()
+          || rhs.has_side_effects()
+          || then.has_side_effects()
+          || otherwise.has_side_effects(),
+      Self::Call { thunk } =>
+        crate::function::IMPURE.contains(&thunk.name().lexeme())
+          || thunk
+            .arguments()
+            .into_iter()
+            .any(Expression::has_side_effects),
+      Self::Group { contents } => contents.has_side_effects(),
+      Self::Backtick { .. } => true,
+      Self::Boolean { .. }
+      | Self::Integer { .. }
+      | Self::StringLiteral { .. }
+      | Self::Variable { .. } => false,
+    }
+  }
+
   pub(crate) fn variables<'expression>(&'expression self) -> Variables<'expression, 'src> {
     Variables::new(self)
   }
@@ -45,8 +179,15 @@ impl<'src> Expression<'src> {
 impl<'src> Display for Expression<'src> {
   fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
     match self {
+      Expression::Arithmetic {
+        lhs, operator, rhs, ..
+      } => write!(f, "{} {} {}", lhs, operator, rhs),
       Expression::Backtick { contents, .. } => write!(f, "`{}`", contents),
-      Expression::Concatination { lhs, rhs } => write!(f, "{} + {}", lhs, rhs),
+      Expression::Boolean { value } => write!(f, "{}", value),
+      Expression::Coalesce { lhs, rhs, .. } => write!(f, "{} || {}", lhs, rhs),
+      Expression::Comparison { lhs, rhs, inverted } =>
+        write!(f, "{} {} {}", lhs, if *inverted { "!=" } else { "==" }, rhs),
+      Expression::Concatination { lhs, rhs, .. } => write!(f, "{} + {}", lhs, rhs),
       Expression::Conditional {
         lhs,
         rhs,
@@ -66,6 +207,7 @@ impl<'src> Display for Expression<'src> {
       Expression::Variable { name } => write!(f, "{}", name.lexeme()),
       Expression::Call { thunk } => write!(f, "{}", thunk),
       Expression::Group { contents } => write!(f, "({})", contents),
+      Expression::Integer { value } => write!(f, "{}", value),
     }
   }
 }
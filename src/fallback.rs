@@ -0,0 +1,40 @@
+use crate::common::*;
+
+/// The default that an empty value fell back to, which is an error to
+/// interpolate with `set strict-interpolation`
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Fallback<'src> {
+  /// `lhs || rhs`, where `lhs` was empty
+  Coalesce { token: Token<'src> },
+  /// `env_var_or_default(key, default)`, where `key` wasn't set
+  EnvVarOrDefault {
+    function: Name<'src>,
+    key:      String,
+  },
+  /// A parameter which wasn't passed an argument
+  Parameter { name: Name<'src> },
+}
+
+impl<'src> Fallback<'src> {
+  pub(crate) fn token(&self) -> Token<'src> {
+    match self {
+      Self::Coalesce { token } => *token,
+      Self::EnvVarOrDefault { function, .. } => function.token(),
+      Self::Parameter { name } => name.token(),
+    }
+  }
+}
+
+impl Display for Fallback<'_> {
+  fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+    match self {
+      Self::Coalesce { .. } => write!(f, "the left-hand side of `||` is empty"),
+      Self::EnvVarOrDefault { key, .. } => write!(f, "environment variable `{}` is not set", key),
+      Self::Parameter { name } => write!(
+        f,
+        "parameter `{}` was not passed an argument",
+        name.lexeme()
+      ),
+    }
+  }
+}
@@ -0,0 +1,89 @@
+use crate::common::*;
+
+use crate::tee::copy;
+
+use std::process::ExitStatus;
+
+/// Run `command` with its stdout and stderr piped through `filter`, which
+/// inherits just's stdout and stderr, unless they are being captured to
+/// `output`, and copied to `log`, if given. Returns the status of `command`,
+/// or an error if `filter` could not be run or did not succeed.
+pub(crate) fn filter(
+  command: &mut Command,
+  mut filter: Command,
+  log: Option<&Sink>,
+  output: Option<&Capture>,
+) -> Result<io::Result<ExitStatus>, OutputError> {
+  if output.is_some() {
+    filter.stdout(Stdio::piped()).stderr(Stdio::piped());
+  }
+
+  let mut filter = filter
+    .stdin(Stdio::piped())
+    .spawn()
+    .map_err(OutputError::Io)?;
+
+  let mut output_copiers = Vec::new();
+
+  if let Some(output) = output {
+    if let Some(stdout) = filter.stdout.take() {
+      output_copiers.push(copy(stdout, vec![output.stdout()]));
+    }
+
+    if let Some(stderr) = filter.stderr.take() {
+      output_copiers.push(copy(stderr, vec![output.stderr()]));
+    }
+  }
+
+  let input: Sink = Arc::new(Mutex::new(filter.stdin.take().ok_or_else(|| {
+    OutputError::Io(io::Error::new(
+      io::ErrorKind::Other,
+      "filter stdin not captured",
+    ))
+  })?));
+
+  let sinks = || iter::once(Arc::clone(&input)).chain(log.cloned()).collect();
+
+  let status = command
+    .stdout(Stdio::piped())
+    .stderr(Stdio::piped())
+    .spawn()
+    .and_then(|mut child| {
+      let mut copiers = Vec::new();
+
+      if let Some(stdout) = child.stdout.take() {
+        copiers.push(copy(stdout, sinks()));
+      }
+
+      if let Some(stderr) = child.stderr.take() {
+        copiers.push(copy(stderr, sinks()));
+      }
+
+      let status = child.wait();
+
+      for copier in copiers {
+        copier.join().ok();
+      }
+
+      status
+    });
+
+  // close the filter's stdin, so that it sees end of file and exits
+  drop(input);
+
+  let filter_status = filter.wait().map_err(OutputError::Io)?;
+
+  for copier in output_copiers {
+    copier.join().ok();
+  }
+
+  match filter_status.code() {
+    Some(0) => Ok(status),
+    Some(code) => Err(OutputError::Code(code)),
+    None => Err(
+      Platform::signal_from_exit_status(filter_status)
+        .map(OutputError::Signal)
+        .unwrap_or(OutputError::Unknown),
+    ),
+  }
+}
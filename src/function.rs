@@ -2,42 +2,238 @@ use crate::common::*;
 
 use Function::*;
 
+use std::thread;
+
 pub(crate) enum Function {
   Nullary(fn(&FunctionContext) -> Result<String, String>),
   Unary(fn(&FunctionContext, &str) -> Result<String, String>),
   Binary(fn(&FunctionContext, &str, &str) -> Result<String, String>),
+  Ternary(fn(&FunctionContext, &str, &str, &str) -> Result<String, String>),
+  UnaryOpt(fn(&FunctionContext, &str, Option<&str>) -> Result<String, String>),
+  /// Takes any number of arguments in the given range, whose end may be
+  /// `usize::MAX` for functions which take an unlimited number of arguments
+  Nary(
+    fn(&FunctionContext, &[String]) -> Result<String, String>,
+    Range<usize>,
+  ),
 }
 
 lazy_static! {
   pub(crate) static ref TABLE: BTreeMap<&'static str, Function> = vec![
+    ("absolute_path", Unary(absolute_path)),
     ("arch", Nullary(arch)),
+    ("assert", Binary(assert)),
+    ("canonicalize", Unary(canonicalize)),
+    ("capture", Ternary(capture)),
+    ("coalesce", Nary(coalesce, 1..usize::MAX)),
+    ("datetime", Unary(datetime)),
+    ("datetime_utc", Unary(datetime_utc)),
+    ("download", Binary(download)),
     ("os", Nullary(os)),
     ("os_family", Nullary(os_family)),
     ("justfile_directory", Nullary(justfile_directory)),
     ("justfile", Nullary(justfile)),
     ("invocation_directory", Nullary(invocation_directory)),
+    ("is_dir", Unary(is_dir)),
+    ("is_file", Unary(is_file)),
+    ("path_exists", Unary(path_exists)),
+    ("env", UnaryOpt(env)),
     ("env_var", Unary(env_var)),
     ("env_var_or_default", Binary(env_var_or_default)),
+    ("error", Unary(error)),
+    ("feature", Unary(feature)),
+    ("join", Nary(join, 2..usize::MAX)),
     ("just_executable", Nullary(just_executable)),
+    ("just_version", Nullary(just_version)),
+    ("quote", Unary(quote)),
+    ("replace", Nary(replace, 3..5)),
+    ("replace_regex", Ternary(replace_regex)),
+    ("require", Unary(require)),
+    ("retry", Binary(retry)),
+    ("sha256", Unary(sha256)),
+    ("shell", Nary(shell, 1..usize::MAX)),
+    ("sha256_file", Unary(sha256_file)),
+    ("trim_end", Unary(trim_end)),
+    ("trim_end_match", Binary(trim_end_match)),
+    ("trim_start", Unary(trim_start)),
+    ("trim_start_match", Binary(trim_start_match)),
+    ("uuid", Nullary(uuid)),
   ]
   .into_iter()
   .collect();
 }
 
+/// Functions which read the environment or the filesystem, run commands, or
+/// return different values each time they're called, and which may not be
+/// called in safe mode
+pub(crate) const IMPURE: &[&str] = &[
+  "canonicalize",
+  "datetime",
+  "datetime_utc",
+  "download",
+  "env",
+  "env_var",
+  "env_var_or_default",
+  "is_dir",
+  "is_file",
+  "path_exists",
+  "require",
+  "retry",
+  "sha256_file",
+  "shell",
+  "uuid",
+];
+
+/// Functions which fail with a message written by the user, which is printed
+/// as is, without mentioning the function
+pub(crate) const USER_ERRORS: &[&str] = &["assert", "error"];
+
 impl Function {
-  pub(crate) fn argc(&self) -> usize {
+  pub(crate) fn argc(&self) -> Range<usize> {
     match *self {
-      Nullary(_) => 0,
-      Unary(_) => 1,
-      Binary(_) => 2,
+      Nullary(_) => 0..1,
+      Unary(_) => 1..2,
+      Binary(_) => 2..3,
+      Ternary(_) => 3..4,
+      UnaryOpt(_) => 1..3,
+      Nary(_, ref argc) => argc.clone(),
     }
   }
 }
 
+fn absolute_path(context: &FunctionContext, path: &str) -> Result<String, String> {
+  let absolute = Search::clean(&context.search.working_directory, Path::new(path));
+
+  absolute
+    .to_str()
+    .map(str::to_owned)
+    .ok_or_else(|| format!("Absolute path is not valid unicode: {}", absolute.display()))
+}
+
+fn assert(_context: &FunctionContext, condition: &str, message: &str) -> Result<String, String> {
+  match condition {
+    "true" => Ok(String::new()),
+    "false" => Err(message.to_owned()),
+    _ => Err(format!(
+      "Invalid assertion condition `{}`, expected `true` or `false`",
+      condition
+    )),
+  }
+}
+
+fn canonicalize(context: &FunctionContext, path: &str) -> Result<String, String> {
+  let canonical =
+    fs::canonicalize(context.search.working_directory.join(path)).map_err(|error| {
+      if error.kind() == io::ErrorKind::NotFound {
+        format!("Path `{}` does not exist", path)
+      } else {
+        format!("Failed to canonicalize `{}`: {}", path, error)
+      }
+    })?;
+
+  let canonical = Search::strip_verbatim_prefix(canonical);
+
+  canonical.to_str().map(str::to_owned).ok_or_else(|| {
+    format!(
+      "Canonical path is not valid unicode: {}",
+      canonical.display()
+    )
+  })
+}
+
 fn arch(_context: &FunctionContext) -> Result<String, String> {
   Ok(target::arch().to_owned())
 }
 
+/// The text matched by capture group `group` of the first match of `regex`
+/// in `text`. `group` is either the number of a group, where `0` is the
+/// entire match, or the name of a named group. Groups which are optional and
+/// didn't participate in the match capture the empty string.
+fn capture(
+  _context: &FunctionContext,
+  regex: &str,
+  text: &str,
+  group: &str,
+) -> Result<String, String> {
+  let compiled = Regex::new(regex).map_err(|error| error.to_string())?;
+
+  let exists = match group.parse::<usize>() {
+    Ok(index) => index < compiled.captures_len(),
+    Err(_) => compiled.capture_names().any(|name| name == Some(group)),
+  };
+
+  if !exists {
+    return Err(format!(
+      "regex `{}` has no capture group `{}`",
+      regex, group
+    ));
+  }
+
+  let captures = compiled
+    .captures(text)
+    .ok_or_else(|| format!("regex `{}` does not match `{}`", regex, text))?;
+
+  let matched = match group.parse::<usize>() {
+    Ok(index) => captures.get(index),
+    Err(_) => captures.name(group),
+  };
+
+  Ok(matched.map_or("", |capture| capture.as_str()).to_owned())
+}
+
+fn coalesce(_context: &FunctionContext, args: &[String]) -> Result<String, String> {
+  Ok(
+    args
+      .iter()
+      .find(|arg| !arg.is_empty())
+      .cloned()
+      .unwrap_or_default(),
+  )
+}
+
+fn datetime(_context: &FunctionContext, format: &str) -> Result<String, String> {
+  DateTime::now_local()?.format(format)
+}
+
+fn datetime_utc(_context: &FunctionContext, format: &str) -> Result<String, String> {
+  DateTime::now_utc()?.format(format)
+}
+
+/// Return the path of the cached copy of `url`, downloading it first if it
+/// isn't cached, and failing if its contents don't match `sha256`
+fn download(context: &FunctionContext, url: &str, sha256: &str) -> Result<String, String> {
+  context
+    .function_cache
+    .get_or_probe("download", &[url, sha256], || {
+      download_uncached(context, url, sha256)
+    })
+}
+
+fn download_uncached(context: &FunctionContext, url: &str, sha256: &str) -> Result<String, String> {
+  if !context.settings.allow_downloads {
+    return Err("downloads are disabled, enable them with `set allow-downloads`".to_owned());
+  }
+
+  let sha256 = sha256.to_lowercase();
+
+  if sha256.len() != 64 || !sha256.chars().all(|c| c.is_ascii_hexdigit()) {
+    return Err(format!("invalid SHA-256 digest `{}`", sha256));
+  }
+
+  let cache = DownloadCache::new(context.config)?;
+
+  let path = if context.config.dry_run || !context.settings.side_effects(context.config) {
+    cache.path(url, &sha256)
+  } else {
+    cache.fetch(url, &sha256, context.config.verbosity.quiet())?
+  };
+
+  path
+    .to_str()
+    .map(str::to_owned)
+    .ok_or_else(|| format!("download path is not valid unicode: {:?}", path))
+}
+
 fn os(_context: &FunctionContext) -> Result<String, String> {
   Ok(target::os().to_owned())
 }
@@ -54,6 +250,39 @@ fn invocation_directory(context: &FunctionContext) -> Result<String, String> {
   .map_err(|e| format!("Error getting shell path: {}", e))
 }
 
+fn is_dir(context: &FunctionContext, path: &str) -> Result<String, String> {
+  Ok(
+    context
+      .search
+      .working_directory
+      .join(path)
+      .is_dir()
+      .to_string(),
+  )
+}
+
+fn is_file(context: &FunctionContext, path: &str) -> Result<String, String> {
+  Ok(
+    context
+      .search
+      .working_directory
+      .join(path)
+      .is_file()
+      .to_string(),
+  )
+}
+
+fn path_exists(context: &FunctionContext, path: &str) -> Result<String, String> {
+  Ok(
+    context
+      .search
+      .working_directory
+      .join(path)
+      .exists()
+      .to_string(),
+  )
+}
+
 fn justfile(context: &FunctionContext) -> Result<String, String> {
   context
     .search
@@ -69,12 +298,17 @@ fn justfile(context: &FunctionContext) -> Result<String, String> {
 }
 
 fn justfile_directory(context: &FunctionContext) -> Result<String, String> {
-  let justfile_directory = context.search.justfile.parent().ok_or_else(|| {
-    format!(
-      "Could not resolve justfile directory. Justfile `{}` had no parent.",
-      context.search.justfile.display()
-    )
-  })?;
+  // a justfile read from standard input has no directory of its own
+  let justfile_directory = if context.search.is_stdin() {
+    &context.search.working_directory
+  } else {
+    context.search.justfile.parent().ok_or_else(|| {
+      format!(
+        "Could not resolve justfile directory. Justfile `{}` had no parent.",
+        context.search.justfile.display()
+      )
+    })?
+  };
 
   justfile_directory
     .to_str()
@@ -87,21 +321,20 @@ fn justfile_directory(context: &FunctionContext) -> Result<String, String> {
     })
 }
 
-fn env_var(context: &FunctionContext, key: &str) -> Result<String, String> {
-  use std::env::VarError::*;
-
-  if let Some(value) = context.dotenv.get(key) {
-    return Ok(value.clone());
+fn env(context: &FunctionContext, key: &str, default: Option<&str>) -> Result<String, String> {
+  match (lookup(context, key)?, default) {
+    (Some(value), _) => Ok(value),
+    (None, Some(default)) => Ok(default.to_owned()),
+    (None, None) => Err(format!("environment variable `{}` not present", key)),
   }
+}
 
-  match env::var(key) {
-    Err(NotPresent) => Err(format!("environment variable `{}` not present", key)),
-    Err(NotUnicode(os_string)) => Err(format!(
-      "environment variable `{}` not unicode: {:?}",
-      key, os_string
-    )),
-    Ok(value) => Ok(value),
-  }
+fn error(_context: &FunctionContext, message: &str) -> Result<String, String> {
+  Err(message.to_owned())
+}
+
+fn env_var(context: &FunctionContext, key: &str) -> Result<String, String> {
+  env(context, key, None)
 }
 
 fn env_var_or_default(
@@ -109,22 +342,51 @@ fn env_var_or_default(
   key: &str,
   default: &str,
 ) -> Result<String, String> {
+  env(context, key, Some(default))
+}
+
+/// Look up `key` in the environment that backticks and recipes are run
+/// with. Exported variables take precedence over variables loaded from
+/// environment files, which take precedence over just's own environment.
+pub(crate) fn lookup(context: &FunctionContext, key: &str) -> Result<Option<String>, String> {
   use std::env::VarError::*;
 
+  if let Some(value) = context.scope.exported(key, context.settings.export) {
+    return Ok(Some(value.to_owned()));
+  }
+
   if let Some(value) = context.dotenv.get(key) {
-    return Ok(value.clone());
+    return Ok(Some(value.clone()));
   }
 
   match env::var(key) {
-    Err(NotPresent) => Ok(default.to_owned()),
+    Err(NotPresent) => Ok(None),
     Err(NotUnicode(os_string)) => Err(format!(
       "environment variable `{}` not unicode: {:?}",
       key, os_string
     )),
-    Ok(value) => Ok(value),
+    Ok(value) => Ok(Some(value)),
   }
 }
 
+fn join(_context: &FunctionContext, args: &[String]) -> Result<String, String> {
+  let mut path = PathBuf::from(&args[0]);
+
+  for arg in &args[1..] {
+    path.push(arg);
+  }
+
+  Ok(path.to_string_lossy().into_owned())
+}
+
+fn just_version(_context: &FunctionContext) -> Result<String, String> {
+  Ok(env!("CARGO_PKG_VERSION").to_owned())
+}
+
+fn feature(_context: &FunctionContext, name: &str) -> Result<String, String> {
+  Ok(Dump::supports(name).to_string())
+}
+
 fn just_executable(_context: &FunctionContext) -> Result<String, String> {
   let exe_path =
     std::env::current_exe().map_err(|e| format!("Error getting current executable: {}", e))?;
@@ -136,3 +398,166 @@ fn just_executable(_context: &FunctionContext) -> Result<String, String> {
     )
   })
 }
+
+fn quote(_context: &FunctionContext, s: &str) -> Result<String, String> {
+  Ok(format!("'{}'", s.replace('\'', "'\\''")))
+}
+
+/// Replace occurrences of `from` in `s` with `to`, all of them, or only the
+/// first `count` if a count is given
+fn replace(_context: &FunctionContext, args: &[String]) -> Result<String, String> {
+  let (s, from, to) = (&args[0], &args[1], &args[2]);
+
+  match args.get(3) {
+    Some(count) => {
+      let count = count
+        .parse::<usize>()
+        .map_err(|_| format!("invalid replacement count `{}`", count))?;
+      Ok(s.replacen(from.as_str(), to, count))
+    },
+    None => Ok(s.replace(from.as_str(), to)),
+  }
+}
+
+fn replace_regex(
+  _context: &FunctionContext,
+  s: &str,
+  regex: &str,
+  replacement: &str,
+) -> Result<String, String> {
+  Ok(
+    Regex::new(regex)
+      .map_err(|error| error.to_string())?
+      .replace_all(s, replacement)
+      .to_string(),
+  )
+}
+
+/// The full path of the executable `name`, which must be on `PATH`
+fn require(context: &FunctionContext, name: &str) -> Result<String, String> {
+  context
+    .function_cache
+    .get_or_probe("require", &[name], || require_uncached(context, name))
+}
+
+fn require_uncached(context: &FunctionContext, name: &str) -> Result<String, String> {
+  let path = context
+    .find_executable(name)
+    .ok_or_else(|| format!("could not find executable `{}` on PATH", name))?;
+
+  path.to_str().map(str::to_owned).ok_or_else(|| {
+    format!(
+      "path of executable `{}` is not valid unicode: {:?}",
+      name, path
+    )
+  })
+}
+
+/// Run `command` like a backtick, retrying up to `attempts` times in total,
+/// with exponential backoff starting at 100 milliseconds
+fn retry(context: &FunctionContext, attempts: &str, command: &str) -> Result<String, String> {
+  let attempts = attempts
+    .parse::<u32>()
+    .ok()
+    .filter(|attempts| *attempts > 0)
+    .ok_or_else(|| format!("invalid attempt count `{}`", attempts))?;
+
+  if context.config.dry_run || !context.settings.side_effects(context.config) {
+    return Ok(format!("`{}`", command));
+  }
+
+  let mut delay = Duration::from_millis(100);
+  let mut attempt = 1;
+
+  loop {
+    match InterruptHandler::guard(|| output(context.backtick_command(command))) {
+      Ok(stdout) => return Ok(stdout),
+      Err(output_error) if attempt == attempts =>
+        return Err(format!(
+          "command failed after {} {}: {}",
+          attempts,
+          Count("attempt", attempts as usize),
+          output_error
+        )),
+      Err(output_error) => {
+        warn!(
+          "Attempt {} of `{}` failed: {}",
+          attempt, command, output_error
+        );
+        thread::sleep(delay);
+        delay *= 2;
+        attempt += 1;
+      },
+    }
+  }
+}
+
+fn shell(context: &FunctionContext, args: &[String]) -> Result<String, String> {
+  let command = &args[0];
+
+  if context.config.dry_run || !context.settings.side_effects(context.config) {
+    return Ok(format!("`{}`", command));
+  }
+
+  let mut cmd = context.backtick_command(command);
+
+  // The command is also passed as `$0`, so that `$1` refers to the first
+  // argument after the command, and `$@` expands to all of them.
+  cmd.args(args);
+
+  InterruptHandler::guard(|| output(cmd)).map_err(|output_error| output_error.to_string())
+}
+
+fn sha256(_context: &FunctionContext, s: &str) -> Result<String, String> {
+  Ok(Sha256::hex(s.as_bytes()))
+}
+
+fn sha256_file(context: &FunctionContext, path: &str) -> Result<String, String> {
+  context
+    .function_cache
+    .get_or_probe("sha256_file", &[path], || {
+      let path = context.search.working_directory.join(path);
+
+      Sha256::file(&path).map_err(|error| format!("Failed to read `{}`: {}", path.display(), error))
+    })
+}
+
+fn trim_end(_context: &FunctionContext, s: &str) -> Result<String, String> {
+  Ok(s.trim_end().to_owned())
+}
+
+fn trim_end_match(_context: &FunctionContext, s: &str, pat: &str) -> Result<String, String> {
+  Ok(s.strip_suffix(pat).unwrap_or(s).to_owned())
+}
+
+fn trim_start(_context: &FunctionContext, s: &str) -> Result<String, String> {
+  Ok(s.trim_start().to_owned())
+}
+
+fn trim_start_match(_context: &FunctionContext, s: &str, pat: &str) -> Result<String, String> {
+  Ok(s.strip_prefix(pat).unwrap_or(s).to_owned())
+}
+
+fn uuid(_context: &FunctionContext) -> Result<String, String> {
+  let mut bytes = [0u8; 16];
+  rand::thread_rng().fill_bytes(&mut bytes);
+
+  // version 4, random
+  bytes[6] = (bytes[6] & 0x0f) | 0x40;
+  // variant 1, RFC 4122
+  bytes[8] = (bytes[8] & 0x3f) | 0x80;
+
+  let hex = bytes
+    .iter()
+    .map(|byte| format!("{:02x}", byte))
+    .collect::<String>();
+
+  Ok(format!(
+    "{}-{}-{}-{}-{}",
+    &hex[0..8],
+    &hex[8..12],
+    &hex[12..16],
+    &hex[16..20],
+    &hex[20..32]
+  ))
+}
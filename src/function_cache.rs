@@ -0,0 +1,122 @@
+use crate::common::*;
+
+/// Results of functions which probe the system, `download()`, `require()`,
+/// and `sha256_file()`, so that calling them more than once with the same
+/// arguments doesn't repeat the work. A cache lives for a single run of a
+/// justfile, so values are shared between assignments, parameter defaults, and
+/// recipe bodies, but never between invocations of just. Only successful
+/// results are cached, and nothing is cached with `--no-function-cache`.
+pub(crate) struct FunctionCache {
+  enabled: bool,
+  values:  RefCell<BTreeMap<(&'static str, Vec<String>), String>>,
+}
+
+impl FunctionCache {
+  pub(crate) fn new(config: &Config) -> Self {
+    Self {
+      enabled: config.function_cache,
+      values:  RefCell::new(BTreeMap::new()),
+    }
+  }
+
+  /// Return the cached result of calling `function` with `arguments`, or call
+  /// `probe` and cache its result, if successful
+  pub(crate) fn get_or_probe(
+    &self,
+    function: &'static str,
+    arguments: &[&str],
+    probe: impl FnOnce() -> Result<String, String>,
+  ) -> Result<String, String> {
+    if !self.enabled {
+      return probe();
+    }
+
+    let key = (
+      function,
+      arguments
+        .iter()
+        .map(|argument| (*argument).to_owned())
+        .collect::<Vec<String>>(),
+    );
+
+    if let Some(value) = self.values.borrow().get(&key) {
+      return Ok(value.clone());
+    }
+
+    let value = probe()?;
+
+    self.values.borrow_mut().insert(key, value.clone());
+
+    Ok(value)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn cached() {
+    let cache = FunctionCache::new(&testing::config(&[]));
+
+    let mut calls = 0;
+
+    for _ in 0..2 {
+      assert_eq!(
+        cache.get_or_probe("f", &["a"], || {
+          calls += 1;
+          Ok("x".to_owned())
+        }),
+        Ok("x".to_owned())
+      );
+    }
+
+    assert_eq!(calls, 1);
+  }
+
+  #[test]
+  fn keyed_by_arguments() {
+    let cache = FunctionCache::new(&testing::config(&[]));
+
+    assert_eq!(
+      cache.get_or_probe("f", &["a"], || Ok("a".to_owned())),
+      Ok("a".to_owned())
+    );
+    assert_eq!(
+      cache.get_or_probe("f", &["b"], || Ok("b".to_owned())),
+      Ok("b".to_owned())
+    );
+    assert_eq!(
+      cache.get_or_probe("g", &["a"], || Ok("g".to_owned())),
+      Ok("g".to_owned())
+    );
+  }
+
+  #[test]
+  fn errors_not_cached() {
+    let cache = FunctionCache::new(&testing::config(&[]));
+
+    assert_eq!(
+      cache.get_or_probe("f", &[], || Err("error".to_owned())),
+      Err("error".to_owned())
+    );
+    assert_eq!(
+      cache.get_or_probe("f", &[], || Ok("x".to_owned())),
+      Ok("x".to_owned())
+    );
+  }
+
+  #[test]
+  fn disabled() {
+    let cache = FunctionCache::new(&testing::config(&["--no-function-cache"]));
+
+    assert_eq!(
+      cache.get_or_probe("f", &[], || Ok("a".to_owned())),
+      Ok("a".to_owned())
+    );
+    assert_eq!(
+      cache.get_or_probe("f", &[], || Ok("b".to_owned())),
+      Ok("b".to_owned())
+    );
+  }
+}
@@ -1,7 +1,64 @@
 use crate::common::*;
 
-pub(crate) struct FunctionContext<'run> {
+pub(crate) struct FunctionContext<'src: 'run, 'run> {
+  pub(crate) config:               &'run Config,
   pub(crate) dotenv:               &'run BTreeMap<String, String>,
+  pub(crate) function_cache:       &'run FunctionCache,
   pub(crate) invocation_directory: &'run Path,
+  pub(crate) scope:                &'run Scope<'src, 'run>,
   pub(crate) search:               &'run Search,
+  pub(crate) settings:             &'run Settings<'run>,
+}
+
+impl<'src, 'run> FunctionContext<'src, 'run> {
+  /// Create a command that runs `raw` in the same way as a backtick
+  pub(crate) fn backtick_command(&self, raw: &str) -> Command {
+    let mut cmd = self.settings.shell_command(self.config);
+
+    cmd.arg(raw);
+
+    cmd.current_dir(&self.search.working_directory);
+
+    match self.settings.backtick_env {
+      BacktickEnv::None => {
+        cmd.env_clear();
+      },
+      BacktickEnv::Exports => {
+        cmd.env_clear();
+        cmd.export(self.settings, self.dotenv, self.scope);
+      },
+      BacktickEnv::Full => {
+        cmd.export(self.settings, self.dotenv, self.scope);
+      },
+    }
+
+    cmd.stdin(process::Stdio::inherit());
+
+    cmd.stderr(if self.config.verbosity.quiet() {
+      process::Stdio::null()
+    } else {
+      process::Stdio::inherit()
+    });
+
+    cmd
+  }
+
+  /// Whether `name` is set in the environment that recipes are run with
+  pub(crate) fn env_var_is_set(&self, name: &str) -> bool {
+    self.scope.exported(name, self.settings.export).is_some()
+      || self.dotenv.contains_key(name)
+      || env::var_os(name).is_some()
+  }
+
+  /// Find the executable `name` in the `PATH` that recipes are run with
+  pub(crate) fn find_executable(&self, name: &str) -> Option<PathBuf> {
+    let path = self
+      .scope
+      .exported("PATH", self.settings.export)
+      .map(OsString::from)
+      .or_else(|| self.dotenv.get("PATH").map(OsString::from))
+      .or_else(|| env::var_os("PATH"));
+
+    which::which_in(name, path, &self.search.working_directory).ok()
+  }
 }
@@ -0,0 +1,179 @@
+use crate::common::*;
+
+/// A shell-style glob pattern, which matches `/`-separated paths or recipe
+/// names:
+///
+/// - `*` matches any number of characters other than `/`
+/// - `**` matches any number of characters, including `/`, and `**/` matches
+///   any number of leading directories, including none
+/// - `?` matches any single character other than `/`
+/// - `[abc]`, `[a-z]`, and `[!abc]` match a single character in, or with `!`,
+///   not in, the given set
+///
+/// All other characters, including unmatched `[`, match themselves, as do
+/// patterns with invalid character classes, like `[z-a]`.
+#[derive(Debug)]
+pub(crate) struct Glob {
+  regex: Regex,
+}
+
+impl Glob {
+  pub(crate) fn new(pattern: &str) -> Self {
+    let chars = pattern.chars().collect::<Vec<char>>();
+    let mut regex = String::from("^");
+    let mut i = 0;
+
+    while i < chars.len() {
+      match chars[i] {
+        '*' if chars.get(i + 1) == Some(&'*') =>
+          if chars.get(i + 2) == Some(&'/') {
+            regex.push_str("(?:.*/)?");
+            i += 3;
+            continue;
+          } else {
+            regex.push_str(".*");
+            i += 1;
+          },
+        '*' => regex.push_str("[^/]*"),
+        '?' => regex.push_str("[^/]"),
+        '[' => {
+          // a `]` right after `[` or `[!` is part of the set
+          let first = if chars.get(i + 1) == Some(&'!') {
+            i + 2
+          } else {
+            i + 1
+          };
+
+          match chars
+            .get(first + 1..)
+            .and_then(|rest| rest.iter().position(|c| *c == ']'))
+          {
+            Some(offset) => {
+              let end = first + 1 + offset;
+              let class = &chars[i + 1..end];
+              regex.push('[');
+              for (j, c) in class.iter().enumerate() {
+                match c {
+                  '!' if j == 0 => regex.push('^'),
+                  '\\' | '[' | ']' | '^' | '&' | '~' => {
+                    regex.push('\\');
+                    regex.push(*c);
+                  },
+                  _ => regex.push(*c),
+                }
+              }
+              regex.push(']');
+              i = end + 1;
+              continue;
+            },
+            None => regex.push_str(r"\["),
+          }
+        },
+        c => regex.push_str(&regex::escape(&c.to_string())),
+      }
+
+      i += 1;
+    }
+
+    regex.push('$');
+
+    // character classes with invalid ranges, like `[z-a]`, are the only way
+    // for `regex` to be invalid
+    let regex = Regex::new(&regex).unwrap_or_else(|_| {
+      Regex::new(&format!("^{}$", regex::escape(pattern))).expect("escaped regex is valid")
+    });
+
+    Self { regex }
+  }
+
+  pub(crate) fn is_match(&self, text: &str) -> bool {
+    self.regex.is_match(text)
+  }
+
+  /// Whether `text` contains glob metacharacters, which recipe names can't,
+  /// and so should be treated as a pattern rather than a name
+  pub(crate) fn is_pattern(text: &str) -> bool {
+    text.contains(&['*', '?', '['][..])
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn case(pattern: &str, text: &str, expected: bool) {
+    assert_eq!(
+      Glob::new(pattern).is_match(text),
+      expected,
+      "`{}` matching `{}`",
+      pattern,
+      text
+    );
+  }
+
+  #[test]
+  fn literal() {
+    case("foo", "foo", true);
+    case("foo", "foobar", false);
+    case("a.b", "axb", false);
+    case("(a|b)", "(a|b)", true);
+  }
+
+  #[test]
+  fn star() {
+    case("test-*", "test-unit", true);
+    case("test-*", "test-", true);
+    case("test-*", "build", false);
+    case("src/*.rs", "src/main.rs", true);
+    case("src/*.rs", "src/foo/main.rs", false);
+  }
+
+  #[test]
+  fn double_star() {
+    case("src/**/*.rs", "src/main.rs", true);
+    case("src/**/*.rs", "src/foo/bar/main.rs", true);
+    case("src/**/*.rs", "tests/main.rs", false);
+    case("**/*.md", "README.md", true);
+    case("**/*.md", "docs/guide.md", true);
+    case("docs/**", "docs/a/b", true);
+  }
+
+  #[test]
+  fn question_mark() {
+    case("v?", "v1", true);
+    case("v?", "v10", false);
+    case("a?b", "a/b", false);
+  }
+
+  #[test]
+  fn class() {
+    case("[abc].txt", "b.txt", true);
+    case("[abc].txt", "d.txt", false);
+    case("[a-c].txt", "c.txt", true);
+    case("[!a-c].txt", "d.txt", true);
+    case("[!a-c].txt", "a.txt", false);
+    case("[]].txt", "].txt", true);
+    case("[^].txt", "^.txt", true);
+  }
+
+  #[test]
+  fn unclosed_class() {
+    case("[abc", "[abc", true);
+    case("[abc", "a", false);
+    case("[!]", "[!]", true);
+  }
+
+  #[test]
+  fn invalid_class() {
+    case("[z-a]", "[z-a]", true);
+    case("[z-a]", "b", false);
+  }
+
+  #[test]
+  fn is_pattern() {
+    assert!(Glob::is_pattern("test-*"));
+    assert!(Glob::is_pattern("test-?"));
+    assert!(Glob::is_pattern("[ab]"));
+    assert!(!Glob::is_pattern("test-unit"));
+  }
+}
@@ -0,0 +1,324 @@
+use crate::common::*;
+
+/// The recipe dependency graph of a justfile, drawn as a forest of trees, or
+/// only the neighborhood of a single recipe, its direct dependents and all of
+/// its dependencies
+pub(crate) struct Graph<'src, 'run> {
+  justfile:   &'run Justfile<'src>,
+  focus:      Option<&'run Recipe<'src>>,
+  sort_order: SortOrder,
+  /// Draw only the trees of public recipes, for `--summary --verbose`, and,
+  /// like `cargo tree`, draw the dependencies of each recipe only once,
+  /// marking later occurrences with `(*)`
+  summary:    bool,
+  /// Print the graph in Graphviz DOT format, for `--graph --format dot`
+  dot:        bool,
+}
+
+impl<'src, 'run> Graph<'src, 'run> {
+  pub(crate) fn new(
+    justfile: &'run Justfile<'src>,
+    focus: Option<&'run Recipe<'src>>,
+    sort_order: SortOrder,
+  ) -> Self {
+    Self {
+      justfile,
+      focus,
+      sort_order,
+      summary: false,
+      dot: false,
+    }
+  }
+
+  pub(crate) fn dot(
+    justfile: &'run Justfile<'src>,
+    focus: Option<&'run Recipe<'src>>,
+    sort_order: SortOrder,
+  ) -> Self {
+    Self {
+      justfile,
+      focus,
+      sort_order,
+      summary: false,
+      dot: true,
+    }
+  }
+
+  pub(crate) fn summary(justfile: &'run Justfile<'src>, sort_order: SortOrder) -> Self {
+    Self {
+      justfile,
+      focus: None,
+      sort_order,
+      summary: true,
+      dot: false,
+    }
+  }
+
+  fn recipes(&self) -> Vec<&'run Recipe<'src>> {
+    self
+      .sort_order
+      .sorted(self.justfile.recipes.values().map(AsRef::as_ref))
+  }
+
+  /// Recipes which no other recipe depends on, or, when drawing a summary,
+  /// public recipes which aren't in the tree of another public recipe
+  fn roots(&self) -> Vec<&'run Recipe<'src>> {
+    let recipes = self.recipes();
+
+    let mut dependencies = BTreeSet::new();
+
+    let mut stack = recipes
+      .iter()
+      .filter(|recipe| !self.summary || recipe.public())
+      .flat_map(|recipe| &recipe.dependencies)
+      .map(|dependency| dependency.recipe.as_ref())
+      .collect::<Vec<&Recipe<'src>>>();
+
+    while let Some(recipe) = stack.pop() {
+      if dependencies.insert(recipe.name()) && self.summary {
+        stack.extend(
+          recipe
+            .dependencies
+            .iter()
+            .map(|dependency| dependency.recipe.as_ref()),
+        );
+      }
+    }
+
+    recipes
+      .into_iter()
+      .filter(|recipe| !self.summary || recipe.public())
+      .filter(|recipe| !dependencies.contains(recipe.name()))
+      .collect()
+  }
+
+  fn write_label(&self, f: &mut Formatter, label: &str, recipe: &Recipe) -> fmt::Result {
+    let color = match self.focus {
+      Some(focus) if focus.name() == recipe.name() => Color::fmt(f).banner(),
+      _ => Color::never(),
+    };
+
+    writeln!(f, "{}", color.paint(label))
+  }
+
+  /// Draw the dependencies of `recipe`. When drawing a summary, `drawn`
+  /// holds the recipes whose dependencies have already been drawn.
+  fn write_dependencies<'a>(
+    &self,
+    f: &mut Formatter,
+    recipe: &'a Recipe<'src>,
+    prefix: &str,
+    drawn: &mut BTreeSet<&'a str>,
+  ) -> fmt::Result {
+    for (i, dependency) in recipe.dependencies.iter().enumerate() {
+      let last = i == recipe.dependencies.len() - 1;
+      write!(f, "{}{}", prefix, if last { "└── " } else { "├── " })?;
+
+      if self.summary
+        && !dependency.recipe.dependencies.is_empty()
+        && !drawn.insert(dependency.recipe.name())
+      {
+        self.write_label(f, &format!("{} (*)", dependency), &dependency.recipe)?;
+        continue;
+      }
+
+      self.write_label(f, &dependency.to_string(), &dependency.recipe)?;
+      self.write_dependencies(
+        f,
+        &dependency.recipe,
+        &format!("{}{}", prefix, if last { "    " } else { "│   " }),
+        drawn,
+      )?;
+    }
+
+    Ok(())
+  }
+
+  /// Quote `text` as a DOT string, escaping quotes and backslashes, and
+  /// replacing newlines with DOT's `\n` escape
+  fn dot_quote(text: &str) -> String {
+    let mut quoted = String::from('"');
+
+    for c in text.chars() {
+      match c {
+        '"' => quoted.push_str("\\\""),
+        '\\' => quoted.push_str("\\\\"),
+        '\n' => quoted.push_str("\\n"),
+        '\r' => {},
+        c => quoted.push(c),
+      }
+    }
+
+    quoted.push('"');
+
+    quoted
+  }
+
+  /// Write the graph in Graphviz DOT format. Recipes are nodes, dependencies
+  /// are edges from the dependent to the dependency, labeled with their
+  /// arguments, if any, and aliases are dashed nodes with dashed edges to
+  /// their targets. With a focus, only the focused recipe, its direct
+  /// dependents, and all of its dependencies are included.
+  fn write_dot(&self, f: &mut Formatter) -> fmt::Result {
+    let recipes = self.recipes();
+
+    // With a focus, the focused recipe and its dependencies, and the
+    // recipes that depend directly on the focused recipe
+    let mut dependencies = BTreeSet::new();
+    let mut dependents = BTreeSet::new();
+
+    if let Some(focus) = self.focus {
+      let mut stack = vec![focus];
+      while let Some(recipe) = stack.pop() {
+        if dependencies.insert(recipe.name()) {
+          stack.extend(
+            recipe
+              .dependencies
+              .iter()
+              .map(|dependency| dependency.recipe.as_ref()),
+          );
+        }
+      }
+
+      for recipe in &recipes {
+        if recipe
+          .dependencies
+          .iter()
+          .any(|dependency| dependency.recipe.name() == focus.name())
+        {
+          dependents.insert(recipe.name());
+        }
+      }
+    }
+
+    let include =
+      |name: &str| self.focus.is_none() || dependencies.contains(name) || dependents.contains(name);
+
+    writeln!(f, "digraph justfile {{")?;
+
+    for recipe in &recipes {
+      if include(recipe.name()) {
+        writeln!(f, "  {};", Self::dot_quote(recipe.name()))?;
+      }
+    }
+
+    let aliases = self
+      .sort_order
+      .sorted(self.justfile.aliases.values())
+      .into_iter()
+      .filter(|alias| include(alias.target.name()))
+      .collect::<Vec<&Alias>>();
+
+    for alias in &aliases {
+      writeln!(
+        f,
+        "  {} [style=dashed];",
+        Self::dot_quote(alias.name.lexeme())
+      )?;
+    }
+
+    for recipe in &recipes {
+      if !include(recipe.name()) {
+        continue;
+      }
+
+      for dependency in &recipe.dependencies {
+        if let Some(focus) = self.focus {
+          if !dependencies.contains(recipe.name()) && dependency.recipe.name() != focus.name() {
+            continue;
+          }
+        }
+
+        write!(
+          f,
+          "  {} -> {}",
+          Self::dot_quote(recipe.name()),
+          Self::dot_quote(dependency.recipe.name())
+        )?;
+
+        if !dependency.arguments.is_empty() {
+          let label = dependency
+            .arguments
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<String>>()
+            .join(" ");
+          write!(f, " [label={}]", Self::dot_quote(&label))?;
+        }
+
+        writeln!(f, ";")?;
+      }
+    }
+
+    for alias in aliases {
+      writeln!(
+        f,
+        "  {} -> {} [style=dashed];",
+        Self::dot_quote(alias.name.lexeme()),
+        Self::dot_quote(alias.target.name())
+      )?;
+    }
+
+    writeln!(f, "}}")
+  }
+}
+
+impl Display for Graph<'_, '_> {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    if self.dot {
+      return self.write_dot(f);
+    }
+
+    let focus = match self.focus {
+      Some(focus) => focus,
+      None => {
+        let mut drawn = BTreeSet::new();
+        for recipe in self.roots() {
+          self.write_label(f, recipe.name(), recipe)?;
+          self.write_dependencies(f, recipe, "", &mut drawn)?;
+        }
+        return Ok(());
+      },
+    };
+
+    let dependents = self
+      .recipes()
+      .into_iter()
+      .filter(|recipe| {
+        recipe
+          .dependencies
+          .iter()
+          .any(|dependency| dependency.recipe.name() == focus.name())
+      })
+      .collect::<Vec<&Recipe>>();
+
+    if dependents.is_empty() {
+      self.write_label(f, focus.name(), focus)?;
+      return self.write_dependencies(f, focus, "", &mut BTreeSet::new());
+    }
+
+    for dependent in dependents {
+      writeln!(f, "{}", dependent.name())?;
+
+      let edges = dependent
+        .dependencies
+        .iter()
+        .filter(|dependency| dependency.recipe.name() == focus.name())
+        .collect::<Vec<&Dependency>>();
+
+      for (i, dependency) in edges.iter().enumerate() {
+        let last = i == edges.len() - 1;
+        write!(f, "{}", if last { "└── " } else { "├── " })?;
+        self.write_label(f, &dependency.to_string(), focus)?;
+        self.write_dependencies(
+          f,
+          focus,
+          if last { "    " } else { "│   " },
+          &mut BTreeSet::new(),
+        )?;
+      }
+    }
+
+    Ok(())
+  }
+}
@@ -67,7 +67,7 @@ impl InterruptHandler {
 
     self.blocks -= 1;
 
-    if self.interrupted {
+    if self.interrupted && self.blocks == 0 {
       Self::exit();
     }
   }
@@ -8,3 +8,35 @@ pub(crate) enum Item<'src> {
   Recipe(UnresolvedRecipe<'src>),
   Set(Set<'src>),
 }
+
+impl<'src> Item<'src> {
+  pub(crate) fn kind(&self) -> &'static str {
+    match self {
+      Self::Alias(_) => "alias",
+      Self::Assignment(_) => "variable",
+      Self::Recipe(_) => "recipe",
+      Self::Set(_) => "setting",
+    }
+  }
+
+  pub(crate) fn name(&self) -> Name<'src> {
+    match self {
+      Self::Alias(alias) => alias.name,
+      Self::Assignment(assignment) => assignment.name,
+      Self::Recipe(recipe) => recipe.name,
+      Self::Set(set) => set.name,
+    }
+  }
+
+  /// The namespace and name of this item, which must be unique within a
+  /// justfile
+  pub(crate) fn key(&self) -> (&'static str, &'src str) {
+    let namespace = match self {
+      Self::Alias(_) | Self::Recipe(_) => "recipe",
+      Self::Assignment(_) => "variable",
+      Self::Set(_) => "setting",
+    };
+
+    (namespace, self.name().lexeme())
+  }
+}
@@ -1,5 +1,7 @@
 use crate::common::*;
 
+use std::mem;
+
 #[derive(Debug, PartialEq)]
 pub(crate) struct Justfile<'src> {
   pub(crate) recipes:     Table<'src, Rc<Recipe<'src>>>,
@@ -7,6 +9,7 @@ pub(crate) struct Justfile<'src> {
   pub(crate) aliases:     Table<'src, Alias<'src>>,
   pub(crate) settings:    Settings<'src>,
   pub(crate) warnings:    Vec<Warning>,
+  pub(crate) overrides:   Vec<LocalOverride<'src>>,
 }
 
 impl<'src> Justfile<'src> {
@@ -24,34 +27,43 @@ impl<'src> Justfile<'src> {
     first
   }
 
+  /// The name of the recipe run when no recipes are given on the command
+  /// line, which is the recipe or alias named by `set default`, or else the
+  /// first recipe
+  pub(crate) fn default_recipe(&self) -> Option<&str> {
+    match &self.settings.default {
+      Some(name) => Some(name),
+      None => self.first().map(Recipe::name),
+    }
+  }
+
   pub(crate) fn count(&self) -> usize {
     self.recipes.len()
   }
 
   pub(crate) fn suggest(&self, input: &str) -> Option<Suggestion> {
-    let mut suggestions = self
-      .recipes
-      .keys()
-      .map(|name| {
-        (edit_distance(name, input), Suggestion {
-          name,
-          target: None,
-        })
-      })
-      .chain(self.aliases.iter().map(|(name, alias)| {
-        (edit_distance(name, input), Suggestion {
+    Suggestion::closest(
+      input,
+      self
+        .recipes
+        .keys()
+        .map(|name| Suggestion::Recipe { name, target: None })
+        .chain(self.aliases.iter().map(|(name, alias)| Suggestion::Recipe {
           name,
           target: Some(alias.target.name.lexeme()),
-        })
-      }))
-      .filter(|(distance, _suggestion)| distance < &3)
-      .collect::<Vec<(usize, Suggestion)>>();
-    suggestions.sort_by_key(|(distance, _suggestion)| *distance);
+        })),
+    )
+  }
 
-    suggestions
-      .into_iter()
-      .map(|(_distance, suggestion)| suggestion)
-      .next()
+  /// Suggest a variable whose name is close to `input`
+  pub(crate) fn suggest_variable(&self, input: &str) -> Option<Suggestion> {
+    Suggestion::closest(
+      input,
+      self
+        .assignments
+        .keys()
+        .map(|name| Suggestion::Variable { name }),
+    )
   }
 
   pub(crate) fn run<'run>(
@@ -60,6 +72,7 @@ impl<'src> Justfile<'src> {
     search: &'run Search,
     overrides: &'run BTreeMap<String, String>,
     arguments: &'run [String],
+    capture: Option<Capture>,
   ) -> RunResult<'run, ()> {
     let unknown_overrides = overrides
       .keys()
@@ -68,17 +81,36 @@ impl<'src> Justfile<'src> {
       .collect::<Vec<&str>>();
 
     if !unknown_overrides.is_empty() {
+      let suggestion = match unknown_overrides.as_slice() {
+        [unknown] => self.suggest_variable(unknown),
+        _ => None,
+      };
       return Err(RuntimeError::UnknownOverrides {
         overrides: unknown_overrides,
+        suggestion,
       });
     }
 
-    let dotenv = if config.load_dotenv {
+    let dotenv = if config.load_dotenv && !config.safe {
       load_dotenv(&config, &self.settings, &search.working_directory)?
     } else {
       BTreeMap::new()
     };
 
+    let function_cache = FunctionCache::new(config);
+
+    // Like the report, the log isn't written in dry runs, since nothing is run
+    let log = match &config.log_json {
+      Some(path) if !config.dry_run =>
+        Some(
+          RunLog::create(path).map_err(|io_error| RuntimeError::LogJson {
+            path: path.clone(),
+            io_error,
+          })?,
+        ),
+      _ => None,
+    };
+
     let scope = {
       let mut scope = Scope::new();
       let mut unknown_overrides = Vec::new();
@@ -93,91 +125,229 @@ impl<'src> Justfile<'src> {
 
       if !unknown_overrides.is_empty() {
         return Err(RuntimeError::UnknownOverrides {
-          overrides: unknown_overrides,
+          overrides:  unknown_overrides,
+          suggestion: None,
         });
       }
 
-      Evaluator::evaluate_assignments(
-        &self.assignments,
-        config,
-        &dotenv,
-        scope,
-        &self.settings,
-        search,
-      )?
+      scope
     };
 
-    if let Subcommand::Evaluate { variables, .. } = &config.subcommand {
-      let mut width = 0;
-
-      for name in scope.names() {
-        if !variables.is_empty() && !variables.iter().any(|variable| variable == name) {
-          continue;
-        }
-
-        width = cmp::max(name.len(), width);
+    if let Subcommand::Evaluate {
+      variables, format, ..
+    } = &config.subcommand
+    {
+      match (variables.as_slice(), format) {
+        ([variable], None) => {
+          let assignment = self.assignments.get(variable.as_str()).ok_or_else(|| {
+            RuntimeError::EvalUnknownVariable {
+              variable:   variable.as_str(),
+              suggestion: self.suggest_variable(variable),
+            }
+          })?;
+
+          print!(
+            "{}",
+            Evaluator::evaluate_single_assignment(
+              assignment,
+              &self.assignments,
+              config,
+              &dotenv,
+              &function_cache,
+              scope,
+              &self.settings,
+              search,
+            )?
+          );
+        },
+        (_, format) => {
+          let scope = Evaluator::evaluate_assignments(
+            &self.assignments,
+            config,
+            &dotenv,
+            &function_cache,
+            scope,
+            &self.settings,
+            search,
+            log.as_ref(),
+          )?;
+
+          if let Some(format) = format {
+            print!(
+              "{}",
+              format.object(
+                config
+                  .sort_order
+                  .sorted(scope.bindings())
+                  .into_iter()
+                  .map(|binding| (binding.name.lexeme(), binding.value.as_str()))
+                  .filter(|(name, _)| variables.is_empty()
+                    || variables.iter().any(|variable| variable == name))
+              )
+            );
+          } else {
+            let mut width = 0;
+
+            for name in scope.names() {
+              if !variables.is_empty() && !variables.iter().any(|variable| variable == name) {
+                continue;
+              }
+
+              width = cmp::max(name.len(), width);
+            }
+
+            for binding in config.sort_order.sorted(scope.bindings()) {
+              if !variables.is_empty()
+                && !variables
+                  .iter()
+                  .any(|variable| variable == binding.name.lexeme())
+              {
+                continue;
+              }
+
+              println!(
+                "{0:1$} := \"{2}\"",
+                binding.name.lexeme(),
+                width,
+                binding.value
+              );
+            }
+          }
+        },
       }
 
-      for binding in scope.bindings() {
-        if !variables.is_empty()
-          && !variables
-            .iter()
-            .any(|variable| variable == binding.name.lexeme())
-        {
-          continue;
-        }
+      return Ok(());
+    }
 
-        println!(
-          "{0:1$} := \"{2}\"",
-          binding.name.lexeme(),
-          width,
-          binding.value
-        );
-      }
+    let scope = Evaluator::evaluate_assignments(
+      &self.assignments,
+      config,
+      &dotenv,
+      &function_cache,
+      scope,
+      &self.settings,
+      search,
+      log.as_ref(),
+    )?;
 
-      return Ok(());
+    if let Subcommand::Command { command, .. } = &config.subcommand {
+      return self.run_command(config, search, &dotenv, &scope, command);
     }
 
     let argvec: Vec<&str> = if !arguments.is_empty() {
       arguments.iter().map(String::as_str).collect()
-    } else if let Some(recipe) = self.first() {
-      let min_arguments = recipe.min_arguments();
+    } else if let Some(name) = self.default_recipe() {
+      let recipe = self.get_recipe(name).unwrap();
+      let min_arguments = recipe.min_arguments().saturating_sub(
+        self
+          .get_alias(name)
+          .map_or(0, |alias| alias.arguments.len()),
+      );
       if min_arguments > 0 {
         return Err(RuntimeError::DefaultRecipeRequiresArguments {
           recipe: recipe.name.lexeme(),
           min_arguments,
         });
       }
-      vec![recipe.name()]
+      vec![name]
     } else {
       return Err(RuntimeError::NoRecipes);
     };
 
+    let argvec = self.apply_unknown_recipe(config, argvec)?;
+
     let arguments = argvec.as_slice();
 
+    let remembered_arguments = RememberedArguments::new(&search.justfile);
+
+    let remembered = argvec
+      .iter()
+      .filter_map(|argument| self.get_recipe(argument))
+      .filter(|recipe| recipe.remember_args())
+      .filter_map(|recipe| {
+        remembered_arguments
+          .load(recipe.name())
+          .map(|arguments| (recipe.name(), arguments))
+      })
+      .collect::<BTreeMap<&str, Vec<String>>>();
+
+    let remembered = remembered
+      .iter()
+      .map(|(recipe, arguments)| {
+        (
+          *recipe,
+          arguments.iter().map(String::as_str).collect::<Vec<&str>>(),
+        )
+      })
+      .collect::<BTreeMap<&str, Vec<&str>>>();
+
     let mut missing = vec![];
     let mut grouped = vec![];
     let mut rest = arguments;
 
     while let Some((argument, mut tail)) = rest.split_first() {
       if let Some(recipe) = self.get_recipe(argument) {
+        // an alias's fixed arguments come before any given after it
+        let fixed = self
+          .get_alias(argument)
+          .map(Alias::argument_values)
+          .unwrap_or_default();
+
         if recipe.parameters.is_empty() {
-          grouped.push((recipe, &[][..]));
+          grouped.push((recipe, Vec::new()));
         } else {
           let argument_range = recipe.argument_range();
-          let argument_count = cmp::min(tail.len(), recipe.max_arguments());
-          if !argument_range.range_contains(&argument_count) {
+          let argument_count = cmp::min(tail.len(), recipe.max_arguments() - fixed.len());
+          if argument_count == 0 && fixed.is_empty() {
+            if let Some(arguments) = remembered.get(recipe.name()) {
+              if argument_range.range_contains(&arguments.len()) {
+                if config.verbosity.loud() {
+                  eprintln!(
+                    "Reusing arguments from previous run of `{}`: {}",
+                    recipe.name(),
+                    arguments.join(" ")
+                  );
+                }
+                grouped.push((recipe, arguments.clone()));
+                rest = tail;
+                continue;
+              }
+            }
+          }
+          if !argument_range.range_contains(&(fixed.len() + argument_count)) {
             return Err(RuntimeError::ArgumentCountMismatch {
               recipe:     recipe.name(),
               parameters: recipe.parameters.iter().collect(),
-              found:      tail.len(),
+              found:      fixed.len() + tail.len(),
               min:        recipe.min_arguments(),
               max:        recipe.max_arguments(),
             });
           }
-          grouped.push((recipe, &tail[0..argument_count]));
+          grouped.push((
+            recipe,
+            fixed
+              .into_iter()
+              .chain(tail[0..argument_count].iter().copied())
+              .collect(),
+          ));
           tail = &tail[argument_count..];
         }
+      } else if Glob::is_pattern(argument) {
+        // Matching recipes are run without arguments, so the words after a
+        // pattern are never taken as its arguments
+        let glob = Glob::new(argument);
+
+        let matches = self
+          .public_recipes(SortOrder::Source)
+          .into_iter()
+          .filter(|recipe| recipe.min_arguments() == 0 && glob.is_match(recipe.name()))
+          .collect::<Vec<&Recipe<Dependency>>>();
+
+        if matches.is_empty() {
+          return Err(RuntimeError::NoMatchingRecipes { pattern: argument });
+        }
+
+        grouped.extend(matches.into_iter().map(|recipe| (recipe, Vec::new())));
       } else {
         missing.push(*argument);
       }
@@ -185,30 +355,186 @@ impl<'src> Justfile<'src> {
     }
 
     if !missing.is_empty() {
-      let suggestion = if missing.len() == 1 {
-        self.suggest(missing.first().unwrap())
-      } else {
-        None
-      };
+      let suggestion = match missing.as_slice() {
+        [missing] => self.suggest(missing),
+        _ => None,
+      }
+      .or_else(|| {
+        // `just foo bar` was probably meant to be `just foo=bar`
+        self
+          .suggest_variable(missing[0])
+          .map(|suggestion| Suggestion::Override {
+            name: suggestion.name(),
+          })
+      });
       return Err(RuntimeError::UnknownRecipes {
         recipes: missing,
         suggestion,
       });
     }
 
+    for (recipe, arguments) in &grouped {
+      recipe.check_choices(arguments)?;
+    }
+
+    if !config.dry_run {
+      self.check_requirements(config, &dotenv, &function_cache, &scope, &search, &grouped)?;
+    }
+
     let context = RecipeContext {
       settings: &self.settings,
+      capture,
+      report: RefCell::new(Report::default()),
       config,
+      function_cache: &function_cache,
+      log: log.as_ref(),
       scope,
       search,
     };
 
     let mut ran = BTreeSet::new();
+    let mut result = Ok(());
+    let mut results = Vec::new();
     for (recipe, arguments) in grouped {
-      self.run_recipe(&context, recipe, arguments, &dotenv, &search, &mut ran)?
+      let invocation = iter::once(recipe.name())
+        .chain(arguments.iter().copied())
+        .collect::<Vec<&str>>()
+        .join(" ");
+
+      if let Err(error) = self.run_recipe(
+        &context, recipe, &arguments, &dotenv, &mut ran, false, false,
+      ) {
+        if !config.keep_going {
+          result = Err(error);
+          break;
+        }
+
+        // with `--keep-going`, errors are printed as they happen, and the
+        // first failure's exit code is returned once every recipe has run
+        if !config.verbosity.quiet() && error.print_message() {
+          context.eprintln(error.render(config.color, config.error_context));
+        }
+        results.push((invocation, Some(error.code())));
+        continue;
+      }
+
+      results.push((invocation, None));
+
+      if recipe.remember_args() && !arguments.is_empty() && !config.dry_run {
+        remembered_arguments.store(recipe.name(), &arguments);
+      }
     }
 
-    Ok(())
+    if config.keep_going {
+      if !config.dry_run && !config.verbosity.quiet() {
+        context.eprintln(Self::keep_going_summary(config.color, &results));
+      }
+
+      let code = results.iter().find_map(|(_, code)| *code);
+
+      if let Some(code) = code {
+        result = Err(RuntimeError::KeepGoing {
+          failed: results
+            .into_iter()
+            .filter(|(_, code)| code.is_some())
+            .map(|(invocation, _)| invocation)
+            .collect(),
+          code,
+        });
+      }
+    }
+
+    if config.timings && !config.dry_run && !config.verbosity.quiet() {
+      context.eprintln(context.report.borrow().timings().trim_end());
+    }
+
+    // The report is written even if a recipe failed, since that's when it's
+    // most useful
+    if let Some((format, path)) = &config.report {
+      if !config.dry_run {
+        if let Err(io_error) = context
+          .report
+          .borrow()
+          .write(*format, path, &search.justfile)
+        {
+          if result.is_ok() {
+            return Err(RuntimeError::Report {
+              path: path.clone(),
+              io_error,
+            });
+          }
+
+          warn!(
+            "Failed to write report to `{}`: {}",
+            path.display(),
+            io_error
+          );
+        }
+      }
+    }
+
+    result
+  }
+
+  /// The summary printed after recipes are run with `--keep-going`, with the
+  /// exit code of each recipe that failed
+  fn keep_going_summary(color: Color, results: &[(String, Option<i32>)]) -> String {
+    let color = color.stderr().banner();
+
+    let mut summary = format!("{}===> Results:{}", color.prefix(), color.suffix());
+
+    for (invocation, code) in results {
+      match code {
+        Some(code) =>
+          summary.push_str(&format!("\n{}: failed with exit code {}", invocation, code)),
+        None => summary.push_str(&format!("\n{}: succeeded", invocation)),
+      }
+    }
+
+    summary
+  }
+
+  /// Apply the `unknown-recipe` setting, if the first argument isn't a recipe
+  fn apply_unknown_recipe<'run>(
+    &'run self,
+    config: &Config,
+    mut arguments: Vec<&'run str>,
+  ) -> RunResult<'src, Vec<&'run str>> {
+    let first = match arguments.first() {
+      Some(first) if self.get_recipe(first).is_none() && !Glob::is_pattern(first) => *first,
+      _ => return Ok(arguments),
+    };
+
+    match self.settings.unknown_recipe {
+      UnknownRecipe::Error => {},
+      UnknownRecipe::Default => match self.default_recipe() {
+        Some(name) => arguments.insert(0, name),
+        None => return Err(RuntimeError::NoRecipes),
+      },
+      UnknownRecipe::SuggestRun =>
+        if let Some(suggestion) = self.suggest(first) {
+          if config.yes || config.dry_run || Self::confirm_suggestion(first, suggestion) {
+            arguments[0] = suggestion.name();
+          }
+        },
+    }
+
+    Ok(arguments)
+  }
+
+  fn confirm_suggestion(unknown: &str, suggestion: Suggestion) -> bool {
+    eprint!(
+      "Justfile does not contain recipe `{}`. Run `{}` instead? (y/N) ",
+      unknown,
+      suggestion.name()
+    );
+
+    let mut line = String::new();
+    if io::stdin().read_line(&mut line).is_err() {
+      line.clear();
+    }
+
+    matches!(line.trim().to_lowercase().as_str(), "y" | "yes")
   }
 
   pub(crate) fn get_alias(&self, name: &str) -> Option<&Alias> {
@@ -225,71 +551,341 @@ impl<'src> Justfile<'src> {
     }
   }
 
+  /// Evaluate the expressions given to boolean settings, e.g.
+  /// `set export := env_var_or_default('CI', '') != ''`, in just's own
+  /// environment. This must be done before the settings are used. The
+  /// expressions are kept, so that callers can tell that the settings'
+  /// values depend on the environment.
+  pub(crate) fn evaluate_settings(
+    &mut self,
+    config: &Config,
+    search: &Search,
+  ) -> RunResult<'src, ()> {
+    let dotenv = BTreeMap::new();
+    let function_cache = FunctionCache::new(config);
+
+    let expressions = mem::take(&mut self.settings.expressions);
+
+    for (name, expression) in &expressions {
+      let value = Evaluator::evaluate_setting(
+        expression,
+        config,
+        &dotenv,
+        &function_cache,
+        &self.settings,
+        search,
+      )?;
+
+      let value = match value.as_str() {
+        "true" => true,
+        "false" => false,
+        _ =>
+          return Err(RuntimeError::InvalidSettingValue {
+            setting: *name,
+            value,
+          }),
+      };
+
+      self.settings.set_bool(name.lexeme(), value);
+    }
+
+    self.settings.expressions = expressions;
+
+    Ok(())
+  }
+
+  /// Check that the executables required by the recipes in `grouped`, their
+  /// dependencies, unless skipped with `--no-dependencies`, and their cleanup
+  /// recipes are all on `PATH`, and that the environment variables they need
+  /// are all set, before any of them run
+  fn check_requirements(
+    &self,
+    config: &Config,
+    dotenv: &BTreeMap<String, String>,
+    function_cache: &FunctionCache,
+    scope: &Scope<'src, '_>,
+    search: &Search,
+    grouped: &[(&Recipe<'src>, Vec<&str>)],
+  ) -> RunResult<'src, ()> {
+    let mut stack = grouped
+      .iter()
+      .map(|(recipe, _)| (*recipe, false))
+      .collect::<Vec<(&Recipe, bool)>>();
+    let mut seen = BTreeSet::new();
+    let mut required = BTreeMap::<&str, Vec<&'src str>>::new();
+    let mut needed = BTreeMap::<&str, (Option<&str>, Vec<&'src str>)>::new();
+
+    while let Some((recipe, is_dependency)) = stack.pop() {
+      if !seen.insert((recipe.name(), is_dependency)) {
+        continue;
+      }
+
+      // a recipe may be visited both as a recipe given on the command line
+      // and as a dependency, but should only be reported once
+      for executable in recipe.requirements() {
+        let recipes = required.entry(executable).or_default();
+        if !recipes.contains(&recipe.name()) {
+          recipes.push(recipe.name());
+        }
+      }
+
+      for (name, description) in recipe.needed_env() {
+        let (first, recipes) = needed.entry(name).or_default();
+        if first.is_none() {
+          *first = description;
+        }
+        if !recipes.contains(&recipe.name()) {
+          recipes.push(recipe.name());
+        }
+      }
+
+      if is_dependency || !config.no_dependencies {
+        stack.extend(
+          recipe
+            .dependencies
+            .iter()
+            .map(|dependency| (dependency.recipe.as_ref(), true)),
+        );
+      }
+
+      stack.extend(
+        recipe
+          .cleanup()
+          .and_then(|cleanup| self.recipes.get(cleanup))
+          .map(|cleanup| (cleanup.as_ref(), true)),
+      );
+    }
+
+    let context = FunctionContext {
+      invocation_directory: &config.invocation_directory,
+      settings: &self.settings,
+      config,
+      dotenv,
+      function_cache,
+      scope,
+      search,
+    };
+
+    let missing = required
+      .into_iter()
+      .filter(|(executable, _)| context.find_executable(executable).is_none())
+      .map(|(executable, mut recipes)| {
+        recipes.sort_unstable();
+        (executable.to_owned(), recipes)
+      })
+      .collect::<Vec<(String, Vec<&str>)>>();
+
+    if !missing.is_empty() {
+      return Err(RuntimeError::MissingExecutables { missing });
+    }
+
+    let missing = needed
+      .into_iter()
+      .filter(|(name, _)| !context.env_var_is_set(name))
+      .map(|(name, (description, mut recipes))| {
+        recipes.sort_unstable();
+        (name.to_owned(), description.map(str::to_owned), recipes)
+      })
+      .collect::<Vec<(String, Option<String>, Vec<&str>)>>();
+
+    if missing.is_empty() {
+      Ok(())
+    } else {
+      Err(RuntimeError::MissingEnvironmentVariables { missing })
+    }
+  }
+
   fn run_recipe<'run>(
     &self,
     context: &'run RecipeContext<'src, 'run>,
     recipe: &Recipe<'src>,
     arguments: &[&'run str],
     dotenv: &BTreeMap<String, String>,
-    search: &'run Search,
     ran: &mut BTreeSet<Vec<String>>,
+    is_dependency: bool,
+    is_cleanup: bool,
   ) -> RunResult<'src, ()> {
+    let search = context.search;
+
+    recipe.check_choices(arguments)?;
+
     let outer = Evaluator::evaluate_parameters(
-      context.config,
+      context,
       dotenv,
       &recipe.parameters,
       arguments,
       &context.scope,
-      context.settings,
-      search,
     )?;
 
-    let scope = outer.child();
+    // A recipe runs once per distinct list of parameter values, including
+    // defaults, so `(build 'linux')` and `(build 'windows')` both run, but
+    // `build` and `(build 'linux')` only run once if `linux` is the default.
+    // Recipes with the `[fresh]` attribute, and cleanup recipes, run every
+    // time, and running a cleanup recipe doesn't count as a run.
+    let mut invocation = vec![recipe.name().to_owned()];
+    for parameter in &recipe.parameters {
+      invocation.push(
+        outer
+          .value(parameter.name.lexeme())
+          .unwrap_or_default()
+          .to_owned(),
+      );
+    }
 
-    let mut evaluator =
-      Evaluator::recipe_evaluator(context.config, dotenv, &scope, context.settings, search);
+    if !recipe.fresh() && !is_cleanup && ran.contains(&invocation) {
+      return Ok(());
+    }
 
-    for Dependency { recipe, arguments } in &recipe.dependencies {
-      let mut invocation = vec![recipe.name().to_owned()];
+    recipe.confirm(context.config)?;
 
-      for argument in arguments {
-        invocation.push(evaluator.evaluate_expression(argument)?);
-      }
+    let scope = outer.child();
+
+    let mut evaluator = Evaluator::recipe_evaluator(context, dotenv, &scope);
 
-      if !ran.contains(&invocation) {
-        let arguments = invocation
+    if context.config.no_dependencies && !is_dependency {
+      if context.config.verbosity.loquacious() && !recipe.dependencies.is_empty() {
+        let color = context.config.color.stderr().banner();
+        let dependencies = recipe
+          .dependencies
           .iter()
-          .skip(1)
-          .map(String::as_ref)
+          .map(|dependency| dependency.recipe.name())
           .collect::<Vec<&str>>();
-        self.run_recipe(context, recipe, &arguments, dotenv, search, ran)?;
+        context.eprintln(format_args!(
+          "{}===> Skipping dependencies of recipe `{}`: {}{}",
+          color.prefix(),
+          recipe.name(),
+          List::and_ticked(dependencies),
+          color.suffix()
+        ));
+      }
+    } else {
+      for Dependency { recipe, arguments } in &recipe.dependencies {
+        let arguments = arguments
+          .iter()
+          .map(|argument| evaluator.evaluate_expression(argument))
+          .collect::<RunResult<Vec<String>>>()?;
+
+        let arguments = arguments.iter().map(String::as_ref).collect::<Vec<&str>>();
+
+        self.run_recipe(context, recipe, &arguments, dotenv, ran, true, false)?;
       }
     }
 
-    recipe.run(context, dotenv, scope.child(), search)?;
+    let cleanup = recipe
+      .cleanup()
+      .and_then(|cleanup| self.recipes.get(cleanup));
 
-    let mut invocation = vec![recipe.name().to_owned()];
-    for argument in arguments.iter().cloned() {
-      invocation.push(argument.to_owned());
+    // Block interrupts while a recipe with a cleanup recipe runs, so that the
+    // cleanup recipe still runs if the user hits ctrl-c. Just exits once the
+    // guard is dropped.
+    let guard = cleanup.map(|_| InterruptGuard::new());
+
+    if let Some(log) = context.log {
+      log.recipe_start(recipe.name(), arguments);
+    }
+
+    let start = Instant::now();
+
+    let result = recipe.run(context, dotenv, scope.child(), is_dependency);
+
+    let elapsed = start.elapsed();
+
+    if let Some(log) = context.log {
+      log.recipe_end(recipe.name(), arguments, elapsed, result.as_ref().err());
+    }
+
+    if !context.config.dry_run {
+      context
+        .report
+        .borrow_mut()
+        .record(recipe.name(), arguments, elapsed, result.as_ref().err());
+    }
+
+    if let Some(cleanup) = cleanup {
+      let cleanup_result = self.run_recipe(context, cleanup, &[], dotenv, ran, true, true);
+      drop(guard);
+      result?;
+      cleanup_result?;
+    } else {
+      result?;
+    }
+
+    if let Some(stats_file) = &context.config.stats_file {
+      if !context.config.dry_run {
+        Stats::record(stats_file, &search.justfile, recipe.name(), elapsed);
+      }
+    }
+
+    if !is_cleanup {
+      ran.insert(invocation);
     }
 
-    ran.insert(invocation);
     Ok(())
   }
 
-  pub(crate) fn public_recipes(&self, source_order: bool) -> Vec<&Recipe<Dependency>> {
-    let mut recipes = self
-      .recipes
-      .values()
-      .map(AsRef::as_ref)
-      .filter(|recipe| recipe.public())
-      .collect::<Vec<&Recipe<Dependency>>>();
+  /// Run `command` for `--command`, in the working directory, with exported
+  /// variables and the dotenv file in its environment
+  fn run_command<'run>(
+    &self,
+    config: &Config,
+    search: &Search,
+    dotenv: &BTreeMap<String, String>,
+    scope: &Scope,
+    command: &'run [String],
+  ) -> RunResult<'run, ()> {
+    let (program, arguments) = command
+      .split_first()
+      .expect("clap requires `--command` to have at least one value");
+
+    if config.dry_run || config.verbosity.loquacious() {
+      let color = if config.highlight {
+        config.color.command()
+      } else {
+        config.color
+      };
+      eprintln!("{}", color.stderr().paint(&command.join(" ")));
+    }
+
+    if config.dry_run {
+      return Ok(());
+    }
 
-    if source_order {
-      recipes.sort_by_key(|recipe| recipe.name.offset);
+    if config.safe {
+      return Err(RuntimeError::UnsafeCommand);
     }
 
-    recipes
+    let mut cmd = Command::new(program);
+
+    cmd
+      .args(arguments)
+      .current_dir(&search.working_directory)
+      .export(&self.settings, dotenv, scope);
+
+    let output_error = match InterruptHandler::guard(|| cmd.status()) {
+      Ok(exit_status) => match exit_status.code() {
+        Some(0) => return Ok(()),
+        Some(code) => OutputError::Code(code),
+        None => Platform::signal_from_exit_status(exit_status)
+          .map(OutputError::Signal)
+          .unwrap_or(OutputError::Unknown),
+      },
+      Err(io_error) => OutputError::Io(io_error),
+    };
+
+    Err(RuntimeError::Command {
+      command: program,
+      output_error,
+    })
+  }
+
+  pub(crate) fn public_recipes(&self, sort_order: SortOrder) -> Vec<&Recipe<Dependency>> {
+    sort_order.sorted(
+      self
+        .recipes
+        .values()
+        .map(AsRef::as_ref)
+        .filter(|recipe| recipe.public()),
+    )
   }
 }
 
@@ -360,7 +956,7 @@ mod tests {
     },
     check: {
       assert_eq!(recipes, &["zz"]);
-      assert_eq!(suggestion, Some(Suggestion {
+      assert_eq!(suggestion, Some(Suggestion::Recipe {
         name: "z",
         target: Some("foo"),
       }
@@ -388,11 +984,14 @@ mod tests {
       recipe,
       line_number,
       code,
+      exit_message,
+      template: _,
     },
     check: {
       assert_eq!(recipe, "a");
       assert_eq!(code, 200);
       assert_eq!(line_number, None);
+      assert_eq!(exit_message, ExitMessage::Full);
     }
   }
 
@@ -407,11 +1006,14 @@ mod tests {
       recipe,
       line_number,
       code,
+      exit_message,
+      template: _,
     },
     check: {
       assert_eq!(recipe, "fail");
       assert_eq!(code, 100);
       assert_eq!(line_number, Some(2));
+      assert_eq!(exit_message, ExitMessage::Full);
     }
   }
 
@@ -426,11 +1028,14 @@ mod tests {
       recipe,
       line_number,
       code,
+      exit_message,
+      template: _,
     },
     check: {
       assert_eq!(recipe, "a");
       assert_eq!(code, 150);
       assert_eq!(line_number, Some(2));
+      assert_eq!(exit_message, ExitMessage::Full);
     }
   }
 
@@ -561,9 +1166,10 @@ mod tests {
        echo {{`f() { return 100; }; f`}}
     ",
     args: ["foo=bar", "baz=bob", "a"],
-    error: UnknownOverrides { overrides },
+    error: UnknownOverrides { overrides, suggestion },
     check: {
       assert_eq!(overrides, &["baz", "foo"]);
+      assert_eq!(suggestion, None);
     }
   }
 
@@ -583,6 +1189,8 @@ mod tests {
       code: _,
       line_number,
       recipe,
+      exit_message: _,
+      template: _,
     },
     check: {
       assert_eq!(recipe, "wut");
@@ -4,14 +4,32 @@ use crate::common::*;
 #[strum(serialize_all = "kebab_case")]
 pub(crate) enum Keyword {
   Alias,
+  AllowDownloads,
+  BacktickEnv,
+  Default,
+  EchoTruncate,
   Else,
+  ExitMessage,
+  ExitMessageTemplate,
   Export,
+  DotenvFiles,
   DotenvLoad,
   True,
   False,
   If,
+  ListHeading,
+  ListPrefix,
+  MetadataSideEffects,
+  NoExitMessage,
+  Pager,
   Set,
   Shell,
+  StrictInterpolation,
+  Tempdir,
+  Timestamps,
+  UnknownRecipe,
+  WindowsPowershell,
+  WindowsShell,
 }
 
 impl Keyword {
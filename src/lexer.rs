@@ -3,6 +3,47 @@ use crate::common::*;
 use CompilationErrorKind::*;
 use TokenKind::*;
 
+/// Characters which look like characters that are meaningful outside of
+/// recipe bodies, and which are reported with the character they look like
+/// instead of as an unknown start of token
+const CONFUSABLES: &[(char, char)] = &[
+  ('\u{00A0}', ' '),
+  ('\u{2002}', ' '),
+  ('\u{2003}', ' '),
+  ('\u{2009}', ' '),
+  ('\u{202F}', ' '),
+  ('\u{3000}', ' '),
+  ('\u{2010}', '-'),
+  ('\u{2011}', '-'),
+  ('\u{2012}', '-'),
+  ('\u{2013}', '-'),
+  ('\u{2014}', '-'),
+  ('\u{2212}', '-'),
+  ('\u{2018}', '\''),
+  ('\u{2019}', '\''),
+  ('\u{201C}', '"'),
+  ('\u{201D}', '"'),
+  ('\u{2236}', ':'),
+  ('\u{FF01}', '!'),
+  ('\u{FF03}', '#'),
+  ('\u{FF04}', '$'),
+  ('\u{FF08}', '('),
+  ('\u{FF09}', ')'),
+  ('\u{FF0A}', '*'),
+  ('\u{FF0B}', '+'),
+  ('\u{FF0C}', ','),
+  ('\u{FF1A}', ':'),
+  ('\u{FF1D}', '='),
+  ('\u{FF20}', '@'),
+  ('\u{FF3B}', '['),
+  ('\u{FF3D}', ']'),
+  ('\u{FF5B}', '{'),
+  ('\u{FF5D}', '}'),
+];
+
+/// Characters which take up no space, and so can't be seen in the source
+const INVISIBLES: &[char] = &['\u{200B}', '\u{200C}', '\u{200D}', '\u{2060}', '\u{FEFF}'];
+
 /// Just language lexer
 ///
 /// The lexer proceeds character-by-character, as opposed to using regular
@@ -269,18 +310,23 @@ impl<'src> Lexer<'src> {
     true
   }
 
-  /// True if `c` can be the first character of an identifier
+  /// True if `c` can be the first character of an identifier: an
+  /// underscore, or a letter as defined by Unicode's `XID_Start` property
   fn is_identifier_start(c: char) -> bool {
-    matches!(c, 'a'..='z' | 'A'..='Z' | '_')
+    c == '_' || UnicodeXID::is_xid_start(c)
   }
 
-  /// True if `c` can be a continuation character of an idenitifier
+  /// True if `c` can be a continuation character of an identifier: a dash, or
+  /// a letter, digit, underscore, or mark as defined by Unicode's
+  /// `XID_Continue` property
   fn is_identifier_continue(c: char) -> bool {
-    if Self::is_identifier_start(c) {
-      return true;
-    }
+    c == '-' || UnicodeXID::is_xid_continue(c)
+  }
 
-    matches!(c, '0'..='9' | '-')
+  /// True if `c` is a combining diacritical mark, which names may not contain,
+  /// so that names which look the same are always spelled the same
+  fn is_combining_diacritic(c: char) -> bool {
+    matches!(c, '\u{0300}'..='\u{036F}')
   }
 
   /// Consume the text and produce a series of tokens
@@ -475,6 +521,7 @@ impl<'src> Lexer<'src> {
   fn lex_normal(&mut self, start: char) -> CompilationResult<'src, ()> {
     match start {
       '!' => self.lex_bang(),
+      '|' => self.lex_bar(),
       '*' => self.lex_single(Asterisk),
       '$' => self.lex_single(Dollar),
       '@' => self.lex_single(At),
@@ -488,15 +535,33 @@ impl<'src> Lexer<'src> {
       '{' => self.lex_delimiter(BraceL),
       '}' => self.lex_delimiter(BraceR),
       '+' => self.lex_single(Plus),
+      '-' => self.lex_single(Minus),
+      '/' => self.lex_single(Slash),
       '#' => self.lex_comment(),
       ' ' => self.lex_whitespace(),
       '`' | '"' | '\'' => self.lex_string(),
       '\n' => self.lex_eol(),
       '\r' => self.lex_eol(),
       '\t' => self.lex_whitespace(),
+      '0'..='9' => self.lex_number(),
       _ if Self::is_identifier_start(start) => self.lex_identifier(),
       _ => {
         self.advance()?;
+
+        if let Some((character, lookalike)) = CONFUSABLES
+          .iter()
+          .find(|(character, _)| *character == start)
+        {
+          return Err(self.error(ConfusableCharacter {
+            character: *character,
+            lookalike: *lookalike,
+          }));
+        }
+
+        if INVISIBLES.contains(&start) {
+          return Err(self.error(InvisibleCharacter { character: start }));
+        }
+
         Err(self.error(UnknownStartOfToken))
       },
     }
@@ -691,6 +756,23 @@ impl<'src> Lexer<'src> {
     }
   }
 
+  /// Lex a token starting with '|'
+  fn lex_bar(&mut self) -> CompilationResult<'src, ()> {
+    self.presume('|')?;
+
+    if self.accepted('|')? {
+      self.token(BarBar);
+      Ok(())
+    } else {
+      // Emit an unspecified token to consume the current character,
+      self.token(Unspecified);
+      // …and advance past another character,
+      self.advance()?;
+      // …so that the error we produce highlights the unexpected character.
+      Err(self.error(UnexpectedCharacter { expected: '|' }))
+    }
+  }
+
   /// Lex a token starting with ':'
   fn lex_colon(&mut self) -> CompilationResult<'src, ()> {
     self.presume(':')?;
@@ -726,7 +808,7 @@ impl<'src> Lexer<'src> {
     Ok(())
   }
 
-  /// Lex name: [a-zA-Z_][a-zA-Z0-9_]*
+  /// Lex name: [\p{XID_Start}_][\p{XID_Continue}-]*
   fn lex_identifier(&mut self) -> CompilationResult<'src, ()> {
     self.advance()?;
 
@@ -738,11 +820,40 @@ impl<'src> Lexer<'src> {
       self.advance()?;
     }
 
+    if let Some(character) = self
+      .lexeme()
+      .chars()
+      .find(|c| Self::is_combining_diacritic(*c))
+    {
+      return Err(self.error(CombiningCharacter {
+        name: self.lexeme(),
+        character,
+      }));
+    }
+
     self.token(Identifier);
 
     Ok(())
   }
 
+  /// Lex number: [0-9]+
+  fn lex_number(&mut self) -> CompilationResult<'src, ()> {
+    while let Some('0'..='9') = self.next {
+      self.advance()?;
+    }
+
+    // names may not start with a digit
+    if let Some(c) = self.next {
+      if Self::is_identifier_start(c) {
+        return Err(self.error(UnknownStartOfToken));
+      }
+    }
+
+    self.token(Number);
+
+    Ok(())
+  }
+
   /// Lex comment: #[^\r\n]
   fn lex_comment(&mut self) -> CompilationResult<'src, ()> {
     self.presume('#')?;
@@ -915,6 +1026,7 @@ mod tests {
       Asterisk => "*",
       At => "@",
       BangEquals => "!=",
+      BarBar => "||",
       BraceL => "{",
       BraceR => "}",
       BracketL => "[",
@@ -929,16 +1041,18 @@ mod tests {
       Indent => "  ",
       InterpolationEnd => "}}",
       InterpolationStart => "{{",
+      Minus => "-",
       ParenL => "(",
       ParenR => ")",
       Plus => "+",
+      Slash => "/",
       Whitespace => " ",
 
       // Empty lexemes
       Dedent | Eof => "",
 
       // Variable lexemes
-      Text | StringToken | Backtick | Identifier | Comment | Unspecified =>
+      Text | StringToken | Backtick | Identifier | Comment | Number | Unspecified =>
         panic!("Token {:?} has no default lexeme", kind),
     }
   }
@@ -993,6 +1107,18 @@ mod tests {
     tokens: (Identifier:"foo"),
   }
 
+  test! {
+    name:   name_unicode,
+    text:   "größe-π_2",
+    tokens: (Identifier:"größe-π_2"),
+  }
+
+  test! {
+    name:   name_unicode_start,
+    text:   "名前 _ö",
+    tokens: (Identifier:"名前", Whitespace, Identifier:"_ö"),
+  }
+
   test! {
     name:   comment,
     text:   "# hello",
@@ -1059,6 +1185,18 @@ mod tests {
     tokens: (BangEquals),
   }
 
+  test! {
+    name:   bar_bar,
+    text:   "||",
+    tokens: (BarBar),
+  }
+
+  test! {
+    name:   coalesce,
+    text:   "a || 'b'",
+    tokens: (Identifier:"a", Whitespace, BarBar, Whitespace, StringToken:"'b'"),
+  }
+
   test! {
     name:   brace_l,
     text:   "{",
@@ -1107,6 +1245,69 @@ mod tests {
     )
   }
 
+  test! {
+    name: minus,
+    text: "alias t := test --release -v",
+    tokens: (
+      Identifier:"alias",
+      Whitespace,
+      Identifier:"t",
+      Whitespace,
+      ColonEquals,
+      Whitespace,
+      Identifier:"test",
+      Whitespace,
+      Minus,
+      Minus,
+      Identifier:"release",
+      Whitespace,
+      Minus,
+      Identifier:"v",
+    )
+  }
+
+  test! {
+    name: arithmetic,
+    text: "x := 1 + 2 - 3 * 4 / 5",
+    tokens: (
+      Identifier:"x",
+      Whitespace,
+      ColonEquals,
+      Whitespace,
+      Number:"1",
+      Whitespace,
+      Plus,
+      Whitespace,
+      Number:"2",
+      Whitespace,
+      Minus,
+      Whitespace,
+      Number:"3",
+      Whitespace,
+      Asterisk,
+      Whitespace,
+      Number:"4",
+      Whitespace,
+      Slash,
+      Whitespace,
+      Number:"5",
+    ),
+  }
+
+  test! {
+    name: number,
+    text: "set echo-truncate := 200",
+    tokens: (
+      Identifier:"set",
+      Whitespace,
+      Identifier:"echo-truncate",
+      Whitespace,
+      ColonEquals,
+      Whitespace,
+      Number:"200",
+    )
+  }
+
   test! {
     name: export_complex,
     text: "export foo = ('foo' + 'bar') + `baz`",
@@ -2123,8 +2324,8 @@ mod tests {
   }
 
   error! {
-    name:   invalid_name_start_dash,
-    input:  "-foo",
+    name:   invalid_name_start_digit,
+    input:  "0foo",
     offset: 0,
     line:   0,
     column: 0,
@@ -2133,13 +2334,52 @@ mod tests {
   }
 
   error! {
-    name:   invalid_name_start_digit,
-    input:  "0foo",
+    name:   confusable_character,
+    input:  "a := ‘b’",
+    offset: 5,
+    line:   0,
+    column: 5,
+    width:  3,
+    kind:   ConfusableCharacter {
+      character: '\u{2018}',
+      lookalike: '\'',
+    },
+  }
+
+  error! {
+    name:   confusable_space,
+    input:  "a\u{00A0}:= 'b'",
+    offset: 1,
+    line:   0,
+    column: 1,
+    width:  2,
+    kind:   ConfusableCharacter {
+      character: '\u{00A0}',
+      lookalike: ' ',
+    },
+  }
+
+  error! {
+    name:   invisible_character,
+    input:  "a\u{200B} := 'b'",
+    offset: 1,
+    line:   0,
+    column: 1,
+    width:  3,
+    kind:   InvisibleCharacter { character: '\u{200B}' },
+  }
+
+  error! {
+    name:   combining_character,
+    input:  "cafe\u{0301} := 'b'",
     offset: 0,
     line:   0,
     column: 0,
-    width:  1,
-    kind:   UnknownStartOfToken,
+    width:  6,
+    kind:   CombiningCharacter {
+      name:      "cafe\u{0301}",
+      character: '\u{0301}',
+    },
   }
 
   error! {
@@ -2212,6 +2452,16 @@ mod tests {
     kind:   UnexpectedCharacter { expected: '=' },
   }
 
+  error! {
+    name:   unexpected_character_after_bar,
+    input:  "|a",
+    offset: 1,
+    line:   0,
+    column: 1,
+    width:  1,
+    kind:   UnexpectedCharacter { expected: '|' },
+  }
+
   error! {
     name:   mismatched_closing_brace,
     input:  "(]",
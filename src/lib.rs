@@ -54,10 +54,16 @@ pub mod node;
 pub(crate) mod fuzzing;
 
 mod alias;
+mod alias_argument;
+mod alias_metadata;
 mod analyzer;
 mod assignment;
 mod assignment_resolver;
+mod attribute;
+mod backtick_env;
 mod binding;
+mod capture;
+mod changed_files;
 mod color;
 mod command_ext;
 mod common;
@@ -67,18 +73,31 @@ mod compiler;
 mod config;
 mod config_error;
 mod count;
+mod datetime;
 mod default;
 mod delimiter;
 mod dependency;
+mod doc_interpolator;
+mod dotenv_error;
+mod dotenv_file;
+mod dotenv_parser;
+mod download_cache;
+mod dump;
 mod empty;
 mod enclosure;
 mod error;
 mod error_result_ext;
 mod evaluator;
+mod exit_message;
 mod expression;
+mod fallback;
+mod filter;
 mod fragment;
 mod function;
+mod function_cache;
 mod function_context;
+mod glob;
+mod graph;
 mod interrupt_guard;
 mod interrupt_handler;
 mod item;
@@ -90,42 +109,64 @@ mod line;
 mod list;
 mod load_dotenv;
 mod load_error;
+mod local_override;
+mod metadata;
 mod module;
 mod name;
+mod operator;
 mod ordinal;
 mod output;
 mod output_error;
+mod output_format;
+mod output_value;
+mod pager;
 mod parameter;
 mod parameter_kind;
+mod parameter_metadata;
 mod parser;
 mod platform;
 mod platform_interface;
 mod position;
 mod positional;
+mod process_limits;
 mod range_ext;
 mod recipe;
 mod recipe_context;
+mod recipe_metadata;
 mod recipe_resolver;
+mod remembered_arguments;
+mod report;
+mod report_format;
+mod resource;
 mod run;
+mod run_log;
 mod runtime_error;
 mod scope;
 mod search;
+mod search_boundary;
 mod search_config;
 mod search_error;
 mod set;
 mod setting;
 mod settings;
+mod sha256;
 mod shebang;
 mod show_whitespace;
+mod sort_order;
+mod sortable;
+mod source_formatter;
+mod stats;
 mod string_kind;
 mod string_literal;
 mod subcommand;
 mod suggestion;
 mod table;
+mod tee;
 mod thunk;
 mod token;
 mod token_kind;
 mod unindent;
+mod unknown_recipe;
 mod unresolved_dependency;
 mod unresolved_recipe;
 mod use_color;
@@ -141,3 +182,6 @@ pub use unindent::unindent;
 
 #[cfg(feature = "summary")]
 pub mod summary;
+
+#[cfg(feature = "api")]
+pub mod api;
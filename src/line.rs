@@ -26,16 +26,23 @@ impl<'src> Line<'src> {
   }
 
   pub(crate) fn is_quiet(&self) -> bool {
-    match self.fragments.first() {
-      Some(Fragment::Text { token }) => token.lexeme().starts_with('@'),
-      _ => false,
-    }
+    self.prefix().contains('@')
   }
 
   pub(crate) fn is_infallable(&self) -> bool {
+    self.prefix().contains('-')
+  }
+
+  /// The line's prefix: `@`, which suppresses echoing, `-`, which ignores
+  /// failure, both, in either order, or nothing
+  pub(crate) fn prefix(&self) -> &'static str {
     match self.fragments.first() {
-      Some(Fragment::Text { token }) => token.lexeme().starts_with('-'),
-      _ => false,
+      Some(Fragment::Text { token }) => ["@-", "-@", "@", "-"]
+        .iter()
+        .find(|prefix| token.lexeme().starts_with(*prefix))
+        .copied()
+        .unwrap_or_default(),
+      _ => "",
     }
   }
 }
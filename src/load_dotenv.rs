@@ -7,14 +7,30 @@ pub(crate) fn load_dotenv(
   settings: &Settings,
   working_directory: &Path,
 ) -> RunResult<'static, BTreeMap<String, String>> {
-  // `dotenv::from_path_iter` should eventually be un-deprecated, see:
-  // https://github.com/dotenv-rs/dotenv/issues/13
-  #![allow(deprecated)]
+  if let Some(path) = &config.dotenv_file {
+    let mut dotenv = BTreeMap::new();
+    load_dotenv_file(&config.invocation_directory.join(path), &mut dotenv)?;
+    return Ok(dotenv);
+  }
 
-  if !settings.dotenv_load.unwrap_or(true) {
+  if !settings.dotenv_load.unwrap_or(true) || !settings.side_effects(config) {
     return Ok(BTreeMap::new());
   }
 
+  if let Some(files) = &settings.dotenv_files {
+    let mut dotenv = BTreeMap::new();
+
+    for file in files {
+      let path = working_directory.join(&file.cooked);
+
+      if path.is_file() {
+        load_dotenv_file(&path, &mut dotenv)?;
+      }
+    }
+
+    return Ok(dotenv);
+  }
+
   for directory in working_directory.ancestors() {
     let path = directory.join(".env");
 
@@ -29,17 +45,33 @@ pub(crate) fn load_dotenv(
       //   }
       // }
 
-      let iter = dotenv::from_path_iter(&path)?;
       let mut dotenv = BTreeMap::new();
-      for result in iter {
-        let (key, value) = result?;
-        if env::var_os(&key).is_none() {
-          dotenv.insert(key, value);
-        }
-      }
+      load_dotenv_file(&path, &mut dotenv)?;
       return Ok(dotenv);
     }
   }
 
   Ok(BTreeMap::new())
 }
+
+/// Load the variables in the dotenv file at `path` into `dotenv`, replacing
+/// any already loaded from other files. Variables already in the environment
+/// are skipped.
+fn load_dotenv_file(path: &Path, dotenv: &mut BTreeMap<String, String>) -> RunResult<'static, ()> {
+  let error = |dotenv_error| RuntimeError::Dotenv {
+    path: path.to_owned(),
+    dotenv_error,
+  };
+
+  let text = fs::read_to_string(path).map_err(|io_error| error(DotenvError::Io(io_error)))?;
+
+  for (key, value) in
+    DotenvParser::parse(&text, &|name| env::var(name).ok(), dotenv).map_err(error)?
+  {
+    if env::var_os(&key).is_none() {
+      dotenv.insert(key, value);
+    }
+  }
+
+  Ok(())
+}
@@ -0,0 +1,31 @@
+use crate::common::*;
+
+/// A recipe, alias, variable, or setting in a local justfile which replaces
+/// one with the same name in the justfile it is layered over
+#[derive(Debug, PartialEq)]
+pub(crate) struct LocalOverride<'src> {
+  /// What kind of item was overridden, e.g. `recipe`
+  pub(crate) kind: &'static str,
+  /// Name of the overriding item in the local justfile
+  pub(crate) name: Name<'src>,
+}
+
+impl Display for LocalOverride<'_> {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    let warning = Color::fmt(f).warning();
+    let message = Color::fmt(f).message();
+
+    write!(
+      f,
+      "{} {}Local justfile overrides {} `{}`{}",
+      warning.paint("note:"),
+      message.prefix(),
+      self.kind,
+      self.name.lexeme(),
+      message.suffix(),
+    )?;
+
+    writeln!(f)?;
+    self.name.token().write_context(f, Color::fmt(f).warning())
+  }
+}
@@ -0,0 +1,378 @@
+use crate::common::*;
+
+use std::{
+  collections::hash_map::DefaultHasher,
+  hash::{Hash, Hasher},
+};
+
+/// Metadata about the recipes and aliases in a justfile, which is everything
+/// `--list` and `--summary` need. Metadata can be cached on disk, keyed by a
+/// hash of the justfile source, so that listing very large justfiles, for
+/// example on every keystroke during shell completion, doesn't require
+/// compiling them each time.
+#[derive(Debug, PartialEq)]
+pub(crate) struct Metadata {
+  /// Public aliases, sorted by name
+  pub(crate) aliases:      Vec<AliasMetadata>,
+  /// Recipes, sorted by name
+  pub(crate) recipes:      Vec<RecipeMetadata>,
+  /// Value of the `list-heading` setting
+  pub(crate) list_heading: Option<String>,
+  /// Value of the `list-prefix` setting
+  pub(crate) list_prefix:  Option<String>,
+  /// Value of the `pager` setting
+  pub(crate) pager:        bool,
+}
+
+impl Metadata {
+  /// The source that metadata is cached for, which depends on both the
+  /// justfile and the local justfile
+  pub(crate) fn source(src: &str, local: Option<&str>) -> String {
+    match local {
+      Some(local) => format!("{}\0{}", src, local),
+      None => src.to_owned(),
+    }
+  }
+
+  /// Load cached metadata for `src` from `cache_dir`, if present and valid
+  pub(crate) fn load(cache_dir: &Path, src: &str) -> Option<Self> {
+    let path = Self::path(cache_dir, src);
+
+    let text = fs::read_to_string(&path).ok()?;
+
+    let metadata = Self::deserialize(&text);
+
+    if metadata.is_none() {
+      warn!("Ignoring invalid metadata cache file `{}`", path.display());
+    }
+
+    metadata
+  }
+
+  /// Cache metadata for `src` in `cache_dir`. Failure to write the cache is
+  /// not fatal, so errors are logged and otherwise ignored.
+  pub(crate) fn store(&self, cache_dir: &Path, src: &str) {
+    let path = Self::path(cache_dir, src);
+
+    if let Err(io_error) =
+      fs::create_dir_all(cache_dir).and_then(|()| fs::write(&path, self.serialize()))
+    {
+      warn!(
+        "Failed to write metadata cache file `{}`: {}",
+        path.display(),
+        io_error
+      );
+    }
+  }
+
+  /// Whether any public recipe's doc comment contains an interpolation,
+  /// which must be evaluated each time recipes are listed
+  pub(crate) fn has_doc_interpolations(&self) -> bool {
+    self
+      .public_recipes(SortOrder::Source)
+      .iter()
+      .filter_map(|recipe| recipe.doc.as_deref())
+      .any(|doc| doc.contains("{{"))
+  }
+
+  /// The recipe called `name`, or the target of the public alias called
+  /// `name`
+  pub(crate) fn get_recipe(&self, name: &str) -> Option<&RecipeMetadata> {
+    let name = self
+      .aliases
+      .iter()
+      .find(|alias| alias.name == name)
+      .map_or(name, |alias| alias.target.as_str());
+
+    self.recipes.iter().find(|recipe| recipe.name == name)
+  }
+
+  pub(crate) fn public_recipes(&self, sort_order: SortOrder) -> Vec<&RecipeMetadata> {
+    sort_order.sorted(self.recipes.iter().filter(|recipe| !recipe.private))
+  }
+
+  /// Public recipes, grouped by the groups given with `[group]` attributes.
+  /// Recipes without groups come first, under `None`, followed by each group
+  /// in `sort_order`, by name or by first appearance. Recipes with more than
+  /// one group appear in each.
+  pub(crate) fn groups(&self, sort_order: SortOrder) -> Vec<(Option<&str>, Vec<&RecipeMetadata>)> {
+    let mut groups: Vec<(Option<&str>, Vec<&RecipeMetadata>)> = vec![(None, Vec::new())];
+
+    for recipe in self.public_recipes(sort_order) {
+      let names = if recipe.groups.is_empty() {
+        vec![None]
+      } else {
+        recipe
+          .groups
+          .iter()
+          .map(|group| Some(group.as_str()))
+          .collect()
+      };
+
+      for name in names {
+        match groups.iter_mut().find(|(group, _)| *group == name) {
+          Some((_, recipes)) => recipes.push(recipe),
+          None => groups.push((name, vec![recipe])),
+        }
+      }
+    }
+
+    if sort_order == SortOrder::Name {
+      groups.sort_by_key(|(group, _)| *group);
+    }
+
+    groups.retain(|(_, recipes)| !recipes.is_empty());
+
+    groups
+  }
+
+  fn path(cache_dir: &Path, src: &str) -> PathBuf {
+    let mut hasher = DefaultHasher::new();
+    env!("CARGO_PKG_VERSION").hash(&mut hasher);
+    src.hash(&mut hasher);
+    cache_dir.join(format!("{:016x}", hasher.finish()))
+  }
+
+  fn serialize(&self) -> String {
+    let mut text = String::new();
+
+    for (name, value) in &[
+      ("list-heading", &self.list_heading),
+      ("list-prefix", &self.list_prefix),
+    ] {
+      text.push_str(&format!(
+        "setting\t{}\t{}\n",
+        name,
+        Self::escape(value.as_deref())
+      ));
+    }
+
+    text.push_str(&format!("setting\tpager\t{}\n", self.pager));
+
+    for alias in &self.aliases {
+      text.push_str(&format!("alias\t{}\t{}", alias.name, alias.target));
+
+      for argument in &alias.arguments {
+        text.push_str(&format!("\t{}", Self::escape(Some(argument))));
+      }
+
+      text.push('\n');
+    }
+
+    for recipe in &self.recipes {
+      text.push_str(&format!(
+        "recipe\t{}\t{}\t{}\t{}\n",
+        recipe.name,
+        recipe.offset,
+        recipe.private,
+        Self::escape(recipe.doc.as_deref()),
+      ));
+
+      for group in &recipe.groups {
+        text.push_str(&format!("group\t{}\n", Self::escape(Some(group))));
+      }
+
+      for parameter in &recipe.parameters {
+        text.push_str(&format!(
+          "parameter\t{}\t{}\t{}\t{}\t{}\n",
+          Self::escape(parameter.prefix.as_deref()),
+          parameter.export,
+          parameter.name,
+          Self::escape(parameter.default.as_deref()),
+          parameter.path,
+        ));
+      }
+    }
+
+    text
+  }
+
+  fn deserialize(text: &str) -> Option<Self> {
+    let mut aliases = Vec::new();
+    let mut recipes: Vec<RecipeMetadata> = Vec::new();
+    let mut list_heading = None;
+    let mut list_prefix = None;
+    let mut pager = false;
+
+    for line in text.lines() {
+      match line.split('\t').collect::<Vec<&str>>().as_slice() {
+        ["setting", "list-heading", value] => list_heading = Self::unescape(value)?,
+        ["setting", "list-prefix", value] => list_prefix = Self::unescape(value)?,
+        ["setting", "pager", value] => pager = value.parse().ok()?,
+        ["alias", name, target, arguments @ ..] => aliases.push(AliasMetadata {
+          name:      (*name).to_owned(),
+          target:    (*target).to_owned(),
+          arguments: arguments
+            .iter()
+            .map(|argument| Self::unescape(argument)?)
+            .collect::<Option<Vec<String>>>()?,
+        }),
+        ["recipe", name, offset, private, doc] => recipes.push(RecipeMetadata {
+          name:       (*name).to_owned(),
+          offset:     offset.parse().ok()?,
+          private:    private.parse().ok()?,
+          doc:        Self::unescape(doc)?,
+          groups:     Vec::new(),
+          parameters: Vec::new(),
+        }),
+        ["group", group] => recipes.last_mut()?.groups.push(Self::unescape(group)??),
+        ["parameter", prefix, export, name, default, path] =>
+          recipes.last_mut()?.parameters.push(ParameterMetadata {
+            prefix:  Self::unescape(prefix)?,
+            export:  export.parse().ok()?,
+            name:    (*name).to_owned(),
+            default: Self::unescape(default)?,
+            path:    path.parse().ok()?,
+          }),
+        _ => return None,
+      }
+    }
+
+    Some(Self {
+      aliases,
+      recipes,
+      list_heading,
+      list_prefix,
+      pager,
+    })
+  }
+
+  /// Escape an optional field, so that it contains no tabs or newlines and
+  /// `None` can be distinguished from `Some("")`
+  fn escape(field: Option<&str>) -> String {
+    let value = match field {
+      Some(value) => value,
+      None => return "-".to_owned(),
+    };
+
+    let mut escaped = String::from("+");
+
+    for c in value.chars() {
+      match c {
+        '\\' => escaped.push_str("\\\\"),
+        '\t' => escaped.push_str("\\t"),
+        '\n' => escaped.push_str("\\n"),
+        '\r' => escaped.push_str("\\r"),
+        _ => escaped.push(c),
+      }
+    }
+
+    escaped
+  }
+
+  /// Unescape a field escaped with `escape`, returning `None` if it is invalid
+  fn unescape(field: &str) -> Option<Option<String>> {
+    if field == "-" {
+      return Some(None);
+    }
+
+    let mut chars = field.strip_prefix('+')?.chars();
+
+    let mut value = String::new();
+
+    while let Some(c) = chars.next() {
+      if c == '\\' {
+        value.push(match chars.next()? {
+          '\\' => '\\',
+          't' => '\t',
+          'n' => '\n',
+          'r' => '\r',
+          _ => return None,
+        });
+      } else {
+        value.push(c);
+      }
+    }
+
+    Some(Some(value))
+  }
+}
+
+impl<'src> From<&Justfile<'src>> for Metadata {
+  fn from(justfile: &Justfile<'src>) -> Self {
+    Self {
+      aliases:      justfile
+        .aliases
+        .values()
+        .filter(|alias| !alias.is_private())
+        .map(AliasMetadata::from)
+        .collect(),
+      recipes:      justfile
+        .recipes
+        .values()
+        .map(|recipe| RecipeMetadata::from(recipe.as_ref()))
+        .collect(),
+      list_heading: justfile.settings.list_heading.clone(),
+      list_prefix:  justfile.settings.list_prefix.clone(),
+      pager:        justfile.settings.pager,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  use pretty_assertions::assert_eq;
+
+  #[test]
+  fn round_trip() {
+    let justfile = testing::compile(
+      "set list-heading := 'Recipes:\\n'\nset pager\nalias b := build\nalias t := build -v \
+       'a\\tb'\n\n# build \\ the\tthing\n[group('a\\tb'), group('c')]\nbuild \
+       +targets='a\\nb':\n\n[path('x')]\n_private x *$y:",
+    );
+
+    let metadata = Metadata::from(&justfile);
+
+    assert_eq!(metadata.aliases, &[
+      AliasMetadata {
+        name:      "b".to_owned(),
+        target:    "build".to_owned(),
+        arguments: Vec::new(),
+      },
+      AliasMetadata {
+        name:      "t".to_owned(),
+        target:    "build".to_owned(),
+        arguments: vec!["-v".to_owned(), "'a\\tb'".to_owned()],
+      },
+    ]);
+    assert_eq!(metadata.recipes.len(), 2);
+    assert!(metadata.recipes[0].parameters[0].path);
+    assert_eq!(metadata.get_recipe("t").unwrap().name, "build");
+    assert!(metadata.pager);
+
+    assert_eq!(Metadata::deserialize(&metadata.serialize()), Some(metadata));
+  }
+
+  #[test]
+  fn invalid() {
+    assert_eq!(
+      Metadata::deserialize("parameter\t-\tfalse\tfoo\t-\tfalse\n"),
+      None
+    );
+    assert_eq!(
+      Metadata::deserialize("recipe\tfoo\t0\tfalse\t-\nparameter\t-\tfalse\tfoo\t-\n"),
+      None
+    );
+    assert_eq!(Metadata::deserialize("recipe\tfoo\t0\tfalse\tdoc\n"), None);
+    assert_eq!(Metadata::deserialize("foo\n"), None);
+  }
+
+  #[test]
+  fn store_and_load() {
+    let tempdir = tempfile::tempdir().unwrap();
+
+    let src = "foo bar:";
+
+    assert_eq!(Metadata::load(tempdir.path(), src), None);
+
+    let metadata = Metadata::from(&testing::compile(src));
+
+    metadata.store(tempdir.path(), src);
+
+    assert_eq!(Metadata::load(tempdir.path(), src), Some(metadata));
+
+    assert_eq!(Metadata::load(tempdir.path(), "foo:"), None);
+  }
+}
@@ -10,7 +10,40 @@ use crate::common::*;
 #[derive(Debug)]
 pub(crate) struct Module<'src> {
   /// Items in the justfile
-  pub(crate) items:    Vec<Item<'src>>,
+  pub(crate) items:     Vec<Item<'src>>,
   /// Non-fatal warnings encountered during parsing
-  pub(crate) warnings: Vec<Warning>,
+  pub(crate) warnings:  Vec<Warning>,
+  /// Items replaced by items in a local justfile
+  pub(crate) overrides: Vec<LocalOverride<'src>>,
+}
+
+impl<'src> Module<'src> {
+  /// Layer `local` over `self`. Items in `local` are added after those in
+  /// `self`, replacing any item in `self` of the same kind and name. Recipes
+  /// and aliases share a namespace, so a local recipe replaces an alias of the
+  /// same name, and vice versa.
+  pub(crate) fn overlay(mut self, local: Module<'src>) -> Module<'src> {
+    for item in local.items {
+      let (namespace, name) = item.key();
+
+      let before = self.items.len();
+
+      self
+        .items
+        .retain(|existing| existing.key() != (namespace, name));
+
+      if self.items.len() != before {
+        self.overrides.push(LocalOverride {
+          kind: item.kind(),
+          name: item.name(),
+        });
+      }
+
+      self.items.push(item);
+    }
+
+    self.warnings.extend(local.warnings);
+
+    self
+  }
 }
@@ -28,9 +28,84 @@ impl<'src> Node<'src> for Item<'src> {
 
 impl<'src> Node<'src> for Alias<'src, Name<'src>> {
   fn tree(&self) -> Tree<'src> {
-    Tree::atom(Keyword::Alias.lexeme())
-      .push(self.name.lexeme())
-      .push(self.target.lexeme())
+    let mut t = Tree::atom(Keyword::Alias.lexeme());
+
+    if !self.attributes.is_empty() {
+      t.push_mut(self.attributes.tree());
+    }
+
+    t.push_mut(self.name.lexeme());
+    t.push_mut(self.target.lexeme());
+
+    for argument in &self.arguments {
+      t.push_mut(Tree::string(&argument.value));
+    }
+
+    t
+  }
+}
+
+impl<'src> Node<'src> for BTreeSet<Attribute<'src>> {
+  fn tree(&self) -> Tree<'src> {
+    let mut attributes = Tree::atom("attributes");
+
+    for attribute in self {
+      match attribute {
+        Attribute::Cleanup(argument)
+        | Attribute::Confirm(Some(argument))
+        | Attribute::Doc(argument)
+        | Attribute::Filter(argument)
+        | Attribute::Group(argument)
+        | Attribute::Inputs(argument)
+        | Attribute::Nice(argument)
+        | Attribute::Path(argument)
+        | Attribute::Tee(argument)
+        | Attribute::Umask(argument) =>
+          attributes.push_mut(Tree::atom(attribute.name()).push(Tree::string(&argument.cooked))),
+        Attribute::Confirm(None) => attributes.push_mut(Tree::string(attribute.name())),
+        Attribute::Limit(resource, value) => attributes.push_mut(
+          Tree::atom(attribute.name())
+            .push(Tree::string(&resource.cooked))
+            .push(Tree::string(&value.cooked)),
+        ),
+        Attribute::Choices(parameter, choices) => {
+          let mut tree = Tree::atom(attribute.name()).push(parameter.lexeme());
+          for choice in choices {
+            tree.push_mut(Tree::string(&choice.cooked));
+          }
+          attributes.push_mut(tree);
+        },
+        Attribute::Requires(executables) => {
+          let mut tree = Tree::atom(attribute.name());
+          for executable in executables {
+            tree.push_mut(Tree::string(&executable.cooked));
+          }
+          attributes.push_mut(tree);
+        },
+        Attribute::NeedsEnv(variables) => {
+          let mut tree = Tree::atom(attribute.name());
+          for (name, description) in variables {
+            tree.push_mut(match description {
+              Some(description) => Tree::atom(":")
+                .push(Tree::string(&name.cooked))
+                .push(Tree::string(&description.cooked)),
+              None => Tree::string(&name.cooked),
+            });
+          }
+          attributes.push_mut(tree);
+        },
+        Attribute::Fresh
+        | Attribute::NoCd
+        | Attribute::NoExitMessage
+        | Attribute::Once
+        | Attribute::Private
+        | Attribute::Quiet
+        | Attribute::RememberArgs
+        | Attribute::Verbose => attributes.push_mut(Tree::string(attribute.name())),
+      }
+    }
+
+    attributes
   }
 }
 
@@ -51,7 +126,19 @@ impl<'src> Node<'src> for Assignment<'src> {
 impl<'src> Node<'src> for Expression<'src> {
   fn tree(&self) -> Tree<'src> {
     match self {
-      Expression::Concatination { lhs, rhs } => Tree::atom("+").push(lhs.tree()).push(rhs.tree()),
+      Expression::Boolean { value } => Tree::atom(value.to_string()),
+      Expression::Coalesce { lhs, rhs, .. } => Tree::atom("||").push(lhs.tree()).push(rhs.tree()),
+      Expression::Comparison { lhs, rhs, inverted } =>
+        Tree::atom(if *inverted { "!=" } else { "==" })
+          .push(lhs.tree())
+          .push(rhs.tree()),
+      Expression::Arithmetic {
+        lhs, operator, rhs, ..
+      } => Tree::atom(operator.to_string())
+        .push(lhs.tree())
+        .push(rhs.tree()),
+      Expression::Concatination { lhs, rhs, .. } =>
+        Tree::atom("+").push(lhs.tree()).push(rhs.tree()),
       Expression::Conditional {
         lhs,
         rhs,
@@ -88,6 +175,31 @@ impl<'src> Node<'src> for Expression<'src> {
             tree.push_mut(a.tree());
             tree.push_mut(b.tree());
           },
+          Ternary {
+            name,
+            args: [a, b, c],
+            ..
+          } => {
+            tree.push_mut(name.lexeme());
+            tree.push_mut(a.tree());
+            tree.push_mut(b.tree());
+            tree.push_mut(c.tree());
+          },
+          UnaryOpt {
+            name, args: (a, b), ..
+          } => {
+            tree.push_mut(name.lexeme());
+            tree.push_mut(a.tree());
+            if let Some(b) = b {
+              tree.push_mut(b.tree());
+            }
+          },
+          Nary { name, args, .. } => {
+            tree.push_mut(name.lexeme());
+            for arg in args {
+              tree.push_mut(arg.tree());
+            }
+          },
         }
 
         tree
@@ -97,6 +209,7 @@ impl<'src> Node<'src> for Expression<'src> {
         string_literal: StringLiteral { cooked, .. },
       } => Tree::string(cooked),
       Expression::Backtick { contents, .. } => Tree::atom("backtick").push(Tree::string(contents)),
+      Expression::Integer { value } => Tree::atom("integer").push(Tree::string(value.to_string())),
       Expression::Group { contents } => Tree::List(vec![contents.tree()]),
     }
   }
@@ -111,6 +224,10 @@ impl<'src> Node<'src> for UnresolvedRecipe<'src> {
       t.push_mut("quiet");
     }
 
+    if !self.attributes.is_empty() {
+      t.push_mut(self.attributes.tree());
+    }
+
     if let Some(doc) = self.doc {
       t.push_mut(Tree::string(doc));
     }
@@ -141,6 +258,10 @@ impl<'src> Node<'src> for UnresolvedRecipe<'src> {
           d.push_mut(argument.tree());
         }
 
+        if dependency.forward {
+          d.push_mut("*");
+        }
+
         dependencies.push_mut(d);
       }
 
@@ -158,6 +279,11 @@ impl<'src> Node<'src> for UnresolvedRecipe<'src> {
 impl<'src> Node<'src> for Parameter<'src> {
   fn tree(&self) -> Tree<'src> {
     let mut children = Vec::new();
+
+    if self.export {
+      children.push(Tree::atom("$"));
+    }
+
     children.push(Tree::atom(self.name.lexeme()));
 
     if let Some(default) = &self.default {
@@ -191,13 +317,36 @@ impl<'src> Node<'src> for Set<'src> {
 
     use Setting::*;
     match &self.value {
-      DotenvLoad(value) | Export(value) => set.push_mut(value.to_string()),
-      Shell(setting::Shell { command, arguments }) => {
+      BacktickEnv(value) => set.push_mut(Tree::string(value.to_string())),
+      EchoTruncate(value) => set.push_mut(Tree::string(value.to_string())),
+      ExitMessage(value) => set.push_mut(Tree::string(value.to_string())),
+      BoolExpression(expression) => set.push_mut(expression.tree()),
+      AllowDownloads(value)
+      | DotenvLoad(value)
+      | Export(value)
+      | MetadataSideEffects(value)
+      | NoExitMessage(value)
+      | Pager(value)
+      | StrictInterpolation(value)
+      | Timestamps(value)
+      | WindowsPowershell(value) => set.push_mut(value.to_string()),
+      DotenvFiles(files) =>
+        for file in files {
+          set.push_mut(Tree::string(&file.cooked));
+        },
+      Shell(setting::Shell { command, arguments })
+      | WindowsShell(setting::Shell { command, arguments }) => {
         set.push_mut(Tree::string(&command.cooked));
         for argument in arguments {
           set.push_mut(Tree::string(&argument.cooked));
         }
       },
+      Default(value)
+      | ExitMessageTemplate(value)
+      | ListHeading(value)
+      | ListPrefix(value)
+      | Tempdir(value) => set.push_mut(Tree::string(&value.cooked)),
+      UnknownRecipe(value) => set.push_mut(Tree::string(value.to_string())),
     }
 
     set
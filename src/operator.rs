@@ -0,0 +1,62 @@
+use crate::common::*;
+
+/// An arithmetic operator, which takes two integers
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(crate) enum Operator {
+  /// `+`, which is only addition if one of its operands is an integer
+  /// expression, and is otherwise concatenation
+  Add,
+  /// `-`
+  Subtract,
+  /// `*`
+  Multiply,
+  /// `/`, which rounds towards zero
+  Divide,
+}
+
+impl Operator {
+  /// Apply this operator to `lhs` and `rhs`, returning `None` if the result
+  /// overflows or `rhs` is a zero divisor
+  pub(crate) fn apply(self, lhs: i64, rhs: i64) -> Option<i64> {
+    match self {
+      Self::Add => lhs.checked_add(rhs),
+      Self::Subtract => lhs.checked_sub(rhs),
+      Self::Multiply => lhs.checked_mul(rhs),
+      Self::Divide => lhs.checked_div(rhs),
+    }
+  }
+}
+
+impl Display for Operator {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(f, "{}", match self {
+      Self::Add => "+",
+      Self::Subtract => "-",
+      Self::Multiply => "*",
+      Self::Divide => "/",
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn apply() {
+    assert_eq!(Operator::Add.apply(1, 2), Some(3));
+    assert_eq!(Operator::Subtract.apply(1, 2), Some(-1));
+    assert_eq!(Operator::Multiply.apply(3, 4), Some(12));
+    assert_eq!(Operator::Divide.apply(7, 2), Some(3));
+    assert_eq!(Operator::Divide.apply(-7, 2), Some(-3));
+  }
+
+  #[test]
+  fn apply_fails() {
+    assert_eq!(Operator::Add.apply(i64::MAX, 1), None);
+    assert_eq!(Operator::Subtract.apply(i64::MIN, 1), None);
+    assert_eq!(Operator::Multiply.apply(i64::MAX, 2), None);
+    assert_eq!(Operator::Divide.apply(1, 0), None);
+    assert_eq!(Operator::Divide.apply(i64::MIN, -1), None);
+  }
+}
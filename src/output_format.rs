@@ -0,0 +1,103 @@
+use crate::common::*;
+
+/// A structured output format for machine-readable subcommand output
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub(crate) enum OutputFormat {
+  Json,
+  Yaml,
+}
+
+impl OutputFormat {
+  /// Serialize `pairs` as an object mapping names to string values
+  pub(crate) fn object<'a>(self, pairs: impl IntoIterator<Item = (&'a str, &'a str)>) -> String {
+    let pairs = pairs.into_iter().collect::<Vec<(&str, &str)>>();
+
+    let mut output = String::new();
+
+    if pairs.is_empty() {
+      output.push_str("{}\n");
+      return output;
+    }
+
+    match self {
+      Self::Json => {
+        output.push_str("{\n");
+        for (i, (name, value)) in pairs.iter().enumerate() {
+          let separator = if i + 1 < pairs.len() { "," } else { "" };
+          output.push_str(&format!(
+            "  {}: {}{}\n",
+            Self::quote(name),
+            Self::quote(value),
+            separator
+          ));
+        }
+        output.push_str("}\n");
+      },
+      Self::Yaml =>
+        for (name, value) in pairs {
+          output.push_str(&format!("{}: {}\n", Self::quote(name), Self::quote(value)));
+        },
+    }
+
+    output
+  }
+
+  /// Serialize `value`
+  pub(crate) fn value(self, value: &OutputValue) -> String {
+    match self {
+      Self::Json => value.json(),
+      Self::Yaml => value.yaml(),
+    }
+  }
+
+  /// Quote `text` as a double-quoted string. JSON string escapes are also
+  /// valid in YAML double-quoted scalars, so this works for both formats.
+  pub(crate) fn quote(text: &str) -> String {
+    let mut quoted = String::from('"');
+
+    for c in text.chars() {
+      match c {
+        '"' => quoted.push_str("\\\""),
+        '\\' => quoted.push_str("\\\\"),
+        '\n' => quoted.push_str("\\n"),
+        '\r' => quoted.push_str("\\r"),
+        '\t' => quoted.push_str("\\t"),
+        c if c.is_control() => quoted.push_str(&format!("\\u{:04x}", u32::from(c))),
+        c => quoted.push(c),
+      }
+    }
+
+    quoted.push('"');
+
+    quoted
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  use pretty_assertions::assert_eq;
+
+  #[test]
+  fn json() {
+    assert_eq!(
+      OutputFormat::Json.object(vec![("a", "b"), ("c", "\"\\\n\u{1}")]),
+      "{\n  \"a\": \"b\",\n  \"c\": \"\\\"\\\\\\n\\u0001\"\n}\n"
+    );
+  }
+
+  #[test]
+  fn yaml() {
+    assert_eq!(
+      OutputFormat::Yaml.object(vec![("a", "b"), ("c", "d\te")]),
+      "\"a\": \"b\"\n\"c\": \"d\\te\"\n"
+    );
+  }
+
+  #[test]
+  fn empty() {
+    assert_eq!(OutputFormat::Json.object(vec![]), "{}\n");
+    assert_eq!(OutputFormat::Yaml.object(vec![]), "{}\n");
+  }
+}
@@ -0,0 +1,278 @@
+use crate::common::*;
+
+/// A structured value, which can be serialized by an `OutputFormat`
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) enum OutputValue {
+  Null,
+  Bool(bool),
+  Number(u64),
+  String(String),
+  Array(Vec<OutputValue>),
+  /// An object, with keys in the order they will be serialized
+  Object(Vec<(String, OutputValue)>),
+}
+
+impl OutputValue {
+  pub(crate) fn object<'a>(pairs: impl IntoIterator<Item = (&'a str, OutputValue)>) -> Self {
+    Self::Object(
+      pairs
+        .into_iter()
+        .map(|(key, value)| (key.to_owned(), value))
+        .collect(),
+    )
+  }
+
+  pub(crate) fn json(&self) -> String {
+    let mut output = String::new();
+    self.write_json(&mut output, 0);
+    output.push('\n');
+    output
+  }
+
+  /// Serialize as JSON on a single line, for JSON lines output
+  pub(crate) fn json_line(&self) -> String {
+    let mut output = String::new();
+    self.write_json_line(&mut output);
+    output.push('\n');
+    output
+  }
+
+  pub(crate) fn yaml(&self) -> String {
+    let mut output = String::new();
+
+    if self.is_collection() && !self.is_empty() {
+      self.write_yaml(&mut output, 0);
+    } else {
+      self.write_scalar(&mut output);
+      output.push('\n');
+    }
+
+    output
+  }
+
+  fn is_collection(&self) -> bool {
+    matches!(self, Self::Array(_) | Self::Object(_))
+  }
+
+  fn is_empty(&self) -> bool {
+    match self {
+      Self::Array(items) => items.is_empty(),
+      Self::Object(pairs) => pairs.is_empty(),
+      _ => false,
+    }
+  }
+
+  /// Write `self` as JSON, indenting nested lines by `indent` levels
+  fn write_json(&self, output: &mut String, indent: usize) {
+    match self {
+      Self::Array(items) if !items.is_empty() => {
+        output.push_str("[\n");
+        for (i, item) in items.iter().enumerate() {
+          Self::indent(output, indent + 1);
+          item.write_json(output, indent + 1);
+          output.push_str(if i + 1 < items.len() { ",\n" } else { "\n" });
+        }
+        Self::indent(output, indent);
+        output.push(']');
+      },
+      Self::Object(pairs) if !pairs.is_empty() => {
+        output.push_str("{\n");
+        for (i, (key, value)) in pairs.iter().enumerate() {
+          Self::indent(output, indent + 1);
+          output.push_str(&OutputFormat::quote(key));
+          output.push_str(": ");
+          value.write_json(output, indent + 1);
+          output.push_str(if i + 1 < pairs.len() { ",\n" } else { "\n" });
+        }
+        Self::indent(output, indent);
+        output.push('}');
+      },
+      _ => self.write_scalar(output),
+    }
+  }
+
+  fn write_json_line(&self, output: &mut String) {
+    match self {
+      Self::Array(items) => {
+        output.push('[');
+        for (i, item) in items.iter().enumerate() {
+          if i > 0 {
+            output.push(',');
+          }
+          item.write_json_line(output);
+        }
+        output.push(']');
+      },
+      Self::Object(pairs) => {
+        output.push('{');
+        for (i, (key, value)) in pairs.iter().enumerate() {
+          if i > 0 {
+            output.push(',');
+          }
+          output.push_str(&OutputFormat::quote(key));
+          output.push(':');
+          value.write_json_line(output);
+        }
+        output.push('}');
+      },
+      _ => self.write_scalar(output),
+    }
+  }
+
+  /// Write a non-empty collection as block-style YAML, indented by `indent`
+  /// levels
+  fn write_yaml(&self, output: &mut String, indent: usize) {
+    let entries: Vec<(Option<&str>, &OutputValue)> = match self {
+      Self::Array(items) => items.iter().map(|item| (None, item)).collect(),
+      Self::Object(pairs) => pairs
+        .iter()
+        .map(|(key, value)| (Some(key.as_str()), value))
+        .collect(),
+      _ => return,
+    };
+
+    for (key, value) in entries {
+      Self::indent(output, indent);
+
+      match key {
+        Some(key) => {
+          output.push_str(&OutputFormat::quote(key));
+          output.push(':');
+        },
+        None => output.push('-'),
+      }
+
+      if value.is_collection() && !value.is_empty() {
+        output.push('\n');
+        value.write_yaml(output, indent + 1);
+      } else {
+        output.push(' ');
+        value.write_scalar(output);
+        output.push('\n');
+      }
+    }
+  }
+
+  /// Write a scalar or an empty collection. These are written the same way
+  /// in JSON and YAML.
+  fn write_scalar(&self, output: &mut String) {
+    match self {
+      Self::Null => output.push_str("null"),
+      Self::Bool(value) => output.push_str(&value.to_string()),
+      Self::Number(value) => output.push_str(&value.to_string()),
+      Self::String(text) => output.push_str(&OutputFormat::quote(text)),
+      Self::Array(_) => output.push_str("[]"),
+      Self::Object(_) => output.push_str("{}"),
+    }
+  }
+
+  fn indent(output: &mut String, indent: usize) {
+    for _ in 0..indent {
+      output.push_str("  ");
+    }
+  }
+}
+
+impl From<bool> for OutputValue {
+  fn from(value: bool) -> Self {
+    Self::Bool(value)
+  }
+}
+
+impl From<u64> for OutputValue {
+  fn from(value: u64) -> Self {
+    Self::Number(value)
+  }
+}
+
+impl From<&str> for OutputValue {
+  fn from(text: &str) -> Self {
+    Self::String(text.to_owned())
+  }
+}
+
+impl From<String> for OutputValue {
+  fn from(text: String) -> Self {
+    Self::String(text)
+  }
+}
+
+impl<T: Into<OutputValue>> From<Option<T>> for OutputValue {
+  fn from(value: Option<T>) -> Self {
+    value.map(Into::into).unwrap_or(Self::Null)
+  }
+}
+
+impl<T: Into<OutputValue>> From<Vec<T>> for OutputValue {
+  fn from(items: Vec<T>) -> Self {
+    Self::Array(items.into_iter().map(Into::into).collect())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  use pretty_assertions::assert_eq;
+
+  fn value() -> OutputValue {
+    OutputValue::object(vec![
+      ("a", OutputValue::from(1)),
+      ("b", vec!["x", "y"].into()),
+      ("c", OutputValue::object(vec![("d", true.into())])),
+      ("e", Vec::<OutputValue>::new().into()),
+      ("f", None::<&str>.into()),
+    ])
+  }
+
+  #[test]
+  fn json() {
+    assert_eq!(
+      value().json(),
+      "{
+  \"a\": 1,
+  \"b\": [
+    \"x\",
+    \"y\"
+  ],
+  \"c\": {
+    \"d\": true
+  },
+  \"e\": [],
+  \"f\": null
+}
+"
+    );
+  }
+
+  #[test]
+  fn json_line() {
+    assert_eq!(
+      value().json_line(),
+      "{\"a\":1,\"b\":[\"x\",\"y\"],\"c\":{\"d\":true},\"e\":[],\"f\":null}\n"
+    );
+  }
+
+  #[test]
+  fn yaml() {
+    assert_eq!(
+      value().yaml(),
+      "\"a\": 1
+\"b\":
+  - \"x\"
+  - \"y\"
+\"c\":
+  \"d\": true
+\"e\": []
+\"f\": null
+"
+    );
+  }
+
+  #[test]
+  fn scalar() {
+    assert_eq!(OutputValue::from("a").json(), "\"a\"\n");
+    assert_eq!(OutputValue::from("a").yaml(), "\"a\"\n");
+    assert_eq!(OutputValue::object(vec![]).yaml(), "{}\n");
+  }
+}
@@ -0,0 +1,131 @@
+use crate::common::*;
+
+use atty::Stream;
+
+/// Prints long output through the user's pager, enabled with `set pager`
+pub(crate) struct Pager;
+
+impl Pager {
+  /// Print `text` to stdout, through the pager if `enabled` is true, stdout is
+  /// a terminal, and `text` has more lines than fit in the terminal
+  pub(crate) fn print(text: &str, enabled: bool) {
+    if enabled && atty::is(Stream::Stdout) {
+      if let Some(height) = Self::terminal_height() {
+        if text.lines().count() >= height {
+          if let Some(command) = Self::command(|key| env::var_os(key)) {
+            match Self::page(&command, text) {
+              Ok(()) => return,
+              Err(io_error) => warn!("Failed to run pager `{}`: {}", command.join(" "), io_error),
+            }
+          }
+        }
+      }
+    }
+
+    print!("{}", text);
+  }
+
+  /// The pager command, from `$JUST_PAGER` or `$PAGER`, falling back to
+  /// `less`, or `None` if the pager is `cat` or empty, in which case output
+  /// should be printed directly
+  fn command(lookup: impl Fn(&str) -> Option<OsString>) -> Option<Vec<String>> {
+    let pager = ["JUST_PAGER", "PAGER"]
+      .iter()
+      .find_map(|key| lookup(key))
+      .map_or_else(
+        || "less".to_owned(),
+        |pager| pager.to_string_lossy().into_owned(),
+      );
+
+    let command = pager
+      .split_whitespace()
+      .map(str::to_owned)
+      .collect::<Vec<String>>();
+
+    match command.first().map(String::as_str) {
+      None | Some("cat") => None,
+      Some(_) => Some(command),
+    }
+  }
+
+  fn page(command: &[String], text: &str) -> io::Result<()> {
+    let mut child = Command::new(&command[0])
+      .args(&command[1..])
+      // like git, have `less` pass through colors, exit if the text fits on
+      // one screen, and leave the text on screen when it exits, unless the
+      // user has configured it otherwise
+      .env("LESS", env::var_os("LESS").unwrap_or_else(|| "FRX".into()))
+      .stdin(Stdio::piped())
+      .spawn()?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+      // the pager may exit without reading all of its input
+      stdin.write_all(text.as_bytes()).ok();
+    }
+
+    child.wait()?;
+
+    Ok(())
+  }
+
+  #[cfg(unix)]
+  fn terminal_height() -> Option<usize> {
+    // SAFETY: `libc::winsize` is a plain C struct, for which all zeroes is a
+    // valid value, and `TIOCGWINSZ` only writes to the `winsize` it is given.
+    unsafe {
+      let mut size = std::mem::zeroed::<libc::winsize>();
+      if libc::ioctl(libc::STDOUT_FILENO, libc::TIOCGWINSZ, &mut size) == 0 && size.ws_row > 0 {
+        Some(usize::from(size.ws_row))
+      } else {
+        None
+      }
+    }
+  }
+
+  #[cfg(windows)]
+  fn terminal_height() -> Option<usize> {
+    None
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn command(environment: &[(&str, &str)]) -> Option<Vec<String>> {
+    Pager::command(|key| {
+      environment
+        .iter()
+        .find(|(name, _)| *name == key)
+        .map(|(_, value)| OsString::from(value))
+    })
+  }
+
+  #[test]
+  fn default() {
+    assert_eq!(command(&[]), Some(vec!["less".to_owned()]));
+  }
+
+  #[test]
+  fn pager() {
+    assert_eq!(
+      command(&[("PAGER", "most -s")]),
+      Some(vec!["most".to_owned(), "-s".to_owned()])
+    );
+  }
+
+  #[test]
+  fn just_pager_takes_precedence() {
+    assert_eq!(
+      command(&[("PAGER", "most"), ("JUST_PAGER", "bat")]),
+      Some(vec!["bat".to_owned()])
+    );
+  }
+
+  #[test]
+  fn disabled() {
+    assert_eq!(command(&[("PAGER", "cat")]), None);
+    assert_eq!(command(&[("PAGER", "")]), None);
+    assert_eq!(command(&[("JUST_PAGER", " "), ("PAGER", "less")]), None);
+  }
+}
@@ -19,6 +19,9 @@ impl<'src> Display for Parameter<'src> {
     if let Some(prefix) = self.kind.prefix() {
       write!(f, "{}", color.annotation().paint(prefix))?;
     }
+    if self.export {
+      write!(f, "{}", color.annotation().paint("$"))?;
+    }
     write!(f, "{}", color.parameter().paint(self.name.lexeme()))?;
     if let Some(ref default) = self.default {
       write!(f, "={}", color.string().paint(&default.to_string()))?;
@@ -0,0 +1,43 @@
+use crate::common::*;
+
+/// The parts of a `Parameter` needed to list and complete it, with the
+/// default value already rendered to a string.
+#[derive(Debug, PartialEq)]
+pub(crate) struct ParameterMetadata {
+  pub(crate) prefix:  Option<String>,
+  pub(crate) export:  bool,
+  pub(crate) name:    String,
+  pub(crate) default: Option<String>,
+  /// Whether the recipe has a `[path]` attribute for this parameter, which
+  /// is set by `RecipeMetadata`
+  pub(crate) path:    bool,
+}
+
+impl<'src> From<&Parameter<'src>> for ParameterMetadata {
+  fn from(parameter: &Parameter<'src>) -> Self {
+    Self {
+      prefix:  parameter.kind.prefix().map(str::to_owned),
+      export:  parameter.export,
+      name:    parameter.name.lexeme().to_owned(),
+      default: parameter.default.as_ref().map(ToString::to_string),
+      path:    false,
+    }
+  }
+}
+
+impl Display for ParameterMetadata {
+  fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+    let color = Color::fmt(f);
+    if let Some(prefix) = &self.prefix {
+      write!(f, "{}", color.annotation().paint(prefix))?;
+    }
+    if self.export {
+      write!(f, "{}", color.annotation().paint("$"))?;
+    }
+    write!(f, "{}", color.parameter().paint(&self.name))?;
+    if let Some(default) = &self.default {
+      write!(f, "={}", color.string().paint(default))?;
+    }
+    Ok(())
+  }
+}
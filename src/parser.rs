@@ -257,9 +257,7 @@ impl<'tokens, 'src> Parser<'tokens, 'src> {
   }
 
   fn accepted_keyword(&mut self, keyword: Keyword) -> CompilationResult<'src, bool> {
-    let next = self.next()?;
-
-    if next.kind == Identifier && next.lexeme() == keyword.lexeme() {
+    if self.next_is_keyword(keyword)? {
       self.advance()?;
       Ok(true)
     } else {
@@ -267,23 +265,41 @@ impl<'tokens, 'src> Parser<'tokens, 'src> {
     }
   }
 
+  /// Check if the next token is the keyword `keyword`
+  fn next_is_keyword(&mut self, keyword: Keyword) -> CompilationResult<'src, bool> {
+    let next = self.next()?;
+    Ok(next.kind == Identifier && next.lexeme() == keyword.lexeme())
+  }
+
   /// Accept a dependency
   fn accept_dependency(&mut self) -> CompilationResult<'src, Option<UnresolvedDependency<'src>>> {
     if let Some(recipe) = self.accept_name()? {
       Ok(Some(UnresolvedDependency {
         arguments: Vec::new(),
+        forward: false,
         recipe,
       }))
     } else if self.accepted(ParenL)? {
       let recipe = self.parse_name()?;
 
       let mut arguments = Vec::new();
+      let mut forward = false;
 
       while !self.accepted(ParenR)? {
+        if self.accepted(Asterisk)? {
+          forward = true;
+          self.expect(ParenR)?;
+          break;
+        }
+
         arguments.push(self.parse_expression()?);
       }
 
-      Ok(Some(UnresolvedDependency { recipe, arguments }))
+      Ok(Some(UnresolvedDependency {
+        recipe,
+        arguments,
+        forward,
+      }))
     } else {
       Ok(None)
     }
@@ -315,9 +331,13 @@ impl<'tokens, 'src> Parser<'tokens, 'src> {
             if self.next_are(&[Identifier, Identifier, Equals]) {
               return Err(self.get(2)?.error(CompilationErrorKind::DeprecatedEquals));
             } else if self.next_are(&[Identifier, Identifier, ColonEquals]) {
-              items.push(Item::Alias(self.parse_alias()?));
+              items.push(Item::Alias(self.parse_alias(BTreeSet::new())?));
             } else {
-              items.push(Item::Recipe(self.parse_recipe(doc, false)?));
+              items.push(Item::Recipe(self.parse_recipe(
+                doc,
+                false,
+                BTreeSet::new(),
+              )?));
             },
           Some(Keyword::Export) =>
             if self.next_are(&[Identifier, Identifier, Equals]) {
@@ -326,7 +346,11 @@ impl<'tokens, 'src> Parser<'tokens, 'src> {
               self.presume_keyword(Keyword::Export)?;
               items.push(Item::Assignment(self.parse_assignment(true)?));
             } else {
-              items.push(Item::Recipe(self.parse_recipe(doc, false)?));
+              items.push(Item::Recipe(self.parse_recipe(
+                doc,
+                false,
+                BTreeSet::new(),
+              )?));
             },
           Some(Keyword::Set) =>
             if self.next_are(&[Identifier, Identifier, ColonEquals])
@@ -335,7 +359,11 @@ impl<'tokens, 'src> Parser<'tokens, 'src> {
             {
               items.push(Item::Set(self.parse_set()?));
             } else {
-              items.push(Item::Recipe(self.parse_recipe(doc, false)?));
+              items.push(Item::Recipe(self.parse_recipe(
+                doc,
+                false,
+                BTreeSet::new(),
+              )?));
             },
           _ =>
             if self.next_are(&[Identifier, Equals]) {
@@ -343,11 +371,30 @@ impl<'tokens, 'src> Parser<'tokens, 'src> {
             } else if self.next_are(&[Identifier, ColonEquals]) {
               items.push(Item::Assignment(self.parse_assignment(false)?));
             } else {
-              items.push(Item::Recipe(self.parse_recipe(doc, false)?));
+              items.push(Item::Recipe(self.parse_recipe(
+                doc,
+                false,
+                BTreeSet::new(),
+              )?));
             },
         }
       } else if self.accepted(At)? {
-        items.push(Item::Recipe(self.parse_recipe(doc, true)?));
+        items.push(Item::Recipe(self.parse_recipe(
+          doc,
+          true,
+          BTreeSet::new(),
+        )?));
+      } else if self.next_is(BracketL) {
+        let attributes = self.parse_attributes()?;
+        if self.next_is(Identifier)
+          && Keyword::from_lexeme(self.next()?.lexeme()) == Some(Keyword::Alias)
+          && self.next_are(&[Identifier, Identifier, ColonEquals])
+        {
+          items.push(Item::Alias(self.parse_alias(attributes)?));
+        } else {
+          let quiet = self.accepted(At)?;
+          items.push(Item::Recipe(self.parse_recipe(doc, quiet, attributes)?));
+        }
       } else {
         return Err(self.unexpected_token()?);
       }
@@ -365,19 +412,77 @@ impl<'tokens, 'src> Parser<'tokens, 'src> {
     } else {
       Ok(Module {
         warnings: Vec::new(),
+        overrides: Vec::new(),
         items,
       })
     }
   }
 
   /// Parse an alias, e.g `alias name := target`
-  fn parse_alias(&mut self) -> CompilationResult<'src, Alias<'src, Name<'src>>> {
+  fn parse_alias(
+    &mut self,
+    attributes: BTreeSet<Attribute<'src>>,
+  ) -> CompilationResult<'src, Alias<'src, Name<'src>>> {
     self.presume_keyword(Keyword::Alias)?;
     let name = self.parse_name()?;
     self.presume_any(&[Equals, ColonEquals])?;
     let target = self.parse_name()?;
+
+    let mut arguments = Vec::new();
+    while let Some(argument) = self.accept_alias_argument()? {
+      arguments.push(argument);
+    }
+
     self.expect_eol()?;
-    Ok(Alias { name, target })
+
+    if let Some(attribute) = attributes
+      .iter()
+      .find(|attribute| !attribute.applies_to_aliases())
+    {
+      return Err(name.error(CompilationErrorKind::AliasInvalidAttribute {
+        alias:     name.lexeme(),
+        attribute: attribute.name(),
+      }));
+    }
+
+    Ok(Alias {
+      arguments,
+      attributes,
+      name,
+      target,
+    })
+  }
+
+  /// Accept a fixed alias argument, which is either a string, or a word made
+  /// of identifiers, numbers, and dashes with no whitespace between them,
+  /// like `--release`
+  fn accept_alias_argument(&mut self) -> CompilationResult<'src, Option<AliasArgument<'src>>> {
+    if self.next_is(StringToken) {
+      let lexeme = self.next()?.lexeme();
+      let value = self.parse_string_literal()?.cooked;
+      return Ok(Some(AliasArgument { lexeme, value }));
+    }
+
+    if !self.next_is(Identifier) && !self.next_is(Minus) && !self.next_is(Number) {
+      return Ok(None);
+    }
+
+    let start = self.advance()?;
+    let mut end = start.offset + start.length;
+
+    while let Some(token) = self.tokens.get(self.next) {
+      if token.offset != end || !matches!(token.kind, Identifier | Minus | Number) {
+        break;
+      }
+      end += self.advance()?.length;
+    }
+
+    let lexeme = &start.src[start.offset..end];
+
+    Ok(Some(AliasArgument {
+      value: lexeme.to_owned(),
+      lexeme,
+    }))
   }
 
   /// Parse an assignment, e.g. `foo := bar`
@@ -393,10 +498,15 @@ impl<'tokens, 'src> Parser<'tokens, 'src> {
     })
   }
 
-  /// Parse an expression, e.g. `1 + 2`
+  /// Parse an expression, e.g. `1 + 2`, `a || 'b'`, or `a == b`
   fn parse_expression(&mut self) -> CompilationResult<'src, Expression<'src>> {
     if self.accepted_keyword(Keyword::If)? {
-      let lhs = self.parse_expression()?;
+      // conditionals may appear on the left-hand side of a condition
+      let lhs = if self.next_is_keyword(Keyword::If)? {
+        self.parse_expression()?
+      } else {
+        self.parse_coalescence()?
+      };
 
       let inverted = self.accepted(BangEquals)?;
 
@@ -404,7 +514,11 @@ impl<'tokens, 'src> Parser<'tokens, 'src> {
         self.expect(EqualsEquals)?;
       }
 
-      let rhs = self.parse_expression()?;
+      let rhs = if self.next_is_keyword(Keyword::If)? {
+        self.parse_expression()?
+      } else {
+        self.parse_coalescence()?
+      };
 
       self.expect(BraceL)?;
 
@@ -429,15 +543,134 @@ impl<'tokens, 'src> Parser<'tokens, 'src> {
       });
     }
 
-    let value = self.parse_value()?;
+    let coalescence = self.parse_coalescence()?;
+
+    let inverted = self.accepted(BangEquals)?;
+
+    if inverted || self.accepted(EqualsEquals)? {
+      let lhs = Box::new(coalescence);
+      let rhs = Box::new(self.parse_coalescence()?);
+      Ok(Expression::Comparison { lhs, rhs, inverted })
+    } else {
+      Ok(coalescence)
+    }
+  }
+
+  /// Parse a coalescence, e.g. `a || 'b'`, which binds more tightly than `==`
+  fn parse_coalescence(&mut self) -> CompilationResult<'src, Expression<'src>> {
+    let sum = self.parse_sum()?;
 
-    if self.accepted(Plus)? {
-      let lhs = Box::new(value);
-      let rhs = Box::new(self.parse_expression()?);
-      Ok(Expression::Concatination { lhs, rhs })
+    if let Some(token) = self.accept(BarBar)? {
+      let lhs = Box::new(sum);
+      // conditionals may appear on the right-hand side of a `||`
+      let rhs = if self.next_is_keyword(Keyword::If)? {
+        self.parse_expression()?
+      } else {
+        self.parse_coalescence()?
+      };
+      let rhs = Box::new(rhs);
+      Ok(Expression::Coalesce { lhs, rhs, token })
     } else {
-      Ok(value)
+      Ok(sum)
+    }
+  }
+
+  /// Parse a sum, e.g. `a + 'b'` or `a - 1`, which binds more tightly than
+  /// `||`. `+` concatenates, unless one of its operands is an integer
+  /// expression, in which case it adds.
+  fn parse_sum(&mut self) -> CompilationResult<'src, Expression<'src>> {
+    let start = self.next()?;
+    let mut lhs = self.parse_product()?;
+
+    loop {
+      let operator = if self.next_is(Plus) {
+        Operator::Add
+      } else if self.next_is(Minus) {
+        Operator::Subtract
+      } else {
+        return Ok(lhs);
+      };
+
+      let token = self.advance()?;
+
+      let rhs_start = self.next()?;
+
+      // conditionals may appear on the right-hand side of a `+` or `-`
+      let rhs = if self.next_is_keyword(Keyword::If)? {
+        self.parse_expression()?
+      } else {
+        self.parse_product()?
+      };
+
+      lhs = if operator == Operator::Add && !lhs.is_integer() && !rhs.is_integer() {
+        Expression::Concatination {
+          lhs: Box::new(lhs),
+          rhs: Box::new(rhs),
+          token,
+        }
+      } else {
+        Self::arithmetic((start, lhs), operator, token, (rhs_start, rhs))?
+      };
+    }
+  }
+
+  /// Parse a product, e.g. `a * 2` or `a / 2`, which binds more tightly than
+  /// `+` and `-`
+  fn parse_product(&mut self) -> CompilationResult<'src, Expression<'src>> {
+    let start = self.next()?;
+    let mut lhs = self.parse_value()?;
+
+    loop {
+      // a `*` before the closing parenthesis of a dependency forwards the
+      // recipe's arguments, as in `(build 'x' *)`
+      let operator = if self.next_is(Asterisk) && !self.next_are(&[Asterisk, ParenR]) {
+        Operator::Multiply
+      } else if self.next_is(Slash) {
+        Operator::Divide
+      } else {
+        return Ok(lhs);
+      };
+
+      let token = self.advance()?;
+
+      let rhs_start = self.next()?;
+      let rhs = self.parse_value()?;
+
+      lhs = Self::arithmetic((start, lhs), operator, token, (rhs_start, rhs))?;
+    }
+  }
+
+  /// Apply `operator` to `lhs` and `rhs`, each paired with its first token.
+  /// Operands which are literals must be integers, so that mistakes like
+  /// `'v' + 1` are caught before anything runs.
+  fn arithmetic(
+    lhs: (Token<'src>, Expression<'src>),
+    operator: Operator,
+    token: Token<'src>,
+    rhs: (Token<'src>, Expression<'src>),
+  ) -> CompilationResult<'src, Expression<'src>> {
+    for (start, operand) in &[&lhs, &rhs] {
+      let non_integer = match operand {
+        Expression::Boolean { .. } => true,
+        Expression::StringLiteral { string_literal } =>
+          string_literal.cooked.parse::<i64>().is_err(),
+        _ => false,
+      };
+
+      if non_integer {
+        return Err(start.error(CompilationErrorKind::NonIntegerOperand {
+          operator,
+          operand: operand.to_string(),
+        }));
+      }
     }
+
+    Ok(Expression::Arithmetic {
+      lhs: Box::new(lhs.1),
+      operator,
+      rhs: Box::new(rhs.1),
+      token,
+    })
   }
 
   /// Parse a value, e.g. `(bar)`
@@ -446,6 +679,16 @@ impl<'tokens, 'src> Parser<'tokens, 'src> {
       Ok(Expression::StringLiteral {
         string_literal: self.parse_string_literal()?,
       })
+    } else if self.next_is(Number) || self.next_are(&[Minus, Number]) {
+      let negative = self.accepted(Minus)?;
+      let token = self.advance()?;
+      let sign = if negative { "-" } else { "" };
+      let value = format!("{}{}", sign, token.lexeme()).parse().map_err(|_| {
+        token.error(CompilationErrorKind::NumberTooLarge {
+          number: token.lexeme(),
+        })
+      })?;
+      Ok(Expression::Integer { value })
     } else if self.next_is(Backtick) {
       let next = self.next()?;
       let kind = StringKind::from_string_or_backtick(next)?;
@@ -471,6 +714,10 @@ impl<'tokens, 'src> Parser<'tokens, 'src> {
         Ok(Expression::Call {
           thunk: Thunk::resolve(name, arguments)?,
         })
+      } else if Keyword::True == name.lexeme() {
+        Ok(Expression::Boolean { value: true })
+      } else if Keyword::False == name.lexeme() {
+        Ok(Expression::Boolean { value: false })
       } else {
         Ok(Expression::Variable { name })
       }
@@ -562,6 +809,7 @@ impl<'tokens, 'src> Parser<'tokens, 'src> {
     &mut self,
     doc: Option<&'src str>,
     quiet: bool,
+    attributes: BTreeSet<Attribute<'src>>,
   ) -> CompilationResult<'src, UnresolvedRecipe<'src>> {
     let name = self.parse_name()?;
 
@@ -605,18 +853,264 @@ impl<'tokens, 'src> Parser<'tokens, 'src> {
 
     let body = self.parse_body()?;
 
+    let parameters = positional
+      .into_iter()
+      .chain(variadic)
+      .collect::<Vec<Parameter>>();
+
+    for attribute in &attributes {
+      if let Attribute::Choices(parameter, _) = attribute {
+        if !parameters
+          .iter()
+          .any(|candidate| candidate.name.lexeme() == parameter.lexeme())
+        {
+          return Err(
+            parameter.error(CompilationErrorKind::UnknownChoicesParameter {
+              recipe:    name.lexeme(),
+              parameter: parameter.lexeme(),
+            }),
+          );
+        }
+      }
+
+      if let Attribute::Path(parameter) = attribute {
+        if !parameters
+          .iter()
+          .any(|candidate| candidate.name.lexeme() == parameter.cooked)
+        {
+          return Err(name.error(CompilationErrorKind::UnknownPathParameter {
+            recipe:    name.lexeme(),
+            parameter: parameter.cooked.clone(),
+          }));
+        }
+      }
+    }
+
     Ok(Recipe {
-      private: name.lexeme().starts_with('_'),
+      private: name.lexeme().starts_with('_') || attributes.contains(&Attribute::Private),
       shebang: body.first().map(Line::is_shebang).unwrap_or(false),
-      parameters: positional.into_iter().chain(variadic).collect(),
+      parameters,
       doc,
       name,
       quiet,
       dependencies,
       body,
+      attributes,
     })
   }
 
+  /// Parse one or more lines of recipe attributes, e.g. `[remember-args]`
+  fn parse_attributes(&mut self) -> CompilationResult<'src, BTreeSet<Attribute<'src>>> {
+    let mut attributes = BTreeSet::new();
+    let mut lines = BTreeMap::new();
+
+    while self.accepted(BracketL)? {
+      loop {
+        let name = self.parse_name()?;
+
+        let attribute = match name.lexeme() {
+          "choices" => {
+            self.expect(ParenL)?;
+            let parameter = self.parse_name()?;
+            self.expect(Colon)?;
+            let mut choices = vec![self.parse_string_literal()?];
+            while self.accepted(Comma)? {
+              choices.push(self.parse_string_literal()?);
+            }
+            self.expect(ParenR)?;
+            Attribute::Choices(parameter, choices)
+          },
+          "cleanup" => {
+            self.expect(ParenL)?;
+            let recipe = self.parse_string_literal()?;
+            self.expect(ParenR)?;
+            Attribute::Cleanup(recipe)
+          },
+          "confirm" =>
+            if self.accepted(ParenL)? {
+              let prompt = self.parse_string_literal()?;
+              self.expect(ParenR)?;
+              Attribute::Confirm(Some(prompt))
+            } else {
+              Attribute::Confirm(None)
+            },
+          "doc" => {
+            self.expect(ParenL)?;
+            let doc = self.parse_string_literal()?;
+            self.expect(ParenR)?;
+            Attribute::Doc(doc)
+          },
+          "filter" => {
+            self.expect(ParenL)?;
+            let filter = self.parse_string_literal()?;
+            self.expect(ParenR)?;
+            Attribute::Filter(filter)
+          },
+          "group" => {
+            self.expect(ParenL)?;
+            let group = self.parse_string_literal()?;
+            self.expect(ParenR)?;
+            Attribute::Group(group)
+          },
+          "inputs" => {
+            self.expect(ParenL)?;
+            let glob = self.parse_string_literal()?;
+            self.expect(ParenR)?;
+            Attribute::Inputs(glob)
+          },
+          "limit" => {
+            self.expect(ParenL)?;
+            let token = self.next()?;
+            let resource = self.parse_string_literal()?;
+            if resource.cooked.parse::<Resource>().is_err() {
+              return Err(token.error(CompilationErrorKind::InvalidAttributeArgument {
+                attribute: name.lexeme(),
+                argument:  resource.raw,
+                expected:  List::or_ticked(Resource::VALUES).to_string(),
+              }));
+            }
+            self.expect(Comma)?;
+            let token = self.next()?;
+            let value = self.parse_string_literal()?;
+            if ProcessLimits::parse_limit(&value.cooked).is_none() {
+              return Err(token.error(CompilationErrorKind::InvalidAttributeArgument {
+                attribute: name.lexeme(),
+                argument:  value.raw,
+                expected:  "a non-negative integer or `unlimited`".to_owned(),
+              }));
+            }
+            self.expect(ParenR)?;
+            Attribute::Limit(resource, value)
+          },
+          "needs-env" => {
+            self.expect(ParenL)?;
+            let mut variables = vec![self.parse_needs_env_variable()?];
+            while self.accepted(Comma)? {
+              variables.push(self.parse_needs_env_variable()?);
+            }
+            self.expect(ParenR)?;
+            Attribute::NeedsEnv(variables)
+          },
+          "nice" => {
+            self.expect(ParenL)?;
+            let token = self.next()?;
+            let nice = self.parse_string_literal()?;
+            if ProcessLimits::parse_nice(&nice.cooked).is_none() {
+              return Err(token.error(CompilationErrorKind::InvalidAttributeArgument {
+                attribute: name.lexeme(),
+                argument:  nice.raw,
+                expected:  "an integer from -20 to 19".to_owned(),
+              }));
+            }
+            self.expect(ParenR)?;
+            Attribute::Nice(nice)
+          },
+          "path" => {
+            self.expect(ParenL)?;
+            let parameter = self.parse_string_literal()?;
+            self.expect(ParenR)?;
+            Attribute::Path(parameter)
+          },
+          "requires" => {
+            self.expect(ParenL)?;
+            let mut executables = vec![self.parse_string_literal()?];
+            while self.accepted(Comma)? {
+              executables.push(self.parse_string_literal()?);
+            }
+            self.expect(ParenR)?;
+            Attribute::Requires(executables)
+          },
+          "tee" => {
+            self.expect(ParenL)?;
+            let path = self.parse_string_literal()?;
+            self.expect(ParenR)?;
+            Attribute::Tee(path)
+          },
+          "umask" => {
+            self.expect(ParenL)?;
+            let token = self.next()?;
+            let umask = self.parse_string_literal()?;
+            if ProcessLimits::parse_umask(&umask.cooked).is_none() {
+              return Err(token.error(CompilationErrorKind::InvalidAttributeArgument {
+                attribute: name.lexeme(),
+                argument:  umask.raw,
+                expected:  "an octal mode from 0 to 777".to_owned(),
+              }));
+            }
+            self.expect(ParenR)?;
+            Attribute::Umask(umask)
+          },
+          _ => Attribute::from_name(name).ok_or_else(|| {
+            name.error(CompilationErrorKind::UnknownAttribute {
+              attribute: name.lexeme(),
+            })
+          })?,
+        };
+
+        // `[limit]` and `[choices]` may be repeated, but only for different
+        // resources and parameters
+        let key = if let Attribute::Limit(resource, _) = &attribute {
+          format!("{}({})", attribute.name(), resource.cooked)
+        } else if let Attribute::Choices(parameter, _) = &attribute {
+          format!("{}({})", attribute.name(), parameter.lexeme())
+        } else if attribute.repeatable() {
+          attribute.to_string()
+        } else {
+          attribute.name().to_owned()
+        };
+
+        if let Some(first) = lines.get(&key) {
+          return Err(name.error(CompilationErrorKind::DuplicateAttribute {
+            attribute: name.lexeme(),
+            first:     *first,
+          }));
+        }
+
+        for (first, second) in &[
+          (Attribute::Fresh, Attribute::Once),
+          (Attribute::Quiet, Attribute::Verbose),
+        ] {
+          if (attribute == *first && attributes.contains(second))
+            || (attribute == *second && attributes.contains(first))
+          {
+            return Err(name.error(CompilationErrorKind::AttributesConflict {
+              first:  first.name(),
+              second: second.name(),
+            }));
+          }
+        }
+
+        lines.insert(key, name.line);
+        attributes.insert(attribute);
+
+        if !self.accepted(Comma)? {
+          break;
+        }
+      }
+
+      self.expect(BracketR)?;
+      self.expect_eol()?;
+    }
+
+    Ok(attributes)
+  }
+
+  /// Parse an argument to `[needs-env]`: the name of an environment variable,
+  /// optionally followed by a colon and a description
+  fn parse_needs_env_variable(
+    &mut self,
+  ) -> CompilationResult<'src, (StringLiteral<'src>, Option<StringLiteral<'src>>)> {
+    let name = self.parse_string_literal()?;
+
+    let description = if self.accepted(Colon)? {
+      Some(self.parse_string_literal()?)
+    } else {
+      None
+    };
+
+    Ok((name, description))
+  }
+
   /// Parse a recipe parameter
   fn parse_parameter(&mut self, kind: ParameterKind) -> CompilationResult<'src, Parameter<'src>> {
     let export = self.accepted(Dollar)?;
@@ -678,25 +1172,51 @@ impl<'tokens, 'src> Parser<'tokens, 'src> {
   }
 
   /// Parse a boolean setting value
-  fn parse_set_bool(&mut self) -> CompilationResult<'src, bool> {
-    if !self.accepted(ColonEquals)? {
-      return Ok(true);
+  /// Parse the value of the `shell` or `windows-shell` setting, e.g.
+  /// `['bash', '-c']`
+  fn parse_shell(&mut self) -> CompilationResult<'src, setting::Shell<'src>> {
+    self.expect(BracketL)?;
+
+    let command = self.parse_string_literal()?;
+
+    let mut arguments = Vec::new();
+
+    if self.accepted(Comma)? {
+      while !self.next_is(BracketR) {
+        arguments.push(self.parse_string_literal()?);
+
+        if !self.accepted(Comma)? {
+          break;
+        }
+      }
     }
 
-    let identifier = self.expect(Identifier)?;
+    self.expect(BracketR)?;
 
-    let value = if Keyword::True == identifier.lexeme() {
-      true
-    } else if Keyword::False == identifier.lexeme() {
-      false
-    } else {
-      return Err(identifier.error(CompilationErrorKind::ExpectedKeyword {
-        expected: vec![Keyword::True, Keyword::False],
-        found:    identifier.lexeme(),
-      }));
+    Ok(setting::Shell { command, arguments })
+  }
+
+  /// Parse the value of a boolean setting, which is `true` if omitted
+  fn parse_set_bool(
+    &mut self,
+    name: Name<'src>,
+    setting: fn(bool) -> Setting<'src>,
+  ) -> CompilationResult<'src, Set<'src>> {
+    if !self.accepted(ColonEquals)? {
+      return Ok(Set {
+        value: setting(true),
+        name,
+      });
+    }
+
+    // `true` and `false` are resolved now, and other expressions when the
+    // justfile is run
+    let value = match self.parse_expression()? {
+      Expression::Boolean { value } => setting(value),
+      expression => Setting::BoolExpression(expression),
     };
 
-    Ok(value)
+    Ok(Set { value, name })
   }
 
   /// Parse a setting
@@ -705,43 +1225,141 @@ impl<'tokens, 'src> Parser<'tokens, 'src> {
     let name = Name::from_identifier(self.presume(Identifier)?);
     let lexeme = name.lexeme();
 
-    if Keyword::DotenvLoad == lexeme {
-      let value = self.parse_set_bool()?;
-      return Ok(Set {
-        value: Setting::DotenvLoad(value),
-        name,
-      });
+    if Keyword::AllowDownloads == lexeme {
+      return self.parse_set_bool(name, Setting::AllowDownloads);
+    } else if Keyword::DotenvLoad == lexeme {
+      return self.parse_set_bool(name, Setting::DotenvLoad);
     } else if Keyword::Export == lexeme {
-      let value = self.parse_set_bool()?;
-      return Ok(Set {
-        value: Setting::Export(value),
-        name,
-      });
+      return self.parse_set_bool(name, Setting::Export);
+    } else if Keyword::MetadataSideEffects == lexeme {
+      return self.parse_set_bool(name, Setting::MetadataSideEffects);
+    } else if Keyword::NoExitMessage == lexeme {
+      return self.parse_set_bool(name, Setting::NoExitMessage);
+    } else if Keyword::Pager == lexeme {
+      return self.parse_set_bool(name, Setting::Pager);
+    } else if Keyword::StrictInterpolation == lexeme {
+      return self.parse_set_bool(name, Setting::StrictInterpolation);
+    } else if Keyword::Timestamps == lexeme {
+      return self.parse_set_bool(name, Setting::Timestamps);
+    } else if Keyword::WindowsPowershell == lexeme {
+      return self.parse_set_bool(name, Setting::WindowsPowershell);
     }
 
     self.expect(ColonEquals)?;
 
-    if name.lexeme() == Keyword::Shell.lexeme() {
-      self.expect(BracketL)?;
+    if name.lexeme() == Keyword::BacktickEnv.lexeme() {
+      let token = self.next()?;
+      let value = self.parse_string_literal()?;
 
-      let command = self.parse_string_literal()?;
+      let backtick_env = value.cooked.parse().map_err(|_| {
+        token.error(CompilationErrorKind::InvalidSettingValue {
+          setting:  name.lexeme(),
+          value:    value.raw,
+          expected: BacktickEnv::VALUES.to_vec(),
+        })
+      })?;
 
-      let mut arguments = Vec::new();
+      Ok(Set {
+        value: Setting::BacktickEnv(backtick_env),
+        name,
+      })
+    } else if name.lexeme() == Keyword::DotenvFiles.lexeme() {
+      self.expect(BracketL)?;
 
-      if self.accepted(Comma)? {
-        while !self.next_is(BracketR) {
-          arguments.push(self.parse_string_literal()?);
+      let mut files = Vec::new();
 
-          if !self.accepted(Comma)? {
-            break;
-          }
+      while !self.next_is(BracketR) {
+        files.push(self.parse_string_literal()?);
+
+        if !self.accepted(Comma)? {
+          break;
         }
       }
 
       self.expect(BracketR)?;
 
       Ok(Set {
-        value: Setting::Shell(setting::Shell { command, arguments }),
+        value: Setting::DotenvFiles(files),
+        name,
+      })
+    } else if name.lexeme() == Keyword::Default.lexeme() {
+      Ok(Set {
+        value: Setting::Default(self.parse_string_literal()?),
+        name,
+      })
+    } else if name.lexeme() == Keyword::EchoTruncate.lexeme() {
+      let token = self.expect(Number)?;
+
+      let echo_truncate = token.lexeme().parse().map_err(|_| {
+        token.error(CompilationErrorKind::NumberTooLarge {
+          number: token.lexeme(),
+        })
+      })?;
+
+      Ok(Set {
+        value: Setting::EchoTruncate(echo_truncate),
+        name,
+      })
+    } else if name.lexeme() == Keyword::ExitMessage.lexeme() {
+      let token = self.next()?;
+      let value = self.parse_string_literal()?;
+
+      let exit_message = value.cooked.parse().map_err(|_| {
+        token.error(CompilationErrorKind::InvalidSettingValue {
+          setting:  name.lexeme(),
+          value:    value.raw,
+          expected: ExitMessage::VALUES.to_vec(),
+        })
+      })?;
+
+      Ok(Set {
+        value: Setting::ExitMessage(exit_message),
+        name,
+      })
+    } else if name.lexeme() == Keyword::ExitMessageTemplate.lexeme() {
+      Ok(Set {
+        value: Setting::ExitMessageTemplate(self.parse_string_literal()?),
+        name,
+      })
+    } else if name.lexeme() == Keyword::ListHeading.lexeme() {
+      Ok(Set {
+        value: Setting::ListHeading(self.parse_string_literal()?),
+        name,
+      })
+    } else if name.lexeme() == Keyword::Tempdir.lexeme() {
+      Ok(Set {
+        value: Setting::Tempdir(self.parse_string_literal()?),
+        name,
+      })
+    } else if name.lexeme() == Keyword::ListPrefix.lexeme() {
+      Ok(Set {
+        value: Setting::ListPrefix(self.parse_string_literal()?),
+        name,
+      })
+    } else if name.lexeme() == Keyword::Shell.lexeme() {
+      Ok(Set {
+        value: Setting::Shell(self.parse_shell()?),
+        name,
+      })
+    } else if name.lexeme() == Keyword::WindowsShell.lexeme() {
+      Ok(Set {
+        value: Setting::WindowsShell(self.parse_shell()?),
+        name,
+      })
+    } else if name.lexeme() == Keyword::UnknownRecipe.lexeme() {
+      let token = self.next()?;
+      let value = self.parse_string_literal()?;
+
+      let unknown_recipe = value.cooked.parse().map_err(|_| {
+        token.error(CompilationErrorKind::InvalidSettingValue {
+          setting:  name.lexeme(),
+          value:    value.raw,
+          expected: UnknownRecipe::VALUES.to_vec(),
+        })
+      })?;
+
+      Ok(Set {
+        value: Setting::UnknownRecipe(unknown_recipe),
         name,
       })
     } else {
@@ -864,6 +1482,18 @@ mod tests {
     tree: (justfile (alias t test)),
   }
 
+  test! {
+    name: alias_arguments,
+    text: "alias t := test --release -v fast 'a b'",
+    tree: (justfile (alias t test "--release" "-v" "fast" "a b")),
+  }
+
+  test! {
+    name: alias_arguments_separated,
+    text: "alias t := test - -v--x -j 4 -2",
+    tree: (justfile (alias t test "-" "-v--x" "-j" "4" "-2")),
+  }
+
   test! {
     name: aliases_multiple,
     text: "alias t := test\nalias b := build",
@@ -937,63 +1567,195 @@ mod tests {
   test! {
     name: addition_chained,
     text: "x := a + b + c",
-    tree: (justfile (assignment x (+ a (+ b c)))),
+    tree: (justfile (assignment x (+ (+ a b) c))),
   }
 
   test! {
-    name: call_one_arg,
-    text: "x := env_var(y)",
-    tree: (justfile (assignment x (call env_var y))),
+    name: integer,
+    text: "x := 8000",
+    tree: (justfile (assignment x (integer 8000))),
   }
 
   test! {
-    name: call_multiple_args,
-    text: "x := env_var_or_default(y, z)",
-    tree: (justfile (assignment x (call env_var_or_default y z))),
+    name: addition_integer,
+    text: "x := a + 1",
+    tree: (justfile (assignment x (+ a (integer 1)))),
   }
 
   test! {
-    name: call_trailing_comma,
-    text: "x := env_var(y,)",
-    tree: (justfile (assignment x (call env_var y))),
+    name: addition_integer_chained,
+    text: "x := a + 1 + b",
+    tree: (justfile (assignment x (+ (+ a (integer 1)) b))),
   }
 
   test! {
-    name: recipe,
-    text: "foo:",
-    tree: (justfile (recipe foo)),
+    name: negative_integer,
+    text: "x := a - -1",
+    tree: (justfile (assignment x (- a (integer "-1")))),
   }
 
   test! {
-    name: recipe_multiple,
-    text: "
-      foo:
-      bar:
-      baz:
-    ",
-    tree: (justfile (recipe foo) (recipe bar) (recipe baz)),
+    name: subtraction_chained,
+    text: "x := a - b - c",
+    tree: (justfile (assignment x (- (- a b) c))),
   }
 
   test! {
-    name: recipe_quiet,
-    text: "@foo:",
-    tree: (justfile (recipe #quiet foo)),
+    name: arithmetic_precedence,
+    text: "x := 1 + 2 * 3 - 4 / a",
+    tree: (justfile (assignment x (- (+ (integer 1) (* (integer 2) (integer 3))) (/ (integer 4) a)))),
   }
 
   test! {
-    name: recipe_parameter_single,
-    text: "foo bar:",
-    tree: (justfile (recipe foo (params (bar)))),
+    name: arithmetic_group,
+    text: "x := (a + b) * 2",
+    tree: (justfile (assignment x (* ((+ a b)) (integer 2)))),
   }
 
   test! {
-    name: recipe_parameter_multiple,
-    text: "foo bar baz:",
-    tree: (justfile (recipe foo (params (bar) (baz)))),
+    name: addition_integer_group,
+    text: "x := a + (b - 1)",
+    tree: (justfile (assignment x (+ a ((- b (integer 1)))))),
   }
 
   test! {
-    name: recipe_default_single,
+    name: arithmetic_numeric_string,
+    text: "x := '1' * 2",
+    tree: (justfile (assignment x (* "1" (integer 2)))),
+  }
+
+  test! {
+    name: arithmetic_dependency_argument,
+    text: "foo: (bar 2 * 3)",
+    tree: (justfile (recipe foo (deps (bar (* (integer 2) (integer 3)))))),
+  }
+
+  test! {
+    name: coalesce_single,
+    text: "x := a || 'b'",
+    tree: (justfile (assignment x (|| a "b"))),
+  }
+
+  test! {
+    name: coalesce_chained,
+    text: "x := a || b || c",
+    tree: (justfile (assignment x (|| a (|| b c)))),
+  }
+
+  test! {
+    name: coalesce_addition_precedence,
+    text: "x := a + b || c + d",
+    tree: (justfile (assignment x (|| (+ a b) (+ c d)))),
+  }
+
+  test! {
+    name: coalesce_conditional,
+    text: "x := if a || b == c { d || e } else { f }",
+    tree: (justfile (assignment x (if (|| a b) == c (|| d e) f))),
+  }
+
+  test! {
+    name: addition_conditional,
+    text: "x := a + if b == c { d } else { e }",
+    tree: (justfile (assignment x (+ a (if b == c d e)))),
+  }
+
+  test! {
+    name: boolean_literals,
+    text: "x := true + false",
+    tree: (justfile (assignment x (+ true false))),
+  }
+
+  test! {
+    name: comparison,
+    text: "x := a == 'b'",
+    tree: (justfile (assignment x (== a "b"))),
+  }
+
+  test! {
+    name: comparison_inverted,
+    text: "x := a != 'b'",
+    tree: (justfile (assignment x (!= a "b"))),
+  }
+
+  test! {
+    name: comparison_precedence,
+    text: "x := a || b == c + d",
+    tree: (justfile (assignment x (== (|| a b) (+ c d)))),
+  }
+
+  test! {
+    name: comparison_conditional,
+    text: "x := if a == b { c == d } else { e != f }",
+    tree: (justfile (assignment x (if a == b (== c d) (!= e f)))),
+  }
+
+  test! {
+    name: call_one_arg,
+    text: "x := env_var(y)",
+    tree: (justfile (assignment x (call env_var y))),
+  }
+
+  test! {
+    name: call_multiple_args,
+    text: "x := env_var_or_default(y, z)",
+    tree: (justfile (assignment x (call env_var_or_default y z))),
+  }
+
+  test! {
+    name: call_optional_arg,
+    text: "x := env(y)\ny := env(y, z)",
+    tree: (justfile (assignment x (call env y)) (assignment y (call env y z))),
+  }
+
+  test! {
+    name: call_three_args,
+    text: "x := replace_regex(a, 'b', c)",
+    tree: (justfile (assignment x (call replace_regex a "b" c))),
+  }
+
+  test! {
+    name: call_trailing_comma,
+    text: "x := env_var(y,)",
+    tree: (justfile (assignment x (call env_var y))),
+  }
+
+  test! {
+    name: recipe,
+    text: "foo:",
+    tree: (justfile (recipe foo)),
+  }
+
+  test! {
+    name: recipe_multiple,
+    text: "
+      foo:
+      bar:
+      baz:
+    ",
+    tree: (justfile (recipe foo) (recipe bar) (recipe baz)),
+  }
+
+  test! {
+    name: recipe_quiet,
+    text: "@foo:",
+    tree: (justfile (recipe #quiet foo)),
+  }
+
+  test! {
+    name: recipe_parameter_single,
+    text: "foo bar:",
+    tree: (justfile (recipe foo (params (bar)))),
+  }
+
+  test! {
+    name: recipe_parameter_multiple,
+    text: "foo bar baz:",
+    tree: (justfile (recipe foo (params (bar) (baz)))),
+  }
+
+  test! {
+    name: recipe_default_single,
     text: r#"foo bar="baz":"#,
     tree: (justfile (recipe foo (params (bar "baz")))),
   }
@@ -1016,6 +1778,18 @@ mod tests {
     tree: (justfile (recipe foo (params *(bar)))),
   }
 
+  test! {
+    name: recipe_export_parameter,
+    text: r#"foo $bar:"#,
+    tree: (justfile (recipe foo (params ($ bar)))),
+  }
+
+  test! {
+    name: recipe_variadic_export_parameter,
+    text: r#"foo a *$bar="baz":"#,
+    tree: (justfile (recipe foo (params (a) *($ bar "baz")))),
+  }
+
   test! {
     name: recipe_variadic_string_default,
     text: r#"foo +bar="baz":"#,
@@ -1052,6 +1826,18 @@ mod tests {
     tree: (justfile (recipe foo (deps bar))),
   }
 
+  test! {
+    name: recipe_dependency_forward,
+    text: "foo a b: (bar *)",
+    tree: (justfile (recipe foo (params (a) (b)) (deps (bar *)))),
+  }
+
+  test! {
+    name: recipe_dependency_forward_after_arguments,
+    text: "foo a: (bar 'x' *) baz",
+    tree: (justfile (recipe foo (params (a)) (deps (bar "x" *) baz))),
+  }
+
   test! {
     name: recipe_dependency_argument_string,
     text: "foo: (bar 'baz')",
@@ -1153,6 +1939,185 @@ mod tests {
     tree: (justfile (recipe bar) (recipe "foo" baz)),
   }
 
+  test! {
+    name: recipe_attribute,
+    text: "
+      # foo
+      [remember-args]
+      @bar baz:
+    ",
+    tree: (justfile (recipe #quiet (attributes "remember-args") "foo" bar (params (baz)))),
+  }
+
+  test! {
+    name: recipe_attribute_separate_lines,
+    text: "
+      [ remember-args ]
+      bar:
+    ",
+    tree: (justfile (recipe (attributes "remember-args") bar)),
+  }
+
+  test! {
+    name: recipe_attribute_doc,
+    text: "
+      # foo
+      [doc('bar'), remember-args]
+      baz:
+    ",
+    tree: (justfile (recipe (attributes (doc "bar") "remember-args") "foo" baz)),
+  }
+
+  test! {
+    name: recipe_attribute_no_exit_message,
+    text: "
+      [no-exit-message]
+      foo:
+    ",
+    tree: (justfile (recipe (attributes "no-exit-message") foo)),
+  }
+
+  test! {
+    name: recipe_attribute_private,
+    text: "
+      [private]
+      foo:
+    ",
+    tree: (justfile (recipe (attributes "private") foo)),
+  }
+
+  test! {
+    name: alias_attribute_private,
+    text: "
+      [private]
+      alias f := foo
+    ",
+    tree: (justfile (alias (attributes "private") f foo)),
+  }
+
+  test! {
+    name: recipe_attribute_cleanup,
+    text: "
+      [cleanup('teardown')]
+      foo:
+    ",
+    tree: (justfile (recipe (attributes (cleanup "teardown")) foo)),
+  }
+
+  test! {
+    name: recipe_attribute_filter,
+    text: "
+      [filter('grep -v noise')]
+      foo:
+    ",
+    tree: (justfile (recipe (attributes (filter "grep -v noise")) foo)),
+  }
+
+  test! {
+    name: recipe_attribute_path,
+    text: "
+      [path('a'), path('b')]
+      foo a b c:
+    ",
+    tree: (justfile (recipe (attributes (path "a") (path "b")) foo (params (a) (b) (c)))),
+  }
+
+  test! {
+    name: recipe_attribute_group,
+    text: "
+      [group('ci'), group('release')]
+      foo:
+    ",
+    tree: (justfile (recipe (attributes (group "ci") (group "release")) foo)),
+  }
+
+  test! {
+    name: recipe_attribute_tee,
+    text: "
+      [tee('logs/build.log')]
+      foo:
+    ",
+    tree: (justfile (recipe (attributes (tee "logs/build.log")) foo)),
+  }
+
+  test! {
+    name: recipe_attribute_choices,
+    text: "
+      [choices(env: 'staging', 'prod'), choices(region: 'eu')]
+      foo env region:
+    ",
+    tree: (justfile (recipe (attributes (choices env "staging" "prod") (choices region "eu")) foo (params (env) (region)))),
+  }
+
+  test! {
+    name: recipe_attribute_requires,
+    text: "
+      [requires('docker', 'jq'), requires('git')]
+      foo:
+    ",
+    tree: (justfile (recipe (attributes (requires "docker" "jq") (requires "git")) foo)),
+  }
+
+  // `needs-env` isn't an identifier, so can't be written with `tree!`
+  #[test]
+  fn recipe_attribute_needs_env() {
+    test(
+      "
+        [needs-env('AWS_PROFILE': 'the profile to deploy with', 'AWS_REGION')]
+        foo:
+      ",
+      Tree::atom("justfile").push(
+        Tree::atom("recipe")
+          .push(
+            Tree::atom("attributes").push(
+              Tree::atom("needs-env")
+                .push(
+                  Tree::atom(":")
+                    .push(Tree::string("AWS_PROFILE"))
+                    .push(Tree::string("the profile to deploy with")),
+                )
+                .push(Tree::string("AWS_REGION")),
+            ),
+          )
+          .push(Tree::atom("foo")),
+      ),
+    );
+  }
+
+  test! {
+    name: recipe_attribute_inputs,
+    text: "
+      [inputs('src/**/*.rs'), inputs('Cargo.toml')]
+      foo:
+    ",
+    tree: (justfile (recipe (attributes (inputs "Cargo.toml") (inputs "src/**/*.rs")) foo)),
+  }
+
+  test! {
+    name: recipe_attribute_process_limits,
+    text: "
+      [umask('0o022'), nice('10')]
+      [limit('nofile', '1024'), limit('core', 'unlimited')]
+      foo:
+    ",
+    tree: (justfile (recipe (attributes (limit "core" "unlimited") (limit "nofile" "1024") (nice "10") (umask "0o022")) foo)),
+  }
+
+  test! {
+    name: recipe_attribute_confirm,
+    text: "
+      [confirm]
+      foo:
+
+      [confirm('Really?')]
+      bar:
+    ",
+    tree: (justfile
+      (recipe (attributes "confirm") foo)
+      (recipe (attributes (confirm "Really?")) bar)
+    ),
+  }
+
   test! {
     name: doc_comment_assignment_clear,
     text: "
@@ -1453,7 +2418,7 @@ mod tests {
     "#,
     tree: (justfile
       (assignment a "0")
-      (assignment c (+ a (+ b (+ a b))))
+      (assignment c (+ (+ (+ a b) a) b))
       (assignment b "1")
     ),
   }
@@ -1467,7 +2432,7 @@ mod tests {
     ",
     tree: (justfile
       (assignment a (backtick "echo hello"))
-      (assignment c (+ a (+ b (+ a b))))
+      (assignment c (+ (+ (+ a b) a) b))
       (assignment b (backtick "echo goodbye"))
     ),
   }
@@ -1660,6 +2625,120 @@ mod tests {
     tree: (justfile (set export false)),
   }
 
+  test! {
+    name: set_export_expression,
+    text: "set export := env_var('CI') == 'true'",
+    tree: (justfile (set export (== (call env_var "CI") "true"))),
+  }
+
+  test! {
+    name: set_backtick_env,
+    text: "set backtick-env := 'exports'",
+    tree: (justfile (set backtick_env "exports")),
+  }
+
+  test! {
+    name: set_exit_message,
+    text: "set exit-message := 'minimal'",
+    tree: (justfile (set exit_message "minimal")),
+  }
+
+  test! {
+    name: set_exit_message_template,
+    text: "set exit-message-template := '{recipe} failed'",
+    tree: (justfile (set exit_message_template "{recipe} failed")),
+  }
+
+  test! {
+    name: set_echo_truncate,
+    text: "set echo-truncate := 200",
+    tree: (justfile (set echo_truncate "200")),
+  }
+
+  test! {
+    name: set_unknown_recipe,
+    text: "set unknown-recipe := 'suggest-run'",
+    tree: (justfile (set unknown_recipe "suggest-run")),
+  }
+
+  test! {
+    name: set_default,
+    text: "set default := 'build'",
+    tree: (justfile (set default "build")),
+  }
+
+  test! {
+    name: set_tempdir,
+    text: "set tempdir := 'tmp'",
+    tree: (justfile (set tempdir "tmp")),
+  }
+
+  test! {
+    name: set_list_heading,
+    text: "set list-heading := \"Recipes:\\n\"",
+    tree: (justfile (set list_heading "Recipes:\n")),
+  }
+
+  test! {
+    name: set_list_prefix,
+    text: "set list-prefix := ''",
+    tree: (justfile (set list_prefix "")),
+  }
+
+  test! {
+    name: set_dotenv_files,
+    text: "set dotenv-files := ['.env', \".env.local\",]",
+    tree: (justfile (set dotenv_files ".env" ".env.local")),
+  }
+
+  test! {
+    name: set_dotenv_files_empty,
+    text: "set dotenv-files := []",
+    tree: (justfile (set dotenv_files)),
+  }
+
+  test! {
+    name: set_metadata_side_effects_false,
+    text: "set metadata-side-effects := false",
+    tree: (justfile (set metadata_side_effects false)),
+  }
+
+  test! {
+    name: set_allow_downloads_implicit,
+    text: "set allow-downloads",
+    tree: (justfile (set allow_downloads true)),
+  }
+
+  test! {
+    name: set_pager_true,
+    text: "set pager := true",
+    tree: (justfile (set pager true)),
+  }
+
+  test! {
+    name: set_strict_interpolation_implicit,
+    text: "set strict-interpolation",
+    tree: (justfile (set strict_interpolation true)),
+  }
+
+  test! {
+    name: set_timestamps_implicit,
+    text: "set timestamps",
+    tree: (justfile (set timestamps true)),
+  }
+
+  test! {
+    name: set_no_exit_message_implicit,
+    text: "set no-exit-message",
+    tree: (justfile (set no_exit_message true)),
+  }
+
+  test! {
+    name: set_no_exit_message_false,
+    text: "set no-exit-message := false",
+    tree: (justfile (set no_exit_message false)),
+  }
+
   test! {
     name: set_dotenv_load_implicit,
     text: "set dotenv-load",
@@ -1714,6 +2793,24 @@ mod tests {
     tree: (justfile (set shell "bash" "-cu" "-l")),
   }
 
+  test! {
+    name: set_windows_shell,
+    text: "set windows-shell := ['powershell.exe', '-NoLogo', '-Command']",
+    tree: (justfile (set windows_shell "powershell.exe" "-NoLogo" "-Command")),
+  }
+
+  test! {
+    name: set_windows_powershell_implicit,
+    text: "set windows-powershell",
+    tree: (justfile (set windows_powershell true)),
+  }
+
+  test! {
+    name: set_windows_powershell_false,
+    text: "set windows-powershell := false",
+    tree: (justfile (set windows_powershell false)),
+  }
+
   test! {
     name: conditional,
     text: "a := if b == c { d } else { e }",
@@ -1757,13 +2854,16 @@ mod tests {
   }
 
   error! {
-    name:   alias_syntax_multiple_rhs,
-    input:  "alias foo := bar baz",
+    name:   alias_syntax_invalid_argument,
+    input:  "alias foo := bar (baz)",
     offset: 17,
     line:   0,
     column: 17,
-    width:  3,
-    kind:   UnexpectedToken { expected: vec![Comment, Eof, Eol], found: Identifier },
+    width:  1,
+    kind:   UnexpectedToken {
+      expected: vec![Comment, Eof, Eol, Identifier, Minus, Number, StringToken],
+      found: ParenL,
+    },
   }
 
   error! {
@@ -1776,6 +2876,215 @@ mod tests {
     kind:   UnexpectedToken {expected: vec![Identifier], found:Eol},
   }
 
+  error! {
+    name:   attribute_unknown,
+    input:  "[foo]\nbar:",
+    offset: 1,
+    line:   0,
+    column: 1,
+    width:  3,
+    kind:   UnknownAttribute { attribute: "foo" },
+  }
+
+  error! {
+    name:   attribute_duplicate,
+    input:  "[remember-args]\n[remember-args]\nbar:",
+    offset: 17,
+    line:   1,
+    column: 1,
+    width:  13,
+    kind:   DuplicateAttribute { attribute: "remember-args", first: 0 },
+  }
+
+  error! {
+    name:   attribute_doc_duplicate,
+    input:  "[doc('a'), doc('b')]\nbar:",
+    offset: 11,
+    line:   0,
+    column: 11,
+    width:  3,
+    kind:   DuplicateAttribute { attribute: "doc", first: 0 },
+  }
+
+  error! {
+    name:   attribute_path_duplicate,
+    input:  "[path('a'), path('a')]\nbar a:",
+    offset: 12,
+    line:   0,
+    column: 12,
+    width:  4,
+    kind:   DuplicateAttribute { attribute: "path", first: 0 },
+  }
+
+  error! {
+    name:   attribute_path_unknown_parameter,
+    input:  "[path('b')]\nbar a:",
+    offset: 12,
+    line:   1,
+    column: 0,
+    width:  3,
+    kind:   UnknownPathParameter { recipe: "bar", parameter: "b".to_owned() },
+  }
+
+  error! {
+    name:   dependency_forward_not_last,
+    input:  "foo a: (bar * 'x')",
+    offset: 14,
+    line:   0,
+    column: 14,
+    width:  3,
+    kind:   UnexpectedToken { expected: vec![ParenR], found: StringToken },
+  }
+
+  error! {
+    name:   attribute_doc_missing_argument,
+    input:  "[doc]\nbar:",
+    offset: 4,
+    line:   0,
+    column: 4,
+    width:  1,
+    kind:   UnexpectedToken { expected: vec![ParenL], found: BracketR },
+  }
+
+  error! {
+    name:   attribute_umask_invalid,
+    input:  "[umask('888')]\nbar:",
+    offset: 7,
+    line:   0,
+    column: 7,
+    width:  5,
+    kind:   InvalidAttributeArgument {
+      attribute: "umask",
+      argument:  "888",
+      expected:  "an octal mode from 0 to 777".to_owned(),
+    },
+  }
+
+  error! {
+    name:   attribute_nice_invalid,
+    input:  "[nice('20')]\nbar:",
+    offset: 6,
+    line:   0,
+    column: 6,
+    width:  4,
+    kind:   InvalidAttributeArgument {
+      attribute: "nice",
+      argument:  "20",
+      expected:  "an integer from -20 to 19".to_owned(),
+    },
+  }
+
+  error! {
+    name:   attribute_limit_unknown_resource,
+    input:  "[limit('files', '1')]\nbar:",
+    offset: 7,
+    line:   0,
+    column: 7,
+    width:  7,
+    kind:   InvalidAttributeArgument {
+      attribute: "limit",
+      argument:  "files",
+      expected:  "`core`, `cpu`, `data`, `fsize`, `nofile`, `nproc`, or `stack`".to_owned(),
+    },
+  }
+
+  error! {
+    name:   attribute_limit_invalid_value,
+    input:  "[limit('nofile', '1k')]\nbar:",
+    offset: 17,
+    line:   0,
+    column: 17,
+    width:  4,
+    kind:   InvalidAttributeArgument {
+      attribute: "limit",
+      argument:  "1k",
+      expected:  "a non-negative integer or `unlimited`".to_owned(),
+    },
+  }
+
+  error! {
+    name:   attribute_limit_duplicate_resource,
+    input:  "[limit('nofile', '1'), limit('nofile', '2')]\nbar:",
+    offset: 23,
+    line:   0,
+    column: 23,
+    width:  5,
+    kind:   DuplicateAttribute { attribute: "limit", first: 0 },
+  }
+
+  error! {
+    name:   attribute_choices_unknown_parameter,
+    input:  "[choices(b: 'x')]\nbar a:",
+    offset: 9,
+    line:   0,
+    column: 9,
+    width:  1,
+    kind:   UnknownChoicesParameter { recipe: "bar", parameter: "b" },
+  }
+
+  error! {
+    name:   attribute_choices_duplicate_parameter,
+    input:  "[choices(a: 'x'), choices(a: 'y')]\nbar a:",
+    offset: 18,
+    line:   0,
+    column: 18,
+    width:  7,
+    kind:   DuplicateAttribute { attribute: "choices", first: 0 },
+  }
+
+  error! {
+    name:   attribute_choices_empty,
+    input:  "[choices(a:)]\nbar a:",
+    offset: 11,
+    line:   0,
+    column: 11,
+    width:  1,
+    kind:   UnexpectedToken {
+      expected: vec![StringToken],
+      found:    ParenR,
+    },
+  }
+
+  error! {
+    name:   alias_invalid_attribute,
+    input:  "[remember-args]\nalias f := foo",
+    offset: 22,
+    line:   1,
+    column: 6,
+    width:  1,
+    kind:   AliasInvalidAttribute { alias: "f", attribute: "remember-args" },
+  }
+
+  error! {
+    name:   attributes_conflict,
+    input:  "[quiet, verbose]\nfoo:",
+    offset: 8,
+    line:   0,
+    column: 8,
+    width:  7,
+    kind:   AttributesConflict { first: "quiet", second: "verbose" },
+  }
+
+  error! {
+    name:   attributes_conflict_fresh_once,
+    input:  "[once]\n[fresh]\nfoo:",
+    offset: 8,
+    line:   1,
+    column: 1,
+    width:  5,
+    kind:   AttributesConflict { first: "fresh", second: "once" },
+  }
+
+  error! {
+    name:   attribute_unterminated,
+    input:  "[remember-args\nbar:",
+    offset: 15,
+    line:   1,
+    column: 0,
+    width:  3,
+    kind:   UnexpectedToken { expected: vec![BracketR, Comma], found: Identifier },
+  }
+
   error! {
     name:   missing_colon,
     input:  "a b c\nd e f",
@@ -1789,6 +3098,44 @@ mod tests {
     },
   }
 
+  error! {
+    name:   arithmetic_string_operand,
+    input:  "x := 'v' + 1",
+    offset: 5,
+    line:   0,
+    column: 5,
+    width:  3,
+    kind:   NonIntegerOperand {
+      operator: Operator::Add,
+      operand:  "'v'".to_owned(),
+    },
+  }
+
+  error! {
+    name:   arithmetic_boolean_operand,
+    input:  "x := a * true",
+    offset: 9,
+    line:   0,
+    column: 9,
+    width:  4,
+    kind:   NonIntegerOperand {
+      operator: Operator::Multiply,
+      operand:  "true".to_owned(),
+    },
+  }
+
+  error! {
+    name:   integer_too_large,
+    input:  "x := 99999999999999999999",
+    offset: 5,
+    line:   0,
+    column: 5,
+    width:  20,
+    kind:   NumberTooLarge {
+      number: "99999999999999999999",
+    },
+  }
+
   error! {
     name:   missing_default_eol,
     input:  "hello arg=\n",
@@ -1800,6 +3147,8 @@ mod tests {
       expected: vec![
         Backtick,
         Identifier,
+        Minus,
+        Number,
         ParenL,
         StringToken,
       ],
@@ -1818,6 +3167,8 @@ mod tests {
       expected: vec![
         Backtick,
         Identifier,
+        Minus,
+        Number,
         ParenL,
         StringToken,
       ],
@@ -1843,7 +3194,7 @@ mod tests {
     column: 0,
     width:  1,
     kind: UnexpectedToken {
-      expected: vec![At, Comment, Eof, Eol, Identifier],
+      expected: vec![At, BracketL, Comment, Eof, Eol, Identifier],
       found: BraceL,
     },
   }
@@ -1859,6 +3210,8 @@ mod tests {
       expected: vec![
         Backtick,
         Identifier,
+        Minus,
+        Number,
         ParenL,
         ParenR,
         StringToken,
@@ -1878,6 +3231,8 @@ mod tests {
       expected: vec![
         Backtick,
         Identifier,
+        Minus,
+        Number,
         ParenL,
         ParenR,
         StringToken,
@@ -2031,6 +3386,73 @@ mod tests {
     },
   }
 
+  error! {
+    name:   set_unknown_recipe_invalid,
+    input:  "set unknown-recipe := 'ignore'",
+    offset: 22,
+    line:   0,
+    column: 22,
+    width:  8,
+    kind:   InvalidSettingValue {
+      setting:  "unknown-recipe",
+      value:    "ignore",
+      expected: vec!["error", "default", "suggest-run"],
+    },
+  }
+
+  error! {
+    name:   set_backtick_env_invalid,
+    input:  "set backtick-env := 'some'",
+    offset: 20,
+    line:   0,
+    column: 20,
+    width:  6,
+    kind:   InvalidSettingValue {
+      setting:  "backtick-env",
+      value:    "some",
+      expected: vec!["none", "exports", "full"],
+    },
+  }
+
+  error! {
+    name:   set_echo_truncate_string,
+    input:  "set echo-truncate := '200'",
+    offset: 21,
+    line:   0,
+    column: 21,
+    width:  5,
+    kind:   UnexpectedToken {
+      expected: vec![Number],
+      found:    StringToken,
+    },
+  }
+
+  error! {
+    name:   set_echo_truncate_too_large,
+    input:  "set echo-truncate := 99999999999999999999999",
+    offset: 21,
+    line:   0,
+    column: 21,
+    width:  23,
+    kind:   NumberTooLarge {
+      number: "99999999999999999999999",
+    },
+  }
+
+  error! {
+    name:   set_exit_message_invalid,
+    input:  "set exit-message := 'short'",
+    offset: 20,
+    line:   0,
+    column: 20,
+    width:  7,
+    kind:   InvalidSettingValue {
+      setting:  "exit-message",
+      value:    "short",
+      expected: vec!["full", "minimal", "none"],
+    },
+  }
+
   error! {
     name:   set_shell_non_string,
     input:  "set shall := []",
@@ -2050,7 +3472,7 @@ mod tests {
     line:   0,
     column: 5,
     width:  3,
-    kind:   UnknownFunction{function: "foo"},
+    kind:   UnknownFunction{function: "foo", suggestion: None},
   }
 
   error! {
@@ -2060,7 +3482,7 @@ mod tests {
     line:   1,
     column: 8,
     width:  3,
-    kind:   UnknownFunction{function: "bar"},
+    kind:   UnknownFunction{function: "bar", suggestion: None},
   }
 
   error! {
@@ -2070,7 +3492,17 @@ mod tests {
     line:   0,
     column: 4,
     width:  3,
-    kind:   UnknownFunction{function: "baz"},
+    kind:   UnknownFunction{function: "baz", suggestion: None},
+  }
+
+  error! {
+    name:   unknown_function_suggestion,
+    input:  "a := env_vra('HOME')",
+    offset: 5,
+    line:   0,
+    column: 5,
+    width:  7,
+    kind:   UnknownFunction{function: "env_vra", suggestion: Some(Suggestion::Function{name: "env_var"})},
   }
 
   error! {
@@ -2083,7 +3515,7 @@ mod tests {
     kind: FunctionArgumentCountMismatch {
       function: "arch",
       found: 1,
-      expected: 0,
+      expected: 0..1,
     },
   }
 
@@ -2097,7 +3529,21 @@ mod tests {
     kind: FunctionArgumentCountMismatch {
       function: "env_var",
       found: 0,
-      expected: 1,
+      expected: 1..2,
+    },
+  }
+
+  error! {
+    name: function_argument_count_unary_opt,
+    input: "x := env()",
+    offset: 5,
+    line: 0,
+    column: 5,
+    width: 3,
+    kind: FunctionArgumentCountMismatch {
+      function: "env",
+      found: 0,
+      expected: 1..3,
     },
   }
 
@@ -2111,7 +3557,35 @@ mod tests {
     kind: FunctionArgumentCountMismatch {
       function: "env_var_or_default",
       found: 1,
-      expected: 2,
+      expected: 2..3,
+    },
+  }
+
+  error! {
+    name: function_argument_count_nary,
+    input: "x := replace('foo', 'o')",
+    offset: 5,
+    line: 0,
+    column: 5,
+    width: 7,
+    kind: FunctionArgumentCountMismatch {
+      function: "replace",
+      found: 2,
+      expected: 3..5,
+    },
+  }
+
+  error! {
+    name: function_argument_count_variadic,
+    input: "x := join('foo')",
+    offset: 5,
+    line: 0,
+    column: 5,
+    width: 4,
+    kind: FunctionArgumentCountMismatch {
+      function: "join",
+      found: 1,
+      expected: 2..usize::MAX,
     },
   }
 }
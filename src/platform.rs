@@ -7,11 +7,18 @@ impl PlatformInterface for Platform {
   fn make_shebang_command(
     path: &Path,
     working_directory: &Path,
-    _command: &str,
-    _argument: Option<&str>,
+    shebang: &Shebang,
   ) -> Result<Command, OutputError> {
-    // shebang scripts can be executed directly on unix
-    let mut cmd = Command::new(path);
+    // shebang scripts can be executed directly on unix, unless the argument
+    // needs to be split, which not all kernels do
+    let mut cmd = if shebang.is_split() {
+      let mut cmd = Command::new(shebang.interpreter);
+      cmd.args(shebang.arguments());
+      cmd.arg(path);
+      cmd
+    } else {
+      Command::new(path)
+    };
 
     cmd.current_dir(working_directory);
 
@@ -43,6 +50,61 @@ impl PlatformInterface for Platform {
       .map(str::to_string)
       .ok_or_else(|| String::from("Error getting current directory: unicode decode error"))
   }
+
+  fn set_process_limits(command: &mut Command, limits: &ProcessLimits) -> Result<(), String> {
+    use std::os::unix::process::CommandExt;
+
+    let limits = limits.clone();
+
+    // SAFETY: the closure runs in the child between `fork` and `exec`, and
+    // only calls `umask`, `getrlimit`, `setrlimit`, and `setpriority`, which
+    // are async-signal-safe, and doesn't allocate
+    unsafe {
+      command.pre_exec(move || {
+        if let Some(umask) = limits.umask {
+          libc::umask(umask as libc::mode_t);
+        }
+
+        for (resource, value) in &limits.resources {
+          let which = match resource {
+            Resource::Core => libc::RLIMIT_CORE,
+            Resource::Cpu => libc::RLIMIT_CPU,
+            Resource::Data => libc::RLIMIT_DATA,
+            Resource::Fsize => libc::RLIMIT_FSIZE,
+            Resource::Nofile => libc::RLIMIT_NOFILE,
+            Resource::Nproc => libc::RLIMIT_NPROC,
+            Resource::Stack => libc::RLIMIT_STACK,
+          };
+
+          let mut rlimit = std::mem::zeroed::<libc::rlimit>();
+
+          if libc::getrlimit(which, &mut rlimit) != 0 {
+            return Err(io::Error::last_os_error());
+          }
+
+          rlimit.rlim_cur = if *value == ProcessLimits::UNLIMITED {
+            libc::RLIM_INFINITY
+          } else {
+            *value as libc::rlim_t
+          };
+
+          if libc::setrlimit(which, &rlimit) != 0 {
+            return Err(io::Error::last_os_error());
+          }
+        }
+
+        if let Some(nice) = limits.nice {
+          if libc::setpriority(libc::PRIO_PROCESS, 0, nice) != 0 {
+            return Err(io::Error::last_os_error());
+          }
+        }
+
+        Ok(())
+      });
+    }
+
+    Ok(())
+  }
 }
 
 #[cfg(windows)]
@@ -50,11 +112,22 @@ impl PlatformInterface for Platform {
   fn make_shebang_command(
     path: &Path,
     working_directory: &Path,
-    command: &str,
-    argument: Option<&str>,
+    shebang: &Shebang,
   ) -> Result<Command, OutputError> {
     use std::borrow::Cow;
 
+    // `#!/usr/bin/env NAME` runs `NAME` from `PATH`, which `Command` can do
+    // without `cygpath` or an `env` executable
+    if let Some(words) = shebang.env_command() {
+      let mut cmd = Command::new(words[0]);
+      cmd.current_dir(working_directory);
+      cmd.args(&words[1..]);
+      cmd.arg(path);
+      return Ok(cmd);
+    }
+
+    let command = shebang.interpreter;
+
     // If the path contains forward slashes…
     let command = if command.contains('/') {
       // …translate path to the interpreter from unix style to windows style.
@@ -73,9 +146,7 @@ impl PlatformInterface for Platform {
 
     cmd.current_dir(working_directory);
 
-    if let Some(argument) = argument {
-      cmd.arg(argument);
-    }
+    cmd.args(shebang.arguments());
 
     cmd.arg(path);
     Ok(cmd)
@@ -108,4 +179,12 @@ impl PlatformInterface for Platform {
         .ok_or_else(|| String::from("Error getting current directory: unicode decode error")),
     }
   }
+
+  fn set_process_limits(_command: &mut Command, _limits: &ProcessLimits) -> Result<(), String> {
+    // windows has no equivalent of umask, and resource limits and priorities
+    // would need a job object, so these attributes are unsupported
+    Err(String::from(
+      "`umask`, `nice`, and `limit` attributes are not supported on Windows",
+    ))
+  }
 }
@@ -6,8 +6,7 @@ pub(crate) trait PlatformInterface {
   fn make_shebang_command(
     path: &Path,
     working_directory: &Path,
-    command: &str,
-    argument: Option<&str>,
+    shebang: &Shebang,
   ) -> Result<Command, OutputError>;
 
   /// Set the execute permission on the file pointed to by `path`
@@ -19,4 +18,8 @@ pub(crate) trait PlatformInterface {
 
   /// Translate a path from a "native" path to a path the interpreter expects
   fn convert_native_path(working_directory: &Path, path: &Path) -> Result<String, String>;
+
+  /// Arrange for `command` to be run with `limits`, returning an error if
+  /// they aren't supported on this platform
+  fn set_process_limits(command: &mut Command, limits: &ProcessLimits) -> Result<(), String>;
 }
@@ -17,6 +17,12 @@ use crate::common::*;
 ///
 /// - Everything else is an argument.
 ///
+/// The first `--` is a terminator, and is removed. Everything after it is an
+/// argument, even if it looks like an override or a search directory. Clap
+/// removes a `--` that comes before the first positional argument, as in
+/// `just -- --foo`, but passes one after it through, as in
+/// `just build -- --foo`, so both are handled here.
+///
 /// Overrides set the values of top-level variables in the justfile being
 /// invoked and are a convenient way to override settings.
 ///
@@ -41,10 +47,15 @@ impl Positional {
     let mut overrides = Vec::new();
     let mut search_directory = None;
     let mut arguments = Vec::new();
+    let mut terminated = false;
 
     if let Some(values) = values {
       for value in values {
-        if search_directory.is_none() && arguments.is_empty() {
+        if !terminated && value == "--" {
+          terminated = true;
+        } else if terminated {
+          arguments.push(value.to_owned());
+        } else if search_directory.is_none() && arguments.is_empty() {
           if let Some(o) = Self::override_from_value(value) {
             overrides.push(o);
           } else if value == "." || value == ".." {
@@ -140,6 +151,30 @@ mod tests {
     arguments: ["foo", "bar"],
   }
 
+  test! {
+    name: terminator,
+    values: ["build", "--", "--release"],
+    overrides: [],
+    search_directory: None,
+    arguments: ["build", "--release"],
+  }
+
+  test! {
+    name: terminator_only_first,
+    values: ["build", "--", "--", "--release"],
+    overrides: [],
+    search_directory: None,
+    arguments: ["build", "--", "--release"],
+  }
+
+  test! {
+    name: terminator_before_overrides_and_search_directory,
+    values: ["foo=bar", "--", "bar=foo", "the-dir/build"],
+    overrides: [("foo", "bar")],
+    search_directory: None,
+    arguments: ["bar=foo", "the-dir/build"],
+  }
+
   test! {
     name: all_overrides,
     values: ["foo=bar", "bar=foo"],
@@ -0,0 +1,100 @@
+use crate::common::*;
+
+/// Limits applied to the processes that a recipe runs, given with `[umask]`,
+/// `[nice]`, and `[limit]` attributes
+#[derive(Debug, Default, PartialEq, Clone)]
+pub(crate) struct ProcessLimits {
+  /// File mode creation mask
+  pub(crate) umask:     Option<u32>,
+  /// Scheduling priority, from -20 to 19
+  pub(crate) nice:      Option<i32>,
+  /// Soft resource limits, where `ProcessLimits::UNLIMITED` is unlimited
+  pub(crate) resources: Vec<(Resource, u64)>,
+}
+
+impl ProcessLimits {
+  pub(crate) const UNLIMITED: u64 = u64::MAX;
+
+  /// Parse the argument to `[umask]`, an octal mode, optionally prefixed with
+  /// `0o`
+  pub(crate) fn parse_umask(text: &str) -> Option<u32> {
+    let digits = text.strip_prefix("0o").unwrap_or(text);
+
+    if digits.is_empty() || digits.starts_with('+') {
+      return None;
+    }
+
+    u32::from_str_radix(digits, 8)
+      .ok()
+      .filter(|umask| *umask <= 0o777)
+  }
+
+  /// Parse the argument to `[nice]`, a niceness from -20 to 19
+  pub(crate) fn parse_nice(text: &str) -> Option<i32> {
+    if text.starts_with('+') {
+      return None;
+    }
+
+    text.parse().ok().filter(|nice| (-20..=19).contains(nice))
+  }
+
+  /// Parse the value argument to `[limit]`, a non-negative integer, or
+  /// `unlimited`
+  pub(crate) fn parse_limit(text: &str) -> Option<u64> {
+    if text == "unlimited" {
+      return Some(Self::UNLIMITED);
+    }
+
+    if text.starts_with('+') {
+      return None;
+    }
+
+    text.parse().ok()
+  }
+
+  pub(crate) fn is_empty(&self) -> bool {
+    self.umask.is_none() && self.nice.is_none() && self.resources.is_empty()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn umask() {
+    assert_eq!(ProcessLimits::parse_umask("022"), Some(0o022));
+    assert_eq!(ProcessLimits::parse_umask("0o077"), Some(0o077));
+    assert_eq!(ProcessLimits::parse_umask("7"), Some(0o007));
+    assert_eq!(ProcessLimits::parse_umask("777"), Some(0o777));
+    assert_eq!(ProcessLimits::parse_umask("1000"), None);
+    assert_eq!(ProcessLimits::parse_umask("089"), None);
+    assert_eq!(ProcessLimits::parse_umask("0o"), None);
+    assert_eq!(ProcessLimits::parse_umask(""), None);
+    assert_eq!(ProcessLimits::parse_umask("+22"), None);
+  }
+
+  #[test]
+  fn nice() {
+    assert_eq!(ProcessLimits::parse_nice("10"), Some(10));
+    assert_eq!(ProcessLimits::parse_nice("-20"), Some(-20));
+    assert_eq!(ProcessLimits::parse_nice("19"), Some(19));
+    assert_eq!(ProcessLimits::parse_nice("20"), None);
+    assert_eq!(ProcessLimits::parse_nice("-21"), None);
+    assert_eq!(ProcessLimits::parse_nice("+1"), None);
+    assert_eq!(ProcessLimits::parse_nice("low"), None);
+  }
+
+  #[test]
+  fn limit() {
+    assert_eq!(ProcessLimits::parse_limit("1024"), Some(1024));
+    assert_eq!(ProcessLimits::parse_limit("0"), Some(0));
+    assert_eq!(
+      ProcessLimits::parse_limit("unlimited"),
+      Some(ProcessLimits::UNLIMITED)
+    );
+    assert_eq!(ProcessLimits::parse_limit("-1"), None);
+    assert_eq!(ProcessLimits::parse_limit("+1"), None);
+    assert_eq!(ProcessLimits::parse_limit("1k"), None);
+  }
+}
@@ -8,16 +8,19 @@ fn error_from_signal(
   recipe: &str,
   line_number: Option<usize>,
   exit_status: ExitStatus,
+  exit_message: ExitMessage,
 ) -> RuntimeError {
   match Platform::signal_from_exit_status(exit_status) {
     Some(signal) => RuntimeError::Signal {
       recipe,
       line_number,
       signal,
+      exit_message,
     },
     None => RuntimeError::Unknown {
       recipe,
       line_number,
+      exit_message,
     },
   }
 }
@@ -25,6 +28,7 @@ fn error_from_signal(
 /// A recipe, e.g. `foo: bar baz`
 #[derive(PartialEq, Debug)]
 pub(crate) struct Recipe<'src, D = Dependency<'src>> {
+  pub(crate) attributes:   BTreeSet<Attribute<'src>>,
   pub(crate) dependencies: Vec<D>,
   pub(crate) doc:          Option<&'src str>,
   pub(crate) body:         Vec<Line<'src>>,
@@ -68,37 +72,472 @@ impl<'src, D> Recipe<'src, D> {
     !self.private
   }
 
+  /// The recipe's documentation, from its `[doc]` attribute if present, and
+  /// its doc comment otherwise
+  pub(crate) fn doc(&self) -> Option<&str> {
+    for attribute in &self.attributes {
+      if let Attribute::Doc(doc) = attribute {
+        return Some(&doc.cooked);
+      }
+    }
+
+    self.doc
+  }
+
+  /// The name of the recipe given by a `[cleanup]` attribute, if any
+  pub(crate) fn cleanup(&self) -> Option<&str> {
+    self.attributes.iter().find_map(|attribute| {
+      if let Attribute::Cleanup(recipe) = attribute {
+        Some(recipe.cooked.as_str())
+      } else {
+        None
+      }
+    })
+  }
+
+  /// The command given by a `[filter]` attribute, if any
+  pub(crate) fn filter(&self) -> Option<&str> {
+    self.attributes.iter().find_map(|attribute| {
+      if let Attribute::Filter(filter) = attribute {
+        Some(filter.cooked.as_str())
+      } else {
+        None
+      }
+    })
+  }
+
+  /// Check that each of `arguments` is one of the values allowed for its
+  /// parameter by a `[choices]` attribute, if there is one. Each argument
+  /// passed to a variadic parameter is checked separately.
+  pub(crate) fn check_choices(&self, arguments: &[&str]) -> RunResult<'src, ()> {
+    let mut rest = arguments;
+
+    for parameter in &self.parameters {
+      let count = if parameter.kind.is_variadic() {
+        rest.len()
+      } else {
+        cmp::min(rest.len(), 1)
+      };
+
+      let (values, tail) = rest.split_at(count);
+      rest = tail;
+
+      let choices = self
+        .attributes
+        .iter()
+        .find_map(|attribute| match attribute {
+          Attribute::Choices(name, choices) if name.lexeme() == parameter.name.lexeme() =>
+            Some((name, choices)),
+          _ => None,
+        });
+
+      if let Some((name, choices)) = choices {
+        for value in values {
+          if !choices.iter().any(|choice| choice.cooked == *value) {
+            return Err(RuntimeError::InvalidChoice {
+              recipe:    self.name(),
+              parameter: *name,
+              value:     (*value).to_owned(),
+              choices:   choices.iter().map(|choice| choice.cooked.clone()).collect(),
+            });
+          }
+        }
+      }
+    }
+
+    Ok(())
+  }
+
+  /// Whether `parameter` takes a filesystem path, given with a `[path]`
+  /// attribute
+  pub(crate) fn is_path_parameter(&self, parameter: &str) -> bool {
+    self
+      .attributes
+      .iter()
+      .any(|attribute| matches!(attribute, Attribute::Path(name) if name.cooked == parameter))
+  }
+
+  /// The path given by a `[tee]` attribute, if any
+  pub(crate) fn tee(&self) -> Option<&str> {
+    self.attributes.iter().find_map(|attribute| {
+      if let Attribute::Tee(path) = attribute {
+        Some(path.cooked.as_str())
+      } else {
+        None
+      }
+    })
+  }
+
+  /// The groups given with `[group]` attributes
+  pub(crate) fn groups(&self) -> Vec<&str> {
+    self
+      .attributes
+      .iter()
+      .filter_map(|attribute| match attribute {
+        Attribute::Group(group) => Some(group.cooked.as_str()),
+        _ => None,
+      })
+      .collect()
+  }
+
+  /// The globs given with `[inputs]` attributes, matching the files which the
+  /// recipe reads
+  pub(crate) fn inputs(&self) -> Vec<Glob> {
+    self
+      .attributes
+      .iter()
+      .filter_map(|attribute| match attribute {
+        Attribute::Inputs(glob) => Some(Glob::new(&glob.cooked)),
+        _ => None,
+      })
+      .collect()
+  }
+
+  /// The executables given with `[requires]` attributes, which must be on
+  /// `PATH` for the recipe to run
+  pub(crate) fn requirements(&self) -> Vec<&str> {
+    self
+      .attributes
+      .iter()
+      .filter_map(|attribute| match attribute {
+        Attribute::Requires(executables) => Some(executables),
+        _ => None,
+      })
+      .flatten()
+      .map(|executable| executable.cooked.as_str())
+      .collect()
+  }
+
+  /// The environment variables given with `[needs-env]` attributes, which
+  /// must be set for the recipe to run, and their descriptions
+  pub(crate) fn needed_env(&self) -> Vec<(&str, Option<&str>)> {
+    self
+      .attributes
+      .iter()
+      .filter_map(|attribute| match attribute {
+        Attribute::NeedsEnv(variables) => Some(variables),
+        _ => None,
+      })
+      .flatten()
+      .map(|(name, description)| {
+        (
+          name.cooked.as_str(),
+          description
+            .as_ref()
+            .map(|description| description.cooked.as_str()),
+        )
+      })
+      .collect()
+  }
+
+  /// The limits given with `[umask]`, `[nice]`, and `[limit]` attributes,
+  /// whose arguments are validated by the parser
+  pub(crate) fn process_limits(&self) -> ProcessLimits {
+    let mut limits = ProcessLimits::default();
+
+    for attribute in &self.attributes {
+      match attribute {
+        Attribute::Limit(resource, value) => {
+          if let (Ok(resource), Some(value)) = (
+            resource.cooked.parse(),
+            ProcessLimits::parse_limit(&value.cooked),
+          ) {
+            limits.resources.push((resource, value));
+          }
+        },
+        Attribute::Nice(nice) => limits.nice = ProcessLimits::parse_nice(&nice.cooked),
+        Attribute::Umask(umask) => limits.umask = ProcessLimits::parse_umask(&umask.cooked),
+        _ => {},
+      }
+    }
+
+    limits
+  }
+
+  /// Whether the recipe runs every time it is invoked or depended on, instead
+  /// of at most once per distinct list of arguments
+  pub(crate) fn fresh(&self) -> bool {
+    self.attributes.contains(&Attribute::Fresh)
+  }
+
+  pub(crate) fn remember_args(&self) -> bool {
+    self.attributes.contains(&Attribute::RememberArgs)
+  }
+
+  /// If the recipe has a `[confirm]` attribute, ask the user whether to run
+  /// it, returning an error if they decline
+  pub(crate) fn confirm(&self, config: &Config) -> RunResult<'src, ()> {
+    if config.yes || config.dry_run {
+      return Ok(());
+    }
+
+    let prompt = self.attributes.iter().find_map(|attribute| {
+      if let Attribute::Confirm(prompt) = attribute {
+        Some(prompt)
+      } else {
+        None
+      }
+    });
+
+    let prompt = match prompt {
+      Some(Some(prompt)) => prompt.cooked.clone(),
+      Some(None) => format!("Run recipe `{}`?", self.name()),
+      None => return Ok(()),
+    };
+
+    eprint!("{} (y/N) ", prompt);
+
+    let mut line = String::new();
+    if io::stdin().read_line(&mut line).is_err() {
+      line.clear();
+    }
+
+    match line.trim().to_lowercase().as_str() {
+      "y" | "yes" => Ok(()),
+      _ => Err(RuntimeError::NotConfirmed {
+        recipe: self.name(),
+      }),
+    }
+  }
+
+  /// How much to print if the recipe fails, which is nothing if it has the
+  /// `[no-exit-message]` attribute or the `no-exit-message` setting is set,
+  /// and is otherwise set by the `exit-message` setting
+  fn exit_message(&self, settings: &Settings) -> ExitMessage {
+    if settings.no_exit_message || self.attributes.contains(&Attribute::NoExitMessage) {
+      ExitMessage::None
+    } else {
+      settings.exit_message
+    }
+  }
+
+  /// The directory in which to run the recipe's commands
+  fn working_directory<'a>(&self, context: &'a RecipeContext) -> &'a Path {
+    if self.attributes.contains(&Attribute::NoCd) {
+      &context.config.invocation_directory
+    } else {
+      &context.search.working_directory
+    }
+  }
+
+  /// Whether lines should be echoed according to the recipe's `[quiet]` or
+  /// `[verbose]` attribute, if it has one
+  fn echo_attribute(&self) -> Option<bool> {
+    if self.attributes.contains(&Attribute::Verbose) {
+      Some(true)
+    } else if self.attributes.contains(&Attribute::Quiet) {
+      Some(false)
+    } else {
+      None
+    }
+  }
+
+  /// Open the file that the recipe's output is copied to, given with a
+  /// `[tee]` attribute, or else in the directory given with `--tee`
+  fn log(&self, context: &RecipeContext<'src, '_>) -> RunResult<'src, Option<Sink>> {
+    let config = context.config;
+
+    let path = if let Some(path) = self.tee() {
+      self.working_directory(context).join(path)
+    } else if let Some(directory) = &config.tee {
+      let timestamp = DateTime::now_local()
+        .and_then(|now| now.format("%Y%m%dT%H%M%S"))
+        .map_err(|message| RuntimeError::Internal { message })?;
+      directory.join(format!("{}-{}.log", self.name(), timestamp))
+    } else {
+      return Ok(None);
+    };
+
+    let file = path
+      .parent()
+      .map_or(Ok(()), fs::create_dir_all)
+      .and_then(|()| fs::File::create(&path))
+      .map_err(|io_error| RuntimeError::Tee {
+        recipe: self.name(),
+        path: path.clone(),
+        io_error,
+      })?;
+
+    Ok(Some(Arc::new(Mutex::new(file))))
+  }
+
+  /// Run `command` with the recipe's process limits, piping its output
+  /// through the recipe's `[filter]` command, if any, and copying it to `log`,
+  /// if given
+  fn status(
+    &self,
+    context: &RecipeContext<'src, '_>,
+    command: &mut Command,
+    log: Option<&Sink>,
+  ) -> RunResult<'src, io::Result<ExitStatus>> {
+    let config = context.config;
+
+    let limits = self.process_limits();
+
+    if !limits.is_empty() {
+      Platform::set_process_limits(command, &limits).map_err(|message| {
+        RuntimeError::ProcessLimits {
+          recipe: self.name(),
+          message,
+        }
+      })?;
+    }
+
+    let output = context.capture.as_ref();
+
+    let text = match (self.filter(), log, output) {
+      (Some(text), ..) if !config.verbosity.quiet() => text,
+      (_, Some(_), _) | (_, _, Some(_)) =>
+        return Ok(InterruptHandler::guard(|| {
+          tee(command, log, output, config.verbosity.quiet())
+        })),
+      _ => return Ok(InterruptHandler::guard(|| command.status())),
+    };
+
+    let mut filter_command = context.settings.shell_command(config);
+
+    filter_command
+      .current_dir(self.working_directory(context))
+      .arg(text);
+
+    InterruptHandler::guard(|| filter(command, filter_command, log, output)).map_err(
+      |output_error| RuntimeError::Filter {
+        recipe: self.name(),
+        output_error,
+      },
+    )
+  }
+
+  /// The parameter values that a recipe would be run with, for the banner
+  /// printed by `--dry-run --verbose`
+  fn plan_arguments(&self, scope: &Scope) -> String {
+    if self.parameters.is_empty() {
+      return String::new();
+    }
+
+    let arguments = self
+      .parameters
+      .iter()
+      .map(|parameter| {
+        format!(
+          "{}={:?}",
+          parameter.name,
+          scope.value(parameter.name.lexeme()).unwrap_or_default()
+        )
+      })
+      .collect::<Vec<String>>();
+
+    format!(" with {}", arguments.join(", "))
+  }
+
+  /// How the script for a shebang recipe would be run, for `--dry-run
+  /// --verbose`, or `None` if the shebang line is invalid
+  fn plan_script(interpreter: Option<&str>, evaluated_lines: &[String]) -> Option<String> {
+    let (program, arguments) = match interpreter {
+      Some(interpreter) => {
+        let mut words = interpreter.split_whitespace();
+        (words.next()?, words.collect::<Vec<&str>>())
+      },
+      None => {
+        let shebang = Shebang::new(evaluated_lines.first()?)?;
+        (shebang.interpreter, shebang.arguments())
+      },
+    };
+
+    let arguments = arguments
+      .iter()
+      .map(|argument| format!("`{}`", argument))
+      .collect::<Vec<String>>();
+
+    Some(match arguments.len() {
+      0 => format!("Would run script with `{}`", program),
+      1 => format!(
+        "Would run script with `{}` and argument {}",
+        program, arguments[0]
+      ),
+      _ => format!(
+        "Would run script with `{}` and arguments {}",
+        program,
+        arguments.join(", ")
+      ),
+    })
+  }
+
   pub(crate) fn run<'run>(
     &self,
     context: &RecipeContext<'src, 'run>,
     dotenv: &BTreeMap<String, String>,
     scope: Scope<'src, 'run>,
-    search: &'run Search,
+    is_dependency: bool,
   ) -> RunResult<'src, ()> {
     let config = &context.config;
 
+    if config.safe && !config.dry_run {
+      return Err(RuntimeError::UnsafeRecipe {
+        recipe: self.name(),
+      });
+    }
+
     if config.verbosity.loquacious() {
       let color = config.color.stderr().banner();
-      eprintln!(
-        "{}===> Running recipe `{}`...{}",
-        color.prefix(),
-        self.name,
-        color.suffix()
-      );
+      if config.dry_run {
+        context.eprintln(format_args!(
+          "{}===> Would run recipe `{}`{}...{}",
+          color.prefix(),
+          self.name,
+          self.plan_arguments(&scope),
+          color.suffix()
+        ));
+      } else {
+        context.eprintln(format_args!(
+          "{}===> Running recipe `{}`...{}",
+          color.prefix(),
+          self.name,
+          color.suffix()
+        ));
+      }
     }
 
-    let mut evaluator =
-      Evaluator::recipe_evaluator(context.config, dotenv, &scope, context.settings, search);
+    let mut evaluator = Evaluator::recipe_evaluator(context, dotenv, &scope);
+
+    // `--shebang` only applies to recipes given on the command line, since
+    // their dependencies may well be written for a different interpreter
+    let interpreter = if is_dependency {
+      None
+    } else {
+      config.shebang.as_deref()
+    };
 
-    if self.shebang {
+    if self.shebang || interpreter.is_some() {
       let mut evaluated_lines = vec![];
+      let mut continued = false;
       for line in &self.body {
-        evaluated_lines.push(evaluator.evaluate_line(line, false)?);
+        let mut evaluated = evaluator.evaluate_line(line, false)?;
+        // strip the `@` and `-` prefixes of linewise recipe lines, which mean
+        // nothing to the interpreter
+        if !self.shebang && !continued {
+          evaluated.drain(..line.prefix().len());
+        }
+        continued = line.is_continuation();
+        evaluated_lines.push(evaluated);
+      }
+
+      if config.dry_run && config.verbosity.loquacious() {
+        let color = config.color.stderr().banner();
+        if let Some(plan) = Self::plan_script(interpreter, &evaluated_lines) {
+          context.eprintln(format_args!(
+            "{}===> {}{}",
+            color.prefix(),
+            plan,
+            color.suffix()
+          ));
+        }
       }
 
-      if config.verbosity.loud() && (config.dry_run || self.quiet) {
+      if config.verbosity.loud() && (config.dry_run || self.echo_attribute().unwrap_or(self.quiet))
+      {
         for line in &evaluated_lines {
-          eprintln!("{}", line);
+          context.echo(config.color, line);
         }
       }
 
@@ -106,13 +545,32 @@ impl<'src, D> Recipe<'src, D> {
         return Ok(());
       }
 
-      let tmp = tempfile::Builder::new()
-        .prefix("just")
-        .tempdir()
-        .map_err(|error| RuntimeError::TmpdirIoError {
-          recipe:   self.name(),
-          io_error: error,
-        })?;
+      let log = self.log(context)?;
+
+      let shebang_line = match interpreter {
+        Some(interpreter) => format!("#!{}", interpreter),
+        None => evaluated_lines
+          .first()
+          .cloned()
+          .ok_or_else(|| RuntimeError::Internal {
+            message: "evaluated_lines was empty".to_owned(),
+          })?,
+      };
+
+      // the first line of a shebang recipe is replaced by `shebang_line`
+      let body_start = if self.shebang { 1 } else { 0 };
+
+      let mut builder = tempfile::Builder::new();
+      builder.prefix("just");
+
+      let tmp = match &context.settings.tempdir {
+        Some(tempdir) => builder.tempdir_in(context.search.working_directory.join(tempdir)),
+        None => builder.tempdir(),
+      }
+      .map_err(|error| RuntimeError::TmpdirIoError {
+        recipe:   self.name(),
+        io_error: error,
+      })?;
       let mut path = tmp.path().to_path_buf();
       path.push(self.name());
       {
@@ -122,20 +580,20 @@ impl<'src, D> Recipe<'src, D> {
         })?;
         let mut text = String::new();
         // add the shebang
-        text += &evaluated_lines[0];
+        text += &shebang_line;
         text += "\n";
         // add blank lines so that lines in the generated script have the same line
         // number as the corresponding lines in the justfile
-        for _ in 1..(self.line_number() + 2) {
+        for _ in 0..(self.line_number() + body_start) {
           text += "\n"
         }
-        for line in &evaluated_lines[1..] {
+        for line in &evaluated_lines[body_start..] {
           text += line;
           text += "\n";
         }
 
         if config.verbosity.grandiloquent() {
-          eprintln!("{}", config.color.doc().stderr().paint(&text));
+          context.eprintln(config.color.doc().stderr().paint(&text));
         }
 
         f.write_all(text.as_bytes())
@@ -151,35 +609,32 @@ impl<'src, D> Recipe<'src, D> {
         io_error: error,
       })?;
 
-      let shebang_line = evaluated_lines
-        .first()
-        .ok_or_else(|| RuntimeError::Internal {
-          message: "evaluated_lines was empty".to_owned(),
-        })?;
-
-      let Shebang {
-        interpreter,
-        argument,
-      } = Shebang::new(shebang_line).ok_or_else(|| RuntimeError::Internal {
+      let shebang = Shebang::new(&shebang_line).ok_or_else(|| RuntimeError::Internal {
         message: format!("bad shebang line: {}", shebang_line),
       })?;
 
       // create a command to run the script
-      let mut command = Platform::make_shebang_command(
-        &path,
-        &context.search.working_directory,
-        interpreter,
-        argument,
-      )
-      .map_err(|output_error| RuntimeError::Cygpath {
-        recipe: self.name(),
-        output_error,
-      })?;
+      let mut command = if let Some(interpreter) = interpreter {
+        let mut words = interpreter.split_whitespace();
+        let mut command = Command::new(words.next().unwrap_or_default());
+        command
+          .args(words)
+          .arg(&path)
+          .current_dir(self.working_directory(context));
+        command
+      } else {
+        Platform::make_shebang_command(&path, self.working_directory(context), &shebang).map_err(
+          |output_error| RuntimeError::Cygpath {
+            recipe: self.name(),
+            output_error,
+          },
+        )?
+      };
 
       command.export(context.settings, dotenv, &scope);
 
       // run it!
-      match InterruptHandler::guard(|| command.status()) {
+      match self.status(context, &mut command, log.as_ref())? {
         Ok(exit_status) =>
           if let Some(code) = exit_status.code() {
             if code != 0 {
@@ -187,21 +642,34 @@ impl<'src, D> Recipe<'src, D> {
                 recipe: self.name(),
                 line_number: None,
                 code,
+                exit_message: self.exit_message(context.settings),
+                template: context.settings.exit_message_template.clone(),
               });
             }
           } else {
-            return Err(error_from_signal(self.name(), None, exit_status));
+            return Err(error_from_signal(
+              self.name(),
+              None,
+              exit_status,
+              self.exit_message(context.settings),
+            ));
           },
         Err(io_error) => {
           return Err(RuntimeError::Shebang {
             recipe: self.name(),
-            command: interpreter.to_owned(),
-            argument: argument.map(String::from),
+            command: shebang.interpreter.to_owned(),
+            argument: shebang.argument.map(String::from),
             io_error,
           });
         },
       };
     } else {
+      let log = if config.dry_run {
+        None
+      } else {
+        self.log(context)?
+      };
+
       let mut lines = self.body.iter().peekable();
       let mut line_number = self.line_number() + 1;
       loop {
@@ -210,6 +678,7 @@ impl<'src, D> Recipe<'src, D> {
         }
         let mut evaluated = String::new();
         let mut continued = false;
+        let prefix = lines.peek().map(|line| line.prefix()).unwrap_or_default();
         let quiet_command = lines.peek().map(|line| line.is_quiet()).unwrap_or(false);
         let infallable_command = lines
           .peek()
@@ -229,10 +698,7 @@ impl<'src, D> Recipe<'src, D> {
             break;
           }
         }
-        let mut command = evaluated.as_str();
-        if quiet_command || infallable_command {
-          command = &command[1..];
-        }
+        let command = &evaluated[prefix.len()..];
 
         if command.is_empty() {
           continue;
@@ -240,23 +706,47 @@ impl<'src, D> Recipe<'src, D> {
 
         if config.dry_run
           || config.verbosity.loquacious()
-          || !((quiet_command ^ self.quiet) || config.verbosity.quiet())
+          || (config.verbosity.loud()
+            && self
+              .echo_attribute()
+              .unwrap_or(!(quiet_command ^ self.quiet)))
         {
           let color = if config.highlight {
             config.color.command()
           } else {
             config.color
           };
-          eprintln!("{}", color.stderr().paint(command));
+          if config.dry_run && config.verbosity.loquacious() {
+            // the plan marks lines that wouldn't be echoed, or whose failure
+            // would be ignored, with `@` and `-`
+            let quiet = !self
+              .echo_attribute()
+              .unwrap_or(!(quiet_command ^ self.quiet));
+            context.echo(
+              color,
+              &format!(
+                "{}{}{}",
+                if quiet { "@" } else { "" },
+                if infallable_command { "-" } else { "" },
+                command
+              ),
+            );
+          } else {
+            context.echo(color, command);
+          }
         }
 
         if config.dry_run {
           continue;
         }
 
+        if let Some(run_log) = context.log {
+          run_log.line(self.name(), line_number, command);
+        }
+
         let mut cmd = context.settings.shell_command(config);
 
-        cmd.current_dir(&context.search.working_directory);
+        cmd.current_dir(self.working_directory(context));
 
         cmd.arg(command);
 
@@ -267,7 +757,7 @@ impl<'src, D> Recipe<'src, D> {
 
         cmd.export(context.settings, dotenv, &scope);
 
-        match InterruptHandler::guard(|| cmd.status()) {
+        match self.status(context, &mut cmd, log.as_ref())? {
           Ok(exit_status) =>
             if let Some(code) = exit_status.code() {
               if code != 0 && !infallable_command {
@@ -275,6 +765,8 @@ impl<'src, D> Recipe<'src, D> {
                   recipe: self.name(),
                   line_number: Some(line_number),
                   code,
+                  exit_message: self.exit_message(context.settings),
+                  template: context.settings.exit_message_template.clone(),
                 });
               }
             } else {
@@ -282,6 +774,7 @@ impl<'src, D> Recipe<'src, D> {
                 self.name(),
                 Some(line_number),
                 exit_status,
+                self.exit_message(context.settings),
               ));
             },
           Err(io_error) => {
@@ -303,12 +796,16 @@ impl<'src, D> Keyed<'src> for Recipe<'src, D> {
   }
 }
 
-impl<'src> Display for Recipe<'src> {
+impl<'src, D: Display> Display for Recipe<'src, D> {
   fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
     if let Some(doc) = self.doc {
       writeln!(f, "# {}", doc)?;
     }
 
+    if !self.attributes.is_empty() {
+      writeln!(f, "{}", Attribute::list(&self.attributes))?;
+    }
+
     if self.quiet {
       write!(f, "@{}", self.name)?;
     } else {
@@ -1,8 +1,94 @@
 use crate::common::*;
 
 pub(crate) struct RecipeContext<'src: 'run, 'run> {
-  pub(crate) config:   &'run Config,
-  pub(crate) scope:    Scope<'src, 'run>,
-  pub(crate) search:   &'run Search,
-  pub(crate) settings: &'run Settings<'src>,
+  pub(crate) capture:        Option<Capture>,
+  pub(crate) config:         &'run Config,
+  pub(crate) function_cache: &'run FunctionCache,
+  pub(crate) log:            Option<&'run RunLog>,
+  pub(crate) report:         RefCell<Report>,
+  pub(crate) scope:          Scope<'src, 'run>,
+  pub(crate) search:         &'run Search,
+  pub(crate) settings:       &'run Settings<'src>,
+}
+
+impl<'src: 'run, 'run> RecipeContext<'src, 'run> {
+  /// Echo recipe line `line` to stderr in `color`, prefixed with the current
+  /// time if `--timestamps` or `set timestamps` is enabled, and truncated if
+  /// `--echo-truncate` or `set echo-truncate` is given
+  pub(crate) fn echo(&self, color: Color, line: &str) {
+    let color = color.stderr();
+
+    let truncated;
+    let line = match self.config.echo_truncate.or(self.settings.echo_truncate) {
+      Some(limit) => {
+        truncated = Self::truncate(line, limit);
+        truncated.as_str()
+      },
+      None => line,
+    };
+
+    if self.config.timestamps || self.settings.timestamps {
+      // `--timestamp-format` has already been checked, so this can only fail
+      // if the system clock is broken, in which case the line is still echoed
+      if let Ok(timestamp) = DateTime::now_local()
+        .or_else(|_| DateTime::now_utc())
+        .and_then(|now| now.format(&self.config.timestamp_format))
+      {
+        self.eprintln(format_args!(
+          "{} {}",
+          color.timestamp().paint(&format!("[{}]", timestamp)),
+          color.paint(line)
+        ));
+        return;
+      }
+    }
+
+    self.eprintln(color.paint(line));
+  }
+
+  /// The first `limit` characters of `line`, followed by a note saying how
+  /// many were left out, or all of `line` if it isn't longer than `limit` or
+  /// `limit` is zero
+  fn truncate(line: &str, limit: usize) -> String {
+    let count = line.chars().count();
+
+    if limit == 0 || count <= limit {
+      return line.to_owned();
+    }
+
+    format!(
+      "{}... [{} more {}]",
+      line.chars().take(limit).collect::<String>(),
+      count - limit,
+      Count("character", count - limit)
+    )
+  }
+
+  /// Print `line` to stderr, or to the captured stderr if output is being
+  /// captured
+  pub(crate) fn eprintln(&self, line: impl Display) {
+    match &self.capture {
+      Some(capture) => capture.eprintln(line),
+      None => eprintln!("{}", line),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn truncate() {
+    assert_eq!(
+      RecipeContext::truncate("abcdef", 3),
+      "abc... [3 more characters]"
+    );
+    assert_eq!(
+      RecipeContext::truncate("ábcdéf", 5),
+      "ábcdé... [1 more character]"
+    );
+    assert_eq!(RecipeContext::truncate("abc", 3), "abc");
+    assert_eq!(RecipeContext::truncate("abcdef", 0), "abcdef");
+  }
 }
@@ -0,0 +1,48 @@
+use crate::common::*;
+
+/// The parts of a `Recipe` needed by `--list`, `--summary`, and completions
+#[derive(Debug, PartialEq)]
+pub(crate) struct RecipeMetadata {
+  pub(crate) name:       String,
+  pub(crate) doc:        Option<String>,
+  pub(crate) parameters: Vec<ParameterMetadata>,
+  pub(crate) private:    bool,
+  /// Groups given with `[group]` attributes
+  pub(crate) groups:     Vec<String>,
+  /// Offset of the recipe name in the justfile, used for source ordering
+  pub(crate) offset:     usize,
+}
+
+impl<'src> From<&Recipe<'src>> for RecipeMetadata {
+  fn from(recipe: &Recipe<'src>) -> Self {
+    Self {
+      name:       recipe.name().to_owned(),
+      doc:        recipe.doc().map(str::to_owned),
+      parameters: recipe
+        .parameters
+        .iter()
+        .map(|parameter| ParameterMetadata {
+          path: recipe.is_path_parameter(parameter.name.lexeme()),
+          ..ParameterMetadata::from(parameter)
+        })
+        .collect(),
+      private:    recipe.private,
+      groups:     recipe.groups().into_iter().map(str::to_owned).collect(),
+      offset:     recipe.name.offset,
+    }
+  }
+}
+
+impl RecipeMetadata {
+  pub(crate) fn max_arguments(&self) -> usize {
+    if self
+      .parameters
+      .iter()
+      .any(|parameter| parameter.prefix.is_some())
+    {
+      usize::max_value() - 1
+    } else {
+      self.parameters.len()
+    }
+  }
+}
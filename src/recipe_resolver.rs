@@ -24,10 +24,12 @@ impl<'src: 'run, 'run> RecipeResolver<'src, 'run> {
     }
 
     for recipe in resolver.resolved_recipes.values() {
-      for parameter in &recipe.parameters {
+      resolver.resolve_cleanup(recipe)?;
+
+      for (i, parameter) in recipe.parameters.iter().enumerate() {
         if let Some(expression) = &parameter.default {
           for variable in expression.variables() {
-            resolver.resolve_variable(&variable, &[])?;
+            resolver.resolve_variable(&variable, &recipe.parameters[..i])?;
           }
         }
       }
@@ -54,17 +56,85 @@ impl<'src: 'run, 'run> RecipeResolver<'src, 'run> {
     Ok(resolver.resolved_recipes)
   }
 
+  /// Check that the recipe named by a `[cleanup]` attribute exists, takes no
+  /// required arguments, and does not lead back to `recipe` through its own
+  /// `[cleanup]` attributes
+  fn resolve_cleanup(&self, recipe: &Recipe<'src>) -> CompilationResult<'src, ()> {
+    let cleanup = match recipe.cleanup() {
+      Some(cleanup) => cleanup,
+      None => return Ok(()),
+    };
+
+    let resolved = self.resolved_recipes.get(cleanup).ok_or_else(|| {
+      recipe.name.error(UnknownCleanupRecipe {
+        recipe:  recipe.name(),
+        unknown: cleanup.to_owned(),
+      })
+    })?;
+
+    if resolved.min_arguments() > 0 {
+      return Err(recipe.name.error(CleanupArgumentCountMismatch {
+        cleanup: cleanup.to_owned(),
+        min:     resolved.min_arguments(),
+      }));
+    }
+
+    let mut circle = vec![recipe.name().to_owned()];
+    let mut next = Some(resolved);
+
+    while let Some(current) = next {
+      circle.push(current.name().to_owned());
+
+      if current.name() == recipe.name() {
+        return Err(recipe.name.error(CircularRecipeCleanup {
+          recipe: recipe.name(),
+          circle,
+        }));
+      }
+
+      if circle[..circle.len() - 1].contains(&circle[circle.len() - 1]) {
+        // a cycle not involving `recipe`, reported when resolving its members
+        break;
+      }
+
+      next = current
+        .cleanup()
+        .and_then(|cleanup| self.resolved_recipes.get(cleanup));
+    }
+
+    Ok(())
+  }
+
   fn resolve_variable(
     &self,
     variable: &Token<'src>,
-    parameters: &[Parameter],
+    parameters: &[Parameter<'src>],
   ) -> CompilationResult<'src, ()> {
     let name = variable.lexeme();
     let undefined =
       !self.assignments.contains_key(name) && !parameters.iter().any(|p| p.name.lexeme() == name);
 
     if undefined {
-      return Err(variable.error(UndefinedVariable { variable: name }));
+      return Err(
+        variable.error(UndefinedVariable {
+          variable:   name,
+          suggestion: Suggestion::closest(
+            name,
+            parameters
+              .iter()
+              .map(|parameter| Suggestion::Variable {
+                name: parameter.name.lexeme(),
+              })
+              .chain(
+                self
+                  .assignments
+                  .keys()
+                  .map(|name| Suggestion::Variable { name }),
+              )
+              .chain(Suggestion::function(name)),
+          ),
+        }),
+      );
     }
 
     Ok(())
@@ -124,6 +194,39 @@ impl<'src: 'run, 'run> RecipeResolver<'src, 'run> {
 mod tests {
   use super::*;
 
+  analysis_error! {
+    name:   circular_recipe_cleanup,
+    input:  "[cleanup('b')]\na:\n[cleanup('a')]\nb:",
+    offset: 15,
+    line:   1,
+    column: 0,
+    width:  1,
+    kind:   CircularRecipeCleanup{
+      recipe: "a",
+      circle: vec!["a".to_owned(), "b".to_owned(), "a".to_owned()],
+    },
+  }
+
+  analysis_error! {
+    name:   self_recipe_cleanup,
+    input:  "[cleanup('a')]\na:",
+    offset: 15,
+    line:   1,
+    column: 0,
+    width:  1,
+    kind:   CircularRecipeCleanup{recipe: "a", circle: vec!["a".to_owned(), "a".to_owned()]},
+  }
+
+  analysis_error! {
+    name:   cleanup_argument_count_mismatch,
+    input:  "[cleanup('b')]\na:\nb x:",
+    offset: 15,
+    line:   1,
+    column: 0,
+    width:  1,
+    kind:   CleanupArgumentCountMismatch{cleanup: "b".to_owned(), min: 1},
+  }
+
   analysis_error! {
     name:   circular_recipe_dependency,
     input:  "a: b\nb: a",
@@ -144,6 +247,16 @@ mod tests {
     kind:   CircularRecipeDependency{recipe: "a", circle: vec!["a", "a"]},
   }
 
+  analysis_error! {
+    name:   unknown_cleanup_recipe,
+    input:  "[cleanup('b')]\na:",
+    offset: 15,
+    line:   1,
+    column: 0,
+    width:  1,
+    kind:   UnknownCleanupRecipe{recipe: "a", unknown: "b".to_owned()},
+  }
+
   analysis_error! {
     name:   unknown_dependency,
     input:  "a: b",
@@ -161,7 +274,18 @@ mod tests {
     line:   1,
     column: 6,
     width:  5,
-    kind:   UndefinedVariable{variable: "hello"},
+    kind:   UndefinedVariable{variable: "hello", suggestion: Some(Suggestion::Function{name: "shell"})},
+  }
+
+  analysis_error! {
+    name:   unknown_variable_suggests_parameter,
+    input:  "x target:
+  echo {{targte}}",
+    offset: 19,
+    line:   1,
+    column: 9,
+    width:  6,
+    kind:   UndefinedVariable{variable: "targte", suggestion: Some(Suggestion::Variable{name: "target"})},
   }
 
   analysis_error! {
@@ -171,7 +295,7 @@ mod tests {
     line:   3,
     column: 16,
     width:  3,
-    kind:   UndefinedVariable{variable: "lol"},
+    kind:   UndefinedVariable{variable: "lol", suggestion: None},
   }
 
   analysis_error! {
@@ -181,7 +305,7 @@ mod tests {
     line:   0,
     column: 4,
     width:  3,
-    kind:   UndefinedVariable{variable: "foo"},
+    kind:   UndefinedVariable{variable: "foo", suggestion: None},
   }
 
   analysis_error! {
@@ -191,6 +315,37 @@ mod tests {
     line:   1,
     column: 10,
     width:  3,
-    kind:   UndefinedVariable{variable: "baz"},
+    kind:   UndefinedVariable{variable: "baz", suggestion: None},
+  }
+
+  analysis_error! {
+    name:   later_parameter_in_default,
+    input:  "a f=g g='x':",
+    offset: 4,
+    line:   0,
+    column: 4,
+    width:  1,
+    kind:   UndefinedVariable{variable: "g", suggestion: None},
+  }
+
+  analysis_error! {
+    name:   unknown_variable_after_string_in_default,
+    input:  "a f=(foo + 'x'):",
+    offset: 5,
+    line:   0,
+    column: 5,
+    width:  3,
+    kind:   UndefinedVariable{variable: "foo", suggestion: None},
+  }
+
+  analysis_error! {
+    name:   unknown_variable_in_function_argument,
+    input:  "a:
+  echo {{ quote(foo) }}",
+    offset: 19,
+    line:   1,
+    column: 16,
+    width:  3,
+    kind:   UndefinedVariable{variable: "foo", suggestion: None},
   }
 }
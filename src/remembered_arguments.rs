@@ -0,0 +1,120 @@
+use crate::common::*;
+
+use std::{
+  collections::hash_map::DefaultHasher,
+  hash::{Hash, Hasher},
+};
+
+/// Arguments from the last successful run of recipes with the
+/// `[remember-args]` attribute, stored in the user's state directory, one
+/// file per justfile and recipe.
+pub(crate) struct RememberedArguments {
+  /// Directory in which arguments are stored, or `None` if no state directory
+  /// could be determined
+  directory: Option<PathBuf>,
+  /// Canonical path of the justfile whose recipes' arguments are remembered
+  justfile:  PathBuf,
+}
+
+impl RememberedArguments {
+  pub(crate) fn new(justfile: &Path) -> Self {
+    Self {
+      directory: Self::state_directory(),
+      justfile:  fs::canonicalize(justfile).unwrap_or_else(|_| justfile.to_owned()),
+    }
+  }
+
+  /// Load the arguments last used to successfully run `recipe`
+  pub(crate) fn load(&self, recipe: &str) -> Option<Vec<String>> {
+    let path = self.path(recipe)?;
+
+    let text = fs::read_to_string(&path).ok()?;
+
+    Some(text.split('\0').map(str::to_owned).collect())
+  }
+
+  /// Remember the arguments used to successfully run `recipe`. Failure to
+  /// write the arguments is not fatal, so errors are logged and otherwise
+  /// ignored.
+  pub(crate) fn store(&self, recipe: &str, arguments: &[&str]) {
+    let path = if let Some(path) = self.path(recipe) {
+      path
+    } else {
+      warn!("Could not determine state directory to remember arguments");
+      return;
+    };
+
+    let result = path
+      .parent()
+      .map(fs::create_dir_all)
+      .unwrap_or(Ok(()))
+      .and_then(|()| fs::write(&path, arguments.join("\0")));
+
+    if let Err(io_error) = result {
+      warn!(
+        "Failed to write remembered arguments file `{}`: {}",
+        path.display(),
+        io_error
+      );
+    }
+  }
+
+  fn path(&self, recipe: &str) -> Option<PathBuf> {
+    let mut hasher = DefaultHasher::new();
+    self.justfile.hash(&mut hasher);
+    recipe.hash(&mut hasher);
+    Some(
+      self
+        .directory
+        .as_ref()?
+        .join(format!("{:016x}", hasher.finish())),
+    )
+  }
+
+  /// `$XDG_STATE_HOME/just/arguments`, falling back to
+  /// `~/.local/state/just/arguments`, or `%LOCALAPPDATA%\just\arguments` on
+  /// Windows
+  fn state_directory() -> Option<PathBuf> {
+    let nonempty = |name| env::var_os(name).filter(|value| !value.is_empty());
+
+    let base = if let Some(state_home) = nonempty("XDG_STATE_HOME") {
+      PathBuf::from(state_home)
+    } else if cfg!(windows) {
+      PathBuf::from(nonempty("LOCALAPPDATA")?)
+    } else {
+      PathBuf::from(nonempty("HOME")?)
+        .join(".local")
+        .join("state")
+    };
+
+    Some(base.join("just").join("arguments"))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  use pretty_assertions::assert_eq;
+
+  #[test]
+  fn store_and_load() {
+    let tempdir = tempfile::tempdir().unwrap();
+
+    let remembered = RememberedArguments {
+      directory: Some(tempdir.path().join("arguments")),
+      justfile:  tempdir.path().join("justfile"),
+    };
+
+    assert_eq!(remembered.load("foo"), None);
+
+    remembered.store("foo", &["a", "", "b c"]);
+
+    assert_eq!(
+      remembered.load("foo"),
+      Some(vec!["a".to_owned(), String::new(), "b c".to_owned()])
+    );
+
+    assert_eq!(remembered.load("bar"), None);
+  }
+}
@@ -0,0 +1,251 @@
+use crate::common::*;
+
+/// Results of the recipes run in a single invocation of just, written to a
+/// file for CI systems to ingest when `--report FORMAT=PATH` is given, and
+/// printed as a table of timings with `--timings`
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct Report {
+  cases: Vec<Case>,
+}
+
+#[derive(Debug, PartialEq)]
+struct Case {
+  /// The recipe name, followed by its arguments, if any
+  name:     String,
+  duration: Duration,
+  /// The error message, if the recipe failed
+  failure:  Option<String>,
+  /// The exit code that just would exit with if the recipe failed, or zero
+  status:   i32,
+}
+
+impl Report {
+  /// Record a run of `recipe` with `arguments`
+  pub(crate) fn record(
+    &mut self,
+    recipe: &str,
+    arguments: &[&str],
+    duration: Duration,
+    error: Option<&RuntimeError>,
+  ) {
+    let name = iter::once(recipe)
+      .chain(arguments.iter().cloned())
+      .collect::<Vec<&str>>()
+      .join(" ");
+
+    self.cases.push(Case {
+      failure: error.map(ToString::to_string),
+      status: error.map_or(EXIT_SUCCESS, RuntimeError::code),
+      name,
+      duration,
+    });
+  }
+
+  /// Write the report to `path` as `format`. `justfile` is used to name the
+  /// suite of recipes.
+  pub(crate) fn write(&self, format: ReportFormat, path: &Path, justfile: &Path) -> io::Result<()> {
+    let text = match format {
+      ReportFormat::Junit => self.junit(&justfile.display().to_string()),
+    };
+
+    fs::write(path, text)
+  }
+
+  fn junit(&self, suite: &str) -> String {
+    let failures = self
+      .cases
+      .iter()
+      .filter(|case| case.failure.is_some())
+      .count();
+
+    let time = Self::seconds(self.cases.iter().map(|case| case.duration).sum());
+
+    let mut xml = String::new();
+
+    xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str(&format!(
+      "<testsuites tests=\"{}\" failures=\"{}\" time=\"{}\">\n",
+      self.cases.len(),
+      failures,
+      time
+    ));
+    xml.push_str(&format!(
+      "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" time=\"{}\">\n",
+      Self::escape(suite),
+      self.cases.len(),
+      failures,
+      time
+    ));
+
+    for case in &self.cases {
+      xml.push_str(&format!(
+        "    <testcase name=\"{}\" classname=\"{}\" time=\"{}\"",
+        Self::escape(&case.name),
+        Self::escape(suite),
+        Self::seconds(case.duration),
+      ));
+
+      match &case.failure {
+        Some(failure) => {
+          xml.push_str(">\n");
+          xml.push_str(&format!(
+            "      <failure message=\"{}\">{}</failure>\n",
+            Self::escape(failure.lines().next().unwrap_or_default()),
+            Self::escape(failure)
+          ));
+          xml.push_str("    </testcase>\n");
+        },
+        None => xml.push_str("/>\n"),
+      }
+    }
+
+    xml.push_str("  </testsuite>\n");
+    xml.push_str("</testsuites>\n");
+
+    xml
+  }
+
+  /// A table of the recipes that were run, in the order that they finished,
+  /// with their durations and exit statuses, followed by the total duration
+  pub(crate) fn timings(&self) -> String {
+    let width = self
+      .cases
+      .iter()
+      .map(|case| UnicodeWidthStr::width(case.name.as_str()))
+      .max()
+      .unwrap_or(0)
+      .max("Recipe".len())
+      .max("Total".len());
+
+    let mut table = format!(
+      "{:width$} {:>9} {:>6}\n",
+      "Recipe",
+      "Duration",
+      "Status",
+      width = width
+    );
+
+    for case in &self.cases {
+      table.push_str(&format!(
+        "{}{:padding$} {:>8}s {:>6}\n",
+        case.name,
+        "",
+        Self::seconds(case.duration),
+        case.status,
+        padding = width - UnicodeWidthStr::width(case.name.as_str())
+      ));
+    }
+
+    table.push_str(&format!(
+      "{:width$} {:>8}s\n",
+      "Total",
+      Self::seconds(self.cases.iter().map(|case| case.duration).sum()),
+      width = width
+    ));
+
+    table
+  }
+
+  fn seconds(duration: Duration) -> String {
+    format!("{:.3}", duration.as_secs_f64())
+  }
+
+  fn escape(text: &str) -> String {
+    let mut escaped = String::new();
+
+    for c in text.chars() {
+      match c {
+        '&' => escaped.push_str("&amp;"),
+        '<' => escaped.push_str("&lt;"),
+        '>' => escaped.push_str("&gt;"),
+        '"' => escaped.push_str("&quot;"),
+        '\'' => escaped.push_str("&apos;"),
+        _ => escaped.push(c),
+      }
+    }
+
+    escaped
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  use pretty_assertions::assert_eq;
+
+  #[test]
+  fn empty() {
+    assert_eq!(
+      Report::default().junit("justfile"),
+      r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites tests="0" failures="0" time="0.000">
+  <testsuite name="justfile" tests="0" failures="0" time="0.000">
+  </testsuite>
+</testsuites>
+"#
+    );
+  }
+
+  #[test]
+  fn junit() {
+    let mut report = Report::default();
+
+    report.record("build", &[], Duration::from_millis(1500), None);
+    report.record(
+      "test",
+      &["a&b"],
+      Duration::from_millis(250),
+      Some(&RuntimeError::Code {
+        recipe:       "test",
+        line_number:  Some(4),
+        code:         2,
+        exit_message: ExitMessage::Full,
+        template:     None,
+      }),
+    );
+
+    assert_eq!(
+      report.junit("<justfile>"),
+      r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites tests="2" failures="1" time="1.750">
+  <testsuite name="&lt;justfile&gt;" tests="2" failures="1" time="1.750">
+    <testcase name="build" classname="&lt;justfile&gt;" time="1.500"/>
+    <testcase name="test a&amp;b" classname="&lt;justfile&gt;" time="0.250">
+      <failure message="Recipe `test` failed on line 4 with exit code 2">Recipe `test` failed on line 4 with exit code 2</failure>
+    </testcase>
+  </testsuite>
+</testsuites>
+"#
+    );
+  }
+
+  #[test]
+  fn timings() {
+    let mut report = Report::default();
+
+    report.record("build", &[], Duration::from_millis(1500), None);
+    report.record(
+      "test",
+      &["unit"],
+      Duration::from_millis(12250),
+      Some(&RuntimeError::Code {
+        recipe:       "test",
+        line_number:  Some(4),
+        code:         2,
+        exit_message: ExitMessage::Full,
+        template:     None,
+      }),
+    );
+
+    assert_eq!(
+      report.timings(),
+      "\
+Recipe     Duration Status
+build        1.500s      0
+test unit   12.250s      2
+Total       13.750s
+"
+    );
+  }
+}
@@ -0,0 +1,19 @@
+use crate::common::*;
+
+/// The format of the report written by `--report FORMAT=PATH`
+#[derive(Debug, PartialEq, Clone, Copy, EnumString, IntoStaticStr)]
+#[strum(serialize_all = "kebab_case")]
+pub(crate) enum ReportFormat {
+  /// JUnit-style XML, which most CI systems can ingest
+  Junit,
+}
+
+impl ReportFormat {
+  pub(crate) const VALUES: &'static [&'static str] = &["junit"];
+}
+
+impl Display for ReportFormat {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(f, "{}", <&str>::from(self))
+  }
+}
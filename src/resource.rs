@@ -0,0 +1,27 @@
+use crate::common::*;
+
+/// A resource whose soft limit can be set with a `[limit]` attribute, named
+/// like the `RLIMIT_*` constants
+#[derive(Debug, PartialEq, Clone, Copy, EnumString)]
+#[strum(serialize_all = "kebab_case")]
+pub(crate) enum Resource {
+  /// Size of core files, in bytes
+  Core,
+  /// CPU time, in seconds
+  Cpu,
+  /// Size of the data segment, in bytes
+  Data,
+  /// Size of files that may be written, in bytes
+  Fsize,
+  /// Number of open file descriptors
+  Nofile,
+  /// Number of processes
+  Nproc,
+  /// Size of the stack, in bytes
+  Stack,
+}
+
+impl Resource {
+  pub(crate) const VALUES: &'static [&'static str] =
+    &["core", "cpu", "data", "fsize", "nofile", "nproc", "stack"];
+}
@@ -13,10 +13,12 @@ pub fn run() -> Result<(), i32> {
 
   let app = Config::app();
 
+  let args = Config::environment_arguments(env::args_os().collect(), |key| env::var_os(key));
+
   info!("Parsing command line arguments…");
-  let matches = app.get_matches();
+  let matches = app.get_matches_from(args);
 
-  let config = Config::from_matches(&matches).eprint(Color::auto())?;
+  let config = Config::from_matches(&matches).eprint(Color::auto(), 0)?;
 
   config.run_subcommand()
 }
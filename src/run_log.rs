@@ -0,0 +1,146 @@
+use crate::common::*;
+
+use std::{
+  convert::TryFrom,
+  time::{SystemTime, UNIX_EPOCH},
+};
+
+/// A stream of events written as JSON lines to the file given with
+/// `--log-json`, one for each recipe that starts or finishes, each recipe line
+/// that is run, and each backtick that is evaluated, for CI systems to build
+/// dashboards from without scraping stderr
+pub(crate) struct RunLog {
+  file: RefCell<fs::File>,
+}
+
+impl RunLog {
+  pub(crate) fn create(path: &Path) -> io::Result<Self> {
+    Ok(Self {
+      file: RefCell::new(fs::File::create(path)?),
+    })
+  }
+
+  pub(crate) fn recipe_start(&self, recipe: &str, arguments: &[&str]) {
+    self.write("recipe-start", vec![
+      ("recipe", recipe.into()),
+      ("arguments", arguments.to_vec().into()),
+    ]);
+  }
+
+  /// Record that `command`, which ends on line `number` of the justfile, is
+  /// about to be run as part of `recipe`
+  pub(crate) fn line(&self, recipe: &str, number: usize, command: &str) {
+    self.write("line", vec![
+      ("recipe", recipe.into()),
+      ("line", (number as u64).into()),
+      ("command", command.into()),
+    ]);
+  }
+
+  pub(crate) fn recipe_end(
+    &self,
+    recipe: &str,
+    arguments: &[&str],
+    duration: Duration,
+    error: Option<&RuntimeError>,
+  ) {
+    self.write("recipe-end", vec![
+      ("recipe", recipe.into()),
+      ("arguments", arguments.to_vec().into()),
+      (
+        "status",
+        Self::status(error.map_or(EXIT_SUCCESS, RuntimeError::code)),
+      ),
+      ("duration_ms", Self::milliseconds(duration)),
+      ("error", error.map(ToString::to_string).into()),
+    ]);
+  }
+
+  /// Record that backtick `command` finished, with exit status `status`, or
+  /// `None` if it couldn't be run or was killed by a signal
+  pub(crate) fn backtick(&self, command: &str, duration: Duration, status: Option<i32>) {
+    self.write("backtick", vec![
+      ("command", command.into()),
+      ("status", status.map_or(OutputValue::Null, Self::status)),
+      ("duration_ms", Self::milliseconds(duration)),
+    ]);
+  }
+
+  fn write(&self, event: &str, fields: Vec<(&str, OutputValue)>) {
+    let time = SystemTime::now()
+      .duration_since(UNIX_EPOCH)
+      .unwrap_or_default();
+
+    let line = OutputValue::object(
+      vec![
+        ("event", event.into()),
+        ("time_ms", Self::milliseconds(time)),
+      ]
+      .into_iter()
+      .chain(fields),
+    )
+    .json_line();
+
+    // a log that can no longer be written to shouldn't stop recipes from
+    // running, so write errors are ignored
+    self.file.borrow_mut().write_all(line.as_bytes()).ok();
+  }
+
+  fn status(status: i32) -> OutputValue {
+    u64::try_from(status).map_or(OutputValue::Null, OutputValue::from)
+  }
+
+  fn milliseconds(duration: Duration) -> OutputValue {
+    u64::try_from(duration.as_millis())
+      .unwrap_or(u64::MAX)
+      .into()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  use pretty_assertions::assert_eq;
+
+  #[test]
+  fn events() {
+    let tempdir = tempfile::tempdir().unwrap();
+    let path = tempdir.path().join("log.jsonl");
+
+    let log = RunLog::create(&path).unwrap();
+    log.recipe_start("foo", &["a"]);
+    log.backtick("date", Duration::from_millis(3), Some(0));
+    log.line("foo", 2, "echo \"a\"");
+    log.recipe_end(
+      "foo",
+      &["a"],
+      Duration::from_millis(1500),
+      Some(&RuntimeError::Code {
+        recipe:       "foo",
+        line_number:  Some(2),
+        code:         3,
+        exit_message: ExitMessage::Full,
+        template:     None,
+      }),
+    );
+    drop(log);
+
+    let time = Regex::new(r#""time_ms":\d+"#).unwrap();
+
+    assert_eq!(
+      time.replace_all(&fs::read_to_string(&path).unwrap(), r#""time_ms":0"#),
+      concat!(
+        r#"{"event":"recipe-start","time_ms":0,"recipe":"foo","arguments":["a"]}"#,
+        "\n",
+        r#"{"event":"backtick","time_ms":0,"command":"date","status":0,"duration_ms":3}"#,
+        "\n",
+        r#"{"event":"line","time_ms":0,"recipe":"foo","line":2,"command":"echo \"a\""}"#,
+        "\n",
+        r#"{"event":"recipe-end","time_ms":0,"recipe":"foo","arguments":["a"],"status":3,"#,
+        r#""duration_ms":1500,"error":"Recipe `foo` failed on line 2 with exit code 3"}"#,
+        "\n",
+      )
+    );
+  }
+}
@@ -14,16 +14,35 @@ pub(crate) enum RuntimeError<'src> {
     output_error: OutputError,
   },
   Code {
-    recipe:      &'src str,
-    line_number: Option<usize>,
-    code:        i32,
+    recipe:       &'src str,
+    line_number:  Option<usize>,
+    code:         i32,
+    exit_message: ExitMessage,
+    template:     Option<String>,
+  },
+  Command {
+    command:      &'src str,
+    output_error: OutputError,
   },
   Cygpath {
     recipe:       &'src str,
     output_error: OutputError,
   },
   Dotenv {
-    dotenv_error: dotenv::Error,
+    path:         PathBuf,
+    dotenv_error: DotenvError,
+  },
+  EmptyInterpolation {
+    expression: String,
+    fallback:   Fallback<'src>,
+  },
+  EvalUnknownVariable {
+    variable:   &'src str,
+    suggestion: Option<Suggestion<'src>>,
+  },
+  Filter {
+    recipe:       &'src str,
+    output_error: OutputError,
   },
   FunctionCall {
     function: Name<'src>,
@@ -36,6 +55,22 @@ pub(crate) enum RuntimeError<'src> {
     recipe:   &'src str,
     io_error: io::Error,
   },
+  KeepGoing {
+    failed: Vec<String>,
+    code:   i32,
+  },
+  LogJson {
+    path:     PathBuf,
+    io_error: io::Error,
+  },
+  ProcessLimits {
+    recipe:  &'src str,
+    message: String,
+  },
+  Report {
+    path:     PathBuf,
+    io_error: io::Error,
+  },
   Shebang {
     recipe:   &'src str,
     command:  String,
@@ -43,26 +78,80 @@ pub(crate) enum RuntimeError<'src> {
     io_error: io::Error,
   },
   Signal {
-    recipe:      &'src str,
-    line_number: Option<usize>,
-    signal:      i32,
+    recipe:       &'src str,
+    line_number:  Option<usize>,
+    signal:       i32,
+    exit_message: ExitMessage,
+  },
+  Tee {
+    recipe:   &'src str,
+    path:     PathBuf,
+    io_error: io::Error,
   },
   TmpdirIoError {
     recipe:   &'src str,
     io_error: io::Error,
   },
   UnknownOverrides {
-    overrides: Vec<&'src str>,
+    overrides:  Vec<&'src str>,
+    suggestion: Option<Suggestion<'src>>,
   },
   UnknownRecipes {
     recipes:    Vec<&'src str>,
     suggestion: Option<Suggestion<'src>>,
   },
   Unknown {
-    recipe:      &'src str,
-    line_number: Option<usize>,
+    recipe:       &'src str,
+    line_number:  Option<usize>,
+    exit_message: ExitMessage,
+  },
+  UnsafeBacktick {
+    token: Token<'src>,
+  },
+  UnsafeCommand,
+  UnsafeFunctionCall {
+    function: Name<'src>,
+  },
+  UnsafeRecipe {
+    recipe: &'src str,
+  },
+  MissingEnvironmentVariables {
+    missing: Vec<(String, Option<String>, Vec<&'src str>)>,
+  },
+  MissingExecutables {
+    missing: Vec<(String, Vec<&'src str>)>,
+  },
+  NoMatchingRecipes {
+    pattern: &'src str,
   },
   NoRecipes,
+  DivisionByZero {
+    token: Token<'src>,
+  },
+  IntegerOverflow {
+    lhs:      i64,
+    operator: Operator,
+    rhs:      i64,
+    token:    Token<'src>,
+  },
+  NonIntegerOperand {
+    operator: Operator,
+    value:    String,
+    token:    Token<'src>,
+  },
+  InvalidChoice {
+    recipe:    &'src str,
+    parameter: Name<'src>,
+    value:     String,
+    choices:   Vec<String>,
+  },
+  InvalidSettingValue {
+    setting: Name<'src>,
+    value:   String,
+  },
+  NotConfirmed {
+    recipe: &'src str,
+  },
   DefaultRecipeRequiresArguments {
     recipe:        &'src str,
     min_arguments: usize,
@@ -73,6 +162,11 @@ impl<'src> Error for RuntimeError<'src> {
   fn code(&self) -> i32 {
     match *self {
       Self::Code { code, .. }
+      | Self::KeepGoing { code, .. }
+      | Self::Command {
+        output_error: OutputError::Code(code),
+        ..
+      }
       | Self::Backtick {
         output_error: OutputError::Code(code),
         ..
@@ -80,14 +174,36 @@ impl<'src> Error for RuntimeError<'src> {
       _ => EXIT_FAILURE,
     }
   }
+
+  fn print_message(&self) -> bool {
+    match *self {
+      Self::Code { exit_message, .. }
+      | Self::Signal { exit_message, .. }
+      | Self::Unknown { exit_message, .. } => exit_message != ExitMessage::None,
+      _ => true,
+    }
+  }
+
+  fn prefixed(&self) -> bool {
+    !matches!(self, Self::Code {
+      template: Some(_),
+      ..
+    })
+  }
 }
 
 impl<'src> RuntimeError<'src> {
   fn context(&self) -> Option<Token> {
     use RuntimeError::*;
     match self {
-      FunctionCall { function, .. } => Some(function.token()),
-      Backtick { token, .. } => Some(*token),
+      FunctionCall { function, .. } | UnsafeFunctionCall { function } => Some(function.token()),
+      Backtick { token, .. } | UnsafeBacktick { token } => Some(*token),
+      EmptyInterpolation { fallback, .. } => Some(fallback.token()),
+      DivisionByZero { token }
+      | IntegerOverflow { token, .. }
+      | NonIntegerOperand { token, .. } => Some(*token),
+      InvalidChoice { parameter, .. } => Some(parameter.token()),
+      InvalidSettingValue { setting, .. } => Some(setting.token()),
       _ => None,
     }
   }
@@ -120,13 +236,44 @@ impl<'src> Display for RuntimeError<'src> {
           write!(f, "\n{}", suggestion)?;
         }
       },
-      UnknownOverrides { overrides } => {
+      EvalUnknownVariable {
+        variable,
+        suggestion,
+      } => {
+        write!(f, "Justfile does not contain variable `{}`.", variable)?;
+        if let Some(suggestion) = *suggestion {
+          write!(f, "\n{}", suggestion)?;
+        }
+      },
+      UnknownOverrides {
+        overrides,
+        suggestion,
+      } => {
         write!(
           f,
           "{} {} overridden on the command line but not present in justfile",
           Count("Variable", overrides.len()),
           List::and_ticked(overrides),
         )?;
+        if let Some(suggestion) = *suggestion {
+          write!(f, "\n{}", suggestion)?;
+        }
+      },
+      UnsafeBacktick { .. } => {
+        writeln!(f, "Backticks may not be evaluated in safe mode")?;
+      },
+      UnsafeCommand => {
+        write!(f, "`--command` may not be used in safe mode")?;
+      },
+      UnsafeFunctionCall { function } => {
+        writeln!(
+          f,
+          "Function `{}` may not be called in safe mode",
+          function.lexeme()
+        )?;
+      },
+      UnsafeRecipe { recipe } => {
+        write!(f, "Recipe `{}` may not be run in safe mode", recipe)?;
       },
       ArgumentCountMismatch {
         recipe,
@@ -178,6 +325,33 @@ impl<'src> Display for RuntimeError<'src> {
         recipe,
         line_number,
         code,
+        template: Some(template),
+        ..
+      } => write!(
+        f,
+        "{}",
+        ExitMessage::expand(template, recipe, *line_number, *code)
+      )?,
+      Code {
+        recipe,
+        exit_message: ExitMessage::Minimal,
+        ..
+      }
+      | Unknown {
+        recipe,
+        exit_message: ExitMessage::Minimal,
+        ..
+      } => write!(f, "Recipe `{}` failed", recipe)?,
+      Signal {
+        recipe,
+        exit_message: ExitMessage::Minimal,
+        ..
+      } => write!(f, "Recipe `{}` was terminated", recipe)?,
+      Code {
+        recipe,
+        line_number,
+        code,
+        ..
       } =>
         if let Some(n) = line_number {
           write!(
@@ -188,6 +362,78 @@ impl<'src> Display for RuntimeError<'src> {
         } else {
           write!(f, "Recipe `{}` failed with exit code {}", recipe, code)?;
         },
+      Filter {
+        recipe,
+        output_error,
+      } => match output_error {
+        OutputError::Code(code) => {
+          write!(
+            f,
+            "Filter for recipe `{}` failed with exit code {}",
+            recipe, code
+          )?;
+        },
+        OutputError::Signal(signal) => {
+          write!(
+            f,
+            "Filter for recipe `{}` was terminated by signal {}",
+            recipe, signal
+          )?;
+        },
+        OutputError::Unknown => {
+          write!(
+            f,
+            "Filter for recipe `{}` experienced an unknown failure",
+            recipe
+          )?;
+        },
+        OutputError::Io(io_error) => {
+          write!(
+            f,
+            "Filter for recipe `{}` could not be run because of an IO error:\n{}",
+            recipe, io_error
+          )?;
+        },
+        OutputError::Utf8(utf8_error) => {
+          write!(
+            f,
+            "Filter for recipe `{}` produced invalid UTF-8: {}",
+            recipe, utf8_error
+          )?;
+        },
+      },
+      Command {
+        command,
+        output_error,
+      } => match output_error {
+        OutputError::Code(code) => {
+          write!(f, "Command `{}` failed with exit code {}", command, code)?;
+        },
+        OutputError::Signal(signal) => {
+          write!(
+            f,
+            "Command `{}` was terminated by signal {}",
+            command, signal
+          )?;
+        },
+        OutputError::Unknown => {
+          write!(f, "Command `{}` experienced an unknown failure", command)?;
+        },
+        OutputError::Io(io_error) => {
+          write!(
+            f,
+            "Command `{}` could not be run because of an IO error:\n{}",
+            command, io_error
+          )?;
+        },
+        OutputError::Utf8(utf8_error) => {
+          write!(
+            f,
+            "Command `{}` produced invalid UTF-8: {}",
+            command, utf8_error
+          )?;
+        },
+      },
       Cygpath {
         recipe,
         output_error,
@@ -242,8 +488,28 @@ impl<'src> Display for RuntimeError<'src> {
           )?;
         },
       },
-      Dotenv { dotenv_error } => {
-        writeln!(f, "Failed to load .env: {}", dotenv_error)?;
+      EmptyInterpolation {
+        expression,
+        fallback,
+      } => {
+        writeln!(
+          f,
+          "Interpolation `{{{{{}}}}}` evaluated to an empty default, because {}",
+          expression, fallback
+        )?;
+      },
+      Dotenv { path, dotenv_error } => {
+        write!(
+          f,
+          "Failed to load environment file `{}`: {}",
+          path.display(),
+          dotenv_error
+        )?;
+      },
+      FunctionCall { function, message }
+        if crate::function::USER_ERRORS.contains(&function.lexeme()) =>
+      {
+        writeln!(f, "{}", message)?;
       },
       FunctionCall { function, message } => {
         writeln!(
@@ -253,6 +519,22 @@ impl<'src> Display for RuntimeError<'src> {
           message
         )?;
       },
+      LogJson { path, io_error } => {
+        write!(
+          f,
+          "Failed to open JSON log `{}`: {}",
+          path.display(),
+          io_error
+        )?;
+      },
+      Report { path, io_error } => {
+        write!(
+          f,
+          "Failed to write report to `{}`: {}",
+          path.display(),
+          io_error
+        )?;
+      },
       Shebang {
         recipe,
         command,
@@ -276,6 +558,7 @@ impl<'src> Display for RuntimeError<'src> {
         recipe,
         line_number,
         signal,
+        ..
       } =>
         if let Some(n) = line_number {
           write!(
@@ -289,6 +572,7 @@ impl<'src> Display for RuntimeError<'src> {
       Unknown {
         recipe,
         line_number,
+        ..
       } =>
         if let Some(n) = line_number {
           write!(
@@ -318,6 +602,34 @@ impl<'src> Display for RuntimeError<'src> {
           ),
         }?;
       },
+      ProcessLimits { recipe, message } => {
+        write!(
+          f,
+          "Process limits for recipe `{}` could not be applied: {}",
+          recipe, message
+        )?;
+      },
+      KeepGoing { failed, .. } => {
+        write!(
+          f,
+          "{} {} failed",
+          Count("Recipe", failed.len()),
+          List::and_ticked(failed),
+        )?;
+      },
+      Tee {
+        recipe,
+        path,
+        io_error,
+      } => {
+        write!(
+          f,
+          "Failed to open log file `{}` for recipe `{}`: {}",
+          path.display(),
+          recipe,
+          io_error
+        )?;
+      },
       TmpdirIoError { recipe, io_error } => writeln!(
         f,
         "Recipe `{}` could not be run because of an IO error while trying to create a temporary \
@@ -361,9 +673,94 @@ impl<'src> Display for RuntimeError<'src> {
           )?;
         },
       },
+      MissingEnvironmentVariables { missing } => {
+        write!(
+          f,
+          "Required {} not set:",
+          Count("environment variable", missing.len())
+        )?;
+        for (name, description, recipes) in missing {
+          write!(f, "\n  `{}`", name)?;
+          if let Some(description) = description {
+            write!(f, " ({})", description)?;
+          }
+          write!(
+            f,
+            ", required by {} {}",
+            Count("recipe", recipes.len()),
+            List::and_ticked(recipes)
+          )?;
+        }
+      },
+      MissingExecutables { missing } => {
+        write!(
+          f,
+          "Could not find required {} on PATH:",
+          Count("executable", missing.len())
+        )?;
+        for (executable, recipes) in missing {
+          write!(
+            f,
+            "\n  `{}`, required by {} {}",
+            executable,
+            Count("recipe", recipes.len()),
+            List::and_ticked(recipes)
+          )?;
+        }
+      },
+      DivisionByZero { .. } => {
+        writeln!(f, "Division by zero")?;
+      },
+      IntegerOverflow {
+        lhs, operator, rhs, ..
+      } => {
+        writeln!(
+          f,
+          "Integer overflow evaluating `{} {} {}`",
+          lhs, operator, rhs
+        )?;
+      },
+      NonIntegerOperand {
+        operator, value, ..
+      } => {
+        writeln!(f, "Operand `{}` of `{}` is not an integer", value, operator)?;
+      },
+      InvalidChoice {
+        recipe,
+        parameter,
+        value,
+        choices,
+      } => {
+        writeln!(
+          f,
+          "Invalid value `{}` for parameter `{}` of recipe `{}`, expected {}",
+          value,
+          parameter.lexeme(),
+          recipe,
+          List::or_ticked(choices),
+        )?;
+      },
+      InvalidSettingValue { setting, value } => {
+        writeln!(
+          f,
+          "Setting `{}` evaluated to `{}`, but must be `true` or `false`",
+          setting.lexeme(),
+          value
+        )?;
+      },
+      NoMatchingRecipes { pattern } => {
+        write!(
+          f,
+          "Justfile contains no recipes matching `{}` which can be run without arguments.",
+          pattern
+        )?;
+      },
       NoRecipes => {
         writeln!(f, "Justfile contains no recipes.",)?;
       },
+      NotConfirmed { recipe } => {
+        write!(f, "Recipe `{}` was not confirmed", recipe)?;
+      },
       DefaultRecipeRequiresArguments {
         recipe,
         min_arguments,
@@ -395,9 +792,3 @@ impl<'src> Display for RuntimeError<'src> {
     Ok(())
   }
 }
-
-impl<'src> From<dotenv::Error> for RuntimeError<'src> {
-  fn from(dotenv_error: dotenv::Error) -> RuntimeError<'src> {
-    RuntimeError::Dotenv { dotenv_error }
-  }
-}
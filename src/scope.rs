@@ -2,26 +2,31 @@ use crate::common::*;
 
 #[derive(Debug)]
 pub(crate) struct Scope<'src: 'run, 'run> {
-  parent:   Option<&'run Scope<'src, 'run>>,
-  bindings: Table<'src, Binding<'src, String>>,
+  parent:    Option<&'run Scope<'src, 'run>>,
+  bindings:  Table<'src, Binding<'src, String>>,
+  /// Defaults that empty values of bindings fell back to
+  fallbacks: BTreeMap<&'src str, Fallback<'src>>,
 }
 
 impl<'src, 'run> Scope<'src, 'run> {
   pub(crate) fn child(&'run self) -> Scope<'src, 'run> {
     Scope {
-      parent:   Some(self),
-      bindings: Table::new(),
+      parent:    Some(self),
+      bindings:  Table::new(),
+      fallbacks: BTreeMap::new(),
     }
   }
 
   pub(crate) fn new() -> Scope<'src, 'run> {
     Scope {
-      parent:   None,
-      bindings: Table::new(),
+      parent:    None,
+      bindings:  Table::new(),
+      fallbacks: BTreeMap::new(),
     }
   }
 
   pub(crate) fn bind(&mut self, export: bool, name: Name<'src>, value: String) {
+    self.fallbacks.remove(name.lexeme());
     self.bindings.insert(Binding {
       name,
       export,
@@ -29,20 +34,51 @@ impl<'src, 'run> Scope<'src, 'run> {
     });
   }
 
+  /// Record that the empty value bound to `name` fell back to `fallback`
+  pub(crate) fn bind_fallback(&mut self, name: &'src str, fallback: Fallback<'src>) {
+    self.fallbacks.insert(name, fallback);
+  }
+
+  /// The default that the value of `name` fell back to, if any
+  pub(crate) fn fallback(&self, name: &str) -> Option<&Fallback<'src>> {
+    if self.bindings.contains_key(name) {
+      self.fallbacks.get(name)
+    } else {
+      self.parent.and_then(|parent| parent.fallback(name))
+    }
+  }
+
   pub(crate) fn bound(&self, name: &str) -> bool {
     self.bindings.contains_key(name)
   }
 
   pub(crate) fn value(&self, name: &str) -> Option<&str> {
+    self.binding(name).map(|binding| binding.value.as_ref())
+  }
+
+  pub(crate) fn binding(&self, name: &str) -> Option<&Binding<'src>> {
     if let Some(binding) = self.bindings.get(name) {
-      Some(binding.value.as_ref())
+      Some(binding)
     } else if let Some(parent) = self.parent {
-      parent.value(name)
+      parent.binding(name)
     } else {
       None
     }
   }
 
+  /// The value of `name`, if it is exported, either because it was declared
+  /// with `export` or because `export_all` is true. Bindings shadow bindings
+  /// of the same name in parent scopes only if they are exported, as is the
+  /// case for the environment that recipes are run with.
+  pub(crate) fn exported(&self, name: &str, export_all: bool) -> Option<&str> {
+    match self.bindings.get(name) {
+      Some(binding) if export_all || binding.export => Some(binding.value.as_ref()),
+      _ => self
+        .parent
+        .and_then(|parent| parent.exported(name, export_all)),
+    }
+  }
+
   pub(crate) fn bindings(&self) -> impl Iterator<Item = &Binding<String>> {
     self.bindings.values()
   }
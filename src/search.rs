@@ -3,6 +3,8 @@ use crate::common::*;
 use std::path::Component;
 
 pub(crate) const FILENAME: &str = "justfile";
+pub(crate) const STDIN: &str = "-";
+const LOCAL_SUFFIX: &str = ".local";
 const PROJECT_ROOT_CHILDREN: &[&str] = &[".bzr", ".git", ".hg", ".svn", "_darcs"];
 
 pub(crate) struct Search {
@@ -13,11 +15,23 @@ pub(crate) struct Search {
 impl Search {
   pub(crate) fn find(
     search_config: &SearchConfig,
+    boundary: SearchBoundary,
     invocation_directory: &Path,
   ) -> SearchResult<Self> {
     match search_config {
       SearchConfig::FromInvocationDirectory => {
-        let justfile = Self::justfile(&invocation_directory)?;
+        let justfile = match Self::justfile(&invocation_directory, boundary) {
+          Err(SearchError::NotFound) =>
+            match Self::global_justfile().filter(|justfile| justfile.is_file()) {
+              Some(justfile) =>
+                return Ok(Self {
+                  justfile,
+                  working_directory: invocation_directory.to_owned(),
+                }),
+              None => return Err(SearchError::NotFound),
+            },
+          result => result?,
+        };
 
         let working_directory = Self::working_directory_from_justfile(&justfile)?;
 
@@ -30,7 +44,7 @@ impl Search {
       SearchConfig::FromSearchDirectory { search_directory } => {
         let search_directory = Self::clean(invocation_directory, search_directory);
 
-        let justfile = Self::justfile(&search_directory)?;
+        let justfile = Self::justfile(&search_directory, boundary)?;
 
         let working_directory = Self::working_directory_from_justfile(&justfile)?;
 
@@ -58,6 +72,22 @@ impl Search {
         justfile:          Self::clean(invocation_directory, justfile),
         working_directory: Self::clean(invocation_directory, working_directory),
       }),
+
+      SearchConfig::FromStdin { working_directory } => Ok(Self {
+        justfile:          PathBuf::from(STDIN),
+        working_directory: Self::working_directory_or_invocation_directory(
+          invocation_directory,
+          working_directory.as_deref(),
+        ),
+      }),
+
+      SearchConfig::GlobalJustfile { working_directory } => Ok(Self {
+        justfile:          Self::global_justfile().ok_or(SearchError::NotFound)?,
+        working_directory: Self::working_directory_or_invocation_directory(
+          invocation_directory,
+          working_directory.as_deref(),
+        ),
+      }),
     }
   }
 
@@ -108,10 +138,67 @@ impl Search {
         justfile:          Self::clean(invocation_directory, justfile),
         working_directory: Self::clean(invocation_directory, working_directory),
       }),
+
+      SearchConfig::FromStdin { .. } => Err(SearchError::Stdin),
+
+      SearchConfig::GlobalJustfile { working_directory } => Ok(Self {
+        justfile:          Self::global_justfile().ok_or(SearchError::NotFound)?,
+        working_directory: Self::working_directory_or_invocation_directory(
+          invocation_directory,
+          working_directory.as_deref(),
+        ),
+      }),
     }
   }
 
-  fn justfile(directory: &Path) -> SearchResult<PathBuf> {
+  /// The path of the user's global justfile, `just/justfile` in
+  /// `$XDG_CONFIG_HOME`, `%APPDATA%` on Windows, or `~/.config`, whether or
+  /// not it exists
+  pub(crate) fn global_justfile() -> Option<PathBuf> {
+    let nonempty = |name| env::var_os(name).filter(|value| !value.is_empty());
+
+    let base = if let Some(config_home) = nonempty("XDG_CONFIG_HOME") {
+      PathBuf::from(config_home)
+    } else if cfg!(windows) {
+      PathBuf::from(nonempty("APPDATA")?)
+    } else {
+      PathBuf::from(nonempty("HOME")?).join(".config")
+    };
+
+    Some(base.join("just").join(FILENAME))
+  }
+
+  fn working_directory_or_invocation_directory(
+    invocation_directory: &Path,
+    working_directory: Option<&Path>,
+  ) -> PathBuf {
+    working_directory
+      .map(|working_directory| Self::clean(invocation_directory, working_directory))
+      .unwrap_or_else(|| invocation_directory.to_owned())
+  }
+
+  /// Whether the justfile is read from standard input, in which case
+  /// `justfile` is not a real path
+  pub(crate) fn is_stdin(&self) -> bool {
+    self.justfile == Path::new(STDIN)
+  }
+
+  /// The path of the local justfile layered over the justfile, the justfile's
+  /// path with `.local` appended, if it exists
+  pub(crate) fn local_justfile(&self) -> Option<PathBuf> {
+    if self.is_stdin() {
+      return None;
+    }
+
+    let mut local = self.justfile.clone().into_os_string();
+    local.push(LOCAL_SUFFIX);
+
+    Some(PathBuf::from(local)).filter(|local| local.is_file())
+  }
+
+  /// Find the justfile in `directory` or its closest ancestor containing one,
+  /// without searching past `boundary`
+  fn justfile(directory: &Path, boundary: SearchBoundary) -> SearchResult<PathBuf> {
     for directory in directory.ancestors() {
       let mut candidates = Vec::new();
 
@@ -136,12 +223,18 @@ impl Search {
       } else if candidates.len() > 1 {
         return Err(SearchError::MultipleCandidates { candidates });
       }
+
+      if boundary.stops_at(directory) {
+        break;
+      }
     }
 
     Err(SearchError::NotFound)
   }
 
-  fn clean(invocation_directory: &Path, path: &Path) -> PathBuf {
+  /// Join `path` to `invocation_directory`, and lexically remove `.` and `..`
+  /// components
+  pub(crate) fn clean(invocation_directory: &Path, path: &Path) -> PathBuf {
     let path = invocation_directory.join(path);
 
     let mut clean = Vec::new();
@@ -156,7 +249,44 @@ impl Search {
       }
     }
 
-    clean.into_iter().collect()
+    Self::strip_verbatim_prefix(clean.into_iter().collect())
+  }
+
+  /// Remove the verbatim prefix from Windows paths like `\\?\C:\foo` and
+  /// `\\?\UNC\server\share\foo`, which are produced by `canonicalize` and
+  /// some shells, but which many tools don't understand. Paths which would be
+  /// too long to use without the prefix are left unchanged.
+  pub(crate) fn strip_verbatim_prefix(path: PathBuf) -> PathBuf {
+    if !cfg!(windows) {
+      return path;
+    }
+
+    match path.to_str().and_then(Self::strip_verbatim_prefix_str) {
+      Some(stripped) => PathBuf::from(stripped),
+      None => path,
+    }
+  }
+
+  fn strip_verbatim_prefix_str(path: &str) -> Option<String> {
+    const MAX_PATH: usize = 260;
+
+    let stripped = if let Some(rest) = path.strip_prefix(r"\\?\UNC\") {
+      format!(r"\\{}", rest)
+    } else if let Some(rest) = path.strip_prefix(r"\\?\") {
+      let mut chars = rest.chars();
+      match (chars.next(), chars.next()) {
+        (Some(letter), Some(':')) if letter.is_ascii_alphabetic() => rest.to_owned(),
+        _ => return None,
+      }
+    } else {
+      return None;
+    };
+
+    if stripped.len() < MAX_PATH {
+      Some(stripped)
+    } else {
+      None
+    }
   }
 
   fn project_root(directory: &Path) -> SearchResult<PathBuf> {
@@ -202,7 +332,7 @@ mod tests {
   #[test]
   fn not_found() {
     let tmp = testing::tempdir();
-    match Search::justfile(tmp.path()) {
+    match Search::justfile(tmp.path(), SearchBoundary::None) {
       Err(SearchError::NotFound) => {
         assert!(true);
       },
@@ -224,7 +354,7 @@ mod tests {
     }
     fs::write(&path, "default:\n\techo ok").unwrap();
     path.pop();
-    match Search::justfile(path.as_path()) {
+    match Search::justfile(path.as_path(), SearchBoundary::None) {
       Err(SearchError::MultipleCandidates { .. }) => {
         assert!(true);
       },
@@ -239,7 +369,7 @@ mod tests {
     path.push(FILENAME);
     fs::write(&path, "default:\n\techo ok").unwrap();
     path.pop();
-    match Search::justfile(path.as_path()) {
+    match Search::justfile(path.as_path(), SearchBoundary::None) {
       Ok(_path) => {
         assert!(true);
       },
@@ -265,7 +395,7 @@ mod tests {
     path.push(spongebob_case);
     fs::write(&path, "default:\n\techo ok").unwrap();
     path.pop();
-    match Search::justfile(path.as_path()) {
+    match Search::justfile(path.as_path(), SearchBoundary::None) {
       Ok(_path) => {
         assert!(true);
       },
@@ -284,7 +414,7 @@ mod tests {
     fs::create_dir(&path).expect("test justfile search: failed to create intermediary directory");
     path.push("b");
     fs::create_dir(&path).expect("test justfile search: failed to create intermediary directory");
-    match Search::justfile(path.as_path()) {
+    match Search::justfile(path.as_path(), SearchBoundary::None) {
       Ok(_path) => {
         assert!(true);
       },
@@ -306,7 +436,7 @@ mod tests {
     path.pop();
     path.push("b");
     fs::create_dir(&path).expect("test justfile search: failed to create intermediary directory");
-    match Search::justfile(path.as_path()) {
+    match Search::justfile(path.as_path(), SearchBoundary::None) {
       Ok(found_path) => {
         path.pop();
         path.push(FILENAME);
@@ -335,12 +465,76 @@ mod tests {
 
     let search_config = SearchConfig::FromInvocationDirectory;
 
-    let search = Search::find(&search_config, &sub).unwrap();
+    let search = Search::find(&search_config, SearchBoundary::None, &sub).unwrap();
 
     assert_eq!(search.justfile, justfile);
     assert_eq!(search.working_directory, sub);
   }
 
+  #[test]
+  fn stdin() {
+    let tmp = tmptree! {
+      sub: {},
+    };
+
+    let search_config = SearchConfig::FromStdin {
+      working_directory: None,
+    };
+
+    let search = Search::find(&search_config, SearchBoundary::None, tmp.path()).unwrap();
+
+    assert!(search.is_stdin());
+    assert_eq!(search.working_directory, tmp.path());
+
+    let search_config = SearchConfig::FromStdin {
+      working_directory: Some(PathBuf::from("sub")),
+    };
+
+    let search = Search::find(&search_config, SearchBoundary::None, tmp.path()).unwrap();
+
+    assert!(search.is_stdin());
+    assert_eq!(search.working_directory, tmp.path().join("sub"));
+
+    assert!(matches!(
+      Search::init(&search_config, tmp.path()),
+      Err(SearchError::Stdin)
+    ));
+
+    assert_eq!(search.local_justfile(), None);
+  }
+
+  #[test]
+  fn local_justfile() {
+    let tmp = tmptree! {
+      justfile: "",
+      sub: {
+        Justfile: "",
+        "Justfile.local": "",
+      },
+    };
+
+    let search = Search::find(
+      &SearchConfig::FromInvocationDirectory,
+      SearchBoundary::None,
+      tmp.path(),
+    )
+    .unwrap();
+
+    assert_eq!(search.local_justfile(), None);
+
+    let search = Search::find(
+      &SearchConfig::FromInvocationDirectory,
+      SearchBoundary::None,
+      &tmp.path().join("sub"),
+    )
+    .unwrap();
+
+    assert_eq!(
+      search.local_justfile(),
+      Some(tmp.path().join("sub").join("Justfile.local"))
+    );
+  }
+
   #[test]
   fn clean() {
     let cases = &[
@@ -362,4 +556,33 @@ mod tests {
       assert_eq!(have, Path::new(want));
     }
   }
+
+  #[test]
+  fn strip_verbatim_prefix() {
+    let long = format!(r"\\?\C:\{}", "a".repeat(300));
+
+    let cases = &[
+      (r"\\?\C:\foo\justfile", Some(r"C:\foo\justfile")),
+      (
+        r"\\?\UNC\server\share\justfile",
+        Some(r"\\server\share\justfile"),
+      ),
+      (r"\\?\Volume{01234567}\justfile", None),
+      (r"C:\foo\justfile", None),
+      (r"\\server\share\justfile", None),
+      (&long, None),
+    ];
+
+    for (path, want) in cases {
+      let have = Search::strip_verbatim_prefix_str(path);
+      assert_eq!(have.as_deref(), *want);
+    }
+  }
+
+  #[test]
+  #[cfg(windows)]
+  fn clean_verbatim() {
+    let have = Search::clean(Path::new(r"\\?\C:\foo"), Path::new(r"bar\justfile"));
+    assert_eq!(have, Path::new(r"C:\foo\bar\justfile"));
+  }
 }
@@ -0,0 +1,77 @@
+use crate::common::*;
+
+/// Where the upward search for a justfile stops, set with
+/// `--search-boundary`, so that a justfile belonging to an enclosing project
+/// isn't used by mistake
+#[derive(Debug, PartialEq, Clone, Copy, EnumString, IntoStaticStr)]
+#[strum(serialize_all = "kebab_case")]
+pub(crate) enum SearchBoundary {
+  /// Search all the way to the root of the filesystem
+  None,
+  /// Stop at the root of a git submodule or linked worktree, which have a
+  /// `.git` file instead of a `.git` directory
+  Worktree,
+  /// Stop at the root of any git repository, submodule, or worktree
+  Repository,
+}
+
+impl SearchBoundary {
+  pub(crate) const VALUES: &'static [&'static str] = &["none", "worktree", "repository"];
+
+  /// Whether the search should stop at `directory`, after looking for a
+  /// justfile in it
+  pub(crate) fn stops_at(self, directory: &Path) -> bool {
+    let git = directory.join(".git");
+
+    match self {
+      Self::None => false,
+      Self::Worktree => git.is_file(),
+      Self::Repository => git.exists(),
+    }
+  }
+}
+
+impl Default for SearchBoundary {
+  fn default() -> Self {
+    Self::None
+  }
+}
+
+impl Display for SearchBoundary {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(f, "{}", <&str>::from(self))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  use test_utilities::tmptree;
+
+  #[test]
+  fn stops_at() {
+    let tmp = tmptree! {
+      repository: {
+        ".git": {},
+      },
+      worktree: {
+        ".git": "gitdir: ../repository/.git/worktrees/worktree",
+      },
+      plain: {},
+    };
+
+    for (boundary, repository, worktree) in &[
+      (SearchBoundary::None, false, false),
+      (SearchBoundary::Worktree, false, true),
+      (SearchBoundary::Repository, true, true),
+    ] {
+      assert_eq!(
+        boundary.stops_at(&tmp.path().join("repository")),
+        *repository
+      );
+      assert_eq!(boundary.stops_at(&tmp.path().join("worktree")), *worktree);
+      assert!(!boundary.stops_at(&tmp.path().join("plain")));
+    }
+  }
+}
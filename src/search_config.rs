@@ -5,7 +5,9 @@ use crate::common::*;
 pub(crate) enum SearchConfig {
   /// Recursively search for the justfile upwards from the invocation directory
   /// to the root, setting the working directory to the directory in which the
-  /// justfile is found.
+  /// justfile is found. If no justfile is found, fall back to the user's global
+  /// justfile, if it exists, with the invocation directory as working
+  /// directory.
   FromInvocationDirectory,
   /// As in `Invocation`, but start from `search_directory`.
   FromSearchDirectory { search_directory: PathBuf },
@@ -17,4 +19,12 @@ pub(crate) enum SearchConfig {
     justfile:          PathBuf,
     working_directory: PathBuf,
   },
+  /// Read the justfile from standard input, with the working directory set to
+  /// the user-specified working directory, if any, or else the invocation
+  /// directory.
+  FromStdin { working_directory: Option<PathBuf> },
+  /// Use the user's global justfile, with the working directory set to the
+  /// user-specified working directory, if any, or else the invocation
+  /// directory.
+  GlobalJustfile { working_directory: Option<PathBuf> },
 }
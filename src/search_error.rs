@@ -26,6 +26,8 @@ pub(crate) enum SearchError {
   NotFound,
   #[snafu(display("Justfile path had no parent: {}", path.display()))]
   JustfileHadNoParent { path: PathBuf },
+  #[snafu(display("Cannot create a justfile on standard input"))]
+  Stdin,
 }
 
 impl Error for SearchError {}
@@ -11,3 +11,9 @@ impl<'src> Keyed<'src> for Set<'src> {
     self.name.lexeme()
   }
 }
+
+impl<'src> Display for Set<'src> {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(f, "set {} := {}", self.name, self.value)
+  }
+}
@@ -2,9 +2,30 @@ use crate::common::*;
 
 #[derive(Debug)]
 pub(crate) enum Setting<'src> {
+  AllowDownloads(bool),
+  BacktickEnv(BacktickEnv),
+  /// A boolean setting whose value is an expression other than `true` or
+  /// `false`, which is evaluated when the justfile is run
+  BoolExpression(Expression<'src>),
+  Default(StringLiteral<'src>),
+  EchoTruncate(usize),
   Shell(Shell<'src>),
+  ExitMessage(ExitMessage),
+  ExitMessageTemplate(StringLiteral<'src>),
   Export(bool),
+  DotenvFiles(Vec<StringLiteral<'src>>),
   DotenvLoad(bool),
+  ListHeading(StringLiteral<'src>),
+  ListPrefix(StringLiteral<'src>),
+  MetadataSideEffects(bool),
+  NoExitMessage(bool),
+  Pager(bool),
+  StrictInterpolation(bool),
+  Tempdir(StringLiteral<'src>),
+  Timestamps(bool),
+  UnknownRecipe(UnknownRecipe),
+  WindowsPowershell(bool),
+  WindowsShell(Shell<'src>),
 }
 
 #[derive(Debug, PartialEq)]
@@ -12,3 +33,49 @@ pub(crate) struct Shell<'src> {
   pub(crate) command:   StringLiteral<'src>,
   pub(crate) arguments: Vec<StringLiteral<'src>>,
 }
+
+impl<'src> Display for Setting<'src> {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    match self {
+      Setting::BacktickEnv(value) => write!(f, "\"{}\"", value),
+      Setting::ExitMessage(value) => write!(f, "\"{}\"", value),
+      Setting::AllowDownloads(value)
+      | Setting::DotenvLoad(value)
+      | Setting::Export(value)
+      | Setting::MetadataSideEffects(value)
+      | Setting::NoExitMessage(value)
+      | Setting::Pager(value)
+      | Setting::StrictInterpolation(value)
+      | Setting::Timestamps(value)
+      | Setting::WindowsPowershell(value) => write!(f, "{}", value),
+      Setting::BoolExpression(expression) => write!(f, "{}", expression),
+      Setting::EchoTruncate(value) => write!(f, "{}", value),
+      Setting::DotenvFiles(files) => {
+        let files = files
+          .iter()
+          .map(ToString::to_string)
+          .collect::<Vec<String>>();
+        write!(f, "[{}]", files.join(", "))
+      },
+      Setting::Default(value)
+      | Setting::ExitMessageTemplate(value)
+      | Setting::ListHeading(value)
+      | Setting::ListPrefix(value)
+      | Setting::Tempdir(value) => write!(f, "{}", value),
+      Setting::Shell(shell) | Setting::WindowsShell(shell) => write!(f, "{}", shell),
+      Setting::UnknownRecipe(value) => write!(f, "\"{}\"", value),
+    }
+  }
+}
+
+impl<'src> Display for Shell<'src> {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(f, "[{}", self.command)?;
+
+    for argument in &self.arguments {
+      write!(f, ", {}", argument)?;
+    }
+
+    write!(f, "]")
+  }
+}
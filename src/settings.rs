@@ -1,21 +1,98 @@
 use crate::common::*;
 
+const WINDOWS_POWERSHELL_SHELL: &str = "powershell.exe";
+const WINDOWS_POWERSHELL_ARGS: &[&str] = &["-NoLogo", "-Command"];
+
 #[derive(Debug, PartialEq)]
 pub(crate) struct Settings<'src> {
-  pub(crate) dotenv_load: Option<bool>,
-  pub(crate) export:      bool,
-  pub(crate) shell:       Option<setting::Shell<'src>>,
+  pub(crate) allow_downloads:       bool,
+  pub(crate) backtick_env:          BacktickEnv,
+  /// The recipe run when no recipes are given on the command line, instead
+  /// of the first recipe
+  pub(crate) default:               Option<String>,
+  pub(crate) dotenv_files:          Option<Vec<StringLiteral<'src>>>,
+  pub(crate) dotenv_load:           Option<bool>,
+  /// Echoed recipe lines longer than this many characters are truncated,
+  /// unless it is zero
+  pub(crate) echo_truncate:         Option<usize>,
+  pub(crate) exit_message:          ExitMessage,
+  /// Printed instead of the usual message when a recipe fails with an exit
+  /// code, after expanding `{recipe}`, `{line}`, and `{code}`
+  pub(crate) exit_message_template: Option<String>,
+  pub(crate) export:                bool,
+  /// Boolean settings whose values are expressions, which are evaluated by
+  /// `Justfile::evaluate_settings`
+  pub(crate) expressions:           Vec<(Name<'src>, Expression<'src>)>,
+  pub(crate) list_heading:          Option<String>,
+  pub(crate) list_prefix:           Option<String>,
+  pub(crate) metadata_side_effects: bool,
+  pub(crate) no_exit_message:       bool,
+  pub(crate) pager:                 bool,
+  pub(crate) shell:                 Option<setting::Shell<'src>>,
+  pub(crate) strict_interpolation:  bool,
+  pub(crate) tempdir:               Option<String>,
+  pub(crate) timestamps:            bool,
+  pub(crate) unknown_recipe:        UnknownRecipe,
+  pub(crate) windows_powershell:    bool,
+  pub(crate) windows_shell:         Option<setting::Shell<'src>>,
 }
 
 impl<'src> Settings<'src> {
   pub(crate) fn new() -> Settings<'src> {
     Settings {
-      dotenv_load: None,
-      export:      false,
-      shell:       None,
+      allow_downloads:       false,
+      backtick_env:          BacktickEnv::default(),
+      default:               None,
+      dotenv_files:          None,
+      dotenv_load:           None,
+      echo_truncate:         None,
+      exit_message:          ExitMessage::default(),
+      exit_message_template: None,
+      export:                false,
+      expressions:           Vec::new(),
+      list_heading:          None,
+      list_prefix:           None,
+      metadata_side_effects: true,
+      no_exit_message:       false,
+      pager:                 false,
+      shell:                 None,
+      strict_interpolation:  false,
+      tempdir:               None,
+      timestamps:            false,
+      unknown_recipe:        UnknownRecipe::default(),
+      windows_powershell:    false,
+      windows_shell:         None,
+    }
+  }
+
+  /// Set the boolean setting `name` to `value`
+  pub(crate) fn set_bool(&mut self, name: &str, value: bool) {
+    match Keyword::from_lexeme(name) {
+      Some(Keyword::AllowDownloads) => self.allow_downloads = value,
+      Some(Keyword::DotenvLoad) => self.dotenv_load = Some(value),
+      Some(Keyword::Export) => self.export = value,
+      Some(Keyword::MetadataSideEffects) => self.metadata_side_effects = value,
+      Some(Keyword::NoExitMessage) => self.no_exit_message = value,
+      Some(Keyword::Pager) => self.pager = value,
+      Some(Keyword::StrictInterpolation) => self.strict_interpolation = value,
+      Some(Keyword::Timestamps) => self.timestamps = value,
+      Some(Keyword::WindowsPowershell) => self.windows_powershell = value,
+      _ => {},
     }
   }
 
+  /// Whether running `config`'s subcommand may load environment files and
+  /// evaluate backticks. This is always true, unless the subcommand only
+  /// prints information about the justfile and the `metadata-side-effects`
+  /// setting is false.
+  pub(crate) fn side_effects(&self, config: &Config) -> bool {
+    self.metadata_side_effects
+      || !matches!(
+        config.subcommand,
+        Subcommand::Evaluate { .. } | Subcommand::List { .. }
+      )
+  }
+
   pub(crate) fn shell_command(&self, config: &Config) -> Command {
     let mut cmd = Command::new(self.shell_binary(config));
 
@@ -25,22 +102,108 @@ impl<'src> Settings<'src> {
   }
 
   pub(crate) fn shell_binary<'a>(&'a self, config: &'a Config) -> &'a str {
-    if let (Some(shell), false) = (&self.shell, config.shell_present) {
-      shell.command.cooked.as_ref()
-    } else {
-      &config.shell
+    match (self.platform_shell(cfg!(windows)), config.shell_present) {
+      (Some((binary, _)), false) => binary,
+      _ => &config.shell,
     }
   }
 
   pub(crate) fn shell_arguments<'a>(&'a self, config: &'a Config) -> Vec<&'a str> {
-    if let (Some(shell), false) = (&self.shell, config.shell_present) {
-      shell
-        .arguments
-        .iter()
-        .map(|argument| argument.cooked.as_ref())
-        .collect()
-    } else {
-      config.shell_args.iter().map(String::as_ref).collect()
+    match (self.platform_shell(cfg!(windows)), config.shell_present) {
+      (Some((_, arguments)), false) => arguments,
+      _ => config.shell_args.iter().map(String::as_ref).collect(),
     }
   }
+
+  /// The shell binary and arguments set by the justfile. On Windows, this is
+  /// `windows-shell` if it's set, followed by PowerShell if
+  /// `windows-powershell` is set, followed by `shell`. Elsewhere, the
+  /// Windows-only settings are ignored.
+  fn platform_shell<'a>(&'a self, windows: bool) -> Option<(&'a str, Vec<&'a str>)> {
+    let cooked = |shell: &'a setting::Shell<'src>| {
+      (
+        shell.command.cooked.as_str(),
+        shell
+          .arguments
+          .iter()
+          .map(|argument| argument.cooked.as_str())
+          .collect(),
+      )
+    };
+
+    if windows {
+      if let Some(shell) = &self.windows_shell {
+        return Some(cooked(shell));
+      }
+
+      if self.windows_powershell {
+        return Some((WINDOWS_POWERSHELL_SHELL, WINDOWS_POWERSHELL_ARGS.to_vec()));
+      }
+    }
+
+    self.shell.as_ref().map(cooked)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  use crate::testing::compile;
+
+  fn platform_shell(text: &str, windows: bool) -> Option<(String, Vec<String>)> {
+    compile(&unindent(text))
+      .settings
+      .platform_shell(windows)
+      .map(|(binary, arguments)| {
+        (
+          binary.to_owned(),
+          arguments.into_iter().map(str::to_owned).collect(),
+        )
+      })
+  }
+
+  #[test]
+  fn platform_shell_default() {
+    assert_eq!(platform_shell("", false), None);
+    assert_eq!(platform_shell("", true), None);
+  }
+
+  #[test]
+  fn platform_shell_windows_shell() {
+    let text = "
+      set shell := ['bash', '-c']
+      set windows-powershell
+      set windows-shell := ['cmd.exe', '/C']
+    ";
+
+    assert_eq!(
+      platform_shell(text, false),
+      Some(("bash".into(), vec!["-c".into()]))
+    );
+    assert_eq!(
+      platform_shell(text, true),
+      Some(("cmd.exe".into(), vec!["/C".into()]))
+    );
+  }
+
+  #[test]
+  fn platform_shell_windows_powershell() {
+    let text = "
+      set shell := ['bash', '-c']
+      set windows-powershell
+    ";
+
+    assert_eq!(
+      platform_shell(text, false),
+      Some(("bash".into(), vec!["-c".into()]))
+    );
+    assert_eq!(
+      platform_shell(text, true),
+      Some(("powershell.exe".into(), vec![
+        "-NoLogo".into(),
+        "-Command".into()
+      ]))
+    );
+  }
 }
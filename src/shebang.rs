@@ -28,6 +28,47 @@ impl<'line> Shebang<'line> {
       argument,
     })
   }
+
+  /// Whether the argument should be split into multiple arguments, as with
+  /// `#!/usr/bin/env -S python -u`. Most kernels pass everything after the
+  /// interpreter as a single argument, so just performs the split itself.
+  pub(crate) fn is_split(&self) -> bool {
+    self
+      .argument
+      .map(|argument| argument.starts_with("-S"))
+      .unwrap_or(false)
+  }
+
+  /// The arguments to pass to the interpreter, before the path to the script
+  pub(crate) fn arguments(&self) -> Vec<&'line str> {
+    match self.argument {
+      Some(argument) if self.is_split() => argument[2..].split_whitespace().collect(),
+      Some(argument) => vec![argument],
+      None => Vec::new(),
+    }
+  }
+
+  /// If the interpreter is `/usr/bin/env`, the program that `env` would look
+  /// up on `PATH`, followed by its arguments. These can be run directly,
+  /// without translating the path to `env`. Only needed on Windows.
+  #[cfg_attr(not(windows), allow(dead_code))]
+  pub(crate) fn env_command(&self) -> Option<Vec<&'line str>> {
+    if self.interpreter != "/usr/bin/env" {
+      return None;
+    }
+
+    let words = match self.argument {
+      Some(argument) if self.is_split() => argument[2..].split_whitespace().collect(),
+      Some(argument) => argument.split_whitespace().collect::<Vec<&str>>(),
+      None => return None,
+    };
+
+    if words.is_empty() {
+      None
+    } else {
+      Some(words)
+    }
+  }
 }
 
 #[cfg(test)]
@@ -93,4 +134,40 @@ mod tests {
     );
     check("#  /usr/bin/env python \t-x\t", None);
   }
+
+  #[test]
+  fn arguments() {
+    fn check(text: &str, split: bool, arguments: &[&str]) {
+      let shebang = Shebang::new(text).unwrap();
+      assert_eq!(shebang.is_split(), split);
+      assert_eq!(shebang.arguments(), arguments);
+    }
+
+    check("#!/bin/bash", false, &[]);
+    check("#!/usr/bin/env python", false, &["python"]);
+    check("#!/usr/bin/env python -x -u", false, &["python -x -u"]);
+    check("#!/usr/bin/env -S python", true, &["python"]);
+    check("#!/usr/bin/env -S python -x -u", true, &[
+      "python", "-x", "-u",
+    ]);
+    check("#!/usr/bin/env -S  python \t-x", true, &["python", "-x"]);
+    check("#!/usr/bin/env -Spython -x", true, &["python", "-x"]);
+    check("#!/usr/bin/env -S", true, &[]);
+  }
+
+  #[test]
+  fn env_command() {
+    fn check(text: &str, command: Option<&[&str]>) {
+      let shebang = Shebang::new(text).unwrap();
+      assert_eq!(shebang.env_command().as_deref(), command);
+    }
+
+    check("#!/bin/bash", None);
+    check("#!/bin/bash -x", None);
+    check("#!/usr/bin/env", None);
+    check("#!/usr/bin/env -S", None);
+    check("#!/usr/bin/env python", Some(&["python"]));
+    check("#!/usr/bin/env python -x -u", Some(&["python", "-x", "-u"]));
+    check("#!/usr/bin/env -S python -x", Some(&["python", "-x"]));
+  }
 }
@@ -0,0 +1,97 @@
+use crate::common::*;
+
+/// The order in which `--list`, `--summary`, `--variables`, `--evaluate`,
+/// `--graph`, and `--dump --format` print recipes and variables. Both orders
+/// depend only on the justfile, and not on how its items are stored, so output
+/// is stable across versions of just.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(crate) enum SortOrder {
+  /// Lexicographic order of names, compared byte by byte. The default.
+  Name,
+  /// The order in which items appear in the justfile, selected with
+  /// `--unsorted`
+  Source,
+}
+
+impl SortOrder {
+  pub(crate) fn from_unsorted(unsorted: bool) -> Self {
+    if unsorted {
+      Self::Source
+    } else {
+      Self::Name
+    }
+  }
+
+  /// Sort `items` in this order. Items that appear at the same offset, which
+  /// can only happen if they come from different files, are sorted by name.
+  pub(crate) fn sort<T: Sortable>(self, items: &mut [T]) {
+    match self {
+      Self::Name => items.sort_by(|a, b| a.sort_name().cmp(b.sort_name())),
+      Self::Source => items.sort_by(|a, b| {
+        a.sort_offset()
+          .cmp(&b.sort_offset())
+          .then_with(|| a.sort_name().cmp(b.sort_name()))
+      }),
+    }
+  }
+
+  /// Collect `items` into a vector, sorted in this order
+  pub(crate) fn sorted<T: Sortable>(self, items: impl IntoIterator<Item = T>) -> Vec<T> {
+    let mut items = items.into_iter().collect::<Vec<T>>();
+    self.sort(&mut items);
+    items
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  struct Item(&'static str, usize);
+
+  impl Sortable for Item {
+    fn sort_name(&self) -> &str {
+      self.0
+    }
+
+    fn sort_offset(&self) -> usize {
+      self.1
+    }
+  }
+
+  fn names(order: SortOrder, items: Vec<Item>) -> Vec<&'static str> {
+    order.sorted(items).into_iter().map(|item| item.0).collect()
+  }
+
+  #[test]
+  fn name() {
+    assert_eq!(
+      names(SortOrder::Name, vec![
+        Item("b", 0),
+        Item("a", 2),
+        Item("B", 3),
+        Item("a-b", 1)
+      ]),
+      &["B", "a", "a-b", "b"]
+    );
+  }
+
+  #[test]
+  fn source() {
+    assert_eq!(
+      names(SortOrder::Source, vec![
+        Item("b", 0),
+        Item("a", 2),
+        Item("d", 1),
+        Item("c", 1)
+      ]),
+      &["b", "c", "d", "a"]
+    );
+  }
+
+  #[test]
+  fn from_unsorted() {
+    assert_eq!(SortOrder::from_unsorted(false), SortOrder::Name);
+    assert_eq!(SortOrder::from_unsorted(true), SortOrder::Source);
+  }
+}
@@ -0,0 +1,59 @@
+use crate::common::*;
+
+/// An item that listing subcommands print, which can be sorted by its name or
+/// by its position in the justfile
+pub(crate) trait Sortable {
+  fn sort_name(&self) -> &str;
+
+  fn sort_offset(&self) -> usize;
+}
+
+impl<T: Sortable + ?Sized> Sortable for &T {
+  fn sort_name(&self) -> &str {
+    (**self).sort_name()
+  }
+
+  fn sort_offset(&self) -> usize {
+    (**self).sort_offset()
+  }
+}
+
+impl<V> Sortable for Binding<'_, V> {
+  fn sort_name(&self) -> &str {
+    self.name.lexeme()
+  }
+
+  fn sort_offset(&self) -> usize {
+    self.name.offset
+  }
+}
+
+impl Sortable for Alias<'_> {
+  fn sort_name(&self) -> &str {
+    self.name.lexeme()
+  }
+
+  fn sort_offset(&self) -> usize {
+    self.name.offset
+  }
+}
+
+impl<D> Sortable for Recipe<'_, D> {
+  fn sort_name(&self) -> &str {
+    self.name.lexeme()
+  }
+
+  fn sort_offset(&self) -> usize {
+    self.name.offset
+  }
+}
+
+impl Sortable for RecipeMetadata {
+  fn sort_name(&self) -> &str {
+    &self.name
+  }
+
+  fn sort_offset(&self) -> usize {
+    self.offset
+  }
+}
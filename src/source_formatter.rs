@@ -0,0 +1,371 @@
+use crate::common::*;
+
+/// Produces a canonical rendering of a justfile's source, used by `--fmt`.
+///
+/// Unlike `--dump`, which renders the analyzed `Justfile` and so loses
+/// comments and source order, the source formatter works from the parsed
+/// `Module` and the token stream, preserving items in their original order,
+/// standalone and trailing comments, and the blank lines separating items.
+/// Runs of consecutive lines with trailing comments have those comments
+/// aligned.
+pub(crate) struct SourceFormatter<'src> {
+  /// Lines of the original source, used to detect blank lines between items
+  lines:    Vec<&'src str>,
+  /// Comments that trail an item on the same line, keyed by line number
+  trailing: BTreeMap<usize, &'src str>,
+  /// Output lines, each with an optional trailing comment
+  output:   Vec<(String, Option<&'src str>)>,
+}
+
+/// A single top-level entry in the source, either an item or a comment on a
+/// line of its own
+enum Entry<'module, 'src> {
+  Comment(Token<'src>),
+  Item(&'module Item<'src>),
+}
+
+impl<'src> SourceFormatter<'src> {
+  pub(crate) fn format(src: &'src str) -> CompilationResult<'src, String> {
+    let tokens = Lexer::lex(src)?;
+    let module = Parser::parse(&tokens)?;
+
+    let mut formatter = SourceFormatter {
+      lines:    src.lines().collect(),
+      trailing: BTreeMap::new(),
+      output:   Vec::new(),
+    };
+
+    let mut entries = Vec::new();
+
+    for (i, token) in tokens.iter().enumerate() {
+      if token.kind != TokenKind::Comment {
+        continue;
+      }
+
+      let previous = tokens[..i]
+        .iter()
+        .rev()
+        .find(|token| token.kind != TokenKind::Whitespace);
+
+      match previous {
+        Some(previous)
+          if previous.line == token.line
+            && !matches!(
+              previous.kind,
+              TokenKind::Eol | TokenKind::Indent | TokenKind::Dedent
+            ) =>
+        {
+          formatter
+            .trailing
+            .insert(token.line, token.lexeme().trim_end());
+        },
+        _ => entries.push((token.line, Entry::Comment(*token))),
+      }
+    }
+
+    for item in &module.items {
+      let line = match item {
+        // Attributes are rendered by the alias or recipe itself, so it takes
+        // the place of its attributes
+        Item::Alias(alias) => formatter.attributes_start(alias.name.line, &alias.attributes),
+        Item::Assignment(assignment) => assignment.name.line,
+        // Doc comments are also rendered by the recipe, so the recipe takes
+        // the place of its doc comment
+        Item::Recipe(recipe) => {
+          let mut line = formatter.attributes_start(recipe.name.line, &recipe.attributes);
+          if recipe.doc.is_some() {
+            line -= 1;
+            entries.retain(|(comment, _)| *comment != line);
+          }
+          line
+        },
+        Item::Set(set) => set.name.line,
+      };
+
+      entries.push((line, Entry::Item(item)));
+    }
+
+    entries.sort_by_key(|(line, _)| *line);
+
+    for (i, (line, entry)) in entries.iter().enumerate() {
+      if i > 0 && formatter.preceded_by_blank_line(*line) {
+        formatter.output.push((String::new(), None));
+      }
+
+      match entry {
+        Entry::Comment(token) => formatter.push(token.lexeme().trim_end(), None),
+        Entry::Item(item) => formatter.item(item),
+      }
+    }
+
+    Ok(formatter.render())
+  }
+
+  /// The first line of the attributes preceding an item on `line`
+  fn attributes_start(&self, mut line: usize, attributes: &BTreeSet<Attribute>) -> usize {
+    if !attributes.is_empty() {
+      while line > 0 && self.lines[line - 1].trim_start().starts_with('[') {
+        line -= 1;
+      }
+    }
+    line
+  }
+
+  fn preceded_by_blank_line(&self, line: usize) -> bool {
+    line > 0
+      && self
+        .lines
+        .get(line - 1)
+        .map(|text| text.trim().is_empty())
+        .unwrap_or(false)
+  }
+
+  fn item(&mut self, item: &Item<'src>) {
+    let line = match item {
+      Item::Alias(alias) => alias.name.line,
+      Item::Assignment(assignment) => assignment.name.line,
+      Item::Recipe(recipe) => recipe.name.line,
+      Item::Set(set) => set.name.line,
+    };
+
+    let trailing = self.trailing.get(&line).cloned();
+
+    match item {
+      Item::Alias(alias) => {
+        if !alias.attributes.is_empty() {
+          self.output.push((Attribute::list(&alias.attributes), None));
+        }
+        let arguments = alias
+          .arguments
+          .iter()
+          .map(|argument| format!(" {}", argument))
+          .collect::<String>();
+        self.push(
+          &format!("alias {} := {}{}", alias.name, alias.target, arguments),
+          trailing,
+        );
+      },
+      Item::Assignment(assignment) => {
+        let export = if assignment.export { "export " } else { "" };
+        self.push(
+          &format!("{}{} := {}", export, assignment.name, assignment.value),
+          trailing,
+        );
+      },
+      Item::Recipe(recipe) => {
+        let header = usize::from(recipe.doc.is_some()) + usize::from(!recipe.attributes.is_empty());
+        for (i, text) in recipe.to_string().lines().enumerate() {
+          if i == header {
+            self.push(text, trailing);
+          } else {
+            self.output.push((text.to_owned(), None));
+          }
+        }
+      },
+      Item::Set(set) => self.push(&set.to_string(), trailing),
+    }
+  }
+
+  fn push(&mut self, text: &str, trailing: Option<&'src str>) {
+    self.output.push((text.trim_end().to_owned(), trailing));
+  }
+
+  fn render(&self) -> String {
+    let mut rendered = String::new();
+
+    let mut i = 0;
+    while i < self.output.len() {
+      let run = self.output[i..]
+        .iter()
+        .take_while(|(_, trailing)| trailing.is_some())
+        .count();
+
+      if run == 0 {
+        rendered.push_str(&self.output[i].0);
+        rendered.push('\n');
+        i += 1;
+        continue;
+      }
+
+      let width = self.output[i..i + run]
+        .iter()
+        .map(|(text, _)| UnicodeWidthStr::width(text.as_str()))
+        .max()
+        .unwrap_or(0);
+
+      for (text, trailing) in &self.output[i..i + run] {
+        let padding = width - UnicodeWidthStr::width(text.as_str());
+        rendered.push_str(text);
+        rendered.push_str(&" ".repeat(padding + 1));
+        rendered.push_str(trailing.unwrap_or_default());
+        rendered.push('\n');
+      }
+
+      i += run;
+    }
+
+    rendered
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  use pretty_assertions::assert_eq;
+
+  macro_rules! test {
+    {
+      name:     $name:ident,
+      src:      $src:expr,
+      expected: $expected:expr,
+    } => {
+      #[test]
+      fn $name() {
+        let src = $src;
+        let formatted = SourceFormatter::format(src).expect("format failed");
+        assert_eq!(formatted, $expected);
+        let reformatted = SourceFormatter::format(&formatted).expect("reformat failed");
+        assert_eq!(reformatted, formatted, "formatting is not idempotent");
+      }
+    }
+  }
+
+  test! {
+    name:     empty,
+    src:      "",
+    expected: "",
+  }
+
+  test! {
+    name:     assignment_whitespace,
+    src:      "foo:=  'bar'   +   baz\nbaz:='x'",
+    expected: "foo := 'bar' + baz\nbaz := 'x'\n",
+  }
+
+  test! {
+    name:     export,
+    src:      "export   foo:='bar'",
+    expected: "export foo := 'bar'\n",
+  }
+
+  test! {
+    name:     dependency_forward,
+    src:      "build a b:   (prepare   'x'   * )\nprepare x a b:",
+    expected: "build a b: (prepare 'x' *)\nprepare x a b:\n",
+  }
+
+  test! {
+    name:     alias,
+    src:      "alias   f:=foo\nfoo:",
+    expected: "alias f := foo\nfoo:\n",
+  }
+
+  test! {
+    name:     set,
+    src:      "set   export\nset shell:=['bash','-c']",
+    expected: "set export := true\nset shell := ['bash', '-c']\n",
+  }
+
+  test! {
+    name:     set_backtick_env,
+    src:      "set backtick-env:='none'",
+    expected: "set backtick-env := \"none\"\n",
+  }
+
+  test! {
+    name:     set_unknown_recipe,
+    src:      "set unknown-recipe:='default'",
+    expected: "set unknown-recipe := \"default\"\n",
+  }
+
+  test! {
+    name:     set_dotenv_files,
+    src:      "set dotenv-files:=[ '.env',\n'.env.local', ]",
+    expected: "set dotenv-files := ['.env', '.env.local']\n",
+  }
+
+  test! {
+    name:     recipe,
+    src:      "foo   a  b='x' +c :   bar  (baz  'x')\n\techo {{ a }}\n\n\techo {{b}}",
+    expected: "foo a b='x' +c: bar (baz 'x')\n    echo {{a}}\n\n    echo {{b}}\n",
+  }
+
+  test! {
+    name:     export_parameters,
+    src:      "foo $a  *$b='x':",
+    expected: "foo $a *$b='x':\n",
+  }
+
+  test! {
+    name:     quiet_recipe,
+    src:      "@foo:\n  echo",
+    expected: "@foo:\n    echo\n",
+  }
+
+  test! {
+    name:     standalone_comments,
+    src:      "# foo\n\n# bar   \nx := 'y'",
+    expected: "# foo\n\n# bar\nx := 'y'\n",
+  }
+
+  test! {
+    name:     doc_comment,
+    src:      "#   some docs  \nfoo:\n  echo",
+    expected: "# some docs\nfoo:\n    echo\n",
+  }
+
+  test! {
+    name:     doc_comment_after_blank_line,
+    src:      "x := 'y'\n\n# some docs\nfoo:",
+    expected: "x := 'y'\n\n# some docs\nfoo:\n",
+  }
+
+  test! {
+    name:     blank_lines_collapsed,
+    src:      "a := 'a'\n\n\n\nb := 'b'\nfoo:\n  echo\n\n\n\nbar:",
+    expected: "a := 'a'\n\nb := 'b'\nfoo:\n    echo\n\nbar:\n",
+  }
+
+  test! {
+    name:     trailing_comments_aligned,
+    src:      "a := 'a' # first\nfoo := 'foo'   # second\n\nb := 'b'    # third",
+    expected: "a := 'a'     # first\nfoo := 'foo' # second\n\nb := 'b' # third\n",
+  }
+
+  test! {
+    name:     recipe_trailing_comment,
+    src:      "# doc\nfoo:  # comment\n  echo",
+    expected: "# doc\nfoo: # comment\n    echo\n",
+  }
+
+  test! {
+    name:     attributes,
+    src:      "x := 'y'\n\n# doc\n[ remember-args ]\n@foo a:  # comment\n  echo",
+    expected: "x := 'y'\n\n# doc\n[remember-args]\n@foo a: # comment\n    echo\n",
+  }
+
+  test! {
+    name:     doc_attribute,
+    src:      "# comment\n[remember-args,doc( \"some\\tdocs\" )]\nfoo:",
+    expected: "# comment\n[doc(\"some\\tdocs\"), remember-args]\nfoo:\n",
+  }
+
+  test! {
+    name:     private_alias,
+    src:      "[ private ]\nalias   f:=foo # comment\n[private]\nfoo:",
+    expected: "[private]\nalias f := foo # comment\n[private]\nfoo:\n",
+  }
+
+  test! {
+    name:     confirm_attribute,
+    src:      "[confirm]\nfoo:\n[confirm('Really?')]\nbar:",
+    expected: "[confirm]\nfoo:\n[confirm('Really?')]\nbar:\n",
+  }
+
+  test! {
+    name:     conditional,
+    src:      "x := if  'a'=='b' {'c'} else {'d'}  # comment",
+    expected: "x := if 'a' == 'b' { 'c' } else { 'd' } # comment\n",
+  }
+}
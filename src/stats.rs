@@ -0,0 +1,168 @@
+use crate::common::*;
+
+use std::{
+  fs::{File, OpenOptions},
+  io::BufRead,
+};
+
+/// Local recipe invocation statistics, recorded when a statistics file is
+/// given with `--stats-file` or `$JUST_STATS_FILE`, and printed by `--stats`.
+///
+/// Each successful recipe run appends a line to the statistics file
+/// containing the run's duration in milliseconds, the recipe name, and the
+/// canonical path of the justfile, separated by tabs. A single file may be
+/// shared between justfiles.
+#[derive(Debug, PartialEq)]
+pub(crate) struct Stats {
+  /// Run count and total duration in milliseconds, keyed by recipe name
+  recipes: BTreeMap<String, (u64, u64)>,
+}
+
+impl Stats {
+  /// Record a successful run of `recipe` in `justfile`. Failure to record
+  /// statistics is not fatal, so errors are logged and otherwise ignored.
+  pub(crate) fn record(path: &Path, justfile: &Path, recipe: &str, duration: Duration) {
+    let justfile = fs::canonicalize(justfile).unwrap_or_else(|_| justfile.to_owned());
+
+    let result = OpenOptions::new()
+      .create(true)
+      .append(true)
+      .open(path)
+      .and_then(|mut file| {
+        writeln!(
+          file,
+          "{}\t{}\t{}",
+          duration.as_millis(),
+          recipe,
+          justfile.display()
+        )
+      });
+
+    if let Err(io_error) = result {
+      warn!(
+        "Failed to record statistics in `{}`: {}",
+        path.display(),
+        io_error
+      );
+    }
+  }
+
+  /// Load statistics for recipes in `justfile` from `path`, ignoring
+  /// malformed lines
+  pub(crate) fn load(path: &Path, justfile: &Path) -> io::Result<Self> {
+    let justfile = fs::canonicalize(justfile).unwrap_or_else(|_| justfile.to_owned());
+    let justfile = justfile.display().to_string();
+
+    let mut recipes: BTreeMap<String, (u64, u64)> = BTreeMap::new();
+
+    for line in io::BufReader::new(File::open(path)?).lines() {
+      let line = line?;
+
+      let mut fields = line.splitn(3, '\t');
+
+      let (duration, recipe) = match (fields.next(), fields.next(), fields.next()) {
+        (Some(duration), Some(recipe), Some(path)) if path == justfile => (duration, recipe),
+        _ => continue,
+      };
+
+      let duration = match duration.parse::<u64>() {
+        Ok(duration) => duration,
+        Err(_) => continue,
+      };
+
+      let (runs, total) = recipes.entry(recipe.to_owned()).or_insert((0, 0));
+      *runs += 1;
+      *total = total.saturating_add(duration);
+    }
+
+    Ok(Self { recipes })
+  }
+
+  /// Recipes with their run count and average duration, most frequently run
+  /// first
+  fn ranked(&self) -> Vec<(&str, u64, Duration)> {
+    let mut ranked = self
+      .recipes
+      .iter()
+      .map(|(recipe, (runs, total))| (recipe.as_str(), *runs, Duration::from_millis(total / runs)))
+      .collect::<Vec<(&str, u64, Duration)>>();
+
+    ranked.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+
+    ranked
+  }
+}
+
+impl Display for Stats {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    let ranked = self.ranked();
+
+    let width = ranked
+      .iter()
+      .map(|(recipe, ..)| UnicodeWidthStr::width(*recipe))
+      .max()
+      .unwrap_or(0)
+      .max("Recipe".len());
+
+    writeln!(
+      f,
+      "{:width$} {:>6} {:>9}",
+      "Recipe",
+      "Runs",
+      "Average",
+      width = width
+    )?;
+
+    for (recipe, runs, average) in ranked {
+      let padding = width - UnicodeWidthStr::width(recipe);
+      writeln!(
+        f,
+        "{}{:padding$} {:>6} {:>8.3}s",
+        recipe,
+        "",
+        runs,
+        average.as_secs_f64(),
+        padding = padding
+      )?;
+    }
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  use pretty_assertions::assert_eq;
+
+  #[test]
+  fn record_and_load() {
+    let tempdir = tempfile::tempdir().unwrap();
+
+    let path = tempdir.path().join("stats");
+    let foo = tempdir.path().join("foo");
+    let bar = tempdir.path().join("bar");
+
+    Stats::record(&path, &foo, "build", Duration::from_millis(1000));
+    Stats::record(&path, &foo, "build", Duration::from_millis(2000));
+    Stats::record(&path, &foo, "test", Duration::from_millis(500));
+    Stats::record(&path, &bar, "build", Duration::from_millis(10));
+
+    let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+    writeln!(file, "garbage").unwrap();
+    writeln!(file, "x\tbuild\t{}", foo.display()).unwrap();
+
+    let stats = Stats::load(&path, &foo).unwrap();
+
+    assert_eq!(stats.ranked(), &[
+      ("build", 2, Duration::from_millis(1500)),
+      ("test", 1, Duration::from_millis(500)),
+    ]);
+
+    assert_eq!(
+      stats.to_string(),
+      "Recipe   Runs   Average\nbuild       2    1.500s\ntest        1    0.500s\n"
+    );
+  }
+}
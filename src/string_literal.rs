@@ -1,6 +1,6 @@
 use crate::common::*;
 
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Debug, Eq, Ord, PartialOrd)]
 pub(crate) struct StringLiteral<'src> {
   pub(crate) kind:   StringKind,
   pub(crate) raw:    &'src str,
@@ -2,39 +2,80 @@ use crate::common::*;
 
 #[derive(PartialEq, Clone, Debug)]
 pub(crate) enum Subcommand {
+  Changed {
+    pattern:   String,
+    since:     String,
+    overrides: BTreeMap<String, String>,
+  },
   Choose {
     overrides: BTreeMap<String, String>,
     chooser:   Option<String>,
   },
+  Command {
+    command:   Vec<String>,
+    overrides: BTreeMap<String, String>,
+  },
+  Complete {
+    words: Vec<String>,
+  },
   Completions {
     shell: String,
   },
-  Dump,
+  Dump {
+    format: Option<OutputFormat>,
+  },
   Edit,
   Evaluate {
+    format:    Option<OutputFormat>,
     overrides: BTreeMap<String, String>,
     variables: Vec<String>,
   },
+  Format {
+    check: bool,
+  },
+  Graph {
+    focus: Option<String>,
+    dot:   bool,
+  },
   Init,
-  List,
+  List {
+    recipe:    Option<String>,
+    groups:    bool,
+    overrides: BTreeMap<String, String>,
+  },
   Run {
     overrides: BTreeMap<String, String>,
     arguments: Vec<String>,
   },
+  SetEnv {
+    variables: Vec<(String, String)>,
+  },
   Show {
-    name: String,
+    names: Vec<String>,
   },
+  Stats,
   Summary,
   Variables,
 }
 
 const FISH_RECIPE_COMPLETIONS: &str = r#"function __fish_just_complete_recipes
-    just --summary 2> /dev/null | tr " " "\n" || echo ""
+    set -l completions (just --complete (commandline -opc)[2..-1] 2> /dev/null)
+    if test "$completions[1]" = recipe
+        printf "%s\n" $completions[2..-1]
+    end
+end
+
+function __fish_just_complete_path
+    set -l completions (just --complete (commandline -opc)[2..-1] 2> /dev/null)
+    test "$completions[1]" = path
 end
 
 # don't suggest files right off
 complete -c just -n "__fish_is_first_arg" --no-files
 
+# complete paths for `[path]` parameters
+complete -c just -n "__fish_just_complete_path" --force-files
+
 # complete recipes
 complete -c just -a '(__fish_just_complete_recipes)'
 
@@ -51,10 +92,10 @@ const ZSH_COMPLETION_REPLACEMENTS: &[(&str, &str)] = &[
     r#"'*--set[Override <VARIABLE> with <VALUE>]: :_just_variables' \"#,
   ),
   (
-    r#"'-s+[Show information about <RECIPE>]' \
-'--show=[Show information about <RECIPE>]' \"#,
-    r#"'-s+[Show information about <RECIPE>]: :_just_commands' \
-'--show=[Show information about <RECIPE>]: :_just_commands' \"#,
+    r#"'-s+[Show information about <RECIPE>, and any other recipes given as arguments, which may be globs]' \
+'--show=[Show information about <RECIPE>, and any other recipes given as arguments, which may be globs]' \"#,
+    r#"'-s+[Show information about <RECIPE>, and any other recipes given as arguments, which may be globs]: :_just_commands' \
+'--show=[Show information about <RECIPE>, and any other recipes given as arguments, which may be globs]: :_just_commands' \"#,
   ),
   (
     "'::ARGUMENTS -- Overrides and recipe(s) to run, defaulting to the first recipe in the \
@@ -74,31 +115,26 @@ const ZSH_COMPLETION_REPLACEMENTS: &[(&str, &str)] = &[
             curcontext="${curcontext%:*}-${words[2]}:"
 
             local lastarg=${words[${#words}]}
-            local recipe
 
-            local cmds; cmds=(
-                ${(s: :)$(_call_program commands just --summary)}
+            local completions; completions=(
+                ${(f)"$(_call_program commands just --complete ${words[2,-2]} 2> /dev/null)"}
             )
 
-            # Find first recipe name
-            for ((i = 2; i < $#words; i++ )) do
-                if [[ ${cmds[(I)${words[i]}]} -gt 0 ]]; then
-                    recipe=${words[i]}
-                    break
-                fi
-            done
-
             if [[ $lastarg = */* ]]; then
                 # Arguments contain slash would be recognised as a file
                 _arguments -s -S $common '*:: :_files'
             elif [[ $lastarg = *=* ]]; then
                 # Arguments contain equal would be recognised as a variable
                 _message "value"
-            elif [[ $recipe ]]; then
-                # Show usage message
-                _message "`just --show $recipe`"
-                # Or complete with other commands
-                #_arguments -s -S $common '*:: :_just_commands'
+            elif [[ ${completions[1]} = path ]]; then
+                # Recipe parameter takes a path
+                _files
+            elif [[ ${completions[1]} = argument ]]; then
+                # Show the parameter the argument is for
+                _message "${completions[2]}"
+            elif [[ ${completions[1]} = recipe ]]; then
+                local recipes; recipes=(${completions[2,-1]})
+                _describe -t commands 'just commands' recipes
             else
                 _arguments -s -S $common '*:: :_just_commands'
             fi
@@ -117,9 +153,15 @@ const ZSH_COMPLETION_REPLACEMENTS: &[(&str, &str)] = &[
     local variables; variables=(
         ${(s: :)$(_call_program commands just --variables)}
     )
-    local commands; commands=(
-        ${${${(M)"${(f)$(_call_program commands just --list)}":#    *}/ ##/}/ ##/:Args: }
+    local completions; completions=(
+        ${(f)"$(_call_program commands just --complete ${words[2,CURRENT-1]} 2> /dev/null)"}
     )
+    local commands
+    if [[ ${completions[1]} = recipe ]]; then
+        commands=(${completions[2,-1]})
+    else
+        commands=(${(s: :)"$(_call_program commands just --summary 2> /dev/null)"})
+    fi
 "#,
   ),
   (
@@ -163,29 +205,39 @@ const POWERSHELL_COMPLETION_REPLACEMENTS: &[(&str, &str)] = &[(
   r#"$completions.Where{ $_.CompletionText -like "$wordToComplete*" } |
         Sort-Object -Property ListItemText"#,
   r#"function Get-JustFileRecipes([string[]]$CommandElements) {
-        $justFileIndex = $commandElements.IndexOf("--justfile");
-
-        if ($justFileIndex -ne -1 && $justFileIndex + 1 -le $commandElements.Length) {
-            $justFileLocation = $commandElements[$justFileIndex + 1]
-        }
-
-        $justArgs = @("--summary")
+        $completions = @(just --complete @CommandElements 2> $null)
 
-        if (Test-Path $justFileLocation) {
-            $justArgs += @("--justfile", $justFileLocation)
+        if ($completions.Length -gt 0 -and $completions[0] -eq "recipe") {
+            return $completions | Select-Object -Skip 1 | ForEach-Object { [CompletionResult]::new($_) }
         }
-
-        $recipes = $(just @justArgs) -split ' '
-        return $recipes | ForEach-Object { [CompletionResult]::new($_) }
     }
 
-    $elementValues = $commandElements | Select-Object -ExpandProperty Value
+    $elementValues = @($commandElements |
+        Select-Object -Skip 1 |
+        Where-Object { $_.Extent.EndOffset -lt $cursorPosition } |
+        ForEach-Object { if ($_ -is [StringConstantExpressionAst]) { $_.Value } else { $_.Extent.Text } })
     $recipes = Get-JustFileRecipes -CommandElements $elementValues
     $completions += $recipes
     $completions.Where{ $_.CompletionText -like "$wordToComplete*" } |
         Sort-Object -Property ListItemText"#,
 )];
 
+const ELVISH_COMPLETION_REPLACEMENTS: &[(&str, &str)] = &[(
+  r#"    $completions[$command]
+}"#,
+  r#"    try {
+        kind @candidates = (just --complete $@words[1:-1] 2>/dev/null)
+        if (eq $kind recipe) {
+            for candidate $candidates {
+                edit:complex-candidate $candidate
+            }
+        }
+    } except {
+    }
+    $completions[$command]
+}"#,
+)];
+
 const BASH_COMPLETION_REPLACEMENTS: &[(&str, &str)] = &[(
   r#"            if [[ ${cur} == -* || ${COMP_CWORD} -eq 1 ]] ; then
                 COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
@@ -194,12 +246,24 @@ const BASH_COMPLETION_REPLACEMENTS: &[(&str, &str)] = &[(
   r#"                if [[ ${cur} == -* ]] ; then
                     COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
                     return 0
-                elif [[ ${COMP_CWORD} -eq 1 ]]; then
-                    local recipes=$(just --summary --color never 2> /dev/null)
-                    if [[ $? -eq 0 ]]; then
-                        COMPREPLY=( $(compgen -W "${recipes}" -- "${cur}") )
-                        return 0
-                    fi
+                fi
+
+                local completions
+                if completions=$(just --complete "${COMP_WORDS[@]:1:COMP_CWORD-1}" 2> /dev/null); then
+                    case "${completions%%$'\n'*}" in
+                        recipe)
+                            COMPREPLY=( $(compgen -W "$(tail -n +2 <<< "${completions}")" -- "${cur}") )
+                            return 0
+                            ;;
+                        path)
+                            COMPREPLY=( $(compgen -f -- "${cur}") )
+                            return 0
+                            ;;
+                        argument)
+                            COMPREPLY=()
+                            return 0
+                            ;;
+                    esac
                 fi"#,
 )];
 
@@ -254,7 +318,10 @@ impl Subcommand {
         for (needle, replacement) in ZSH_COMPLETION_REPLACEMENTS {
           replace(verbosity, &mut script, needle, replacement)?;
         },
-      Shell::Elvish => {},
+      Shell::Elvish =>
+        for (needle, replacement) in ELVISH_COMPLETION_REPLACEMENTS {
+          replace(verbosity, &mut script, needle, replacement)?;
+        },
     }
 
     println!("{}", script.trim());
@@ -1,17 +1,151 @@
 use crate::common::*;
 
 #[derive(Clone, Copy, Debug, PartialEq)]
-pub(crate) struct Suggestion<'src> {
-  pub(crate) name:   &'src str,
-  pub(crate) target: Option<&'src str>,
+pub(crate) enum Suggestion<'src> {
+  /// A recipe, or, if `target` is given, an alias for the recipe `target`
+  Recipe {
+    name:   &'src str,
+    target: Option<&'src str>,
+  },
+  /// A variable, for an unknown variable or override
+  Variable { name: &'src str },
+  /// A function, for an unknown variable or function
+  Function { name: &'src str },
+  /// A variable, for an unknown recipe which was probably meant to be an
+  /// override, e.g. `just version 1.0` instead of `just version=1.0`
+  Override { name: &'src str },
+}
+
+impl<'src> Suggestion<'src> {
+  /// Suggestions with an edit distance from the input of this or more are
+  /// too dissimilar to be useful
+  const MAX_DISTANCE: usize = 3;
+
+  /// Whether a candidate at `distance` from `input` is similar enough to
+  /// suggest. Short inputs are close to almost everything, so at least half
+  /// of `input` must be unchanged.
+  fn similar(input: &str, distance: usize) -> bool {
+    distance < Self::MAX_DISTANCE && distance * 2 <= input.chars().count()
+  }
+
+  pub(crate) fn name(self) -> &'src str {
+    match self {
+      Self::Recipe { name, .. }
+      | Self::Variable { name }
+      | Self::Function { name }
+      | Self::Override { name } => name,
+    }
+  }
+
+  /// The candidate closest to `input`, if any is close enough. Ties are
+  /// broken in favor of candidates which come first.
+  pub(crate) fn closest(
+    input: &str,
+    candidates: impl IntoIterator<Item = Suggestion<'src>>,
+  ) -> Option<Suggestion<'src>> {
+    candidates
+      .into_iter()
+      .map(|candidate| (edit_distance(candidate.name(), input), candidate))
+      .filter(|(distance, _candidate)| Self::similar(input, *distance))
+      .min_by_key(|(distance, _candidate)| *distance)
+      .map(|(_distance, candidate)| candidate)
+  }
+
+  /// The function whose name is closest to `input`, if any is close enough
+  pub(crate) fn function(input: &str) -> Option<Suggestion<'static>> {
+    Suggestion::closest(
+      input,
+      crate::function::TABLE
+        .keys()
+        .map(|name| Suggestion::Function { name }),
+    )
+  }
 }
 
 impl<'src> Display for Suggestion<'src> {
   fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-    write!(f, "Did you mean `{}`", self.name)?;
-    if let Some(target) = self.target {
-      write!(f, ", an alias for `{}`", target)?;
+    match self {
+      Self::Recipe { name, target } => {
+        write!(f, "Did you mean `{}`", name)?;
+        if let Some(target) = target {
+          write!(f, ", an alias for `{}`", target)?;
+        }
+        write!(f, "?")
+      },
+      Self::Variable { name } => write!(f, "Did you mean `{}`?", name),
+      Self::Function { name } => write!(f, "Did you mean `{}()`?", name),
+      Self::Override { name } => write!(
+        f,
+        "Did you mean to override variable `{}` with `{}=VALUE`?",
+        name, name
+      ),
     }
-    write!(f, "?")
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn closest() {
+    assert_eq!(
+      Suggestion::closest("bulid", vec![
+        Suggestion::Variable { name: "build" },
+        Suggestion::Recipe {
+          name:   "build",
+          target: None,
+        },
+        Suggestion::Variable { name: "bulid!" },
+      ]),
+      Some(Suggestion::Variable { name: "bulid!" })
+    );
+  }
+
+  #[test]
+  fn closest_tie() {
+    assert_eq!(
+      Suggestion::closest("ab", vec![
+        Suggestion::Variable { name: "a" },
+        Suggestion::Function { name: "b" },
+      ]),
+      Some(Suggestion::Variable { name: "a" })
+    );
+  }
+
+  #[test]
+  fn closest_too_far() {
+    assert_eq!(
+      Suggestion::closest("abc", vec![Suggestion::Variable { name: "xyz" }]),
+      None
+    );
+  }
+
+  #[test]
+  fn function() {
+    assert_eq!(
+      Suggestion::function("uuid4"),
+      Some(Suggestion::Function { name: "uuid" })
+    );
+  }
+
+  #[test]
+  fn display() {
+    assert_eq!(
+      Suggestion::Recipe {
+        name:   "b",
+        target: Some("build"),
+      }
+      .to_string(),
+      "Did you mean `b`, an alias for `build`?"
+    );
+    assert_eq!(
+      Suggestion::Function { name: "arch" }.to_string(),
+      "Did you mean `arch()`?"
+    );
+    assert_eq!(
+      Suggestion::Override { name: "version" }.to_string(),
+      "Did you mean to override variable `version` with `version=VALUE`?"
+    );
   }
 }
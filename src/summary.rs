@@ -182,13 +182,30 @@ impl Assignment {
 
 #[derive(Eq, PartialEq, Hash, Ord, PartialOrd, Debug, Clone)]
 pub enum Expression {
+  Arithmetic {
+    lhs:      Box<Expression>,
+    operator: String,
+    rhs:      Box<Expression>,
+  },
   Backtick {
     command: String,
   },
+  Boolean {
+    value: bool,
+  },
   Call {
     name:      String,
     arguments: Vec<Expression>,
   },
+  Coalesce {
+    lhs: Box<Expression>,
+    rhs: Box<Expression>,
+  },
+  Comparison {
+    lhs:      Box<Expression>,
+    rhs:      Box<Expression>,
+    inverted: bool,
+  },
   Concatination {
     lhs: Box<Expression>,
     rhs: Box<Expression>,
@@ -200,6 +217,9 @@ pub enum Expression {
     otherwise: Box<Expression>,
     inverted:  bool,
   },
+  Integer {
+    value: i64,
+  },
   String {
     text: String,
   },
@@ -212,6 +232,13 @@ impl Expression {
   fn new(expression: &full::Expression) -> Expression {
     use full::Expression::*;
     match expression {
+      Arithmetic {
+        lhs, operator, rhs, ..
+      } => Expression::Arithmetic {
+        lhs:      Box::new(Expression::new(lhs)),
+        operator: operator.to_string(),
+        rhs:      Box::new(Expression::new(rhs)),
+      },
       Backtick { contents, .. } => Expression::Backtick {
         command: (*contents).to_owned(),
       },
@@ -230,8 +257,42 @@ impl Expression {
           name:      name.lexeme().to_owned(),
           arguments: vec![Expression::new(a), Expression::new(b)],
         },
+        full::Thunk::Ternary {
+          name,
+          args: [a, b, c],
+          ..
+        } => Expression::Call {
+          name:      name.lexeme().to_owned(),
+          arguments: vec![Expression::new(a), Expression::new(b), Expression::new(c)],
+        },
+        full::Thunk::UnaryOpt {
+          name, args: (a, b), ..
+        } => {
+          let mut arguments = vec![Expression::new(a)];
+          if let Some(b) = b {
+            arguments.push(Expression::new(b));
+          }
+          Expression::Call {
+            name: name.lexeme().to_owned(),
+            arguments,
+          }
+        },
+        full::Thunk::Nary { name, args, .. } => Expression::Call {
+          name:      name.lexeme().to_owned(),
+          arguments: args.iter().map(Expression::new).collect(),
+        },
+      },
+      Boolean { value } => Expression::Boolean { value: *value },
+      Coalesce { lhs, rhs, .. } => Expression::Coalesce {
+        lhs: Box::new(Expression::new(lhs)),
+        rhs: Box::new(Expression::new(rhs)),
+      },
+      Comparison { lhs, rhs, inverted } => Expression::Comparison {
+        lhs:      Box::new(Expression::new(lhs)),
+        rhs:      Box::new(Expression::new(rhs)),
+        inverted: *inverted,
       },
-      Concatination { lhs, rhs } => Expression::Concatination {
+      Concatination { lhs, rhs, .. } => Expression::Concatination {
         lhs: Box::new(Expression::new(lhs)),
         rhs: Box::new(Expression::new(rhs)),
       },
@@ -248,6 +309,7 @@ impl Expression {
         otherwise: Box::new(Expression::new(otherwise)),
         inverted:  *inverted,
       },
+      Integer { value } => Expression::Integer { value: *value },
       StringLiteral { string_literal } => Expression::String {
         text: string_literal.cooked.to_string(),
       },
@@ -0,0 +1,75 @@
+use crate::common::*;
+
+use std::{io::Read, process::ExitStatus, thread};
+
+/// Run `command`, copying its stdout and stderr to `output`, unless `quiet`
+/// is true, and to `log`, if given. Output goes to just's stdout and stderr,
+/// unless it is being captured.
+pub(crate) fn tee(
+  command: &mut Command,
+  log: Option<&Sink>,
+  output: Option<&Capture>,
+  quiet: bool,
+) -> io::Result<ExitStatus> {
+  let mut child = command
+    .stdout(Stdio::piped())
+    .stderr(Stdio::piped())
+    .spawn()?;
+
+  let mut copiers = Vec::new();
+
+  if let Some(stdout) = child.stdout.take() {
+    let mut sinks = log.cloned().into_iter().collect::<Vec<Sink>>();
+    if !quiet {
+      sinks.push(output.map_or_else(|| Arc::new(Mutex::new(io::stdout())), Capture::stdout));
+    }
+    copiers.push(copy(stdout, sinks));
+  }
+
+  if let Some(stderr) = child.stderr.take() {
+    let mut sinks = log.cloned().into_iter().collect::<Vec<Sink>>();
+    if !quiet {
+      sinks.push(output.map_or_else(|| Arc::new(Mutex::new(io::stderr())), Capture::stderr));
+    }
+    copiers.push(copy(stderr, sinks));
+  }
+
+  let status = child.wait();
+
+  for copier in copiers {
+    copier.join().ok();
+  }
+
+  status
+}
+
+/// Copy `source` to each of `sinks` on a new thread. Output is still read
+/// after writing to a sink fails, so the command doesn't block on a full pipe
+/// if, for example, a filter exits early.
+pub(crate) fn copy(
+  mut source: impl Read + Send + 'static,
+  sinks: Vec<Sink>,
+) -> thread::JoinHandle<()> {
+  thread::spawn(move || {
+    let mut buffer = [0; 8192];
+    let mut open = vec![true; sinks.len()];
+
+    loop {
+      let n = match source.read(&mut buffer) {
+        Ok(0) | Err(_) => break,
+        Ok(n) => n,
+      };
+
+      for (sink, open) in sinks.iter().zip(open.iter_mut()) {
+        if *open {
+          if let Ok(mut sink) = sink.lock() {
+            *open = sink
+              .write_all(&buffer[..n])
+              .and_then(|()| sink.flush())
+              .is_ok();
+          }
+        }
+      }
+    }
+  })
+}
@@ -101,6 +101,7 @@ macro_rules! run_error {
             &search,
             &overrides,
             &arguments,
+            None,
           ).expect_err("Expected runtime error") {
             $error => $check
             other => {
@@ -20,9 +20,78 @@ pub(crate) enum Thunk<'src> {
     function: fn(&FunctionContext, &str, &str) -> Result<String, String>,
     args:     [Box<Expression<'src>>; 2],
   },
+  Ternary {
+    name:     Name<'src>,
+    #[derivative(Debug = "ignore", PartialEq = "ignore")]
+    function: fn(&FunctionContext, &str, &str, &str) -> Result<String, String>,
+    args:     [Box<Expression<'src>>; 3],
+  },
+  UnaryOpt {
+    name:     Name<'src>,
+    #[derivative(Debug = "ignore", PartialEq = "ignore")]
+    function: fn(&FunctionContext, &str, Option<&str>) -> Result<String, String>,
+    args:     (Box<Expression<'src>>, Option<Box<Expression<'src>>>),
+  },
+  Nary {
+    name:     Name<'src>,
+    #[derivative(Debug = "ignore", PartialEq = "ignore")]
+    function: fn(&FunctionContext, &[String]) -> Result<String, String>,
+    args:     Vec<Expression<'src>>,
+  },
 }
 
 impl<'src> Thunk<'src> {
+  pub(crate) fn name(&self) -> &Name<'src> {
+    match self {
+      Self::Nullary { name, .. }
+      | Self::Unary { name, .. }
+      | Self::Binary { name, .. }
+      | Self::Ternary { name, .. }
+      | Self::UnaryOpt { name, .. }
+      | Self::Nary { name, .. } => name,
+    }
+  }
+
+  /// The expressions passed as arguments, in order
+  pub(crate) fn arguments(&self) -> Vec<&Expression<'src>> {
+    match self {
+      Self::Nullary { .. } => Vec::new(),
+      Self::Unary { arg, .. } => vec![arg],
+      Self::Binary { args: [a, b], .. } => vec![a, b],
+      Self::Ternary {
+        args: [a, b, c], ..
+      } => vec![a, b, c],
+      Self::UnaryOpt { args: (a, b), .. } => {
+        let mut arguments = vec![a.as_ref()];
+        if let Some(b) = b {
+          arguments.push(b);
+        }
+        arguments
+      },
+      Self::Nary { args, .. } => args.iter().collect(),
+    }
+  }
+
+  /// The expressions passed as arguments, in order, for modification
+  pub(crate) fn arguments_mut(&mut self) -> Vec<&mut Expression<'src>> {
+    match self {
+      Self::Nullary { .. } => Vec::new(),
+      Self::Unary { arg, .. } => vec![arg],
+      Self::Binary { args: [a, b], .. } => vec![a, b],
+      Self::Ternary {
+        args: [a, b, c], ..
+      } => vec![a, b, c],
+      Self::UnaryOpt { args: (a, b), .. } => {
+        let mut arguments = vec![a.as_mut()];
+        if let Some(b) = b {
+          arguments.push(b);
+        }
+        arguments
+      },
+      Self::Nary { args, .. } => args.iter_mut().collect(),
+    }
+  }
+
   pub(crate) fn resolve(
     name: Name<'src>,
     mut arguments: Vec<Expression<'src>>,
@@ -47,6 +116,34 @@ impl<'src> Thunk<'src> {
             name,
           })
         },
+        (Function::Ternary(function), 3) => {
+          let c = Box::new(arguments.pop().unwrap());
+          let b = Box::new(arguments.pop().unwrap());
+          let a = Box::new(arguments.pop().unwrap());
+          Ok(Thunk::Ternary {
+            function: *function,
+            args: [a, b, c],
+            name,
+          })
+        },
+        (Function::UnaryOpt(function), 1..=2) => {
+          let b = if arguments.len() == 2 {
+            Some(Box::new(arguments.pop().unwrap()))
+          } else {
+            None
+          };
+          let a = Box::new(arguments.pop().unwrap());
+          Ok(Thunk::UnaryOpt {
+            function: *function,
+            args: (a, b),
+            name,
+          })
+        },
+        (Function::Nary(function, argc), found) if argc.contains(&found) => Ok(Thunk::Nary {
+          function: *function,
+          args: arguments,
+          name,
+        }),
         _ => Err(
           name.error(CompilationErrorKind::FunctionArgumentCountMismatch {
             function: name.lexeme(),
@@ -57,7 +154,8 @@ impl<'src> Thunk<'src> {
       }
     } else {
       Err(name.error(CompilationErrorKind::UnknownFunction {
-        function: name.lexeme(),
+        function:   name.lexeme(),
+        suggestion: Suggestion::function(name.lexeme()),
       }))
     }
   }
@@ -72,6 +170,31 @@ impl Display for Thunk<'_> {
       Binary {
         name, args: [a, b], ..
       } => write!(f, "{}({}, {})", name.lexeme(), a, b),
+      Ternary {
+        name,
+        args: [a, b, c],
+        ..
+      } => write!(f, "{}({}, {}, {})", name.lexeme(), a, b, c),
+      UnaryOpt {
+        name,
+        args: (a, None),
+        ..
+      } => write!(f, "{}({})", name.lexeme(), a),
+      UnaryOpt {
+        name,
+        args: (a, Some(b)),
+        ..
+      } => write!(f, "{}({}, {})", name.lexeme(), a, b),
+      Nary { name, args, .. } => {
+        write!(f, "{}(", name.lexeme())?;
+        for (i, arg) in args.iter().enumerate() {
+          if i > 0 {
+            write!(f, ", ")?;
+          }
+          write!(f, "{}", arg)?;
+        }
+        write!(f, ")")
+      },
     }
   }
 }
@@ -19,60 +19,102 @@ impl<'src> Token<'src> {
     CompilationError { token: *self, kind }
   }
 
+  /// Write the source lines spanned by this token, with the token underlined
+  /// by carets. Tokens that span multiple lines, such as multi-line strings,
+  /// are underlined on each line. The formatter width, if present, is the
+  /// number of additional lines of context to write before and after the
+  /// token.
   pub(crate) fn write_context(&self, f: &mut Formatter, color: Color) -> fmt::Result {
-    let width = if self.length == 0 { 1 } else { self.length };
-
-    let line_number = self.line.ordinal();
-    match self.src.lines().nth(self.line) {
-      Some(line) => {
-        let mut i = 0;
-        let mut space_column = 0;
-        let mut space_line = String::new();
-        let mut space_width = 0;
-        for c in line.chars() {
-          if c == '\t' {
-            space_line.push_str("    ");
-            if i < self.column {
-              space_column += 4;
-            }
-            if i >= self.column && i < self.column + width {
-              space_width += 4;
-            }
-          } else {
-            if i < self.column {
-              space_column += UnicodeWidthChar::width(c).unwrap_or(0);
-            }
-            if i >= self.column && i < self.column + width {
-              space_width += UnicodeWidthChar::width(c).unwrap_or(0);
-            }
-            space_line.push(c);
-          }
-          i += c.len_utf8();
-        }
-        let line_number_width = line_number.to_string().len();
-        writeln!(f, "{0:1$} |", "", line_number_width)?;
-        writeln!(f, "{} | {}", line_number, space_line)?;
-        write!(f, "{0:1$} |", "", line_number_width)?;
+    let context = f.width().unwrap_or(0);
+
+    let lines = self.src.lines().collect::<Vec<&str>>();
+
+    if self.line >= lines.len() {
+      if self.offset != self.src.len() {
         write!(
           f,
-          " {0:1$}{2}{3:^<4$}{5}",
+          "internal error: Error has invalid line number: {}",
+          self.line.ordinal()
+        )?;
+      }
+      return Ok(());
+    }
+
+    let segments = self
+      .lexeme()
+      .trim_end_matches(|c| c == '\r' || c == '\n')
+      .split('\n')
+      .collect::<Vec<&str>>();
+
+    let first = self.line;
+    let last = (first + segments.len() - 1).min(lines.len() - 1);
+    let start = first.saturating_sub(context);
+    let end = (last + context).min(lines.len() - 1);
+
+    let line_number_width = end.ordinal().to_string().len();
+
+    writeln!(f, "{0:1$} |", "", line_number_width)?;
+
+    for (i, line) in lines.iter().enumerate().take(end + 1).skip(start) {
+      if i > start {
+        writeln!(f)?;
+      }
+
+      let (space_line, space_column, space_width) = if i < first || i > last {
+        (Self::expand_tabs(line, 0, 0).0, 0, 0)
+      } else if first == last {
+        let width = if self.length == 0 { 1 } else { self.length };
+        let (space_line, space_column, space_width) =
+          Self::expand_tabs(line, self.column, self.column + width);
+        (space_line, space_column, space_width.max(1))
+      } else {
+        let column = if i == first { self.column } else { 0 };
+        Self::expand_tabs(line, column, column + segments[i - first].len())
+      };
+
+      write!(f, "{:>2$} | {}", i.ordinal(), space_line, line_number_width)?;
+
+      if space_width > 0 {
+        writeln!(f)?;
+        write!(
+          f,
+          "{0:1$} | {0:2$}{3}{0:^<4$}{5}",
           "",
+          line_number_width,
           space_column,
           color.prefix(),
-          "",
-          space_width.max(1),
+          space_width,
           color.suffix()
         )?;
-      },
-      None =>
-        if self.offset != self.src.len() {
-          write!(
-            f,
-            "internal error: Error has invalid line number: {}",
-            line_number
-          )?
-        },
+      }
     }
+
     Ok(())
   }
+
+  /// Expand tabs in `line` to four spaces, returning the expanded line, and
+  /// the display column and display width of the byte range `begin..end`.
+  fn expand_tabs(line: &str, begin: usize, end: usize) -> (String, usize, usize) {
+    let mut i = 0;
+    let mut space_column = 0;
+    let mut space_line = String::new();
+    let mut space_width = 0;
+    for c in line.chars() {
+      let width = if c == '\t' {
+        space_line.push_str("    ");
+        4
+      } else {
+        space_line.push(c);
+        UnicodeWidthChar::width(c).unwrap_or(0)
+      };
+      if i < begin {
+        space_column += width;
+      }
+      if i >= begin && i < end {
+        space_width += width;
+      }
+      i += c.len_utf8();
+    }
+    (space_line, space_column, space_width)
+  }
 }
@@ -6,6 +6,7 @@ pub(crate) enum TokenKind {
   At,
   Backtick,
   BangEquals,
+  BarBar,
   BraceL,
   BraceR,
   BracketL,
@@ -24,9 +25,12 @@ pub(crate) enum TokenKind {
   Indent,
   InterpolationEnd,
   InterpolationStart,
+  Minus,
+  Number,
   ParenL,
   ParenR,
   Plus,
+  Slash,
   StringToken,
   Text,
   Unspecified,
@@ -41,6 +45,7 @@ impl Display for TokenKind {
       At => "'@'",
       Backtick => "backtick",
       BangEquals => "'!='",
+      BarBar => "'||'",
       BraceL => "'{'",
       BraceR => "'}'",
       BracketL => "'['",
@@ -59,9 +64,12 @@ impl Display for TokenKind {
       Indent => "indent",
       InterpolationEnd => "'}}'",
       InterpolationStart => "'{{'",
+      Minus => "'-'",
+      Number => "number",
       ParenL => "'('",
       ParenR => "')'",
       Plus => "'+'",
+      Slash => "'/'",
       StringToken => "string",
       Text => "command text",
       Unspecified => "unspecified",
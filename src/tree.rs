@@ -18,6 +18,14 @@ macro_rules! tree {
     $crate::tree::Tree::atom(stringify!($atom))
   };
 
+  // before `$atom:literal`, which would otherwise try to parse `-` as the
+  // start of a negative number
+  {
+    -
+  } => {
+    $crate::tree::Tree::atom("-")
+  };
+
   {
     $atom:literal
   } => {
@@ -42,6 +50,18 @@ macro_rules! tree {
     $crate::tree::Tree::atom("*")
   };
 
+  {
+    /
+  } => {
+    $crate::tree::Tree::atom("/")
+  };
+
+  {
+    $
+  } => {
+    $crate::tree::Tree::atom("$")
+  };
+
   {
     ==
   } => {
@@ -53,6 +73,12 @@ macro_rules! tree {
   } => {
     $crate::tree::Tree::atom("!=")
   };
+
+  {
+    ||
+  } => {
+    $crate::tree::Tree::atom("||")
+  };
 }
 
 /// A `Tree` is either…
@@ -0,0 +1,30 @@
+use crate::common::*;
+
+/// What to do when the first argument on the command line isn't a recipe,
+/// set with `set unknown-recipe`
+#[derive(Debug, PartialEq, Clone, Copy, EnumString, IntoStaticStr)]
+#[strum(serialize_all = "kebab_case")]
+pub(crate) enum UnknownRecipe {
+  /// Fail with an error
+  Error,
+  /// Run the default recipe, passing all arguments to it
+  Default,
+  /// Offer to run the closest matching recipe instead
+  SuggestRun,
+}
+
+impl UnknownRecipe {
+  pub(crate) const VALUES: &'static [&'static str] = &["error", "default", "suggest-run"];
+}
+
+impl Default for UnknownRecipe {
+  fn default() -> Self {
+    Self::Error
+  }
+}
+
+impl Display for UnknownRecipe {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(f, "{}", <&str>::from(self))
+  }
+}
@@ -4,4 +4,27 @@ use crate::common::*;
 pub(crate) struct UnresolvedDependency<'src> {
   pub(crate) recipe:    Name<'src>,
   pub(crate) arguments: Vec<Expression<'src>>,
+  /// Whether the dependency ends with `*`, which forwards all of the
+  /// dependent recipe's parameters as arguments after `arguments`
+  pub(crate) forward:   bool,
+}
+
+impl<'src> Display for UnresolvedDependency<'src> {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    if self.arguments.is_empty() && !self.forward {
+      write!(f, "{}", self.recipe)
+    } else {
+      write!(f, "({}", self.recipe)?;
+
+      for argument in &self.arguments {
+        write!(f, " {}", argument)?;
+      }
+
+      if self.forward {
+        write!(f, " *")?;
+      }
+
+      write!(f, ")")
+    }
+  }
 }
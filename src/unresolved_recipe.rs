@@ -8,34 +8,43 @@ impl<'src> UnresolvedRecipe<'src> {
     resolved: Vec<Rc<Recipe<'src>>>,
   ) -> CompilationResult<'src, Recipe<'src>> {
     assert_eq!(self.dependencies.len(), resolved.len());
-    for (unresolved, resolved) in self.dependencies.iter().zip(&resolved) {
-      assert_eq!(unresolved.recipe.lexeme(), resolved.name.lexeme());
-      if !resolved
-        .argument_range()
-        .contains(&unresolved.arguments.len())
-      {
-        return Err(unresolved.recipe.error(
-          CompilationErrorKind::DependencyArgumentCountMismatch {
-            dependency: unresolved.recipe.lexeme(),
-            found:      unresolved.arguments.len(),
-            min:        resolved.min_arguments(),
-            max:        resolved.max_arguments(),
-          },
-        ));
-      }
-    }
+    let parameters = &self.parameters;
 
     let dependencies = self
       .dependencies
       .into_iter()
       .zip(resolved)
-      .map(|(unresolved, resolved)| Dependency {
-        recipe:    resolved,
-        arguments: unresolved.arguments,
+      .map(|(unresolved, resolved)| {
+        assert_eq!(unresolved.recipe.lexeme(), resolved.name.lexeme());
+
+        let mut arguments = unresolved.arguments;
+
+        if unresolved.forward {
+          arguments.extend(parameters.iter().map(|parameter| Expression::Variable {
+            name: parameter.name,
+          }));
+        }
+
+        if !resolved.argument_range().contains(&arguments.len()) {
+          return Err(unresolved.recipe.error(
+            CompilationErrorKind::DependencyArgumentCountMismatch {
+              dependency: unresolved.recipe.lexeme(),
+              found:      arguments.len(),
+              min:        resolved.min_arguments(),
+              max:        resolved.max_arguments(),
+            },
+          ));
+        }
+
+        Ok(Dependency {
+          recipe: resolved,
+          arguments,
+        })
       })
-      .collect();
+      .collect::<CompilationResult<Vec<Dependency>>>()?;
 
     Ok(Recipe {
+      attributes: self.attributes,
       doc: self.doc,
       body: self.body,
       name: self.name,
@@ -15,10 +15,15 @@ impl<'expression, 'src> Iterator for Variables<'expression, 'src> {
 
   fn next(&mut self) -> Option<Token<'src>> {
     match self.stack.pop() {
-      None
-      | Some(Expression::StringLiteral { .. })
+      None => None,
+      Some(Expression::StringLiteral { .. })
       | Some(Expression::Backtick { .. })
-      | Some(Expression::Call { .. }) => None,
+      | Some(Expression::Boolean { .. })
+      | Some(Expression::Integer { .. }) => self.next(),
+      Some(Expression::Call { thunk }) => {
+        self.stack.extend(thunk.arguments().into_iter().rev());
+        self.next()
+      },
       Some(Expression::Conditional {
         lhs,
         rhs,
@@ -33,7 +38,10 @@ impl<'expression, 'src> Iterator for Variables<'expression, 'src> {
         self.next()
       },
       Some(Expression::Variable { name, .. }) => Some(name.token()),
-      Some(Expression::Concatination { lhs, rhs }) => {
+      Some(Expression::Arithmetic { lhs, rhs, .. })
+      | Some(Expression::Coalesce { lhs, rhs, .. })
+      | Some(Expression::Comparison { lhs, rhs, .. })
+      | Some(Expression::Concatination { lhs, rhs, .. }) => {
         self.stack.push(lhs);
         self.stack.push(rhs);
         self.next()
@@ -0,0 +1,176 @@
+use crate::common::*;
+
+test! {
+  name:     fixed_arguments,
+  justfile: "
+    alias r := build --release
+
+    build *flags:
+      @echo build {{flags}}
+  ",
+  args:     ("r"),
+  stdout:   "build --release\n",
+}
+
+test! {
+  name:     additional_arguments,
+  justfile: "
+    alias r := build --release
+
+    build *flags:
+      @echo build {{flags}}
+  ",
+  args:     ("r", "-v", "--locked"),
+  stdout:   "build --release -v --locked\n",
+}
+
+test! {
+  name:     string_arguments,
+  justfile: "
+    alias d := deploy 'us east' \"prod\\tx\"
+
+    deploy region env:
+      @echo '{{region}}' '{{env}}'
+  ",
+  args:     ("d"),
+  stdout:   "us east prod\tx\n",
+}
+
+test! {
+  name:     chained,
+  justfile: "
+    alias t := test --release
+    alias u := t --lib
+
+    test *flags:
+      @echo test {{flags}}
+  ",
+  args:     ("u", "foo"),
+  stdout:   "test --release --lib foo\n",
+}
+
+test! {
+  name:     followed_by_recipe,
+  justfile: "
+    alias d := deploy prod
+
+    deploy env:
+      @echo deploy {{env}}
+
+    check:
+      @echo check
+  ",
+  args:     ("d", "check"),
+  stdout:   "
+    deploy prod
+    check
+  ",
+}
+
+test! {
+  name:     too_few_arguments,
+  justfile: "
+    alias d := deploy prod
+
+    deploy env region:
+      @echo deploy {{env}} {{region}}
+  ",
+  args:     ("d"),
+  stderr:   "
+    error: Recipe `deploy` got 1 argument but takes 2
+    usage:
+        just deploy env region
+  ",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     too_many_arguments,
+  justfile: "
+    alias d := deploy prod eu
+
+    deploy env:
+  ",
+  args:     ("d"),
+  stderr:   "
+    error: Alias `d` passes 2 arguments to recipe `deploy`, which takes at most 1 argument
+      |
+    1 | alias d := deploy prod eu
+      |       ^
+  ",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     default,
+  justfile: "
+    set default := 'r'
+
+    alias r := build --release
+
+    build mode:
+      @echo build {{mode}}
+  ",
+  stdout:   "build --release\n",
+}
+
+test! {
+  name:     list,
+  justfile: "
+    alias d := deploy prod
+    alias r := build --release
+
+    build *flags:
+
+    deploy env region='eu':
+  ",
+  args:     ("--list"),
+  stdout:   "
+    Available recipes:
+        build *flags
+        r *flags               # alias for `build --release`
+        deploy env region='eu'
+        d region='eu'          # alias for `deploy prod`
+  ",
+}
+
+test! {
+  name:     fmt,
+  justfile: "
+    alias r := build --release 'a b'
+
+    build *flags:
+  ",
+  args:     ("--fmt", "--check"),
+}
+
+test! {
+  name:     dump,
+  justfile: "
+    alias   r  :=  build   --release 'a b'
+
+    build *flags:
+  ",
+  args:     ("--dump"),
+  stdout:   "
+    alias r := build --release 'a b'
+
+    build *flags:
+  ",
+}
+
+test! {
+  name:     show,
+  justfile: "
+    alias r := build --release
+
+    build *flags:
+      cargo build {{flags}}
+  ",
+  args:     ("--show", "r"),
+  stdout:   "
+    alias r := build --release
+    build *flags:
+        cargo build {{flags}}
+  ",
+}
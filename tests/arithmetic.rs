@@ -0,0 +1,276 @@
+use crate::common::*;
+
+test! {
+  name:     addition,
+  justfile: "
+    base_port := 8000
+    port := base_port + 1
+
+    foo:
+      echo {{port}}
+  ",
+  stdout:   "8001\n",
+  stderr:   "echo 8001\n",
+}
+
+test! {
+  name:     precedence,
+  justfile: "
+    x := 2 + 3 * 4 - 10 / 3
+
+    foo:
+      echo {{x}}
+  ",
+  stdout:   "11\n",
+  stderr:   "echo 11\n",
+}
+
+test! {
+  name:     subtraction_left_associative,
+  justfile: "
+    x := 10 - 3 - 2
+
+    foo:
+      echo {{x}}
+  ",
+  stdout:   "5\n",
+  stderr:   "echo 5\n",
+}
+
+test! {
+  name:     groups,
+  justfile: "
+    x := (1 + 2) * 3
+
+    foo:
+      echo {{x}}
+  ",
+  stdout:   "9\n",
+  stderr:   "echo 9\n",
+}
+
+test! {
+  name:     negative_results,
+  justfile: "
+    x := 1 - 5
+
+    foo:
+      echo {{x / 2}}
+  ",
+  stdout:   "-2\n",
+  stderr:   "echo -2\n",
+}
+
+test! {
+  name:     concatenation_unchanged,
+  justfile: "
+    x := '1' + '2'
+    port := 8000
+
+    foo:
+      echo {{x}} {{'localhost:' + port}}
+  ",
+  stdout:   "12 localhost:8000\n",
+  stderr:   "echo 12 localhost:8000\n",
+}
+
+test! {
+  name:     integer_variables,
+  justfile: "
+    base_port := 8000
+
+    foo:
+      echo {{base_port + base_port}}
+  ",
+  stdout:   "16000\n",
+  stderr:   "echo 16000\n",
+}
+
+test! {
+  name:     integer_variable_sums,
+  justfile: "
+    total := double + base
+    double := base + base
+    base := 1
+
+    foo:
+      echo {{total}}
+  ",
+  stdout:   "3\n",
+  stderr:   "echo 3\n",
+}
+
+test! {
+  name:     parameter_and_integer_variable_concatenate,
+  justfile: "
+    port := 8000
+
+    foo host:
+      echo {{host + port}}
+  ",
+  args:     ("foo", "1"),
+  stdout:   "18000\n",
+  stderr:   "echo 18000\n",
+}
+
+test! {
+  name:     overridden_integer_variable,
+  justfile: "
+    port := 8000
+
+    foo:
+      echo {{port + port}}
+  ",
+  args:     ("port=x"),
+  stderr:   "
+    error: Operand `x` of `+` is not an integer
+      |
+    4 |   echo {{port + port}}
+      |               ^
+  ",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     negative_literals,
+  justfile: "
+    x := -5
+    y := x - -2
+
+    foo n=-1:
+      echo {{x}} {{y}} {{n}}
+  ",
+  stdout:   "-5 -3 -1\n",
+  stderr:   "echo -5 -3 -1\n",
+}
+
+test! {
+  name:     parameters,
+  justfile: "
+    foo n:
+      echo {{n * 2}}
+  ",
+  args:     ("foo", "21"),
+  stdout:   "42\n",
+  stderr:   "echo 42\n",
+}
+
+test! {
+  name:     parameter_default,
+  justfile: "
+    foo n=(2 * 3):
+      echo {{n}}
+  ",
+  stdout:   "6\n",
+  stderr:   "echo 6\n",
+}
+
+test! {
+  name:     overridden_variable,
+  justfile: "
+    base := 10
+    x := base + 1
+
+    foo:
+      echo {{x}}
+  ",
+  args:     ("base=20"),
+  stdout:   "21\n",
+  stderr:   "echo 21\n",
+}
+
+test! {
+  name:     non_integer_literal,
+  justfile: "
+    x := 'v' + 1
+  ",
+  stderr:   "
+    error: Operand `'v'` of `+` is not an integer
+      |
+    1 | x := 'v' + 1
+      |      ^^^
+  ",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     non_integer_value,
+  justfile: "
+    foo n:
+      echo {{n - 1}}
+  ",
+  args:     ("foo", "bar"),
+  stderr:   "
+    error: Operand `bar` of `-` is not an integer
+      |
+    2 |   echo {{n - 1}}
+      |            ^
+  ",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     division_by_zero,
+  justfile: "
+    x := 1 / 0
+
+    foo:
+      echo {{x}}
+  ",
+  stderr:   "
+    error: Division by zero
+      |
+    1 | x := 1 / 0
+      |        ^
+  ",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     overflow,
+  justfile: "
+    x := 9223372036854775807 + 1
+
+    foo:
+      echo {{x}}
+  ",
+  stderr:   "
+    error: Integer overflow evaluating `9223372036854775807 + 1`
+      |
+    1 | x := 9223372036854775807 + 1
+      |                          ^
+  ",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     too_large,
+  justfile: "
+    x := 9223372036854775808
+  ",
+  stderr:   "
+    error: Number `9223372036854775808` is too large
+      |
+    1 | x := 9223372036854775808
+      |      ^^^^^^^^^^^^^^^^^^^
+  ",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     evaluate,
+  justfile: "
+    x := 6 * 7
+  ",
+  args:     ("--evaluate"),
+  stdout:   "x := \"42\"\n",
+}
+
+test! {
+  name:     dump,
+  justfile: "
+    x := (1 + 2)*3
+  ",
+  args:     ("--dump"),
+  stdout:   "x := (1 + 2) * 3\n",
+}
@@ -0,0 +1,906 @@
+use crate::common::*;
+
+test! {
+  name:     doc_attribute_overrides_comment_in_list,
+  justfile: "
+    # comment
+    [doc('from attribute')]
+    foo:
+
+    [doc(\"escaped\\tdoc\")]
+    bar:
+
+    # plain comment
+    baz:
+  ",
+  args:     ("--list"),
+  stdout:   "
+    Available recipes:
+        bar # escaped\tdoc
+        baz # plain comment
+        foo # from attribute
+  ",
+}
+
+test! {
+  name:     doc_attribute_alias,
+  justfile: "
+    alias f := foo
+
+    [doc('some docs')]
+    foo:
+  ",
+  args:     ("--list"),
+  stdout:   "
+    Available recipes:
+        foo # some docs
+        f   # alias for `foo`
+  ",
+}
+
+test! {
+  name:     doc_attribute_show,
+  justfile: "
+    # comment
+    [doc('some docs')]
+    foo:
+      echo foo
+  ",
+  args:     ("--show", "foo"),
+  stdout:   r#"
+    # comment
+    [doc('some docs')]
+    foo:
+        echo foo
+  "#,
+}
+
+test! {
+  name:     doc_attribute_requires_string,
+  justfile: "
+    [doc(foo)]
+    foo:
+  ",
+  args:     ("foo"),
+  stdout:   "",
+  stderr:   "
+    error: Expected string, but found identifier
+      |
+    1 | [doc(foo)]
+      |      ^^^
+  ",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     private_attribute_list,
+  justfile: "
+    alias b := bar
+    [private]
+    alias f := foo
+
+    [private]
+    foo:
+
+    bar:
+  ",
+  args:     ("--list"),
+  stdout:   "
+    Available recipes:
+        bar
+        b   # alias for `bar`
+  ",
+}
+
+test! {
+  name:     private_attribute_summary,
+  justfile: "
+    [private]
+    foo:
+
+    bar:
+  ",
+  args:     ("--summary"),
+  stdout:   "bar\n",
+}
+
+test! {
+  name:     private_attribute_recipe_runnable,
+  justfile: "
+    [private]
+    alias f := foo
+
+    [private]
+    foo:
+      echo foo
+  ",
+  args:     ("f"),
+  stdout:   "foo\n",
+  stderr:   "echo foo\n",
+}
+
+test! {
+  name:     private_attribute_alias_of_public_recipe,
+  justfile: "
+    [private]
+    alias f := foo
+
+    foo:
+  ",
+  args:     ("--list"),
+  stdout:   "
+    Available recipes:
+        foo
+  ",
+}
+
+test! {
+  name:     alias_invalid_attribute,
+  justfile: "
+    [remember-args]
+    alias f := foo
+
+    foo:
+  ",
+  args:     ("foo"),
+  stdout:   "",
+  stderr:   "
+    error: Alias `f` has invalid attribute `remember-args`
+      |
+    2 | alias f := foo
+      |       ^
+  ",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     quiet_attribute,
+  justfile: "
+    [quiet]
+    foo:
+      echo foo
+      @echo bar
+  ",
+  stdout:   "foo\nbar\n",
+}
+
+test! {
+  name:     quiet_attribute_shebang,
+  justfile: "
+    [quiet]
+    @foo:
+      #!/usr/bin/env sh
+      echo foo
+  ",
+  stdout:   "foo\n",
+}
+
+test! {
+  name:     quiet_attribute_overridden_by_verbose_flag,
+  justfile: "
+    [quiet]
+    foo:
+      echo foo
+  ",
+  args:     ("--verbose"),
+  stdout:   "foo\n",
+  stderr:   "===> Running recipe `foo`...\necho foo\n",
+}
+
+test! {
+  name:     quiet_attribute_dry_run,
+  justfile: "
+    [quiet]
+    foo:
+      echo foo
+  ",
+  args:     ("--dry-run"),
+  stderr:   "echo foo\n",
+}
+
+test! {
+  name:     verbose_attribute,
+  justfile: "
+    [verbose]
+    @foo:
+      echo foo
+      @echo bar
+  ",
+  stdout:   "foo\nbar\n",
+  stderr:   "echo foo\necho bar\n",
+}
+
+test! {
+  name:     verbose_attribute_shebang,
+  justfile: "
+    [verbose]
+    foo:
+      #!/usr/bin/env sh
+      echo foo
+  ",
+  stdout:   "foo\n",
+  stderr:   "#!/usr/bin/env sh\necho foo\n",
+}
+
+test! {
+  name:     verbose_attribute_overridden_by_quiet_flag,
+  justfile: "
+    [verbose]
+    foo:
+      echo foo >&2
+  ",
+  args:     ("--quiet"),
+}
+
+test! {
+  name:     verbose_attribute_only_affects_recipe,
+  justfile: "
+    [verbose]
+    foo: bar
+      @echo foo
+
+    @bar:
+      echo bar
+  ",
+  stdout:   "bar\nfoo\n",
+  stderr:   "echo foo\n",
+}
+
+test! {
+  name:     confirm_accepted,
+  justfile: "
+    [confirm]
+    deploy:
+      echo deployed
+  ",
+  stdin:    "y\n",
+  stdout:   "deployed\n",
+  stderr:   "Run recipe `deploy`? (y/N) echo deployed\n",
+}
+
+test! {
+  name:     confirm_accepted_yes,
+  justfile: "
+    [confirm]
+    deploy:
+      echo deployed
+  ",
+  stdin:    "YES\n",
+  stdout:   "deployed\n",
+  stderr:   "Run recipe `deploy`? (y/N) echo deployed\n",
+}
+
+test! {
+  name:     confirm_declined,
+  justfile: "
+    [confirm]
+    deploy: build
+      echo deployed
+
+    build:
+      echo built
+  ",
+  stdin:    "n\n",
+  stderr:   "Run recipe `deploy`? (y/N) error: Recipe `deploy` was not confirmed\n",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     confirm_no_input,
+  justfile: "
+    [confirm]
+    deploy:
+      echo deployed
+  ",
+  stderr:   "Run recipe `deploy`? (y/N) error: Recipe `deploy` was not confirmed\n",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     confirm_custom_prompt,
+  justfile: "
+    [confirm('Deploy to production?')]
+    deploy:
+      echo deployed
+  ",
+  stdin:    "y\n",
+  stdout:   "deployed\n",
+  stderr:   "Deploy to production? (y/N) echo deployed\n",
+}
+
+test! {
+  name:     confirm_yes_flag,
+  justfile: "
+    [confirm]
+    deploy:
+      echo deployed
+  ",
+  args:     ("--yes"),
+  stdout:   "deployed\n",
+  stderr:   "echo deployed\n",
+}
+
+test! {
+  name:     confirm_dry_run,
+  justfile: "
+    [confirm]
+    deploy:
+      echo deployed
+  ",
+  args:     ("--dry-run"),
+  stderr:   "echo deployed\n",
+}
+
+test! {
+  name:     confirm_dependency,
+  justfile: "
+    deploy: build
+      echo deployed
+
+    [confirm]
+    build:
+      echo built
+  ",
+  stdin:    "y\n",
+  stdout:   "built\ndeployed\n",
+  stderr:   "Run recipe `build`? (y/N) echo built\necho deployed\n",
+}
+
+test! {
+  name:     cleanup,
+  justfile: "
+    [cleanup('down')]
+    up:
+      echo up
+
+    down:
+      echo down
+  ",
+  stdout:   "up\ndown\n",
+  stderr:   "echo up\necho down\n",
+}
+
+test! {
+  name:     cleanup_after_failure,
+  justfile: "
+    [cleanup('down')]
+    up:
+      echo up
+      exit 3
+
+    down:
+      echo down
+  ",
+  stdout:   "up\ndown\n",
+  stderr:   "echo up\nexit 3\necho down\nerror: Recipe `up` failed on line 4 with exit code 3\n",
+  status:   3,
+}
+
+test! {
+  name:     cleanup_failure,
+  justfile: "
+    [cleanup('down')]
+    up:
+      echo up
+
+    down:
+      exit 2
+  ",
+  stdout:   "up\n",
+  stderr:   "echo up\nexit 2\nerror: Recipe `down` failed on line 6 with exit code 2\n",
+  status:   2,
+}
+
+test! {
+  name:     cleanup_not_run_if_dependency_fails,
+  justfile: "
+    [cleanup('down')]
+    up: build
+      echo up
+
+    build:
+      exit 1
+
+    down:
+      echo down
+  ",
+  stderr:   "exit 1\nerror: Recipe `build` failed on line 6 with exit code 1\n",
+  status:   1,
+}
+
+test! {
+  name:     cleanup_dry_run,
+  justfile: "
+    [cleanup('down')]
+    up:
+      echo up
+
+    down:
+      echo down
+  ",
+  args:     ("--dry-run"),
+  stderr:   "echo up\necho down\n",
+}
+
+test! {
+  name:     shared_cleanup,
+  justfile: "
+    [cleanup('down')]
+    a:
+      echo a
+
+    [cleanup('down')]
+    b:
+      echo b
+
+    down:
+      echo down
+  ",
+  args:     ("a", "b"),
+  stdout:   "a\ndown\nb\ndown\n",
+  stderr:   "echo a\necho down\necho b\necho down\n",
+}
+
+test! {
+  name:     cleanup_also_dependency,
+  justfile: "
+    [cleanup('down')]
+    up: down
+      echo up
+
+    down:
+      echo down
+  ",
+  stdout:   "down\nup\ndown\n",
+  stderr:   "echo down\necho up\necho down\n",
+}
+
+test! {
+  name:     unknown_cleanup_recipe,
+  justfile: "
+    [cleanup('down')]
+    up:
+  ",
+  stderr:   "
+    error: Recipe `up` has unknown cleanup recipe `down`
+      |
+    2 | up:
+      | ^^
+  ",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     filter,
+  justfile: "
+    [filter('sed -e s/secret/***/')]
+    foo:
+      @echo the secret is out
+      @echo secret on stderr >&2
+  ",
+  stdout:   "the *** is out\n*** on stderr\n",
+}
+
+test! {
+  name:     filter_shebang,
+  justfile: "
+    [filter('tr a-z A-Z')]
+    foo:
+      #!/usr/bin/env sh
+      echo hello
+  ",
+  stdout:   "HELLO\n",
+}
+
+test! {
+  name:     filter_recipe_failure,
+  justfile: "
+    [filter('cat')]
+    foo:
+      @echo hello
+      @exit 3
+  ",
+  stdout:   "hello\n",
+  stderr:   "error: Recipe `foo` failed on line 4 with exit code 3\n",
+  status:   3,
+}
+
+test! {
+  name:     filter_failure,
+  justfile: "
+    [filter('exit 4')]
+    foo:
+      @echo hello
+  ",
+  stderr:   "error: Filter for recipe `foo` failed with exit code 4\n",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     requires_found,
+  justfile: "
+    [requires('sh', 'cat')]
+    foo:
+      @echo hello
+  ",
+  stdout:   "hello\n",
+}
+
+test! {
+  name:     requires_missing,
+  justfile: "
+    [requires('sh', 'just-missing-executable')]
+    foo: bar
+      @echo foo
+
+    [requires('just-missing-executable'), requires('just-other-missing-executable')]
+    bar:
+      @echo bar
+  ",
+  stderr:   "
+    error: Could not find required executables on PATH:
+      `just-missing-executable`, required by recipes `bar` and `foo`
+      `just-other-missing-executable`, required by recipe `bar`
+  ",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     requires_checked_before_dependencies_run,
+  justfile: "
+    foo: bar
+      @echo foo
+
+    [requires('just-missing-executable')]
+    bar:
+      @echo bar
+  ",
+  stderr:   "
+    error: Could not find required executable on PATH:
+      `just-missing-executable`, required by recipe `bar`
+  ",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     requires_cleanup_recipe,
+  justfile: "
+    [cleanup('bar')]
+    foo:
+      @echo foo
+
+    [requires('just-missing-executable')]
+    bar:
+      @echo bar
+  ",
+  stderr:   "
+    error: Could not find required executable on PATH:
+      `just-missing-executable`, required by recipe `bar`
+  ",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     requires_other_recipe,
+  justfile: "
+    foo:
+      @echo foo
+
+    [requires('just-missing-executable')]
+    bar:
+      @echo bar
+  ",
+  stdout:   "foo\n",
+}
+
+test! {
+  name:     requires_dry_run,
+  justfile: "
+    [requires('just-missing-executable')]
+    foo:
+      echo foo
+  ",
+  args:     ("--dry-run"),
+  stderr:   "echo foo\n",
+}
+
+test! {
+  name:     needs_env_set,
+  justfile: "
+    [needs-env('JUST_TEST_VARIABLE')]
+    foo:
+      @echo $JUST_TEST_VARIABLE
+  ",
+  env:      {
+    "JUST_TEST_VARIABLE": "hello",
+  },
+  stdout:   "hello\n",
+}
+
+test! {
+  name:     needs_env_missing,
+  justfile: "
+    [needs-env('JUST_MISSING_VARIABLE': 'the name of something', 'JUST_TEST_VARIABLE')]
+    foo: bar
+      @echo foo
+
+    [needs-env('JUST_MISSING_VARIABLE'), needs-env('JUST_OTHER_MISSING_VARIABLE')]
+    bar:
+      @echo bar
+  ",
+  env:      {
+    "JUST_TEST_VARIABLE": "hello",
+  },
+  stderr:   "
+    error: Required environment variables not set:
+      `JUST_MISSING_VARIABLE` (the name of something), required by recipes `bar` and `foo`
+      `JUST_OTHER_MISSING_VARIABLE`, required by recipe `bar`
+  ",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     needs_env_exported,
+  justfile: "
+    export JUST_EXPORTED_VARIABLE := 'hello'
+
+    [needs-env('JUST_EXPORTED_VARIABLE')]
+    foo:
+      @echo $JUST_EXPORTED_VARIABLE
+  ",
+  stdout:   "hello\n",
+}
+
+test! {
+  name:     needs_env_not_exported,
+  justfile: "
+    JUST_UNEXPORTED_VARIABLE := 'hello'
+
+    [needs-env('JUST_UNEXPORTED_VARIABLE')]
+    foo:
+      @echo foo
+  ",
+  stderr:   "
+    error: Required environment variable not set:
+      `JUST_UNEXPORTED_VARIABLE`, required by recipe `foo`
+  ",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     needs_env_dotenv,
+  justfile: "
+    [needs-env('DOTENV_KEY')]
+    foo:
+      @echo $DOTENV_KEY
+  ",
+  stdout:   "dotenv-value\n",
+}
+
+test! {
+  name:     needs_env_dry_run,
+  justfile: "
+    [needs-env('JUST_MISSING_VARIABLE')]
+    foo:
+      echo foo
+  ",
+  args:     ("--dry-run"),
+  stderr:   "echo foo\n",
+}
+
+test! {
+  name:     groups,
+  justfile: "
+    alias t := test
+
+    [group('ci')]
+    lint:
+
+    # run tests
+    [group('ci'), group('dev')]
+    test:
+
+    [group('dev')]
+    watch:
+
+    build:
+  ",
+  args:     ("--list", "--groups"),
+  stdout:   "
+    Available recipes:
+        build
+
+        [ci]
+        lint
+        test  # run tests
+        t     # alias for `test`
+
+        [dev]
+        test  # run tests
+        t     # alias for `test`
+        watch
+  ",
+}
+
+test! {
+  name:     groups_unsorted,
+  justfile: "
+    [group('z')]
+    b:
+
+    [group('a')]
+    a:
+  ",
+  args:     ("--list", "--groups", "--unsorted"),
+  stdout:   "
+    Available recipes:
+        [z]
+        b
+
+        [a]
+        a
+  ",
+}
+
+test! {
+  name:     groups_ignored_without_flag,
+  justfile: "
+    [group('ci')]
+    lint:
+
+    build:
+  ",
+  args:     ("--list"),
+  stdout:   "
+    Available recipes:
+        build
+        lint
+  ",
+}
+
+test! {
+  name:     groups_private,
+  justfile: "
+    [group('ci'), private]
+    lint:
+
+    build:
+  ",
+  args:     ("--list", "--groups"),
+  stdout:   "
+    Available recipes:
+        build
+  ",
+}
+
+#[cfg(unix)]
+test! {
+  name:     umask,
+  justfile: "
+    [umask('077')]
+    foo:
+      @umask
+
+    [umask('0o022')]
+    bar:
+      #!/bin/sh
+      umask
+  ",
+  args:     ("foo", "bar"),
+  stdout:   "0077\n0022\n",
+}
+
+#[cfg(unix)]
+test! {
+  name:     limit,
+  justfile: "
+    [limit('nofile', '64')]
+    foo:
+      @ulimit -n
+  ",
+  stdout:   "64\n",
+}
+
+#[cfg(unix)]
+test! {
+  name:     nice,
+  justfile: "
+    [nice('19')]
+    foo:
+      @nice
+  ",
+  stdout:   "19\n",
+}
+
+test! {
+  name:     limit_invalid_resource,
+  justfile: "
+    [limit('files', '64')]
+    foo:
+  ",
+  stderr:   "
+    error: Invalid argument `files` for attribute `limit`, expected `core`, `cpu`, `data`, `fsize`, `nofile`, `nproc`, or `stack`
+      |
+    1 | [limit('files', '64')]
+      |        ^^^^^^^
+  ",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     once_dependency,
+  justfile: "
+    all: a b
+
+    a: stamp
+    b: stamp
+
+    [once]
+    stamp:
+      @echo stamp
+  ",
+  stdout:   "stamp\n",
+}
+
+test! {
+  name:     fresh_dependency,
+  justfile: "
+    all: a b
+
+    a: stamp
+      @echo a
+
+    b: stamp
+      @echo b
+
+    [fresh]
+    stamp:
+      @echo stamp
+  ",
+  stdout:   "
+    stamp
+    a
+    stamp
+    b
+  ",
+}
+
+test! {
+  name:     fresh_command_line,
+  justfile: "
+    [fresh]
+    stamp:
+      @echo stamp
+  ",
+  args:     ("stamp", "stamp"),
+  stdout:   "stamp\nstamp\n",
+}
+
+test! {
+  name:     fresh_dependencies_run_once,
+  justfile: "
+    [fresh]
+    a: b
+      @echo a
+
+    b:
+      @echo b
+  ",
+  args:     ("a", "a"),
+  stdout:   "b\na\na\n",
+}
+
+test! {
+  name:     fresh_once_conflict,
+  justfile: "
+    [fresh, once]
+    stamp:
+  ",
+  stderr:   "
+    error: Attributes `fresh` and `once` may not be used together
+      |
+    1 | [fresh, once]
+      |         ^^^^
+  ",
+  status:   EXIT_FAILURE,
+}
@@ -0,0 +1,105 @@
+use crate::common::*;
+
+test! {
+  name:     literals,
+  justfile: "
+    foo:
+      echo {{ true }} {{ false }}
+  ",
+  stdout:   "true false\n",
+  stderr:   "echo true false\n",
+}
+
+test! {
+  name:     comparisons,
+  justfile: "
+    a := 'x' == 'x'
+    b := 'x' == 'y'
+    c := 'x' != 'x'
+    d := 'x' != 'y'
+  ",
+  args:     ("--evaluate"),
+  stdout:   "
+    a := \"true\"
+    b := \"false\"
+    c := \"false\"
+    d := \"true\"
+  ",
+}
+
+test! {
+  name:     comparison_precedence,
+  justfile: "
+    x := '' || 'a' == 'a' + ''
+  ",
+  args:     ("--evaluate"),
+  stdout:   "x := \"true\"\n",
+}
+
+test! {
+  name:     comparison_in_conditional,
+  justfile: "
+    x := if ('a' == 'a') == true { 'yes' } else { 'no' }
+  ",
+  args:     ("--evaluate"),
+  stdout:   "x := \"yes\"\n",
+}
+
+test! {
+  name:     setting_expression_true,
+  justfile: "
+    set export := env_var_or_default('JUST_TEST_EXPORT', 'false') == 'true'
+
+    x := 'hello'
+
+    foo:
+      @echo ${x:-unset}
+  ",
+  env:      {
+    "JUST_TEST_EXPORT": "true",
+  },
+  stdout:   "hello\n",
+}
+
+test! {
+  name:     setting_expression_false,
+  justfile: "
+    set export := env_var_or_default('JUST_TEST_EXPORT', 'false') == 'true'
+
+    x := 'hello'
+
+    foo:
+      @echo ${x:-unset}
+  ",
+  stdout:   "unset\n",
+}
+
+test! {
+  name:     setting_expression_invalid_value,
+  justfile: "
+    set export := 'yes'
+  ",
+  stderr:   "
+    error: Setting `export` evaluated to `yes`, but must be `true` or `false`
+      |
+    1 | set export := 'yes'
+      |     ^^^^^^
+  ",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     setting_expression_variable,
+  justfile: "
+    x := 'true'
+
+    set export := x
+  ",
+  stderr:   "
+    error: Setting `export` refers to variable `x`, but settings are evaluated before variables
+      |
+    3 | set export := x
+      |               ^
+  ",
+  status:   EXIT_FAILURE,
+}
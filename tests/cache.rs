@@ -0,0 +1,115 @@
+use crate::common::*;
+
+const JUSTFILE: &str = "
+alias b := build
+
+# build it
+build target:
+
+_private:
+";
+
+#[test]
+fn list_uses_cached_metadata() {
+  let tmp = tmptree! {
+    justfile: JUSTFILE,
+  };
+
+  let list = || {
+    let output = just(tmp.path(), &["--cache-dir", "cache", "--list"]);
+
+    assert!(output.status.success());
+
+    String::from_utf8(output.stdout).unwrap()
+  };
+
+  let expected = "Available recipes:
+    build target # build it
+    b target     # alias for `build`
+";
+
+  assert_eq!(list(), expected);
+
+  let entries = fs::read_dir(tmp.path().join("cache"))
+    .unwrap()
+    .map(|entry| entry.unwrap().path())
+    .collect::<Vec<_>>();
+
+  assert_eq!(entries.len(), 1);
+
+  let cached = fs::read_to_string(&entries[0]).unwrap();
+
+  fs::write(&entries[0], cached.replace("build it", "from cache")).unwrap();
+
+  assert_eq!(list(), expected.replace("build it", "from cache"));
+}
+
+#[test]
+fn summary_invalid_cache_ignored() {
+  let tmp = tmptree! {
+    justfile: JUSTFILE,
+  };
+
+  let summary = || {
+    let output = just(tmp.path(), &["--cache-dir", "cache", "--summary"]);
+
+    assert!(output.status.success());
+
+    String::from_utf8(output.stdout).unwrap()
+  };
+
+  assert_eq!(summary(), "build\n");
+
+  for entry in fs::read_dir(tmp.path().join("cache")).unwrap() {
+    fs::write(entry.unwrap().path(), "garbage").unwrap();
+  }
+
+  assert_eq!(summary(), "build\n");
+}
+
+#[test]
+fn no_cache_by_default() {
+  let tmp = tmptree! {
+    justfile: JUSTFILE,
+  };
+
+  let output = just(tmp.path(), &["--list"]);
+
+  assert!(output.status.success());
+
+  assert_eq!(fs::read_dir(tmp.path()).unwrap().count(), 1);
+}
+
+#[test]
+fn list_interpolates_cached_docs() {
+  let tmp = tmptree! {
+    justfile: "
+env := env_var_or_default('DEPLOY_ENV', 'staging')
+
+# deploy to {{env}}
+deploy:
+",
+  };
+
+  let list = |env: &str| {
+    let output = just_command(tmp.path())
+      .args(&["--cache-dir", "cache", "--list"])
+      .env("DEPLOY_ENV", env)
+      .output()
+      .unwrap();
+
+    assert!(output.status.success());
+
+    String::from_utf8(output.stdout).unwrap()
+  };
+
+  assert_eq!(
+    list("staging"),
+    "Available recipes:\n    deploy # deploy to staging\n"
+  );
+
+  assert_eq!(
+    list("production"),
+    "Available recipes:\n    deploy # deploy to production\n"
+  );
+}
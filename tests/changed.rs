@@ -0,0 +1,140 @@
+use crate::common::*;
+
+const JUSTFILE: &str = "
+[inputs('src/**/*.rs')]
+test-unit:
+  @echo unit
+
+[inputs('docs/**'), inputs('README.md')]
+test-docs:
+  @echo docs
+
+test-all:
+  @echo all
+
+[inputs('src/**/*.rs')]
+build:
+  @echo build
+";
+
+fn git(tmp: &tempfile::TempDir, args: &[&str]) {
+  let status = Command::new("git")
+    .current_dir(tmp.path())
+    .args(&[
+      "-c",
+      "user.name=just",
+      "-c",
+      "user.email=just@example.com",
+      "-c",
+      "commit.gpgsign=false",
+    ])
+    .args(args)
+    .status()
+    .unwrap();
+
+  assert!(status.success());
+}
+
+fn repository() -> tempfile::TempDir {
+  let tmp = tmptree! {
+    justfile: JUSTFILE,
+    "README.md": "",
+    src: {
+      "main.rs": "",
+    },
+    docs: {
+      "guide.md": "",
+    },
+  };
+
+  git(&tmp, &["init", "--quiet"]);
+  git(&tmp, &["add", "--all"]);
+  git(&tmp, &["commit", "--quiet", "--message", "initial"]);
+
+  tmp
+}
+
+#[test]
+fn nothing_changed() {
+  let tmp = repository();
+
+  let output = just(tmp.path(), &["--changed", "test-*"]);
+
+  assert!(output.status.success());
+  assert_stdout(&output, "");
+  assert_eq!(
+    str::from_utf8(&output.stderr).unwrap(),
+    "No recipes matching `test-*` have inputs changed since `HEAD`.\n"
+  );
+}
+
+#[test]
+fn uncommitted_change() {
+  let tmp = repository();
+
+  fs::write(tmp.path().join("src/main.rs"), "fn main() {}").unwrap();
+
+  let output = just(tmp.path(), &["--changed", "test-*"]);
+
+  assert!(output.status.success());
+  assert_stdout(&output, "unit\n");
+}
+
+#[test]
+fn untracked_file() {
+  let tmp = repository();
+
+  fs::write(tmp.path().join("docs/faq.md"), "").unwrap();
+
+  let output = just(tmp.path(), &["--changed", "*"]);
+
+  assert!(output.status.success());
+  assert_stdout(&output, "docs\n");
+}
+
+#[test]
+fn changed_since() {
+  let tmp = repository();
+
+  fs::write(tmp.path().join("README.md"), "# just").unwrap();
+  fs::write(tmp.path().join("src/main.rs"), "fn main() {}").unwrap();
+  git(&tmp, &["commit", "--quiet", "--all", "--message", "second"]);
+
+  let output = just(tmp.path(), &["--changed", "*"]);
+  assert!(output.status.success());
+  assert_stdout(&output, "");
+
+  let output = just(tmp.path(), &["--changed", "*", "--changed-since", "HEAD~1"]);
+  assert!(output.status.success());
+  assert_stdout(&output, "build\ndocs\nunit\n");
+}
+
+#[test]
+fn no_matching_recipes() {
+  let tmp = repository();
+
+  let output = just(tmp.path(), &["--changed", "deploy-*"]);
+
+  assert!(!output.status.success());
+  assert_eq!(
+    str::from_utf8(&output.stderr).unwrap(),
+    "Justfile contains no recipes matching `deploy-*` which can be run without arguments.\n"
+  );
+}
+
+#[test]
+fn invalid_ref() {
+  let tmp = repository();
+
+  let output = just(tmp.path(), &[
+    "--changed",
+    "*",
+    "--changed-since",
+    "nonexistent",
+  ]);
+
+  assert!(!output.status.success());
+  assert!(str::from_utf8(&output.stderr).unwrap().starts_with(
+    "Failed to get files changed since `nonexistent`: `git merge-base nonexistent HEAD` failed:"
+  ));
+}
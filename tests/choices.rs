@@ -0,0 +1,149 @@
+use crate::common::*;
+
+const JUSTFILE: &str = "
+  [choices(env: 'staging', 'prod')]
+  deploy env:
+    echo {{env}}
+";
+
+test! {
+  name:     valid,
+  justfile: JUSTFILE,
+  args:     ("deploy", "prod"),
+  stdout:   "prod\n",
+  stderr:   "echo prod\n",
+}
+
+test! {
+  name:     invalid,
+  justfile: JUSTFILE,
+  args:     ("deploy", "production"),
+  stderr:   "
+    error: Invalid value `production` for parameter `env` of recipe `deploy`, expected `staging` or `prod`
+      |
+    1 | [choices(env: 'staging', 'prod')]
+      |          ^^^
+  ",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     checked_before_any_recipe_runs,
+  justfile: "
+    build:
+      echo build
+
+    [choices(env: 'staging', 'prod')]
+    deploy env:
+      echo {{env}}
+  ",
+  args:     ("build", "deploy", "dev"),
+  stderr:   "
+    error: Invalid value `dev` for parameter `env` of recipe `deploy`, expected `staging` or `prod`
+      |
+    4 | [choices(env: 'staging', 'prod')]
+      |          ^^^
+  ",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     dependency_arguments,
+  justfile: "
+    release: (deploy 'dev')
+
+    [choices(env: 'staging', 'prod')]
+    deploy env:
+      echo {{env}}
+  ",
+  args:     ("release"),
+  stderr:   "
+    error: Invalid value `dev` for parameter `env` of recipe `deploy`, expected `staging` or `prod`
+      |
+    3 | [choices(env: 'staging', 'prod')]
+      |          ^^^
+  ",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     default_not_checked,
+  justfile: "
+    [choices(env: 'staging', 'prod')]
+    deploy env='dev':
+      echo {{env}}
+  ",
+  stdout:   "dev\n",
+  stderr:   "echo dev\n",
+}
+
+test! {
+  name:     variadic,
+  justfile: "
+    [choices(targets: 'linux', 'macos', 'windows')]
+    build +targets:
+      echo {{targets}}
+  ",
+  args:     ("build", "linux", "windows"),
+  stdout:   "linux windows\n",
+  stderr:   "echo linux windows\n",
+}
+
+test! {
+  name:     variadic_invalid,
+  justfile: "
+    [choices(targets: 'linux', 'macos', 'windows')]
+    build +targets:
+      echo {{targets}}
+  ",
+  args:     ("build", "linux", "bsd"),
+  stderr:   "
+    error: Invalid value `bsd` for parameter `targets` of recipe `build`, expected `linux`, `macos`, or `windows`
+      |
+    1 | [choices(targets: 'linux', 'macos', 'windows')]
+      |          ^^^^^^^
+  ",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     other_parameters_unchecked,
+  justfile: "
+    [choices(env: 'staging', 'prod')]
+    deploy version env:
+      echo {{version}} {{env}}
+  ",
+  args:     ("deploy", "1.0", "prod"),
+  stdout:   "1.0 prod\n",
+  stderr:   "echo 1.0 prod\n",
+}
+
+test! {
+  name:     alias_arguments,
+  justfile: "
+    alias d := deploy 'dev'
+
+    [choices(env: 'staging', 'prod')]
+    deploy env:
+      echo {{env}}
+  ",
+  args:     ("d"),
+  stderr:   "
+    error: Invalid value `dev` for parameter `env` of recipe `deploy`, expected `staging` or `prod`
+      |
+    3 | [choices(env: 'staging', 'prod')]
+      |          ^^^
+  ",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     show,
+  justfile: JUSTFILE,
+  args:     ("--show", "deploy"),
+  stdout:   "
+    [choices(env: 'staging', 'prod')]
+    deploy env:
+        echo {{env}}
+  ",
+}
@@ -0,0 +1,108 @@
+use crate::common::*;
+
+test! {
+  name:     nonempty_lhs,
+  justfile: "
+    x := 'a' || 'b'
+
+    foo:
+      echo {{ x }}
+  ",
+  stdout:   "a\n",
+  stderr:   "echo a\n",
+}
+
+test! {
+  name:     empty_lhs,
+  justfile: "
+    x := '' || 'b'
+
+    foo:
+      echo {{ x }}
+  ",
+  stdout:   "b\n",
+  stderr:   "echo b\n",
+}
+
+test! {
+  name:     rhs_unevaluated,
+  justfile: "
+    foo:
+      echo {{ 'a' || `exit 1` }}
+  ",
+  stdout:   "a\n",
+  stderr:   "echo a\n",
+}
+
+test! {
+  name:     chained,
+  justfile: "
+    foo:
+      echo {{ '' || '' || 'c' || 'd' }}
+  ",
+  stdout:   "c\n",
+  stderr:   "echo c\n",
+}
+
+test! {
+  name:     binds_less_tightly_than_addition,
+  justfile: "
+    foo:
+      echo {{ '' + '' || 'a' + 'b' }}
+  ",
+  stdout:   "ab\n",
+  stderr:   "echo ab\n",
+}
+
+test! {
+  name:     environment_variable,
+  justfile: "
+    target := env_var_or_default('TARGET', '') || 'debug'
+
+    foo:
+      echo {{ target }}
+  ",
+  args:     ("--evaluate", "target"),
+  env:      { "TARGET": "", },
+  stdout:   "debug",
+}
+
+test! {
+  name:     parameter_default,
+  justfile: "
+    x := ''
+
+    foo a=(x || 'b'):
+      echo {{ a }}
+  ",
+  stdout:   "b\n",
+  stderr:   "echo b\n",
+}
+
+test! {
+  name:     dump,
+  justfile: "
+    x := y || 'b'
+    y := ''
+  ",
+  args:     ("--dump"),
+  stdout:   "
+    x := y || 'b'
+
+    y := ''
+  ",
+}
+
+test! {
+  name:     single_bar,
+  justfile: "
+    x := 'a' | 'b'
+  ",
+  stderr:   "
+    error: Expected character `|`
+      |
+    1 | x := 'a' | 'b'
+      |           ^
+  ",
+  status:   EXIT_FAILURE,
+}
@@ -0,0 +1,119 @@
+use crate::common::*;
+
+test! {
+  name: exported_variables,
+  justfile: "
+    export FOO := 'foo'
+    bar := 'bar'
+  ",
+  args: ("--command", "sh", "-c", "echo $FOO ${bar:-unset}"),
+  stdout: "foo unset\n",
+}
+
+test! {
+  name: export_setting,
+  justfile: "
+    set export
+
+    bar := 'bar'
+  ",
+  args: ("--command", "printenv", "bar"),
+  stdout: "bar\n",
+}
+
+test! {
+  name: overrides,
+  justfile: "
+    export FOO := 'foo'
+  ",
+  args: ("--set", "FOO", "bar", "--command", "printenv", "FOO"),
+  stdout: "bar\n",
+}
+
+test! {
+  name: exec,
+  justfile: "
+    export FOO := 'foo'
+  ",
+  args: ("--exec", "printenv", "FOO"),
+  stdout: "foo\n",
+}
+
+test! {
+  name: dotenv,
+  justfile: "",
+  args: ("--command", "printenv", "DOTENV_KEY"),
+  stdout: "dotenv-value\n",
+}
+
+test! {
+  name: no_recipes,
+  justfile: "",
+  args: ("--command", "echo", "hello"),
+  stdout: "hello\n",
+}
+
+test! {
+  name: dry_run,
+  justfile: "
+    x := 'y'
+  ",
+  args: ("--dry-run", "--command", "echo", "hello"),
+  stderr: "echo hello\n",
+}
+
+test! {
+  name: verbose,
+  justfile: "",
+  args: ("--verbose", "--command", "echo", "hello"),
+  stdout: "hello\n",
+  stderr: "echo hello\n",
+}
+
+test! {
+  name: exit_code,
+  justfile: "",
+  args: ("--command", "sh", "-c", "exit 42"),
+  stderr: "error: Command `sh` failed with exit code 42\n",
+  status: 42,
+}
+
+#[test]
+fn not_found() {
+  let tmp = tmptree! {
+    justfile: "",
+  };
+
+  let output = Command::new(executable_path("just"))
+    .current_dir(tmp.path())
+    .args(&["--command", "just-command-that-does-not-exist"])
+    .output()
+    .unwrap();
+
+  assert_eq!(output.status.code(), Some(EXIT_FAILURE));
+  assert!(str::from_utf8(&output.stderr).unwrap().starts_with(
+    "error: Command `just-command-that-does-not-exist` could not be run because of an IO error:\n"
+  ));
+}
+
+#[test]
+fn working_directory() {
+  let tmp = tmptree! {
+    justfile: "",
+    sub: {},
+  };
+
+  let output = Command::new(executable_path("just"))
+    .current_dir(tmp.path().join("sub"))
+    .args(&["--command", "pwd"])
+    .output()
+    .unwrap();
+
+  assert!(output.status.success());
+  assert_eq!(
+    Path::new(str::from_utf8(&output.stdout).unwrap().trim())
+      .canonicalize()
+      .unwrap(),
+    tmp.path().canonicalize().unwrap()
+  );
+}
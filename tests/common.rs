@@ -3,9 +3,10 @@ pub(crate) use std::{
   env, fs,
   io::Write,
   iter,
-  path::Path,
-  process::{Command, Stdio},
+  path::{Path, PathBuf},
+  process::{Command, Output, Stdio},
   str,
+  time::{SystemTime, UNIX_EPOCH},
 };
 
 pub(crate) use executable_path::executable_path;
@@ -13,3 +14,26 @@ pub(crate) use just::unindent;
 pub(crate) use libc::{EXIT_FAILURE, EXIT_SUCCESS};
 pub(crate) use test_utilities::{assert_stdout, tempdir, tmptree};
 pub(crate) use which::which;
+
+/// Run `just` in `dir` with `args`
+pub(crate) fn just(dir: &Path, args: &[&str]) -> Output {
+  just_command(dir).args(args).output().unwrap()
+}
+
+/// A `Command` which runs `just` in `dir`, for tests which also need to
+/// change its environment
+pub(crate) fn just_command(dir: &Path) -> Command {
+  let mut command = Command::new(executable_path("just"));
+  command.current_dir(dir);
+  command
+}
+
+/// Whether `output` indicates success, and its stdout and stderr, for tests
+/// which check several runs
+pub(crate) fn outcome(output: Output) -> (bool, String, String) {
+  (
+    output.status.success(),
+    String::from_utf8(output.stdout).unwrap(),
+    String::from_utf8(output.stderr).unwrap(),
+  )
+}
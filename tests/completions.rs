@@ -1,7 +1,8 @@
-use std::process::Command;
+use std::{fs, process::Command};
 
 use executable_path::executable_path;
 use tempfile::tempdir;
+use test_utilities::{assert_stdout, tmptree};
 
 #[test]
 fn output() {
@@ -20,3 +21,93 @@ fn output() {
 
   assert!(text.starts_with("_just() {"));
 }
+
+#[test]
+fn complete() {
+  let tmp = tmptree! {
+    justfile: "
+[path('file')]
+edit file mode:
+
+build +targets:
+
+[private]
+hidden:
+
+alias b := build
+",
+    sub: {
+      "other.just": "other:",
+    },
+  };
+
+  let recipes = "recipe\nb\nbuild\nedit\n";
+
+  let cases: &[(&[&str], &str)] = &[
+    (&[], recipes),
+    (&["edit"], "path\n"),
+    (&["edit", "foo"], "argument\nmode\n"),
+    (&["edit", "foo", "bar"], recipes),
+    (&["build"], "argument\n+targets\n"),
+    (&["build", "a", "b"], "argument\n+targets\n"),
+    (&["--dry-run", "edit"], "path\n"),
+    (&["x=y", "edit"], "path\n"),
+    (&["b"], "argument\n+targets\n"),
+    (&["--justfile", "sub/other.just"], "recipe\nother\n"),
+    (&["-f", "sub/other.just", "--dry-run"], "recipe\nother\n"),
+    (&["--list"], ""),
+  ];
+
+  for (words, expected) in cases {
+    let output = Command::new(executable_path("just"))
+      .arg("--complete")
+      .args(*words)
+      .current_dir(tmp.path())
+      .output()
+      .unwrap();
+
+    assert_stdout(&output, expected);
+  }
+}
+
+#[test]
+fn complete_cached() {
+  let tmp = tmptree! {
+    justfile: "
+[path('file')]
+edit file mode:
+",
+  };
+
+  // the first completion caches the metadata, and the second uses it
+  for _ in 0..2 {
+    let output = Command::new(executable_path("just"))
+      .args(&["--complete", "--cache-dir", "cache", "edit"])
+      .current_dir(tmp.path())
+      .output()
+      .unwrap();
+
+    assert_stdout(&output, "path\n");
+
+    assert_eq!(fs::read_dir(tmp.path().join("cache")).unwrap().count(), 1);
+  }
+}
+
+#[test]
+fn complete_option_value() {
+  let tmp = tmptree! {
+    justfile: "foo:",
+  };
+
+  for words in &[&["--justfile"][..], &["-f"], &["--color"], &["--shell"]] {
+    let output = Command::new(executable_path("just"))
+      .arg("--complete")
+      .args(*words)
+      .current_dir(tmp.path())
+      .output()
+      .unwrap();
+
+    assert!(!output.status.success(), "{:?} succeeded", words);
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "");
+  }
+}
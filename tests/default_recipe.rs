@@ -0,0 +1,135 @@
+use crate::common::*;
+
+test! {
+  name:     setting,
+  justfile: "
+    set default := 'build'
+
+    test:
+      @echo test
+
+    build:
+      @echo build
+  ",
+  stdout:   "build\n",
+}
+
+test! {
+  name:     setting_alias,
+  justfile: "
+    set default := 'b'
+
+    alias b := build
+
+    test:
+      @echo test
+
+    build:
+      @echo build
+  ",
+  stdout:   "build\n",
+}
+
+test! {
+  name:     setting_optional_parameters,
+  justfile: "
+    set default := 'build'
+
+    test:
+      @echo test
+
+    build target='debug':
+      @echo {{target}}
+  ",
+  stdout:   "debug\n",
+}
+
+test! {
+  name:     setting_private_recipe,
+  justfile: "
+    set default := '_help'
+
+    test:
+      @echo test
+
+    _help:
+      @echo help
+  ",
+  stdout:   "help\n",
+}
+
+test! {
+  name:     recipes_given,
+  justfile: "
+    set default := 'build'
+
+    test:
+      @echo test
+
+    build:
+      @echo build
+  ",
+  args:     ("test"),
+  stdout:   "test\n",
+}
+
+test! {
+  name:     unknown_recipe_default,
+  justfile: "
+    set default := 'run'
+    set unknown-recipe := 'default'
+
+    build:
+      @echo build
+
+    run *args:
+      @echo {{args}}
+  ",
+  args:     ("lint"),
+  stdout:   "lint\n",
+}
+
+test! {
+  name:     unknown,
+  justfile: "
+    set default := 'biuld'
+
+    build:
+  ",
+  stderr:   "
+    error: Default recipe `biuld` does not exist
+      |
+    1 | set default := 'biuld'
+      |     ^^^^^^^
+  ",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     requires_arguments,
+  justfile: "
+    set default := 'build'
+
+    test:
+
+    build target:
+  ",
+  args:     ("test"),
+  stderr:   "
+    error: Recipe `build` cannot be used as default recipe since it requires at least 1 argument.
+      |
+    1 | set default := 'build'
+      |     ^^^^^^^
+  ",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     fmt,
+  justfile: "
+    set default := 'build'
+
+    build:
+  ",
+  args:     ("--fmt", "--check"),
+}
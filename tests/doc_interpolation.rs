@@ -0,0 +1,138 @@
+use crate::common::*;
+
+test! {
+  name:     list,
+  justfile: "
+    env := env_var_or_default('DEPLOY_ENV', 'staging')
+
+    # deploy to {{env}}
+    deploy:
+  ",
+  args:     ("--list"),
+  stdout:   "
+    Available recipes:
+        deploy # deploy to staging
+  ",
+}
+
+test! {
+  name:     list_environment,
+  justfile: "
+    env := env_var_or_default('DEPLOY_ENV', 'staging')
+
+    # deploy to {{ env }}
+    deploy:
+  ",
+  args:     ("--list"),
+  env:      {
+    "DEPLOY_ENV": "production",
+  },
+  stdout:   "
+    Available recipes:
+        deploy # deploy to production
+  ",
+}
+
+test! {
+  name:     list_fallback,
+  justfile: "
+    env := error('unconfigured')
+
+    # deploy to {{env}} with {{ tool }}
+    deploy:
+  ",
+  args:     ("--list"),
+  stdout:   "
+    Available recipes:
+        deploy # deploy to {{env}} with {{ tool }}
+  ",
+}
+
+test! {
+  name:     list_recipe,
+  justfile: "
+    env := 'staging'
+
+    # deploy to {{env}}
+    deploy: build
+
+    # build for {{env}}
+    build:
+  ",
+  args:     ("--list", "deploy"),
+  stdout:   "
+    deploy    # deploy to staging
+        build # build for staging
+  ",
+}
+
+test! {
+  name:     safe,
+  justfile: "
+    env := env_var_or_default('DEPLOY_ENV', 'staging')
+
+    # deploy to {{env}}
+    deploy:
+  ",
+  args:     ("--safe", "--list"),
+  stdout:   "
+    Available recipes:
+        deploy # deploy to {{env}}
+  ",
+}
+
+test! {
+  name:     dump_unchanged,
+  justfile: "
+    env := 'staging'
+
+    # deploy to {{env}}
+    deploy:
+  ",
+  args:     ("--dump"),
+  stdout:   "
+    env := 'staging'
+
+    # deploy to {{env}}
+    deploy:
+  ",
+}
+
+test! {
+  name:     list_overrides,
+  justfile: "
+    env := 'staging'
+
+    # deploy to {{env}}
+    deploy:
+  ",
+  args:     ("--set", "env", "production", "--list"),
+  stdout:   "
+    Available recipes:
+        deploy # deploy to production
+  ",
+}
+
+#[test]
+fn list_no_metadata_side_effects() {
+  let tmp = tmptree! {
+    justfile: "
+set metadata-side-effects := false
+
+marker := `touch ran`
+env := 'staging'
+
+# deploy {{marker}} to {{env}}
+deploy:
+",
+  };
+
+  let output = just(tmp.path(), &["--list"]);
+
+  assert!(output.status.success());
+  assert_eq!(
+    String::from_utf8_lossy(&output.stdout),
+    "Available recipes:\n    deploy # deploy {{marker}} to staging\n"
+  );
+  assert!(!tmp.path().join("ran").exists());
+}
@@ -66,6 +66,72 @@ test! {
   dotenv_load: false,
 }
 
+#[test]
+fn set_dotenv_files() {
+  let tmp = tmptree! {
+    ".env": "A=env\nB=env",
+    ".env.local": "B=local\nC=local",
+    justfile: "
+set dotenv-files := ['.env', '.env.local', '.env.missing']
+
+default:
+  @echo $A $B $C
+",
+  };
+
+  let output = process::Command::new(executable_path("just"))
+    .current_dir(tmp.path())
+    .output()
+    .expect("just invocation failed");
+
+  assert_eq!(output.status.code().unwrap(), 0);
+
+  let stdout = str::from_utf8(&output.stdout).unwrap();
+  assert_eq!(stdout, "env local local\n");
+}
+
+#[test]
+fn set_dotenv_files_replaces_default() {
+  let tmp = tmptree! {
+    ".env": "A=env",
+    ".env.ci": "B=ci",
+    justfile: "set dotenv-files := ['.env.ci']\n\ndefault:\n  @echo ${A:-unset} $B",
+  };
+
+  let output = process::Command::new(executable_path("just"))
+    .current_dir(tmp.path())
+    .output()
+    .expect("just invocation failed");
+
+  assert_eq!(output.status.code().unwrap(), 0);
+
+  let stdout = str::from_utf8(&output.stdout).unwrap();
+  assert_eq!(stdout, "unset ci\n");
+}
+
+#[test]
+fn set_dotenv_files_error() {
+  let tmp = tmptree! {
+    ".env.bad": "A='unterminated",
+    justfile: "set dotenv-files := ['.env.bad']\n\ndefault:",
+  };
+
+  let output = process::Command::new(executable_path("just"))
+    .current_dir(tmp.path())
+    .output()
+    .expect("just invocation failed");
+
+  assert_eq!(output.status.code().unwrap(), 1);
+
+  let stderr = str::from_utf8(&output.stderr).unwrap();
+  assert!(
+    stderr.starts_with("error: Failed to load environment file `")
+      && stderr.contains(".env.bad`: "),
+    "unexpected stderr: {}",
+    stderr
+  );
+}
+
 // Un-comment this on 2021-07-01.
 //
 // test! {
@@ -91,3 +157,92 @@ test! {
 //   ",
 //   dotenv_load: false,
 // }
+
+#[test]
+fn dotenv_file_flag() {
+  let tmp = tmptree! {
+    ".env": "A=env",
+    ci: {
+      "vars": "A=ci",
+    },
+    justfile: "set dotenv-load := false\n\ndefault:\n  @echo $A",
+  };
+
+  let output = process::Command::new(executable_path("just"))
+    .current_dir(tmp.path())
+    .args(&["--dotenv-file", "ci/vars"])
+    .output()
+    .expect("just invocation failed");
+
+  assert_eq!(output.status.code().unwrap(), 0);
+
+  let stdout = str::from_utf8(&output.stdout).unwrap();
+  assert_eq!(stdout, "ci\n");
+}
+
+#[test]
+fn dotenv_file_flag_missing() {
+  let tmp = tmptree! {
+    justfile: "default:",
+  };
+
+  let output = process::Command::new(executable_path("just"))
+    .current_dir(tmp.path())
+    .args(&["--dotenv-file", "missing"])
+    .output()
+    .expect("just invocation failed");
+
+  assert_eq!(output.status.code().unwrap(), 1);
+
+  let stderr = str::from_utf8(&output.stderr).unwrap();
+  assert!(
+    stderr.starts_with("error: Failed to load environment file `") && stderr.contains("missing`"),
+    "unexpected stderr: {}",
+    stderr
+  );
+}
+
+#[test]
+fn dotenv_syntax() {
+  let tmp = tmptree! {
+    ".env": "# settings\nexport HOST=localhost\nURL=\"http://${HOST}:$PORT\"\nPORT=80\nGREETING='hello\n  world'",
+    justfile: "default:\n  @echo \"$URL\"\n  @echo \"$GREETING\"",
+  };
+
+  let output = process::Command::new(executable_path("just"))
+    .current_dir(tmp.path())
+    .env_remove("HOST")
+    .env_remove("PORT")
+    .output()
+    .expect("just invocation failed");
+
+  assert_eq!(output.status.code().unwrap(), 0);
+
+  let stdout = str::from_utf8(&output.stdout).unwrap();
+  assert_eq!(stdout, "http://localhost:\nhello\n  world\n");
+}
+
+#[test]
+fn dotenv_syntax_error() {
+  let tmp = tmptree! {
+    ".env": "A=a\n\nB=b c",
+    justfile: "default:",
+  };
+
+  let output = process::Command::new(executable_path("just"))
+    .current_dir(tmp.path())
+    .output()
+    .expect("just invocation failed");
+
+  assert_eq!(output.status.code().unwrap(), 1);
+
+  let stderr = str::from_utf8(&output.stderr).unwrap();
+  assert!(
+    stderr.ends_with(
+      ".env`: Unexpected `c` after value on line 3, column 5, values containing whitespace must \
+       be quoted\n"
+    ),
+    "unexpected stderr: {}",
+    stderr
+  );
+}
@@ -0,0 +1,182 @@
+use crate::common::*;
+
+const ABC: &str = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+
+const JUSTFILE: &str = "
+set allow-downloads
+
+url := ''
+sha256 := ''
+
+default:
+  @echo {{download(url, sha256)}}
+";
+
+/// Run just in `tmp` with `--cache-dir cache`, downloading `tmp/data`
+fn download(tmp: &Path, sha256: &str, args: &[&str]) -> Output {
+  just_command(tmp)
+    .args(&["--cache-dir", "cache"])
+    .args(args)
+    .arg(format!("url=file://{}", tmp.join("data").display()))
+    .arg(format!("sha256={}", sha256))
+    .output()
+    .unwrap()
+}
+
+fn curl_missing() -> bool {
+  which("curl").is_err()
+}
+
+#[test]
+fn downloads_and_caches() {
+  if curl_missing() {
+    return;
+  }
+
+  let tmp = tmptree! {
+    justfile: JUSTFILE,
+    data: "abc",
+  };
+
+  let output = download(tmp.path(), ABC, &[]);
+
+  let path = tmp
+    .path()
+    .join("cache")
+    .join("downloads")
+    .join(ABC)
+    .join("data");
+
+  assert_stdout(&output, &format!("{}\n", path.display()));
+  assert_eq!(fs::read_to_string(&path).unwrap(), "abc");
+
+  // the cached copy is used once the original is gone
+  fs::remove_file(tmp.path().join("data")).unwrap();
+
+  let output = download(tmp.path(), ABC, &[]);
+
+  assert_stdout(&output, &format!("{}\n", path.display()));
+}
+
+#[test]
+fn checksum_mismatch() {
+  if curl_missing() {
+    return;
+  }
+
+  let tmp = tmptree! {
+    justfile: JUSTFILE,
+    data: "abd",
+  };
+
+  let output = download(tmp.path(), ABC, &[]);
+
+  assert_eq!(output.status.code(), Some(EXIT_FAILURE));
+  assert!(str::from_utf8(&output.stderr)
+    .unwrap()
+    .starts_with(&format!(
+      "error: Call to function `download` failed: checksum mismatch for `file://{}`: expected {}, \
+       found a52d159f262b2c6ddb724a61840befc36eb30c88877a4030b65cbe86298449c9",
+      tmp.path().join("data").display(),
+      ABC
+    )));
+
+  let directory = tmp.path().join("cache").join("downloads").join(ABC);
+  assert_eq!(fs::read_dir(directory).unwrap().count(), 0);
+}
+
+#[test]
+fn download_failure() {
+  if curl_missing() {
+    return;
+  }
+
+  let tmp = tmptree! {
+    justfile: JUSTFILE,
+  };
+
+  let output = download(tmp.path(), ABC, &[]);
+
+  assert_eq!(output.status.code(), Some(EXIT_FAILURE));
+  assert!(str::from_utf8(&output.stderr)
+    .unwrap()
+    .contains("error: Call to function `download` failed: failed to download `file://"));
+}
+
+#[test]
+fn dry_run() {
+  let tmp = tmptree! {
+    justfile: "
+set allow-downloads
+
+default:
+  echo {{download('https://example.com/tool.tar.gz', sha256('abc'))}}
+",
+  };
+
+  let output = just(tmp.path(), &["--cache-dir", "cache", "--dry-run"]);
+
+  assert_eq!(
+    str::from_utf8(&output.stderr).unwrap(),
+    format!(
+      "echo {}\n",
+      tmp
+        .path()
+        .join("cache")
+        .join("downloads")
+        .join(ABC)
+        .join("tool.tar.gz")
+        .display()
+    )
+  );
+  assert!(!tmp.path().join("cache").exists());
+}
+
+test! {
+  name:     disabled,
+  justfile: "
+    x := download('https://example.com/tool', sha256('abc'))
+  ",
+  args:     ("--evaluate"),
+  stderr:   "
+    error: Call to function `download` failed: downloads are disabled, enable them with `set allow-downloads`
+      |
+    1 | x := download('https://example.com/tool', sha256('abc'))
+      |      ^^^^^^^^
+  ",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     invalid_digest,
+  justfile: "
+    set allow-downloads
+
+    x := download('https://example.com/tool', 'abc')
+  ",
+  args:     ("--evaluate"),
+  stderr:   "
+    error: Call to function `download` failed: invalid SHA-256 digest `abc`
+      |
+    3 | x := download('https://example.com/tool', 'abc')
+      |      ^^^^^^^^
+  ",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     safe_mode,
+  justfile: "
+    set allow-downloads
+
+    x := download('https://example.com/tool', sha256('abc'))
+  ",
+  args:     ("--safe", "--evaluate"),
+  stderr:   "
+    error: Function `download` may not be called in safe mode
+      |
+    3 | x := download('https://example.com/tool', sha256('abc'))
+      |      ^^^^^^^^
+  ",
+  status:   EXIT_FAILURE,
+}
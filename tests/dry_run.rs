@@ -0,0 +1,92 @@
+use crate::common::*;
+
+test! {
+  name: verbose_prints_plan,
+  justfile: "
+    build target='debug': (setup target)
+      echo building {{target}}
+      @echo done
+
+    setup mode:
+      -echo setup {{mode}}
+  ",
+  args: ("--dry-run", "--verbose", "build", "release"),
+  stderr: "
+    ===> Would run recipe `setup` with mode=\"release\"...
+    -echo setup release
+    ===> Would run recipe `build` with target=\"release\"...
+    echo building release
+    @echo done
+  ",
+}
+
+test! {
+  name: verbose_marks_quiet_lines,
+  justfile: "
+    @foo *args:
+      echo {{args}}
+      @echo bar
+  ",
+  args: ("--dry-run", "--verbose", "foo", "a", "b"),
+  stderr: "
+    ===> Would run recipe `foo` with args=\"a b\"...
+    @echo a b
+    echo bar
+  ",
+}
+
+test! {
+  name: verbose_shebang,
+  justfile: "
+    foo:
+      #!/usr/bin/env python3
+      print(1)
+
+    bar:
+      #!/usr/bin/env -S python3 -u
+      print(1)
+
+    baz:
+      #!/bin/sh
+      echo baz
+  ",
+  args: ("--dry-run", "--verbose", "foo", "bar", "baz"),
+  stderr: "
+    ===> Would run recipe `foo`...
+    ===> Would run script with `/usr/bin/env` and argument `python3`
+    #!/usr/bin/env python3
+    print(1)
+    ===> Would run recipe `bar`...
+    ===> Would run script with `/usr/bin/env` and arguments `python3`, `-u`
+    #!/usr/bin/env -S python3 -u
+    print(1)
+    ===> Would run recipe `baz`...
+    ===> Would run script with `/bin/sh`
+    #!/bin/sh
+    echo baz
+  ",
+}
+
+test! {
+  name: verbose_shebang_flag,
+  justfile: "
+    foo:
+      @echo foo
+  ",
+  args: ("--dry-run", "--verbose", "--shebang", "bash -x"),
+  stderr: "
+    ===> Would run recipe `foo`...
+    ===> Would run script with `bash` and argument `-x`
+    echo foo
+  ",
+}
+
+test! {
+  name: without_verbose,
+  justfile: "
+    foo x:
+      @echo {{x}}
+  ",
+  args: ("--dry-run", "foo", "bar"),
+  stderr: "echo bar\n",
+}
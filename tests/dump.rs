@@ -0,0 +1,162 @@
+test! {
+  name:     json,
+  justfile: "
+    set export
+
+    alias b := build
+
+    version := `cat VERSION`
+
+    # build it
+    [private]
+    build +$FLAGS='-O': (compile 'x')
+      echo {{ if version == '1' { 'one' } else { 'other' } }}
+
+    compile target:
+  ",
+  args:     ("--dump", "--format", "json"),
+  stdout:   r#"
+    {
+      "schema_version": 1,
+      "just_version": "0.9.0",
+      "features": [
+        "aliases",
+        "attribute:private",
+        "backticks",
+        "conditionals",
+        "dependency-arguments",
+        "exported-parameters",
+        "setting:dotenv-load",
+        "setting:export",
+        "variadic-parameters"
+      ],
+      "settings": {
+        "allow_downloads": false,
+        "backtick_env": "full",
+        "default": null,
+        "dotenv_files": null,
+        "dotenv_load": true,
+        "echo_truncate": null,
+        "exit_message": "full",
+        "exit_message_template": null,
+        "export": true,
+        "list_heading": null,
+        "list_prefix": null,
+        "metadata_side_effects": true,
+        "no_exit_message": false,
+        "pager": false,
+        "shell": null,
+        "strict_interpolation": false,
+        "tempdir": null,
+        "timestamps": false,
+        "unknown_recipe": "error",
+        "windows_powershell": false,
+        "windows_shell": null
+      },
+      "aliases": {
+        "b": "build"
+      },
+      "assignments": {
+        "version": {
+          "export": false,
+          "value": "`cat VERSION`"
+        }
+      },
+      "recipes": {
+        "build": {
+          "attributes": [
+            "private"
+          ],
+          "doc": "build it",
+          "private": true,
+          "quiet": false,
+          "shebang": false,
+          "parameters": [
+            {
+              "name": "FLAGS",
+              "kind": "plus",
+              "export": true,
+              "default": "'-O'"
+            }
+          ],
+          "dependencies": [
+            {
+              "recipe": "compile",
+              "arguments": [
+                "'x'"
+              ]
+            }
+          ],
+          "body": [
+            "echo {{if version == '1' { 'one' } else { 'other' } }}"
+          ]
+        },
+        "compile": {
+          "attributes": [],
+          "doc": null,
+          "private": false,
+          "quiet": false,
+          "shebang": false,
+          "parameters": [
+            {
+              "name": "target",
+              "kind": "singular",
+              "export": false,
+              "default": null
+            }
+          ],
+          "dependencies": [],
+          "body": []
+        }
+      }
+    }
+  "#,
+}
+
+test! {
+  name:     yaml,
+  justfile: "
+    set shell := ['bash', '-c']
+
+    foo := env_var_or_default('FOO', 'bar')
+  ",
+  args:     ("--dump", "--format", "yaml"),
+  stdout:   r#"
+    "schema_version": 1
+    "just_version": "0.9.0"
+    "features":
+      - "function:env_var_or_default"
+      - "setting:dotenv-load"
+      - "setting:shell"
+    "settings":
+      "allow_downloads": false
+      "backtick_env": "full"
+      "default": null
+      "dotenv_files": null
+      "dotenv_load": true
+      "echo_truncate": null
+      "exit_message": "full"
+      "exit_message_template": null
+      "export": false
+      "list_heading": null
+      "list_prefix": null
+      "metadata_side_effects": true
+      "no_exit_message": false
+      "pager": false
+      "shell":
+        - "bash"
+        - "-c"
+      "strict_interpolation": false
+      "tempdir": null
+      "timestamps": false
+      "unknown_recipe": "error"
+      "windows_powershell": false
+      "windows_shell": null
+    "aliases": {}
+    "assignments":
+      "foo":
+        "export": false
+        "value": "env_var_or_default('FOO', 'bar')"
+    "recipes": {}
+  "#,
+}
@@ -0,0 +1,114 @@
+use crate::common::*;
+
+test! {
+  name:     setting,
+  justfile: "
+    set echo-truncate := 10
+
+    a:
+      echo 0123456789abcdef
+  ",
+  stdout:   "0123456789abcdef\n",
+  stderr:   "echo 01234... [11 more characters]\n",
+}
+
+test! {
+  name:     short_lines_not_truncated,
+  justfile: "
+    set echo-truncate := 10
+
+    a:
+      echo 01234
+  ",
+  stdout:   "01234\n",
+  stderr:   "echo 01234\n",
+}
+
+test! {
+  name:     one_more_character,
+  justfile: "
+    set echo-truncate := 10
+
+    a:
+      echo 012345
+  ",
+  stdout:   "012345\n",
+  stderr:   "echo 01234... [1 more character]\n",
+}
+
+test! {
+  name:     flag,
+  justfile: "
+    a:
+      echo 0123456789abcdef
+  ",
+  args:     ("--echo-truncate", "10"),
+  stdout:   "0123456789abcdef\n",
+  stderr:   "echo 01234... [11 more characters]\n",
+}
+
+test! {
+  name:     flag_overrides_setting,
+  justfile: "
+    set echo-truncate := 10
+
+    a:
+      echo 0123456789abcdef
+  ",
+  args:     ("--echo-truncate", "15"),
+  stdout:   "0123456789abcdef\n",
+  stderr:   "echo 0123456789... [6 more characters]\n",
+}
+
+test! {
+  name:     zero_disables,
+  justfile: "
+    set echo-truncate := 10
+
+    a:
+      echo 0123456789abcdef
+  ",
+  args:     ("--echo-truncate", "0"),
+  stdout:   "0123456789abcdef\n",
+  stderr:   "echo 0123456789abcdef\n",
+}
+
+test! {
+  name:     dry_run,
+  justfile: "
+    set echo-truncate := 10
+
+    a:
+      echo 0123456789abcdef
+  ",
+  args:     ("--dry-run"),
+  stderr:   "echo 01234... [11 more characters]\n",
+}
+
+test! {
+  name:     interpolations_truncated_after_evaluation,
+  justfile: "
+    set echo-truncate := 10
+
+    x := '0123456789'
+
+    a:
+      echo {{x}}
+  ",
+  stdout:   "0123456789\n",
+  stderr:   "echo 01234... [5 more characters]\n",
+}
+
+test! {
+  name:     invalid_value,
+  justfile: "
+    set echo-truncate := 'ten'
+  ",
+  stderr:   "
+    error: Expected number, but found string
+      |
+    1 | set echo-truncate := 'ten'
+      |                      ^^^^^
+  ",
+  status:   EXIT_FAILURE,
+}
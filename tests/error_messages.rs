@@ -23,3 +23,84 @@ test! {
   ",
   status: EXIT_FAILURE,
 }
+
+test! {
+  name: context_lines,
+  justfile: "
+    a := 'a'
+    b := 'b'
+    c := d
+    e := 'e'
+    f := 'f'
+  ",
+  args: ("--error-context", "1"),
+  stderr: "
+    error: Variable `d` not defined
+      |
+    2 | b := 'b'
+    3 | c := d
+      |      ^
+    4 | e := 'e'
+  ",
+  status: EXIT_FAILURE,
+  dotenv_load: false,
+}
+
+test! {
+  name: context_lines_clamped,
+  justfile: "
+    a := b
+  ",
+  args: ("--error-context", "3"),
+  stderr: "
+    error: Variable `b` not defined
+      |
+    1 | a := b
+      |      ^
+  ",
+  status: EXIT_FAILURE,
+  dotenv_load: false,
+}
+
+test! {
+  name: multi_line_span,
+  justfile: "
+    a := `echo foo
+    echo bar`
+  ",
+  args: ("--safe", "--evaluate"),
+  stderr: "
+    error: Backticks may not be evaluated in safe mode
+      |
+    1 | a := `echo foo
+      |      ^^^^^^^^^
+    2 | echo bar`
+      | ^^^^^^^^^
+  ",
+  status: EXIT_FAILURE,
+  dotenv_load: false,
+}
+
+test! {
+  name: confusable_character,
+  justfile: "version := “1.0.0”",
+  stderr: "
+    error: Character `“` (U+201C) looks like `\"`, but is not valid here
+      |
+    1 | version := “1.0.0”
+      |            ^
+  ",
+  status: EXIT_FAILURE,
+}
+
+test! {
+  name: invisible_character,
+  justfile: "build\u{200B}:",
+  stderr: "
+    error: Invisible character U+200B is not valid here
+      |
+    1 | build\u{200B}:
+      |      ^
+  ",
+  status: EXIT_FAILURE,
+}
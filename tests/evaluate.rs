@@ -1,3 +1,5 @@
+use crate::common::*;
+
 test! {
   name:     evaluate,
   justfile: r#"
@@ -41,3 +43,168 @@ test! {
     c := "z"
   "#,
 }
+
+test! {
+  name:     evaluate_single,
+  justfile: "
+    a := 'x'
+    b := 'y'
+    c := 'z'
+  ",
+  args:     ("--evaluate", "b"),
+  stdout:   "y",
+}
+
+test! {
+  name:     evaluate_single_override,
+  justfile: "
+    a := 'x'
+    b := a + 'y'
+  ",
+  args:     ("--evaluate", "a=w", "b"),
+  stdout:   "wy",
+}
+
+test! {
+  name:     evaluate_single_dotenv,
+  justfile: "
+    a := env_var('DOTENV_KEY')
+  ",
+  args:     ("--evaluate", "a"),
+  stdout:   "dotenv-value",
+}
+
+test! {
+  name:     evaluate_single_sees_earlier_exports,
+  justfile: "
+    export A := 'hello'
+    b := `echo ${A:-unset}`
+  ",
+  args:     ("--evaluate", "b"),
+  stdout:   "hello",
+}
+
+test! {
+  name:     evaluate_single_only_dependencies,
+  justfile: "
+    a := `exit 1`
+    export b := `exit 1`
+    c := 'y'
+  ",
+  args:     ("--evaluate", "c"),
+  stdout:   "y",
+}
+
+test! {
+  name:     evaluate_single_safe,
+  justfile: "
+    a := 'x'
+    b := env_var_or_default('JUST_TEST_UNSET', 'y')
+    c := a + 'z'
+  ",
+  args:     ("--safe", "--evaluate", "c"),
+  stdout:   "xz",
+}
+
+test! {
+  name:     evaluate_single_unknown,
+  justfile: "
+    a := 'x'
+  ",
+  args:     ("--evaluate", "b"),
+  stderr:   "error: Justfile does not contain variable `b`.\n",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     evaluate_format_json,
+  justfile: r#"
+    a := 'x'
+    b := "quote \" and\nnewline"
+    c := `echo bar`
+  "#,
+  args:     ("--evaluate", "--format", "json"),
+  stdout:   r#"
+    {
+      "a": "x",
+      "b": "quote \" and\nnewline",
+      "c": "bar"
+    }
+  "#,
+}
+
+test! {
+  name:     evaluate_format_yaml,
+  justfile: "
+    a := 'x'
+    b := a + 'y'
+  ",
+  args:     ("--evaluate", "--format", "yaml"),
+  stdout:   r#"
+    "a": "x"
+    "b": "xy"
+  "#,
+}
+
+test! {
+  name:     evaluate_format_selected_variables,
+  justfile: "
+    a := 'x'
+    b := 'y'
+    c := 'z'
+  ",
+  args:     ("--evaluate", "--format", "json", "a=w", "c", "a"),
+  stdout:   r#"
+    {
+      "a": "w",
+      "c": "z"
+    }
+  "#,
+}
+
+test! {
+  name:     evaluate_format_single_variable,
+  justfile: "
+    a := 'x'
+    b := 'y'
+  ",
+  args:     ("--evaluate", "--format", "yaml", "b"),
+  stdout:   "\"b\": \"y\"\n",
+}
+
+test! {
+  name:     evaluate_format_empty,
+  justfile: "
+    foo:
+  ",
+  args:     ("--evaluate", "--format", "json"),
+  stdout:   "{}\n",
+}
+
+test! {
+  name:     evaluate_without_metadata_side_effects,
+  justfile: "
+    set metadata-side-effects := false
+
+    a := `exit 1`
+    b := env_var_or_default('DOTENV_KEY', 'unset')
+  ",
+  args:     ("--evaluate"),
+  stdout:   "
+    a := \"`exit 1`\"
+    b := \"unset\"
+  ",
+}
+
+test! {
+  name:     run_without_metadata_side_effects,
+  justfile: "
+    set metadata-side-effects := false
+
+    a := `echo foo`
+
+    default:
+      @echo {{a}} $DOTENV_KEY
+  ",
+  stdout:   "foo dotenv-value\n",
+}
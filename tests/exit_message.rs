@@ -0,0 +1,152 @@
+use crate::common::*;
+
+test! {
+  name:     full,
+  justfile: "
+    set exit-message := 'full'
+
+    a:
+      @exit 100
+  ",
+  stderr:   "error: Recipe `a` failed on line 4 with exit code 100\n",
+  status:   100,
+}
+
+test! {
+  name:     minimal,
+  justfile: "
+    set exit-message := 'minimal'
+
+    a:
+      @exit 100
+  ",
+  stderr:   "error: Recipe `a` failed\n",
+  status:   100,
+}
+
+test! {
+  name:     minimal_shebang,
+  justfile: "
+    set exit-message := 'minimal'
+
+    a:
+      #!/usr/bin/env sh
+      exit 100
+  ",
+  stderr:   "error: Recipe `a` failed\n",
+  status:   100,
+}
+
+test! {
+  name:     none,
+  justfile: "
+    set exit-message := 'none'
+
+    a:
+      @exit 100
+  ",
+  status:   100,
+}
+
+test! {
+  name:     none_other_errors_still_printed,
+  justfile: "
+    set exit-message := 'none'
+
+    a x:
+      @exit 100
+  ",
+  args:     ("a"),
+  stderr:   "
+    error: Recipe `a` got 0 arguments but takes 1
+    usage:
+        just a x
+  ",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     invalid,
+  justfile: "
+    set exit-message := 'short'
+  ",
+  stderr:   "
+    error: Invalid value `short` for setting `exit-message`, expected `full`, `minimal`, or `none`
+      |
+    1 | set exit-message := 'short'
+      |                     ^^^^^^^
+  ",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     template,
+  justfile: "
+    set exit-message-template := '::error::{recipe} failed on line {line} ({code})'
+
+    a:
+      @exit 100
+  ",
+  stderr:   "::error::a failed on line 4 (100)\n",
+  status:   100,
+}
+
+test! {
+  name:     template_shebang,
+  justfile: "
+    set exit-message-template := '{recipe}:{line}:{code}'
+
+    a:
+      #!/usr/bin/env sh
+      exit 100
+  ",
+  stderr:   "a::100\n",
+  status:   100,
+}
+
+test! {
+  name:     template_none,
+  justfile: "
+    set exit-message := 'none'
+    set exit-message-template := '{recipe} failed'
+
+    a:
+      @exit 100
+  ",
+  status:   100,
+}
+
+test! {
+  name:     template_no_exit_message_attribute,
+  justfile: "
+    set exit-message-template := '{recipe} failed'
+
+    [no-exit-message]
+    a:
+      @exit 100
+  ",
+  status:   100,
+}
+
+test! {
+  name:     template_keep_going,
+  justfile: "
+    set exit-message-template := '{recipe} failed'
+
+    a:
+      @exit 100
+
+    b:
+      @exit 101
+  ",
+  args:     ("--keep-going", "a", "b"),
+  stderr:   "
+    a failed
+    b failed
+    ===> Results:
+    a: failed with exit code 100
+    b: failed with exit code 101
+    error: Recipes `a` and `b` failed
+  ",
+  status:   100,
+}
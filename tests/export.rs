@@ -1,3 +1,5 @@
+use crate::common::*;
+
 test! {
   name:     success,
   justfile: r#"
@@ -146,7 +148,7 @@ test! {
 }
 
 test! {
-  name: setting_override_undefined,
+  name: setting_override_defined,
   justfile: r#"
     set export
 
@@ -158,12 +160,12 @@ test! {
       echo $D
   "#,
   args: ("A=zzz", "foo"),
-  stdout: "undefined\nundefined\n",
+  stdout: "defined\nundefined\n",
   stderr: "echo $B\necho $D\n",
 }
 
 test! {
-  name: setting_variable_not_visible,
+  name: setting_variable_visible,
   justfile: r#"
     export A := 'hello'
     export B := `if [ -n "${A+1}" ]; then echo defined; else echo undefined; fi`
@@ -172,6 +174,201 @@ test! {
       echo $B
   "#,
   args: ("A=zzz"),
-  stdout: "undefined\n",
+  stdout: "defined\n",
   stderr: "echo $B\n",
 }
+
+test! {
+  name: parameter_list,
+  justfile: "
+    # build it
+    build $TARGET *$FLAGS='-O':
+  ",
+  args: ("--list"),
+  stdout: "
+    Available recipes:
+        build $TARGET *$FLAGS='-O' # build it
+  ",
+}
+
+test! {
+  name: parameter_dump,
+  justfile: "
+    build $TARGET +$FLAGS='-O':
+      echo {{TARGET}} $FLAGS
+  ",
+  args: ("--dump"),
+  stdout: "
+    build $TARGET +$FLAGS='-O':
+        echo {{TARGET}} $FLAGS
+  ",
+}
+
+test! {
+  name: variadic_parameter,
+  justfile: "
+    build *$FLAGS:
+      echo $FLAGS
+  ",
+  args: ("build", "a", "b"),
+  stdout: "a b\n",
+  stderr: "echo $FLAGS\n",
+}
+
+test! {
+  name:     backtick_env_full,
+  justfile: "
+    set backtick-env := 'full'
+
+    export FOO := 'a'
+
+    default x=`echo $FOO-${HOME:+home}`:
+      @echo {{x}}
+  ",
+  stdout:   "a-home\n",
+}
+
+test! {
+  name:     backtick_env_exports,
+  justfile: "
+    set backtick-env := 'exports'
+
+    export FOO := 'a'
+
+    default x=`echo $FOO-${HOME:-none}`:
+      @echo {{x}}
+  ",
+  stdout:   "a-none\n",
+}
+
+test! {
+  name:     backtick_env_none,
+  justfile: "
+    set backtick-env := 'none'
+
+    export FOO := 'a'
+
+    default x=`echo ${FOO:-none}-${HOME:-none}`:
+      @echo {{x}}
+  ",
+  stdout:   "none-none\n",
+}
+
+test! {
+  name:     backtick_env_does_not_affect_recipes,
+  justfile: "
+    set backtick-env := 'none'
+
+    default:
+      @echo ${HOME:+home}
+  ",
+  stdout:   "home\n",
+}
+
+test! {
+  name:     backtick_env_invalid,
+  justfile: "set backtick-env := 'all'",
+  stderr:   "
+    error: Invalid value `all` for setting `backtick-env`, expected `none`, `exports`, or `full`
+      |
+    1 | set backtick-env := 'all'
+      |                     ^^^^^
+  ",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     exported_variable_visible_to_later_backtick,
+  justfile: "
+    export FOO := 'a'
+
+    bar := `echo $FOO`
+
+    default:
+      @echo {{bar}}
+  ",
+  stdout:   "a\n",
+}
+
+test! {
+  name:     exported_variable_not_visible_to_earlier_backtick,
+  justfile: "
+    bar := `echo ${FOO:-none}`
+
+    export FOO := 'a'
+
+    default:
+      @echo {{bar}}
+  ",
+  stdout:   "none\n",
+}
+
+test! {
+  name:     exported_variables_evaluated_in_file_order,
+  justfile: "
+    export B := 'b'
+
+    export A := `echo $B`
+
+    c := `echo $A$B`
+
+    default:
+      @echo {{c}}
+  ",
+  stdout:   "bb\n",
+}
+
+test! {
+  name:     exported_override_visible_to_backtick,
+  justfile: "
+    bar := `echo $FOO`
+
+    export FOO := 'a'
+
+    default:
+      @echo {{bar}}
+  ",
+  args:     ("--set", "FOO", "b"),
+  stdout:   "b\n",
+}
+
+test! {
+  name:     export_setting_visible_to_later_backtick,
+  justfile: "
+    set export
+
+    foo := 'a'
+
+    bar := `echo $foo`
+
+    default:
+      @echo {{bar}}
+  ",
+  stdout:   "a\n",
+}
+
+test! {
+  name:     env_var_sees_exported_variable,
+  justfile: "
+    export JUST_TEST_VARIABLE := 'a'
+
+    bar := env_var('JUST_TEST_VARIABLE')
+
+    default:
+      @echo {{bar}}
+  ",
+  stdout:   "a\n",
+}
+
+test! {
+  name:     env_var_does_not_see_unexported_variable,
+  justfile: "
+    JUST_TEST_VARIABLE := 'a'
+
+    bar := env_var_or_default('JUST_TEST_VARIABLE', 'b')
+
+    default:
+      @echo {{bar}}
+  ",
+  stdout:   "b\n",
+}
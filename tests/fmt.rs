@@ -0,0 +1,102 @@
+use std::{fs, process::Command};
+
+use executable_path::executable_path;
+
+use test_utilities::tmptree;
+
+const UNFORMATTED: &str = "
+# assignments
+foo:='a'   # the foo
+barbaz:=  foo+'b' # the barbaz
+
+
+# build it
+build   target='x' :   clean
+  echo {{target}}
+clean:
+";
+
+const FORMATTED: &str = "# assignments
+foo := 'a'          # the foo
+barbaz := foo + 'b' # the barbaz
+
+# build it
+build target='x': clean
+    echo {{target}}
+clean:
+";
+
+#[test]
+fn rewrites_justfile() {
+  let tmp = tmptree! {
+    justfile: UNFORMATTED,
+  };
+
+  let output = Command::new(executable_path("just"))
+    .current_dir(tmp.path())
+    .arg("--fmt")
+    .output()
+    .unwrap();
+
+  assert!(output.status.success());
+
+  assert_eq!(
+    fs::read_to_string(tmp.path().join("justfile")).unwrap(),
+    FORMATTED
+  );
+}
+
+#[test]
+fn check_unformatted() {
+  let tmp = tmptree! {
+    justfile: UNFORMATTED,
+  };
+
+  let output = Command::new(executable_path("just"))
+    .current_dir(tmp.path())
+    .args(&["--fmt", "--check"])
+    .output()
+    .unwrap();
+
+  assert!(!output.status.success());
+
+  assert_eq!(
+    fs::read_to_string(tmp.path().join("justfile")).unwrap(),
+    UNFORMATTED
+  );
+}
+
+#[test]
+fn check_formatted() {
+  let tmp = tmptree! {
+    justfile: FORMATTED,
+  };
+
+  let output = Command::new(executable_path("just"))
+    .current_dir(tmp.path())
+    .args(&["--fmt", "--check"])
+    .output()
+    .unwrap();
+
+  assert!(output.status.success());
+}
+
+#[test]
+fn invalid_justfile() {
+  let tmp = tmptree! {
+    justfile: "foo := bar",
+  };
+
+  let output = Command::new(executable_path("just"))
+    .current_dir(tmp.path())
+    .arg("--fmt")
+    .output()
+    .unwrap();
+
+  assert!(!output.status.success());
+
+  assert_eq!(
+    fs::read_to_string(tmp.path().join("justfile")).unwrap(),
+    "foo := bar"
+  );
+}
@@ -0,0 +1,52 @@
+use crate::common::*;
+
+const JUSTFILE: &str = "
+  default:
+    @echo a > data
+    @echo {{sha256_file('data')}}
+    @echo b > data
+    @echo {{sha256_file('data')}}
+";
+
+test! {
+  name:     cached,
+  justfile: JUSTFILE,
+  stdout:   "
+    87428fc522803d31065e7bce3cf03fe475096631e5e07bbd7a0fde60c4cf25c7
+    87428fc522803d31065e7bce3cf03fe475096631e5e07bbd7a0fde60c4cf25c7
+  ",
+}
+
+test! {
+  name:     no_function_cache,
+  justfile: JUSTFILE,
+  args:     ("--no-function-cache"),
+  stdout:   "
+    87428fc522803d31065e7bce3cf03fe475096631e5e07bbd7a0fde60c4cf25c7
+    0263829989b6fd954f72baaf2fc64bc2e2f01d692d4de72986ea808f6e99813f
+  ",
+}
+
+test! {
+  name:     no_function_cache_environment_variable,
+  justfile: JUSTFILE,
+  env:      {
+    "JUST_NO_FUNCTION_CACHE": "1",
+  },
+  stdout:   "
+    87428fc522803d31065e7bce3cf03fe475096631e5e07bbd7a0fde60c4cf25c7
+    0263829989b6fd954f72baaf2fc64bc2e2f01d692d4de72986ea808f6e99813f
+  ",
+}
+
+test! {
+  name:     shared_between_assignments_and_recipes,
+  justfile: "
+    before := sha256_file('justfile')
+
+    default:
+      @echo '# changed' >> justfile
+      @echo {{ if sha256_file('justfile') == before { 'cached' } else { 'not cached' } }}
+  ",
+  stdout:   "cached\n",
+}
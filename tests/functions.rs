@@ -0,0 +1,836 @@
+use crate::common::*;
+
+test! {
+  name:     trim_start,
+  justfile: "
+    x := trim_start('  foo  ')
+
+    default:
+      @echo '[{{x}}]'
+  ",
+  stdout:   "[foo  ]\n",
+}
+
+test! {
+  name:     trim_end,
+  justfile: "
+    x := trim_end('  foo  ')
+
+    default:
+      @echo '[{{x}}]'
+  ",
+  stdout:   "[  foo]\n",
+}
+
+test! {
+  name:     trim_start_match,
+  justfile: "
+    a := trim_start_match('./src/./main.rs', './')
+    b := trim_start_match('src/main.rs', './')
+
+    default:
+      @echo {{a}} {{b}}
+  ",
+  stdout:   "src/./main.rs src/main.rs\n",
+}
+
+test! {
+  name:     trim_end_match,
+  justfile: "
+    a := trim_end_match('foo.tar.gz', '.gz')
+    b := trim_end_match('foo.tar', '.gz')
+
+    default:
+      @echo {{a}} {{b}}
+  ",
+  stdout:   "foo.tar foo.tar\n",
+}
+
+test! {
+  name:     quote,
+  justfile: r#"
+    x := quote("it's $HOME")
+
+    default:
+      @echo {{x}}
+  "#,
+  stdout:   "it's $HOME\n",
+}
+
+test! {
+  name:     replace_regex,
+  justfile: "
+    x := replace_regex('foo-1.2.3', '([0-9]+)\\.([0-9]+)', '${2}_$1')
+
+    default:
+      @echo {{x}}
+  ",
+  stdout:   "foo-2_1.3\n",
+}
+
+test! {
+  name:     replace_regex_invalid,
+  justfile: "
+    x := replace_regex('foo', '(', 'bar')
+
+    default:
+      @echo {{x}}
+  ",
+  stderr:   "
+    error: Call to function `replace_regex` failed: regex parse error:
+        (
+        ^
+    error: unclosed group
+      |
+    1 | x := replace_regex('foo', '(', 'bar')
+      |      ^^^^^^^^^^^^^
+  ",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     capture,
+  justfile: r"
+    version := capture('version (\d+)\.(\d+)', `echo tool version 1.23`, '2')
+
+    default:
+      @echo {{version}}
+  ",
+  stdout:   "23\n",
+}
+
+test! {
+  name:     capture_entire_match,
+  justfile: r"
+    x := capture('\d+\.\d+', 'tool version 1.23', '0')
+  ",
+  args:     ("--evaluate", "x"),
+  stdout:   "1.23",
+}
+
+test! {
+  name:     capture_named_group,
+  justfile: r"
+    x := capture('(?P<major>\d+)\.(?P<minor>\d+)', 'tool version 1.23', 'major')
+  ",
+  args:     ("--evaluate", "x"),
+  stdout:   "1",
+}
+
+test! {
+  name:     capture_optional_group,
+  justfile: r"
+    x := capture('(\d+)(-rc)?', '1.2', '2') || 'none'
+  ",
+  args:     ("--evaluate", "x"),
+  stdout:   "none",
+}
+
+test! {
+  name:     capture_no_match,
+  justfile: r"
+    x := capture('\d+', 'none', '0')
+  ",
+  args:     ("--evaluate"),
+  stderr:   r"
+    error: Call to function `capture` failed: regex `\d+` does not match `none`
+      |
+    1 | x := capture('\d+', 'none', '0')
+      |      ^^^^^^^
+  ",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     capture_no_such_group,
+  justfile: "
+    x := capture('(a)', 'a', '2')
+  ",
+  args:     ("--evaluate"),
+  stderr:   "
+    error: Call to function `capture` failed: regex `(a)` has no capture group `2`
+      |
+    1 | x := capture('(a)', 'a', '2')
+      |      ^^^^^^^
+  ",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     capture_no_such_named_group,
+  justfile: "
+    x := capture('(?P<a>a)', 'a', 'b')
+  ",
+  args:     ("--evaluate"),
+  stderr:   "
+    error: Call to function `capture` failed: regex `(?P<a>a)` has no capture group `b`
+      |
+    1 | x := capture('(?P<a>a)', 'a', 'b')
+      |      ^^^^^^^
+  ",
+  status:   EXIT_FAILURE,
+}
+
+#[test]
+fn path_exists_is_dir_is_file() {
+  let tmp = tmptree! {
+    justfile: "
+x := path_exists('foo') + ' ' + path_exists('bar') + ' ' + path_exists('baz')
+y := is_dir('foo') + ' ' + is_dir('bar') + ' ' + is_dir('baz')
+z := is_file('foo') + ' ' + is_file('bar') + ' ' + is_file('baz')
+
+default:
+  @echo {{x}}
+  @echo {{y}}
+  @echo {{z}}
+",
+    foo: {},
+    bar: "",
+  };
+
+  let output = Command::new(executable_path("just"))
+    .current_dir(tmp.path())
+    .output()
+    .unwrap();
+
+  assert_stdout(
+    &output,
+    "true true false\ntrue false false\nfalse true false\n",
+  );
+}
+
+#[test]
+fn path_exists_relative_to_working_directory() {
+  let tmp = tmptree! {
+    justfile: "
+default:
+  @echo {{path_exists('foo')}}
+",
+    sub: {
+      foo: "",
+    },
+  };
+
+  let output = Command::new(executable_path("just"))
+    .current_dir(tmp.path())
+    .arg("--justfile")
+    .arg(tmp.path().join("justfile"))
+    .arg("--working-directory")
+    .arg(tmp.path().join("sub"))
+    .output()
+    .unwrap();
+
+  assert_stdout(&output, "true\n");
+}
+
+test! {
+  name:     retry_success,
+  justfile: "
+    x := retry('3', 'echo attempt >> attempts; [ $(wc -l < attempts) -ge 2 ] && echo ok')
+
+    default:
+      @echo {{x}}
+      @wc -l < attempts
+  ",
+  stdout:   "ok\n2\n",
+}
+
+test! {
+  name:     retry_failure,
+  justfile: "
+    x := retry('2', 'echo attempt >&2; exit 3')
+
+    default:
+      @echo {{x}}
+  ",
+  stderr:   "
+    attempt
+    attempt
+    error: Call to function `retry` failed: command failed after 2 attempts: Process exited with status code 3
+      |
+    1 | x := retry('2', 'echo attempt >&2; exit 3')
+      |      ^^^^^
+  ",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     retry_invalid_attempts,
+  justfile: "
+    x := retry('0', 'echo foo')
+
+    default:
+      @echo {{x}}
+  ",
+  stderr:   "
+    error: Call to function `retry` failed: invalid attempt count `0`
+      |
+    1 | x := retry('0', 'echo foo')
+      |      ^^^^^
+  ",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     retry_dry_run,
+  justfile: "
+    x := retry('2', 'exit 1')
+
+    default:
+      echo {{x}}
+  ",
+  args:     ("--dry-run"),
+  stderr:   "echo `exit 1`\n",
+}
+
+test! {
+  name:     sha256,
+  justfile: "
+    x := sha256('abc')
+
+    default:
+      @echo {{x}}
+  ",
+  stdout:   "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad\n",
+}
+
+#[test]
+fn sha256_file() {
+  let tmp = tmptree! {
+    justfile: "
+default:
+  @echo {{sha256_file('sub/data')}}
+",
+    sub: {
+      data: "abc",
+    },
+  };
+
+  let output = Command::new(executable_path("just"))
+    .current_dir(tmp.path())
+    .output()
+    .unwrap();
+
+  assert_stdout(
+    &output,
+    "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad\n",
+  );
+}
+
+#[test]
+fn uuid() {
+  let tmp = tmptree! {
+    justfile: "
+default:
+  @echo {{uuid()}}
+  @echo {{uuid()}}
+",
+  };
+
+  let output = Command::new(executable_path("just"))
+    .current_dir(tmp.path())
+    .output()
+    .unwrap();
+
+  assert!(output.status.success());
+
+  let stdout = str::from_utf8(&output.stdout).unwrap();
+  let uuids = stdout.lines().collect::<Vec<&str>>();
+
+  assert_eq!(uuids.len(), 2);
+  assert_ne!(uuids[0], uuids[1]);
+
+  for uuid in uuids {
+    let groups = uuid.split('-').map(str::len).collect::<Vec<usize>>();
+    assert_eq!(groups, &[8, 4, 4, 4, 12]);
+    assert!(uuid.chars().all(|c| c == '-' || c.is_ascii_hexdigit()));
+    assert_eq!(&uuid[14..15], "4");
+    assert!("89ab".contains(&uuid[19..20]));
+  }
+}
+
+#[test]
+fn datetime_utc() {
+  let tmp = tmptree! {
+    justfile: "
+default:
+  @echo {{datetime_utc('%s')}}
+",
+  };
+
+  let before = SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .unwrap()
+    .as_secs();
+
+  let output = Command::new(executable_path("just"))
+    .current_dir(tmp.path())
+    .output()
+    .unwrap();
+
+  let after = SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .unwrap()
+    .as_secs();
+
+  assert!(output.status.success());
+
+  let timestamp = str::from_utf8(&output.stdout)
+    .unwrap()
+    .trim()
+    .parse::<u64>()
+    .unwrap();
+
+  assert!(before <= timestamp && timestamp <= after);
+}
+
+test! {
+  name:     datetime_literal,
+  justfile: "
+    x := datetime('%%') + datetime_utc('%%')
+
+    default:
+      @echo '{{x}}'
+  ",
+  stdout:   "%%\n",
+}
+
+test! {
+  name:     datetime_invalid_specifier,
+  justfile: "
+    x := datetime('%Q')
+
+    default:
+      @echo {{x}}
+  ",
+  stderr:   "
+    error: Call to function `datetime` failed: invalid format specifier `%Q`
+      |
+    1 | x := datetime('%Q')
+      |      ^^^^^^^^
+  ",
+  status:   EXIT_FAILURE,
+}
+
+#[test]
+fn absolute_path() {
+  let tmp = tmptree! {
+    justfile: "
+default:
+  @echo {{absolute_path('foo/../bar/./baz')}}
+  @echo {{justfile_directory()}}/bar/baz
+",
+  };
+
+  let output = Command::new(executable_path("just"))
+    .current_dir(tmp.path())
+    .output()
+    .unwrap();
+
+  assert!(output.status.success());
+
+  let stdout = str::from_utf8(&output.stdout).unwrap();
+  let lines = stdout.lines().collect::<Vec<&str>>();
+
+  assert_eq!(lines.len(), 2);
+  assert_eq!(lines[0], lines[1]);
+}
+
+#[test]
+#[cfg(unix)]
+fn canonicalize() {
+  let tmp = tmptree! {
+    justfile: "
+default:
+  @echo {{canonicalize('link/data')}}
+",
+    sub: {
+      data: "",
+    },
+  };
+
+  std::os::unix::fs::symlink(tmp.path().join("sub"), tmp.path().join("link")).unwrap();
+
+  let output = Command::new(executable_path("just"))
+    .current_dir(tmp.path())
+    .output()
+    .unwrap();
+
+  let expected = fs::canonicalize(tmp.path().join("sub/data")).unwrap();
+
+  assert_stdout(&output, &format!("{}\n", expected.display()));
+}
+
+test! {
+  name:     canonicalize_missing,
+  justfile: "
+    x := canonicalize('missing')
+
+    default:
+      @echo {{x}}
+  ",
+  stderr:   "
+    error: Call to function `canonicalize` failed: Path `missing` does not exist
+      |
+    1 | x := canonicalize('missing')
+      |      ^^^^^^^^^^^^
+  ",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     error,
+  justfile: "
+    x := if os() == 'plan9' { 'ok' } else { error('unsupported os') }
+
+    default:
+      @echo {{x}}
+  ",
+  stderr:   "
+    error: unsupported os
+      |
+    1 | x := if os() == 'plan9' { 'ok' } else { error('unsupported os') }
+      |                                         ^^^^^
+  ",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     error_not_evaluated,
+  justfile: "
+    x := if 'a' == 'a' { 'ok' } else { error('unreachable') }
+
+    default:
+      @echo {{x}}
+  ",
+  stdout:   "ok\n",
+}
+
+test! {
+  name:     assert_true,
+  justfile: "
+    x := assert('true', 'unreachable') + 'ok'
+
+    default:
+      @echo {{x}}
+  ",
+  stdout:   "ok\n",
+}
+
+test! {
+  name:     assert_false,
+  justfile: "
+    default target:
+      @echo {{ assert(path_exists(target), 'target `' + target + '` does not exist') }}
+  ",
+  args:     ("default", "missing"),
+  stderr:   "
+    error: target `missing` does not exist
+      |
+    2 |   @echo {{ assert(path_exists(target), 'target `' + target + '` does not exist') }}
+      |            ^^^^^^
+  ",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     assert_invalid_condition,
+  justfile: "
+    x := assert('yes', 'message')
+
+    default:
+      @echo {{x}}
+  ",
+  stderr:   "
+    error: Invalid assertion condition `yes`, expected `true` or `false`
+      |
+    1 | x := assert('yes', 'message')
+      |      ^^^^^^
+  ",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     shell_no_arguments,
+  justfile: "
+    x := shell('echo hello')
+
+    default:
+      @echo {{x}}
+  ",
+  stdout:   "hello\n",
+}
+
+test! {
+  name:     shell_positional_arguments,
+  justfile: "
+    x := shell('echo $1-$2 $#', 'foo bar', 'baz')
+
+    default:
+      @echo {{x}}
+  ",
+  stdout:   "foo bar-baz 2\n",
+}
+
+test! {
+  name:     shell_arguments_are_expressions,
+  justfile: "
+    name := 'world'
+
+    default:
+      @echo {{ shell('echo \"$1\"', 'hello ' + name) }}
+  ",
+  stdout:   "hello world\n",
+}
+
+test! {
+  name:     shell_respects_shell_setting,
+  justfile: "
+    set shell := ['bash', '-c']
+
+    x := shell('echo ${BASH_VERSION:+bash}')
+
+    default:
+      @echo {{x}}
+  ",
+  stdout:   "bash\n",
+}
+
+test! {
+  name:     shell_failure,
+  justfile: "
+    x := shell('exit 3')
+
+    default:
+      @echo {{x}}
+  ",
+  stderr:   "
+    error: Call to function `shell` failed: Process exited with status code 3
+      |
+    1 | x := shell('exit 3')
+      |      ^^^^^
+  ",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     shell_no_arguments_error,
+  justfile: "
+    x := shell()
+  ",
+  stderr:   "
+    error: Function `shell` called with 0 arguments but takes 1 or more
+      |
+    1 | x := shell()
+      |      ^^^^^
+  ",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     shell_dry_run,
+  justfile: "
+    x := shell('echo $1', 'foo')
+
+    default:
+      echo {{x}}
+  ",
+  args:     ("--dry-run"),
+  stderr:   "echo `echo $1`\n",
+}
+
+test! {
+  name:     replace,
+  justfile: "
+    x := replace('foo-bar-baz', '-', '_')
+
+    default:
+      @echo {{x}}
+  ",
+  stdout:   "foo_bar_baz\n",
+}
+
+test! {
+  name:     replace_count,
+  justfile: "
+    x := replace('foo-bar-baz', '-', '_', '1')
+
+    default:
+      @echo {{x}}
+  ",
+  stdout:   "foo_bar-baz\n",
+}
+
+test! {
+  name:     replace_invalid_count,
+  justfile: "
+    x := replace('foo', 'o', '0', 'all')
+
+    default:
+      @echo {{x}}
+  ",
+  stderr:   "
+    error: Call to function `replace` failed: invalid replacement count `all`
+      |
+    1 | x := replace('foo', 'o', '0', 'all')
+      |      ^^^^^^^
+  ",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     replace_argument_count,
+  justfile: "
+    x := replace('a', 'b', 'c', 'd', 'e')
+  ",
+  stderr:   "
+    error: Function `replace` called with 5 arguments but takes 3 or 4
+      |
+    1 | x := replace('a', 'b', 'c', 'd', 'e')
+      |      ^^^^^^^
+  ",
+  status:   EXIT_FAILURE,
+}
+
+#[cfg(unix)]
+test! {
+  name:     join,
+  justfile: "
+    x := join('a', 'b', 'c/d', 'e')
+
+    default:
+      @echo {{x}}
+  ",
+  stdout:   "a/b/c/d/e\n",
+}
+
+#[cfg(unix)]
+test! {
+  name:     join_absolute,
+  justfile: "
+    x := join('a', '/b', 'c')
+
+    default:
+      @echo {{x}}
+  ",
+  stdout:   "/b/c\n",
+}
+
+test! {
+  name:     join_argument_count,
+  justfile: "
+    x := join('a')
+  ",
+  stderr:   "
+    error: Function `join` called with 1 argument but takes 2 or more
+      |
+    1 | x := join('a')
+      |      ^^^^
+  ",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     coalesce,
+  justfile: "
+    x := coalesce('', env_var_or_default('JUST_TEST_UNSET', ''), 'dev', 'prod')
+
+    default:
+      @echo {{x}}
+  ",
+  stdout:   "dev\n",
+}
+
+test! {
+  name:     coalesce_first,
+  justfile: "
+    x := coalesce(`echo 1.0.0`, 'dev')
+
+    default:
+      @echo {{x}}
+  ",
+  stdout:   "1.0.0\n",
+}
+
+test! {
+  name:     coalesce_all_empty,
+  justfile: "
+    x := coalesce('', '') + 'empty'
+
+    default:
+      @echo {{x}}
+  ",
+  stdout:   "empty\n",
+}
+
+test! {
+  name:     coalesce_argument_count,
+  justfile: "
+    x := coalesce()
+  ",
+  stderr:   "
+    error: Function `coalesce` called with 0 arguments but takes 1 or more
+      |
+    1 | x := coalesce()
+      |      ^^^^^^^^
+  ",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     require,
+  justfile: "
+    sh := require('sh')
+
+    default:
+      @test -x {{sh}} && echo found
+  ",
+  stdout:   "found\n",
+}
+
+test! {
+  name:     require_missing,
+  justfile: "
+    x := require('just-missing-executable')
+  ",
+  args:     ("--evaluate"),
+  stderr:   "
+    error: Call to function `require` failed: could not find executable `just-missing-executable` on PATH
+      |
+    1 | x := require('just-missing-executable')
+      |      ^^^^^^^
+  ",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     just_version,
+  justfile: "
+    x := just_version()
+  ",
+  args:     ("--evaluate", "x"),
+  stdout:   env!("CARGO_PKG_VERSION"),
+}
+
+test! {
+  name:     feature,
+  justfile: "
+    features := feature('aliases') + ' ' + feature('function:uuid') + ' ' + feature('setting:shell')
+    missing := feature('modules') + ' ' + feature('function:nope') + ' ' + feature('')
+
+    default:
+      @echo {{features}} {{missing}}
+  ",
+  stdout:   "true true true false false false\n",
+}
+
+test! {
+  name:     feature_conditional,
+  justfile: "
+    flags := if feature('setting:modules') == 'true' { '--modules' } else { '--no-modules' }
+
+    default:
+      @echo {{flags}}
+  ",
+  stdout:   "--no-modules\n",
+}
@@ -0,0 +1,125 @@
+use crate::common::*;
+
+const JUSTFILE: &str = "
+  build:
+    @echo build
+
+  test-unit:
+    @echo test-unit
+
+  test-integration flags='--quiet':
+    @echo test-integration {{flags}}
+
+  test-package package:
+    @echo test-package {{package}}
+
+  _test-helper:
+    @echo test-helper
+
+  echo message:
+    @echo {{message}}
+";
+
+test! {
+  name:     definition_order,
+  justfile: JUSTFILE,
+  args:     ("test-*"),
+  stdout:   "
+    test-unit
+    test-integration --quiet
+  ",
+}
+
+test! {
+  name:     followed_by_recipe,
+  justfile: JUSTFILE,
+  args:     ("test-*", "build"),
+  stdout:   "
+    test-unit
+    test-integration --quiet
+    build
+  ",
+}
+
+test! {
+  name:     preceded_by_recipe,
+  justfile: JUSTFILE,
+  args:     ("build", "test-u?it"),
+  stdout:   "
+    build
+    test-unit
+  ",
+}
+
+test! {
+  name:     character_class,
+  justfile: JUSTFILE,
+  args:     ("[bt]*-unit"),
+  stdout:   "test-unit\n",
+}
+
+test! {
+  name:     argument_not_expanded,
+  justfile: JUSTFILE,
+  args:     ("echo", "test-*"),
+  stdout:   "test-*\n",
+}
+
+test! {
+  name:     recipes_run_once,
+  justfile: JUSTFILE,
+  args:     ("test-unit", "test-*"),
+  stdout:   "
+    test-unit
+    test-integration --quiet
+  ",
+}
+
+test! {
+  name:     no_matches,
+  justfile: JUSTFILE,
+  args:     ("deploy-*"),
+  stderr:   "error: Justfile contains no recipes matching `deploy-*` which can be run without arguments.\n",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     no_matches_without_arguments,
+  justfile: JUSTFILE,
+  args:     ("test-pack*"),
+  stderr:   "error: Justfile contains no recipes matching `test-pack*` which can be run without arguments.\n",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     no_matches_runs_nothing,
+  justfile: JUSTFILE,
+  args:     ("build", "deploy-*"),
+  stderr:   "error: Justfile contains no recipes matching `deploy-*` which can be run without arguments.\n",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     unknown_recipe_default,
+  justfile: "
+    set unknown-recipe := 'default'
+
+    default *args:
+      @echo default {{args}}
+
+    test-unit:
+      @echo test-unit
+  ",
+  args:     ("test-*"),
+  stdout:   "test-unit\n",
+}
+
+test! {
+  name:     dry_run,
+  justfile: JUSTFILE,
+  args:     ("--dry-run", "test-*"),
+  stderr:   "
+    echo test-unit
+    echo test-integration --quiet
+  ",
+}
@@ -0,0 +1,127 @@
+use crate::common::*;
+
+/// Run just in `tmp/project`, with `tmp/config` as the config directory
+fn just_in_project(tmp: &Path, args: &[&str]) -> Output {
+  just_command(&tmp.join("project"))
+    .env("XDG_CONFIG_HOME", tmp.join("config"))
+    .args(args)
+    .output()
+    .unwrap()
+}
+
+#[test]
+fn fallback() {
+  let tmp = tmptree! {
+    config: {
+      just: {
+        justfile: "foo:\n  @cat data",
+      },
+    },
+    project: {
+      data: "project",
+    },
+  };
+
+  let output = just_in_project(tmp.path(), &["foo"]);
+
+  assert!(output.status.success());
+  assert_eq!(str::from_utf8(&output.stdout).unwrap(), "project");
+}
+
+#[test]
+fn project_justfile_takes_precedence() {
+  let tmp = tmptree! {
+    config: {
+      just: {
+        justfile: "foo:\n  @echo global",
+      },
+    },
+    project: {
+      justfile: "foo:\n  @echo project",
+    },
+  };
+
+  let output = just_in_project(tmp.path(), &["foo"]);
+
+  assert!(output.status.success());
+  assert_eq!(str::from_utf8(&output.stdout).unwrap(), "project\n");
+}
+
+#[test]
+fn flag() {
+  let tmp = tmptree! {
+    config: {
+      just: {
+        justfile: "foo:\n  @echo global\n  @cat data",
+      },
+    },
+    project: {
+      justfile: "foo:\n  @echo project",
+      data: "data",
+    },
+  };
+
+  let output = just_in_project(tmp.path(), &["--global-justfile", "foo"]);
+
+  assert!(output.status.success());
+  assert_eq!(str::from_utf8(&output.stdout).unwrap(), "global\ndata");
+}
+
+#[test]
+fn working_directory() {
+  let tmp = tmptree! {
+    config: {
+      just: {
+        justfile: "foo:\n  @cat data",
+      },
+    },
+    project: {
+      sub: {
+        data: "sub",
+      },
+    },
+  };
+
+  let output = just_in_project(tmp.path(), &["-g", "--working-directory", "sub", "foo"]);
+
+  assert!(output.status.success());
+  assert_eq!(str::from_utf8(&output.stdout).unwrap(), "sub");
+}
+
+#[test]
+fn missing() {
+  let tmp = tmptree! {
+    project: {},
+  };
+
+  let output = just_in_project(tmp.path(), &["-g"]);
+
+  assert!(!output.status.success());
+  assert_eq!(
+    str::from_utf8(&output.stderr).unwrap(),
+    format!(
+      "error: Failed to read justfile at `{}`: No such file or directory (os error 2)\n",
+      tmp
+        .path()
+        .join("config")
+        .join("just")
+        .join("justfile")
+        .display()
+    )
+  );
+}
+
+#[test]
+fn no_fallback_without_global_justfile() {
+  let tmp = tmptree! {
+    project: {},
+  };
+
+  let output = just_in_project(tmp.path(), &[]);
+
+  assert!(!output.status.success());
+  assert_eq!(
+    str::from_utf8(&output.stderr).unwrap(),
+    "error: No justfile found\n"
+  );
+}
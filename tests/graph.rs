@@ -0,0 +1,295 @@
+use crate::common::*;
+
+test! {
+  name:     roots,
+  justfile: "
+    all: build test
+
+    build: compile
+
+    test: compile
+      @echo test
+
+    compile:
+
+    lint:
+  ",
+  args:     ("--graph"),
+  stdout:   "
+    all
+    ├── build
+    │   └── compile
+    └── test
+        └── compile
+    lint
+  ",
+}
+
+test! {
+  name:     dependency_arguments,
+  justfile: "
+    release: (build 'release') (build 'debug')
+
+    build mode:
+  ",
+  args:     ("--graph"),
+  stdout:   "
+    release
+    ├── (build 'release')
+    └── (build 'debug')
+  ",
+}
+
+test! {
+  name:     unsorted,
+  justfile: "
+    b: c
+    a:
+    c:
+  ",
+  args:     ("--graph", "--unsorted"),
+  stdout:   "
+    b
+    └── c
+    a
+  ",
+}
+
+test! {
+  name:     focus,
+  justfile: "
+    deploy: build
+
+    test: build lint
+
+    build: compile
+
+    compile: fetch
+
+    fetch:
+
+    lint:
+  ",
+  args:     ("--graph", "--focus", "build"),
+  stdout:   "
+    deploy
+    └── build
+        └── compile
+            └── fetch
+    test
+    └── build
+        └── compile
+            └── fetch
+  ",
+}
+
+test! {
+  name:     focus_without_dependents,
+  justfile: "
+    deploy: build
+    build:
+  ",
+  args:     ("--graph", "--focus", "deploy"),
+  stdout:   "
+    deploy
+    └── build
+  ",
+}
+
+test! {
+  name:     focus_unknown_recipe,
+  justfile: "
+    build:
+  ",
+  args:     ("--graph", "--focus", "biuld"),
+  stderr:   "Justfile does not contain recipe `biuld`.\nDid you mean `build`?\n",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     focus_requires_graph,
+  justfile: "
+    build:
+  ",
+  args:     ("--focus", "build"),
+  stderr:   "
+    error: The following required arguments were not provided:
+        --graph
+
+    USAGE:
+        just --color <COLOR> --error-context <N> --focus <RECIPE> --graph --shell <SHELL> --shell-arg <SHELL-ARG>... --timestamp-format <FORMAT>
+
+    For more information try --help
+  ",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     circular_dependency,
+  justfile: "
+    a: b
+    b: c
+    c: a
+  ",
+  args:     ("--graph"),
+  stderr:   "
+    error: Recipe `c` has circular dependency `a -> b -> c -> a`
+    a
+    └── b
+        └── c
+            └── a
+      |
+    3 | c: a
+      |    ^
+  ",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     summary_verbose,
+  justfile: "
+    all: build test
+
+    build: compile
+
+    test: compile _helper
+      @echo test
+
+    compile: fetch
+
+    fetch:
+
+    _helper: lint
+
+    lint:
+
+    _private: all
+  ",
+  args:     ("--summary", "--verbose"),
+  stdout:   "
+    all
+    ├── build
+    │   └── compile
+    │       └── fetch
+    └── test
+        ├── compile (*)
+        └── _helper
+            └── lint
+  ",
+}
+
+test! {
+  name:     summary_verbose_unsorted,
+  justfile: "
+    b: c
+    a:
+    c:
+  ",
+  args:     ("--summary", "--verbose", "--unsorted"),
+  stdout:   "
+    b
+    └── c
+    a
+  ",
+}
+
+test! {
+  name:     summary_verbose_no_recipes,
+  justfile: "",
+  args:     ("--summary", "--verbose"),
+  stderr:   "Justfile contains no recipes.\n",
+}
+
+test! {
+  name:     dot,
+  justfile: "
+    alias b := build
+
+    release: (build 'release') test
+
+    test: build
+
+    build mode='debug':
+  ",
+  args:     ("--graph", "--format", "dot"),
+  stdout:   "
+    digraph justfile {
+      \"build\";
+      \"release\";
+      \"test\";
+      \"b\" [style=dashed];
+      \"release\" -> \"build\" [label=\"'release'\"];
+      \"release\" -> \"test\";
+      \"test\" -> \"build\";
+      \"b\" -> \"build\" [style=dashed];
+    }
+  ",
+}
+
+test! {
+  name:     dot_quotes_labels,
+  justfile: r#"
+    a: (b "x\"y" 'z\')
+
+    b p q:
+  "#,
+  args:     ("--graph", "--format", "dot"),
+  stdout:   r#"
+    digraph justfile {
+      "a";
+      "b";
+      "a" -> "b" [label="\"x\\\"y\" 'z\\'"];
+    }
+  "#,
+}
+
+test! {
+  name:     dot_focus,
+  justfile: "
+    alias d := deploy
+
+    deploy: build
+
+    test: build lint
+
+    build: compile
+
+    compile:
+
+    lint:
+  ",
+  args:     ("--graph", "--format", "dot", "--focus", "build"),
+  stdout:   "
+    digraph justfile {
+      \"build\";
+      \"compile\";
+      \"deploy\";
+      \"test\";
+      \"d\" [style=dashed];
+      \"build\" -> \"compile\";
+      \"deploy\" -> \"build\";
+      \"test\" -> \"build\";
+      \"d\" -> \"deploy\" [style=dashed];
+    }
+  ",
+}
+
+test! {
+  name:     dot_unsupported_by_dump,
+  justfile: "
+    build:
+  ",
+  args:     ("--dump", "--format", "dot"),
+  stderr:   "error: `--dump` does not support `--format dot`\n",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     graph_unsupported_format,
+  justfile: "
+    build:
+  ",
+  args:     ("--graph", "--format", "json"),
+  stderr:   "error: `--graph` does not support `--format json`\n",
+  status:   EXIT_FAILURE,
+}
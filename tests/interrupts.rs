@@ -14,7 +14,7 @@ mod unix {
     }
   }
 
-  fn interrupt_test(justfile: &str) {
+  fn interrupt_test(justfile: &str) -> tempfile::TempDir {
     let tmp = tempdir();
     let mut justfile_path = tmp.path().to_path_buf();
     justfile_path.push("justfile");
@@ -44,6 +44,8 @@ mod unix {
     }
 
     assert_eq!(status.code(), Some(130));
+
+    tmp
   }
 
   #[test]
@@ -81,4 +83,21 @@ default:
 ",
     );
   }
+
+  #[test]
+  #[ignore]
+  fn interrupt_cleanup() {
+    let tmp = interrupt_test(
+      "
+[cleanup('down')]
+default:
+  @sleep 1
+
+down:
+  @touch cleaned-up
+",
+    );
+
+    assert!(tmp.path().join("cleaned-up").is_file());
+  }
 }
@@ -0,0 +1,96 @@
+use crate::common::*;
+
+test! {
+  name: runs_remaining_recipes,
+  justfile: "
+    a:
+      @exit 3
+
+    b:
+      @echo b
+
+    c:
+      @exit 4
+  ",
+  args: ("--keep-going", "a", "b", "c"),
+  stdout: "b\n",
+  stderr: "
+    error: Recipe `a` failed on line 2 with exit code 3
+    error: Recipe `c` failed on line 8 with exit code 4
+    ===> Results:
+    a: failed with exit code 3
+    b: succeeded
+    c: failed with exit code 4
+    error: Recipes `a` and `c` failed
+  ",
+  status: 3,
+}
+
+test! {
+  name: success,
+  justfile: "
+    a:
+      @echo a
+
+    b x:
+      @echo {{x}}
+  ",
+  args: ("-k", "a", "b", "foo"),
+  stdout: "a\nfoo\n",
+  stderr: "
+    ===> Results:
+    a: succeeded
+    b foo: succeeded
+  ",
+}
+
+test! {
+  name: failed_dependency,
+  justfile: "
+    a: b
+      @echo a
+
+    b:
+      @exit 2
+
+    c:
+      @echo c
+  ",
+  args: ("--keep-going", "a", "c"),
+  stdout: "c\n",
+  stderr: "
+    error: Recipe `b` failed on line 5 with exit code 2
+    ===> Results:
+    a: failed with exit code 2
+    c: succeeded
+    error: Recipe `a` failed
+  ",
+  status: 2,
+}
+
+test! {
+  name: quiet,
+  justfile: "
+    a:
+      @exit 3
+
+    b:
+      @echo b
+  ",
+  args: ("--keep-going", "--quiet", "a", "b"),
+  status: 3,
+}
+
+test! {
+  name: without_keep_going,
+  justfile: "
+    a:
+      @exit 3
+
+    b:
+      @echo b
+  ",
+  args: ("a", "b"),
+  stderr: "error: Recipe `a` failed on line 2 with exit code 3\n",
+  status: 3,
+}
@@ -3,23 +3,66 @@ mod test;
 
 mod common;
 
+mod alias_arguments;
+mod arithmetic;
+mod attributes;
+mod booleans;
+mod cache;
+mod changed;
+mod choices;
 mod choose;
+mod coalesce;
+mod command;
 mod completions;
 mod conditional;
+mod default_recipe;
 mod delimiters;
+mod doc_interpolation;
 mod dotenv;
+mod download;
+mod dry_run;
+mod dump;
+mod echo_truncate;
 mod edit;
 mod error_messages;
 mod evaluate;
 mod examples;
+mod exit_message;
 mod export;
+mod fmt;
+mod function_cache;
+mod functions;
+mod glob_invocation;
+mod global_justfile;
+mod graph;
 mod init;
 mod interrupts;
 mod invocation_directory;
+mod keep_going;
+mod local_justfile;
+mod log_json;
 mod misc;
+mod no_dependencies;
+mod no_exit_message;
+mod ordering;
+mod pager;
+mod parameter_forwarding;
 mod quiet;
 mod readme;
+mod remember_args;
+mod report;
+mod safe;
 mod search;
+mod set_env;
+mod shebang;
 mod shell;
+mod show;
+mod stats;
+mod stdin;
 mod string;
+mod suggestions;
+mod tee;
+mod timestamps;
+mod timings;
+mod unknown_recipe;
 mod working_directory;
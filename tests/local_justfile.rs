@@ -0,0 +1,139 @@
+use crate::common::*;
+
+#[test]
+fn recipes_are_added() {
+  let tmp = tmptree! {
+    justfile: "foo:\n  @echo foo",
+    "justfile.local": "bar:\n  @echo bar",
+  };
+
+  let output = just(tmp.path(), &["foo", "bar"]);
+
+  assert!(output.status.success());
+  assert_eq!(str::from_utf8(&output.stdout).unwrap(), "foo\nbar\n");
+}
+
+#[test]
+fn recipes_are_overridden() {
+  let tmp = tmptree! {
+    justfile: "default: foo\n\nfoo:\n  @echo shared",
+    "justfile.local": "foo:\n  @echo local",
+  };
+
+  let output = just(tmp.path(), &[]);
+
+  assert!(output.status.success());
+  assert_eq!(str::from_utf8(&output.stdout).unwrap(), "local\n");
+  assert_eq!(str::from_utf8(&output.stderr).unwrap(), "");
+}
+
+#[test]
+fn variables_are_overridden() {
+  let tmp = tmptree! {
+    justfile: "x := 'shared'\n\nfoo:\n  @echo {{x}}",
+    "justfile.local": "x := 'local'",
+  };
+
+  let output = just(tmp.path(), &[]);
+
+  assert!(output.status.success());
+  assert_eq!(str::from_utf8(&output.stdout).unwrap(), "local\n");
+}
+
+#[test]
+fn settings_are_overridden() {
+  let tmp = tmptree! {
+    justfile: "set shell := ['bash', '-c']\n\nfoo:\n  @echo $0",
+    "justfile.local": "set shell := ['sh', '-c']",
+  };
+
+  let output = just(tmp.path(), &[]);
+
+  assert!(output.status.success());
+  assert_eq!(str::from_utf8(&output.stdout).unwrap(), "sh\n");
+}
+
+#[test]
+fn alias_overrides_recipe() {
+  let tmp = tmptree! {
+    justfile: "foo:\n  @echo foo\n\nb:\n  @echo shared",
+    "justfile.local": "alias b := foo",
+  };
+
+  let output = just(tmp.path(), &["b"]);
+
+  assert!(output.status.success());
+  assert_eq!(str::from_utf8(&output.stdout).unwrap(), "foo\n");
+}
+
+#[test]
+fn overrides_are_reported_when_verbose() {
+  let tmp = tmptree! {
+    justfile: "x := 'shared'\n\nfoo:\n  @echo {{x}}",
+    "justfile.local": "x := 'local'\n\nfoo:\n  @echo mine",
+  };
+
+  let output = just(tmp.path(), &["--verbose"]);
+
+  assert!(output.status.success());
+  assert_eq!(str::from_utf8(&output.stdout).unwrap(), "mine\n");
+  assert_eq!(
+    str::from_utf8(&output.stderr).unwrap(),
+    "note: Local justfile overrides variable `x`
+  |
+1 | x := 'local'
+  | ^
+note: Local justfile overrides recipe `foo`
+  |
+3 | foo:
+  | ^^^
+===> Running recipe `foo`...
+echo mine
+"
+  );
+}
+
+#[test]
+fn local_errors_are_reported() {
+  let tmp = tmptree! {
+    justfile: "foo:\n  @echo foo",
+    "justfile.local": "bar:\n  echo {{baz}}",
+  };
+
+  let output = just(tmp.path(), &[]);
+
+  assert_eq!(output.status.code(), Some(EXIT_FAILURE));
+  assert_eq!(
+    str::from_utf8(&output.stderr).unwrap(),
+    "error: Variable `baz` not defined
+  |
+2 |   echo {{baz}}
+  |          ^^^
+"
+  );
+}
+
+#[test]
+fn justfile_name_is_respected() {
+  let tmp = tmptree! {
+    Justfile: "foo:\n  @echo foo",
+    "Justfile.local": "foo:\n  @echo local",
+  };
+
+  let output = just(tmp.path(), &[]);
+
+  assert!(output.status.success());
+  assert_eq!(str::from_utf8(&output.stdout).unwrap(), "local\n");
+}
+
+#[test]
+fn format_ignores_local_justfile() {
+  let tmp = tmptree! {
+    justfile: "foo:\n    echo foo\n",
+    "justfile.local": "bar:\n    echo bar\n",
+  };
+
+  let output = just(tmp.path(), &["--fmt", "--check"]);
+
+  assert!(output.status.success());
+}
@@ -0,0 +1,113 @@
+use crate::common::*;
+
+/// Run just in `tmp` with `args`, returning its exit status and the contents of
+/// `log.jsonl`, with times and durations replaced by `0`, or `None` if no log
+/// was written
+fn run_with_log(tmp: &Path, args: &[&str]) -> (Option<i32>, Option<String>) {
+  let output = just(tmp, args);
+
+  let log = fs::read_to_string(tmp.join("log.jsonl")).ok().map(|log| {
+    regex::Regex::new(r#""(time_ms|duration_ms)":\d+"#)
+      .unwrap()
+      .replace_all(&log, r#""$1":0"#)
+      .into_owned()
+  });
+
+  (output.status.code(), log)
+}
+
+#[test]
+fn recipes_and_lines() {
+  let tmp = tmptree! {
+    justfile: "build: setup\n  echo build\n  @echo done\n\nsetup:\n  @echo setup",
+  };
+
+  let (status, log) = run_with_log(tmp.path(), &["--log-json", "log.jsonl", "build"]);
+
+  assert_eq!(status, Some(EXIT_SUCCESS));
+  assert_eq!(
+    log.unwrap(),
+    r#"{"event":"recipe-start","time_ms":0,"recipe":"setup","arguments":[]}
+{"event":"line","time_ms":0,"recipe":"setup","line":6,"command":"echo setup"}
+{"event":"recipe-end","time_ms":0,"recipe":"setup","arguments":[],"status":0,"duration_ms":0,"error":null}
+{"event":"recipe-start","time_ms":0,"recipe":"build","arguments":[]}
+{"event":"line","time_ms":0,"recipe":"build","line":2,"command":"echo build"}
+{"event":"line","time_ms":0,"recipe":"build","line":3,"command":"echo done"}
+{"event":"recipe-end","time_ms":0,"recipe":"build","arguments":[],"status":0,"duration_ms":0,"error":null}
+"#
+  );
+}
+
+#[test]
+fn failure() {
+  let tmp = tmptree! {
+    justfile: "test name:\n  @echo {{name}}\n  @exit 3",
+  };
+
+  let (status, log) = run_with_log(tmp.path(), &["--log-json", "log.jsonl", "test", "unit"]);
+
+  assert_eq!(status, Some(3));
+  assert_eq!(
+    log.unwrap(),
+    r#"{"event":"recipe-start","time_ms":0,"recipe":"test","arguments":["unit"]}
+{"event":"line","time_ms":0,"recipe":"test","line":2,"command":"echo unit"}
+{"event":"line","time_ms":0,"recipe":"test","line":3,"command":"exit 3"}
+{"event":"recipe-end","time_ms":0,"recipe":"test","arguments":["unit"],"status":3,"duration_ms":0,"error":"Recipe `test` failed on line 3 with exit code 3"}
+"#
+  );
+}
+
+#[test]
+fn backticks() {
+  let tmp = tmptree! {
+    justfile: "x := `echo a`\n\nfoo y=`exit 2`:\n  @echo {{x}}",
+  };
+
+  let (status, log) = run_with_log(tmp.path(), &["--log-json", "log.jsonl", "foo", "b"]);
+
+  assert_eq!(status, Some(EXIT_SUCCESS));
+  assert_eq!(
+    log.unwrap(),
+    r#"{"event":"backtick","time_ms":0,"command":"echo a","status":0,"duration_ms":0}
+{"event":"recipe-start","time_ms":0,"recipe":"foo","arguments":["b"]}
+{"event":"line","time_ms":0,"recipe":"foo","line":4,"command":"echo a"}
+{"event":"recipe-end","time_ms":0,"recipe":"foo","arguments":["b"],"status":0,"duration_ms":0,"error":null}
+"#
+  );
+
+  let (status, log) = run_with_log(tmp.path(), &["--log-json", "log.jsonl", "foo"]);
+
+  assert_eq!(status, Some(2));
+  assert_eq!(
+    log.unwrap(),
+    r#"{"event":"backtick","time_ms":0,"command":"echo a","status":0,"duration_ms":0}
+{"event":"backtick","time_ms":0,"command":"exit 2","status":2,"duration_ms":0}
+"#
+  );
+}
+
+#[test]
+fn dry_run() {
+  let tmp = tmptree! {
+    justfile: "foo:\n  echo foo",
+  };
+
+  let (status, log) = run_with_log(tmp.path(), &["--log-json", "log.jsonl", "--dry-run"]);
+
+  assert_eq!(status, Some(EXIT_SUCCESS));
+  assert_eq!(log, None);
+}
+
+#[test]
+fn unwritable() {
+  let tmp = tmptree! {
+    justfile: "foo:\n  echo foo",
+  };
+
+  let output = just(tmp.path(), &["--log-json", "missing/log.jsonl"]);
+
+  assert_eq!(output.status.code(), Some(EXIT_FAILURE));
+  assert!(str::from_utf8(&output.stderr)
+    .unwrap()
+    .starts_with("error: Failed to open JSON log `missing/log.jsonl`: "));
+}
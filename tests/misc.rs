@@ -111,6 +111,49 @@ test! {
   status: EXIT_FAILURE,
 }
 
+test! {
+  name: alias_to_alias,
+  justfile: "
+    alias b := build
+    alias bb := b
+
+    build:
+      echo build
+  ",
+  args: ("bb"),
+  stdout: "build\n",
+  stderr: "echo build\n",
+}
+
+test! {
+  name: alias_to_alias_listing,
+  justfile: "
+    alias b := build
+    alias bb := b
+
+    build:
+  ",
+  args: ("--list"),
+  stdout: "
+    Available recipes:
+        build
+        b     # alias for `build`
+        bb    # alias for `build`
+  ",
+}
+
+test! {
+  name: circular_alias,
+  justfile: "alias a := b\nalias b := a\n",
+  stderr: "
+    error: Alias `a` has circular target `a -> b -> a`
+      |
+    1 | alias a := b
+      |       ^
+  ",
+  status: EXIT_FAILURE,
+}
+
 test! {
   name: alias_shadows_recipe,
   justfile: "bar:\n  echo bar\nalias foo := bar\nfoo:\n  echo foo",
@@ -1021,6 +1064,56 @@ b:
   "#,
 }
 
+test! {
+  name:     list_heading_and_prefix_settings,
+  justfile: r#"
+set list-heading := "Recipes:\n"
+set list-prefix := "  - "
+
+a:
+b:
+"#,
+  args:     ("--list"),
+  stdout:   r#"
+    Recipes:
+      - a
+      - b
+  "#,
+}
+
+test! {
+  name:     list_heading_setting_empty,
+  justfile: r#"
+set list-heading := ''
+set list-prefix := '* '
+
+a:
+b:
+"#,
+  args:     ("--list"),
+  stdout:   r#"
+    * a
+    * b
+  "#,
+}
+
+test! {
+  name:     list_heading_and_prefix_arguments_override_settings,
+  justfile: r#"
+set list-heading := "Recipes:\n"
+set list-prefix := "  - "
+
+a:
+b:
+"#,
+  args:     ("--list", "--list-heading", "Cool stuff…\n", "--list-prefix", "* "),
+  stdout:   r#"
+    Cool stuff…
+    * a
+    * b
+  "#,
+}
+
 test! {
   name:     show_suggestion,
   justfile: r#"
@@ -1234,6 +1327,57 @@ error: Recipe `infallable` failed on line 3 with exit code 202
   status: 202,
 }
 
+test! {
+  name:     quiet_infallable_command,
+  justfile: "
+    infallable:
+      @-exit 101
+      -@exit 102
+      @echo done
+  ",
+  stdout:   "done\n",
+}
+
+test! {
+  name:     quiet_infallable_command_with_interpolation,
+  justfile: "
+    code := '101'
+
+    infallable:
+      @-{{ 'exit' }} {{code}}
+      -@echo {{code}}
+  ",
+  stdout:   "101\n",
+}
+
+test! {
+  name:     quiet_infallable_command_dry_run,
+  justfile: "
+    infallable:
+      -@exit 101
+      @-exit 102
+  ",
+  args:     ("--dry-run"),
+  stderr:   "
+    exit 101
+    exit 102
+  ",
+}
+
+test! {
+  name:     quiet_infallable_command_shebang_flag,
+  justfile: "
+    infallable:
+      @-echo a
+      -@echo b
+  ",
+  args:     ("--shebang", "/bin/sh"),
+  stdout:   "
+    a
+    b
+  ",
+}
+
 test! {
   name:     quiet_recipe,
   justfile: r#"
@@ -1257,6 +1401,18 @@ test! {
   stderr:   "#!/bin/sh\necho hello\n",
 }
 
+test! {
+  name:     shebang_split_arguments,
+  justfile: "
+    foo:
+      #!/usr/bin/env -S sh -e -u
+      false
+      echo unreachable
+  ",
+  stderr:   "error: Recipe `foo` failed with exit code 1\n",
+  status:   EXIT_FAILURE,
+}
+
 test! {
   name:     shebang_line_numbers,
   justfile: r#"
@@ -1456,6 +1612,11 @@ test! {
   args:     ("a"),
   stdout:   "",
   stderr:   "error: Recipe `d` has circular dependency `a -> b -> c -> d -> a`
+a
+└── b
+    └── c
+        └── d
+            └── a
   |
 4 | d: a
   |    ^
@@ -1862,6 +2023,57 @@ foo x=y:
    stderr:   "echo foo\n",
 }
 
+test! {
+   name:     default_earlier_parameter,
+   justfile: "
+foo x y=(x + '-tests') z=replace(y, '-', '_'):
+  echo {{x}} {{y}} {{z}}
+",
+   args:     ("foo", "bar"),
+   stdout:   "bar bar-tests bar_tests\n",
+   stderr:   "echo bar bar-tests bar_tests\n",
+}
+
+test! {
+   name:     default_earlier_parameter_supplied,
+   justfile: "
+foo x y=(x + '-tests'):
+  echo {{x}} {{y}}
+",
+   args:     ("foo", "bar", "baz"),
+   stdout:   "bar baz\n",
+   stderr:   "echo bar baz\n",
+}
+
+test! {
+   name:     default_later_parameter,
+   justfile: "
+foo x=y y='bar':
+  echo {{x}}
+",
+   stdout:   "",
+   stderr:   r#"error: Variable `y` not defined
+  |
+1 | foo x=y y='bar':
+  |       ^
+"#,
+   status:   EXIT_FAILURE,
+}
+
+test! {
+   name:     dependency_argument_default_parameter,
+   justfile: "
+test target tests=(target + '-tests'): (build tests)
+  echo test {{target}}
+
+build target:
+  echo build {{target}}
+",
+   args:     ("test", "foo"),
+   stdout:   "build foo-tests\ntest foo\n",
+   stderr:   "echo build foo-tests\necho test foo\n",
+}
+
 test! {
   name:     test_os_arch_functions_in_default,
   justfile: r#"
@@ -2175,20 +2387,62 @@ test! {
   shell: false,
 }
 
+test! {
+  name: same_dependency_different_arguments,
+  justfile: "
+    all: (build 'linux') (build 'windows') (build 'linux')
+
+    build target:
+      echo {{target}}
+  ",
+  args: (),
+  stdout: "linux\nwindows\n",
+  stderr: "echo linux\necho windows\n",
+  shell: false,
+}
+
+test! {
+  name: duplicate_dependency_default_argument,
+  justfile: "
+    all: build (build 'linux') (build 'windows')
+
+    build target='linux':
+      echo {{target}}
+  ",
+  args: (),
+  stdout: "linux\nwindows\n",
+  stderr: "echo linux\necho windows\n",
+  shell: false,
+}
+
+test! {
+  name: dependency_and_command_line_different_arguments,
+  justfile: "
+    all: (build 'linux')
+
+    build target:
+      echo {{target}}
+  ",
+  args: ("all", "build", "windows"),
+  stdout: "linux\nwindows\n",
+  stderr: "echo linux\necho windows\n",
+  shell: false,
+}
+
 test! {
   name: parameter_cross_reference_error,
   justfile: "
     foo:
 
-    bar a b=a:
+    bar a=b b='':
   ",
   args: (),
   stdout: "",
   stderr: "
-    error: Variable `a` not defined
+    error: Variable `b` not defined
       |
-    3 | bar a b=a:
-      |         ^
+    3 | bar a=b b='':
+      |       ^
   ",
   status: EXIT_FAILURE,
   shell: false,
@@ -2222,6 +2476,18 @@ test! {
   shell: false,
 }
 
+test! {
+  name: variables_backticks_not_evaluated,
+  justfile: "
+    a := `exit 1`
+    export b := 'b'
+  ",
+  args: ("--variables"),
+  stdout: "a b\n",
+  stderr: "",
+  shell: false,
+}
+
 test! {
   name: interpolation_evaluation_ignore_quiet,
   justfile: r#"
@@ -2303,3 +2569,304 @@ test! {
   "#,
   args: (),
 }
+
+test! {
+  name:     env_function,
+  justfile: "
+    a := env('JUST_TEST_VARIABLE')
+    b := env('JUST_TEST_MISSING', 'default')
+    c := env('JUST_TEST_VARIABLE', 'default')
+
+    default:
+      @echo {{a}} {{b}} {{c}}
+  ",
+  env:      {
+    "JUST_TEST_VARIABLE": "value",
+  },
+  stdout:   "value default value\n",
+}
+
+test! {
+  name:     env_function_failure,
+  justfile: "a:\n  echo {{env('JUST_TEST_MISSING')}}",
+  args:     ("a"),
+  stdout:   "",
+  stderr:   "error: Call to function `env` failed: environment variable `JUST_TEST_MISSING` not \
+             present\n  |\n2 |   echo {{env('JUST_TEST_MISSING')}}\n  |          ^^^\n",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     env_function_argument_count,
+  justfile: "a := env('A', 'B', 'C')",
+  stderr:   "
+    error: Function `env` called with 3 arguments but takes 1 or 2
+      |
+    1 | a := env('A', 'B', 'C')
+      |      ^^^
+  ",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     list_recipe,
+  justfile: "
+    # cut a release
+    release version: build (test 'all')
+
+    # build the binary
+    build: compile
+
+    compile:
+
+    # run the tests
+    test target='unit': compile
+
+    unrelated:
+  ",
+  args:     ("--list", "release"),
+  stdout:   "
+    release version  # cut a release
+        build        # build the binary
+            compile
+        (test 'all') # run the tests
+            compile
+  ",
+}
+
+test! {
+  name:     list_recipe_alias,
+  justfile: "
+    alias b := build
+
+    build: compile
+
+    compile:
+  ",
+  args:     ("-l", "b"),
+  stdout:   "
+    build
+        compile
+  ",
+}
+
+test! {
+  name:     list_recipe_unknown,
+  justfile: "
+    build:
+  ",
+  args:     ("--list", "biuld"),
+  stdout:   "",
+  stderr:   "Justfile does not contain recipe `biuld`.\nDid you mean `build`?\n",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     unicode_names,
+  justfile: "
+    größe := 'groß'
+
+    построить größe-π='3':
+      @echo {{größe}} {{größe-π}}
+  ",
+  args:     ("größe=klein", "построить"),
+  stdout:   "klein 3\n",
+}
+
+test! {
+  name:     strict_interpolation_variable,
+  justfile: "
+    set strict-interpolation
+
+    dir := env_var_or_default('JUST_TEST_UNSET_DIRECTORY', '')
+
+    clean:
+      rm -rf /{{dir}}
+  ",
+  stderr:   "
+    error: Interpolation `{{dir}}` evaluated to an empty default, because environment variable `JUST_TEST_UNSET_DIRECTORY` is not set
+      |
+    3 | dir := env_var_or_default('JUST_TEST_UNSET_DIRECTORY', '')
+      |        ^^^^^^^^^^^^^^^^^^
+  ",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     strict_interpolation_parameter,
+  justfile: "
+    set strict-interpolation
+
+    foo bar='':
+      echo {{bar}}
+  ",
+  stderr:   "
+    error: Interpolation `{{bar}}` evaluated to an empty default, because parameter `bar` was not passed an argument
+      |
+    3 | foo bar='':
+      |     ^^^
+  ",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     strict_interpolation_function_call,
+  justfile: "
+    set strict-interpolation
+
+    foo:
+      echo {{env_var_or_default('JUST_TEST_UNSET_DIRECTORY', '')}}
+  ",
+  stderr:   "
+    error: Interpolation `{{env_var_or_default('JUST_TEST_UNSET_DIRECTORY', '')}}` evaluated to an empty default, because environment variable `JUST_TEST_UNSET_DIRECTORY` is not set
+      |
+    4 |   echo {{env_var_or_default('JUST_TEST_UNSET_DIRECTORY', '')}}
+      |          ^^^^^^^^^^^^^^^^^^
+  ",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     strict_interpolation_coalesce,
+  justfile: "
+    set strict-interpolation
+
+    dir := env_var_or_default('JUST_TEST_UNSET_DIRECTORY', 'x') || ''
+
+    foo:
+      echo {{'' || dir}}{{'' || ''}}
+  ",
+  stderr:   "
+    error: Interpolation `{{'' || ''}}` evaluated to an empty default, because the left-hand side of `||` is empty
+      |
+    6 |   echo {{'' || dir}}{{'' || ''}}
+      |                          ^^
+  ",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     strict_interpolation_empty_literal,
+  justfile: "
+    set strict-interpolation
+
+    foo:
+      echo {{'' + ''}}x
+  ",
+  stdout:   "x\n",
+  stderr:   "echo x\n",
+}
+
+test! {
+  name:     strict_interpolation_empty_variadic,
+  justfile: "
+    set strict-interpolation
+
+    foo *args:
+      echo hi {{args}}
+  ",
+  stdout:   "hi\n",
+  stderr:   "echo hi \n",
+}
+
+test! {
+  name:     strict_interpolation_empty_argument,
+  justfile: "
+    set strict-interpolation
+
+    bar x='':
+      echo {{x}}y
+  ",
+  args:     ("bar", ""),
+  stdout:   "y\n",
+  stderr:   "echo y\n",
+}
+
+test! {
+  name:     strict_interpolation_empty_environment_variable,
+  justfile: "
+    set strict-interpolation
+
+    dir := env_var_or_default('JUST_TEST_EMPTY_DIRECTORY', '')
+
+    foo:
+      echo {{dir}}x
+  ",
+  env:      {
+    "JUST_TEST_EMPTY_DIRECTORY": "",
+  },
+  stdout:   "x\n",
+  stderr:   "echo x\n",
+}
+
+test! {
+  name:     strict_interpolation_nonempty,
+  justfile: "
+    set strict-interpolation
+
+    foo bar='baz':
+      echo {{bar}}
+  ",
+  stdout:   "baz\n",
+  stderr:   "echo baz\n",
+}
+
+test! {
+  name:     strict_interpolation_disabled,
+  justfile: "
+    foo bar='':
+      echo {{bar}}x
+  ",
+  stdout:   "x\n",
+  stderr:   "echo x\n",
+}
+
+test! {
+  name:     argument_terminator,
+  justfile: "
+    foo +args:
+      echo {{args}}
+  ",
+  args:     ("foo", "--", "--list", "--", "a=b"),
+  stdout:   "--list -- a=b\n",
+  stderr:   "echo --list -- a=b\n",
+}
+
+test! {
+  name: environment_variable_flag,
+  justfile: "
+    foo:
+      echo foo
+  ",
+  env: {
+    "JUST_DRY_RUN": "true",
+  },
+  stderr: "echo foo\n",
+}
+
+test! {
+  name: environment_variable_flag_false,
+  justfile: "
+    foo:
+      echo foo
+  ",
+  env: {
+    "JUST_DRY_RUN": "false",
+  },
+  stdout: "foo\n",
+  stderr: "echo foo\n",
+}
+
+test! {
+  name: environment_variable_option_overridden,
+  justfile: "
+    foo:
+      echo foo
+  ",
+  args: ("--shell-arg", "-cu"),
+  env: {
+    "JUST_SHELL_ARG": "-bogus",
+  },
+  stdout: "foo\n",
+  stderr: "echo foo\n",
+}
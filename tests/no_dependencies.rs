@@ -0,0 +1,125 @@
+use crate::common::*;
+
+test! {
+  name: skips_dependencies,
+  justfile: "
+    build: setup
+      echo build
+
+    setup:
+      echo setup
+  ",
+  args: ("--no-dependencies", "build"),
+  stdout: "build\n",
+  stderr: "echo build\n",
+}
+
+test! {
+  name: dependencies_of_dependencies_are_not_run,
+  justfile: "
+    package: build
+      echo package
+
+    build: setup
+      echo build
+
+    setup:
+      echo setup
+  ",
+  args: ("--no-dependencies", "package", "build"),
+  stdout: "package\nbuild\n",
+  stderr: "echo package\necho build\n",
+}
+
+test! {
+  name: dependency_arguments_are_not_evaluated,
+  justfile: "
+    build: (setup `exit 1`)
+      echo build
+
+    setup mode:
+      echo {{mode}}
+  ",
+  args: ("--no-dependencies"),
+  stdout: "build\n",
+  stderr: "echo build\n",
+}
+
+test! {
+  name: cleanup_recipes_still_run,
+  justfile: "
+    [cleanup('teardown')]
+    build: setup
+      echo build
+
+    setup:
+      echo setup
+
+    teardown:
+      echo teardown
+  ",
+  args: ("--no-dependencies"),
+  stdout: "build\nteardown\n",
+  stderr: "echo build\necho teardown\n",
+}
+
+test! {
+  name: dry_run,
+  justfile: "
+    build: setup
+      echo build
+
+    setup:
+      echo setup
+  ",
+  args: ("--no-dependencies", "--dry-run"),
+  stderr: "echo build\n",
+}
+
+test! {
+  name: verbose,
+  justfile: "
+    build: setup (lint 'all')
+      echo build
+
+    setup:
+
+    lint what:
+  ",
+  args: ("--no-dependencies", "--dry-run", "--verbose"),
+  stderr: "
+    ===> Skipping dependencies of recipe `build`: `setup` and `lint`
+    ===> Would run recipe `build`...
+    echo build
+  ",
+}
+
+test! {
+  name: requirements_of_dependencies_are_not_checked,
+  justfile: "
+    build: setup
+      echo build
+
+    [requires('just-missing-executable')]
+    setup:
+  ",
+  args: ("--no-dependencies"),
+  stdout: "build\n",
+  stderr: "echo build\n",
+}
+
+test! {
+  name: environment_variable,
+  justfile: "
+    build: setup
+      echo build
+
+    setup:
+      echo setup
+  ",
+  env: {
+    "JUST_NO_DEPENDENCIES": "1",
+  },
+  stdout: "build\n",
+  stderr: "echo build\n",
+}
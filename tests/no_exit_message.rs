@@ -0,0 +1,129 @@
+use crate::common::*;
+
+test! {
+  name:     recipe_exit_message_suppressed,
+  justfile: "
+    [no-exit-message]
+    hello:
+      @echo hello
+      @exit 100
+  ",
+  stdout:   "hello\n",
+  status:   100,
+}
+
+test! {
+  name:     shebang_exit_message_suppressed,
+  justfile: "
+    [no-exit-message]
+    hello:
+      #!/usr/bin/env bash
+      echo hello
+      exit 100
+  ",
+  stdout:   "hello\n",
+  status:   100,
+}
+
+test! {
+  name:     recipe_has_doc_comment,
+  justfile: "
+    # This is a doc comment
+    [no-exit-message]
+    hello:
+      @exit 100
+  ",
+  args:     ("--list"),
+  stdout:   "
+    Available recipes:
+        hello # This is a doc comment
+  ",
+}
+
+test! {
+  name:     other_recipes_print_exit_message,
+  justfile: "
+    [no-exit-message]
+    a: b
+      @exit 100
+
+    b:
+      @exit 101
+  ",
+  stderr:   "error: Recipe `b` failed on line 6 with exit code 101\n",
+  status:   101,
+}
+
+test! {
+  name:     dependency_exit_message_suppressed,
+  justfile: "
+    a: b
+      @exit 100
+
+    [no-exit-message]
+    b:
+      @exit 101
+  ",
+  status:   101,
+}
+
+test! {
+  name:     exit_message_setting,
+  justfile: "
+    set no-exit-message
+
+    a:
+      @exit 100
+  ",
+  status:   100,
+}
+
+test! {
+  name:     exit_message_setting_false,
+  justfile: "
+    set no-exit-message := false
+
+    a:
+      @exit 100
+  ",
+  stderr:   "error: Recipe `a` failed on line 4 with exit code 100\n",
+  status:   100,
+}
+
+test! {
+  name:     other_errors_still_printed,
+  justfile: "
+    set no-exit-message
+
+    a x:
+      @exit 100
+  ",
+  args:     ("a"),
+  stderr:   "
+    error: Recipe `a` got 0 arguments but takes 1
+    usage:
+        just a x
+  ",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     keep_going,
+  justfile: "
+    [no-exit-message]
+    a:
+      @exit 100
+
+    b:
+      @exit 101
+  ",
+  args:     ("--keep-going", "a", "b"),
+  stderr:   "
+    error: Recipe `b` failed on line 6 with exit code 101
+    ===> Results:
+    a: failed with exit code 100
+    b: failed with exit code 101
+    error: Recipes `a` and `b` failed
+  ",
+  status:   100,
+}
@@ -0,0 +1,187 @@
+use crate::common::*;
+
+// Items are deliberately declared in neither name order nor reverse name
+// order, so that these tests fail if output follows storage order rather
+// than the requested order.
+const JUSTFILE: &str = "
+  b := '1'
+  c := '2'
+  a := '3'
+
+  alias z := b
+
+  b:
+
+  c: b
+
+  [group('x')]
+  a:
+";
+
+test! {
+  name:     summary,
+  justfile: JUSTFILE,
+  args:     ("--summary"),
+  stdout:   "a b c\n",
+}
+
+test! {
+  name:     summary_source,
+  justfile: JUSTFILE,
+  args:     ("--summary", "--unsorted"),
+  stdout:   "b c a\n",
+}
+
+test! {
+  name:     variables,
+  justfile: JUSTFILE,
+  args:     ("--variables"),
+  stdout:   "a b c\n",
+}
+
+test! {
+  name:     variables_source,
+  justfile: JUSTFILE,
+  args:     ("--variables", "--unsorted"),
+  stdout:   "b c a\n",
+}
+
+test! {
+  name:     list,
+  justfile: JUSTFILE,
+  args:     ("--list"),
+  stdout:   "
+    Available recipes:
+        a
+        b
+        z # alias for `b`
+        c
+  ",
+}
+
+test! {
+  name:     list_source,
+  justfile: JUSTFILE,
+  args:     ("--list", "--unsorted"),
+  stdout:   "
+    Available recipes:
+        b
+        z # alias for `b`
+        c
+        a
+  ",
+}
+
+test! {
+  name:     groups_source,
+  justfile: JUSTFILE,
+  args:     ("--list", "--groups", "--unsorted"),
+  stdout:   "
+    Available recipes:
+        b
+        z # alias for `b`
+        c
+
+        [x]
+        a
+  ",
+}
+
+test! {
+  name:     evaluate,
+  justfile: JUSTFILE,
+  args:     ("--evaluate"),
+  stdout:   "
+    a := \"3\"
+    b := \"1\"
+    c := \"2\"
+  ",
+}
+
+test! {
+  name:     evaluate_source,
+  justfile: JUSTFILE,
+  args:     ("--evaluate", "--unsorted"),
+  stdout:   "
+    b := \"1\"
+    c := \"2\"
+    a := \"3\"
+  ",
+}
+
+test! {
+  name:     graph_source,
+  justfile: JUSTFILE,
+  args:     ("--graph", "--unsorted"),
+  stdout:   "
+    c
+    └── b
+    a
+  ",
+}
+
+/// The `assignments` and `recipes` sections of the JSON dump of `JUSTFILE`,
+/// which follow the header and settings, whose contents are covered by the
+/// tests in `dump.rs`
+fn dump(args: &[&str]) -> String {
+  let tmp = tempdir();
+
+  fs::write(tmp.path().join("justfile"), unindent(JUSTFILE)).unwrap();
+
+  let output = Command::new(executable_path("just"))
+    .current_dir(tmp.path())
+    .args(&["--dump", "--format", "json"])
+    .args(args)
+    .output()
+    .unwrap();
+
+  assert!(output.status.success());
+
+  let stdout = str::from_utf8(&output.stdout).unwrap();
+
+  let start = stdout.find("  \"assignments\"").unwrap();
+
+  stdout[start..]
+    .lines()
+    .filter(|line| line.starts_with("  \"") || line.starts_with("    \""))
+    .map(|line| format!("{}\n", &line[2..]))
+    .collect()
+}
+
+#[test]
+fn dump_json() {
+  assert_eq!(
+    dump(&[]),
+    unindent(
+      r#"
+        "assignments": {
+          "a": {
+          "b": {
+          "c": {
+        "recipes": {
+          "a": {
+          "b": {
+          "c": {
+      "#
+    )
+  );
+}
+
+#[test]
+fn dump_json_source() {
+  assert_eq!(
+    dump(&["--unsorted"]),
+    unindent(
+      r#"
+        "assignments": {
+          "b": {
+          "c": {
+          "a": {
+        "recipes": {
+          "b": {
+          "c": {
+          "a": {
+      "#
+    )
+  );
+}
@@ -0,0 +1,117 @@
+use crate::common::*;
+
+test! {
+  name: not_a_terminal,
+  justfile: "
+    set pager
+
+    foo:
+    bar:
+  ",
+  args: ("--list"),
+  env: {
+    "JUST_PAGER": "sed s/^/paged:/",
+  },
+  stdout: "
+    Available recipes:
+        bar
+        foo
+  ",
+}
+
+/// Run `just --list` in a pseudo-terminal with `rows` rows, using `script`
+#[cfg(target_os = "linux")]
+fn list_in_terminal(justfile: &'static str, rows: usize) -> Option<String> {
+  let tmp = tmptree! {
+    justfile: justfile,
+  };
+
+  list_in_terminal_in(tmp.path(), rows, "", &[])
+}
+
+/// Run `just --list` with `args` in `dir` in a pseudo-terminal with `rows`
+/// rows and the environment variables in `env`, using `script`
+#[cfg(target_os = "linux")]
+fn list_in_terminal_in(
+  dir: &Path,
+  rows: usize,
+  args: &str,
+  env: &[(&str, &str)],
+) -> Option<String> {
+  let script = which("script").ok()?;
+
+  let output = Command::new(script)
+    .current_dir(dir)
+    .env("JUST_PAGER", "sed s/^/paged:/")
+    .envs(env.iter().copied())
+    .args(&["--quiet", "--return", "--command"])
+    .arg(format!(
+      "stty rows {}; {} --list {}",
+      rows,
+      executable_path("just").display(),
+      args
+    ))
+    .arg("/dev/null")
+    .output()
+    .unwrap();
+
+  assert!(output.status.success());
+
+  Some(
+    String::from_utf8(output.stdout)
+      .unwrap()
+      .replace("\r\n", "\n"),
+  )
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn long_output_is_paged() {
+  let justfile = "set pager\n\nfoo:\nbar:\nbaz:";
+
+  if let Some(stdout) = list_in_terminal(justfile, 3) {
+    assert_eq!(
+      stdout,
+      "paged:Available recipes:\npaged:    bar\npaged:    baz\npaged:    foo\n"
+    );
+  }
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn short_output_is_not_paged() {
+  let justfile = "set pager\n\nfoo:\nbar:\nbaz:";
+
+  if let Some(stdout) = list_in_terminal(justfile, 24) {
+    assert_eq!(stdout, "Available recipes:\n    bar\n    baz\n    foo\n");
+  }
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn disabled_by_default() {
+  let justfile = "foo:\nbar:\nbaz:";
+
+  if let Some(stdout) = list_in_terminal(justfile, 3) {
+    assert_eq!(stdout, "Available recipes:\n    bar\n    baz\n    foo\n");
+  }
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn setting_expression_not_cached() {
+  let tmp = tmptree! {
+    justfile: "set pager := env_var_or_default('PAGE', 'n') == 'y'\n\nfoo:\nbar:\nbaz:",
+  };
+
+  let paged = "paged:Available recipes:\npaged:    bar\npaged:    baz\npaged:    foo\n";
+  let unpaged = "Available recipes:\n    bar\n    baz\n    foo\n";
+
+  if let Some(stdout) = list_in_terminal_in(tmp.path(), 3, "--cache-dir cache", &[("PAGE", "y")]) {
+    assert_eq!(stdout, paged);
+  }
+
+  if let Some(stdout) = list_in_terminal_in(tmp.path(), 3, "--cache-dir cache", &[("PAGE", "n")]) {
+    assert_eq!(stdout, unpaged);
+  }
+}
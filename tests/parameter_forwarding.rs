@@ -0,0 +1,111 @@
+use crate::common::*;
+
+test! {
+  name:     forward,
+  justfile: "
+    build target mode: (prepare *)
+      @echo build {{target}} {{mode}}
+
+    prepare target mode:
+      @echo prepare {{target}} {{mode}}
+  ",
+  args:     ("build", "linux", "release"),
+  stdout:   "
+    prepare linux release
+    build linux release
+  ",
+}
+
+test! {
+  name:     forward_after_arguments,
+  justfile: "
+    build target: (prepare 'build' *)
+
+    prepare step target:
+      @echo {{step}} {{target}}
+  ",
+  args:     ("build", "linux"),
+  stdout:   "build linux\n",
+}
+
+test! {
+  name:     forward_defaults,
+  justfile: "
+    build target='linux': (prepare *)
+
+    prepare target:
+      @echo {{target}}
+  ",
+  stdout:   "linux\n",
+}
+
+test! {
+  name:     forward_variadic,
+  justfile: "
+    build *flags: (prepare *)
+
+    prepare flags:
+      @echo {{flags}}
+  ",
+  args:     ("build", "-a", "-b"),
+  stdout:   "-a -b\n",
+}
+
+test! {
+  name:     forward_nothing,
+  justfile: "
+    build: (prepare *)
+
+    prepare:
+      @echo prepare
+  ",
+  stdout:   "prepare\n",
+}
+
+test! {
+  name:     forward_count_mismatch,
+  justfile: "
+    build target mode: (prepare *)
+
+    prepare target:
+  ",
+  args:     ("build", "linux", "release"),
+  stderr:   "
+    error: Dependency `prepare` got 2 arguments but takes 1 argument
+      |
+    1 | build target mode: (prepare *)
+      |                     ^^^^^^^
+  ",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     forward_must_be_last,
+  justfile: "
+    build target: (prepare * 'x')
+
+    prepare target:
+  ",
+  stderr:   "
+    error: Expected ')', but found string
+      |
+    1 | build target: (prepare * 'x')
+      |                          ^^^
+  ",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     dump,
+  justfile: "
+    build target: (prepare *)
+
+    prepare target:
+  ",
+  args:     ("--dump"),
+  stdout:   "
+    build target: (prepare target)
+
+    prepare target:
+  ",
+}
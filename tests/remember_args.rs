@@ -0,0 +1,103 @@
+use crate::common::*;
+
+const JUSTFILE: &str = "
+[remember-args]
+deploy environment region='us':
+  echo {{environment}} {{region}}
+
+[remember-args]
+fail code:
+  exit {{code}}
+";
+
+/// Run just in `tmp` with `args`, remembering arguments in `tmp/state`
+fn run(tmp: &tempfile::TempDir, args: &[&str]) -> (bool, String, String) {
+  outcome(
+    just_command(tmp.path())
+      .env("XDG_STATE_HOME", tmp.path().join("state"))
+      .args(args)
+      .output()
+      .unwrap(),
+  )
+}
+
+#[test]
+fn reuses_arguments_from_previous_run() {
+  let tmp = tmptree! {
+    justfile: JUSTFILE,
+  };
+
+  assert_eq!(
+    run(&tmp, &["deploy", "staging", "eu"]),
+    (
+      true,
+      "staging eu\n".to_owned(),
+      "echo staging eu\n".to_owned()
+    )
+  );
+
+  assert_eq!(
+    run(&tmp, &["deploy"]),
+    (
+      true,
+      "staging eu\n".to_owned(),
+      "Reusing arguments from previous run of `deploy`: staging eu\necho staging eu\n".to_owned()
+    )
+  );
+
+  assert_eq!(
+    run(&tmp, &["deploy", "production"]),
+    (
+      true,
+      "production us\n".to_owned(),
+      "echo production us\n".to_owned()
+    )
+  );
+
+  assert_eq!(
+    run(&tmp, &["--quiet", "deploy"]),
+    (true, String::new(), String::new())
+  );
+}
+
+#[test]
+fn no_previous_run() {
+  let tmp = tmptree! {
+    justfile: JUSTFILE,
+  };
+
+  let (success, stdout, stderr) = run(&tmp, &["deploy"]);
+
+  assert!(!success);
+  assert_eq!(stdout, "");
+  assert!(stderr.starts_with("error: Recipe `deploy` got 0 arguments but takes at least 1"));
+}
+
+#[test]
+fn failed_run_not_remembered() {
+  let tmp = tmptree! {
+    justfile: JUSTFILE,
+  };
+
+  assert!(run(&tmp, &["fail", "0"]).0);
+  assert!(!run(&tmp, &["fail", "1"]).0);
+
+  assert_eq!(
+    run(&tmp, &["fail"]),
+    (
+      true,
+      String::new(),
+      "Reusing arguments from previous run of `fail`: 0\nexit 0\n".to_owned()
+    )
+  );
+}
+
+#[test]
+fn dry_run_not_remembered() {
+  let tmp = tmptree! {
+    justfile: JUSTFILE,
+  };
+
+  assert!(run(&tmp, &["--dry-run", "deploy", "staging"]).0);
+  assert!(!run(&tmp, &["deploy"]).0);
+}
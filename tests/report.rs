@@ -0,0 +1,130 @@
+use crate::common::*;
+
+const JUSTFILE: &str = "
+build: setup
+  @echo build
+
+setup:
+
+fail arg:
+  exit 1
+";
+
+/// Read the report at `path`, replacing durations, which vary between
+/// runs, with `T`
+fn report(path: &Path) -> String {
+  let report = fs::read_to_string(path).unwrap();
+
+  let mut pieces = report.split("time=\"");
+  let mut normalized = pieces.next().unwrap().to_owned();
+
+  for piece in pieces {
+    normalized.push_str("time=\"T");
+    normalized.push_str(&piece[piece.find('"').unwrap()..]);
+  }
+
+  normalized
+}
+
+#[test]
+fn junit_success() {
+  let tmp = tmptree! {
+    justfile: JUSTFILE,
+  };
+
+  assert!(just(tmp.path(), &["--report", "junit=report.xml", "build"])
+    .status
+    .success());
+
+  let justfile = tmp.path().join("justfile").display().to_string();
+
+  assert_eq!(
+    report(&tmp.path().join("report.xml")),
+    format!(
+      r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites tests="2" failures="0" time="T">
+  <testsuite name="{0}" tests="2" failures="0" time="T">
+    <testcase name="setup" classname="{0}" time="T"/>
+    <testcase name="build" classname="{0}" time="T"/>
+  </testsuite>
+</testsuites>
+"#,
+      justfile
+    )
+  );
+}
+
+#[test]
+fn junit_failure() {
+  let tmp = tmptree! {
+    justfile: JUSTFILE,
+  };
+
+  assert!(!just(tmp.path(), &[
+    "--report",
+    "junit=report.xml",
+    "setup",
+    "fail",
+    "foo",
+    "build"
+  ])
+  .status
+  .success());
+
+  let justfile = tmp.path().join("justfile").display().to_string();
+
+  assert_eq!(
+    report(&tmp.path().join("report.xml")),
+    format!(
+      r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites tests="2" failures="1" time="T">
+  <testsuite name="{0}" tests="2" failures="1" time="T">
+    <testcase name="setup" classname="{0}" time="T"/>
+    <testcase name="fail foo" classname="{0}" time="T">
+      <failure message="Recipe `fail` failed on line 8 with exit code 1">Recipe `fail` failed on line 8 with exit code 1</failure>
+    </testcase>
+  </testsuite>
+</testsuites>
+"#,
+      justfile
+    )
+  );
+}
+
+#[test]
+fn dry_run() {
+  let tmp = tmptree! {
+    justfile: JUSTFILE,
+  };
+
+  assert!(just(tmp.path(), &[
+    "--dry-run",
+    "--report",
+    "junit=report.xml",
+    "build"
+  ])
+  .status
+  .success());
+
+  assert!(!tmp.path().join("report.xml").exists());
+}
+
+test! {
+  name:     unwritable,
+  justfile: "
+    foo:
+  ",
+  args:     ("--report", "junit=missing/report.xml"),
+  stderr:   "error: Failed to write report to `missing/report.xml`: No such file or directory (os error 2)\n",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     invalid,
+  justfile: "
+    foo:
+  ",
+  args:     ("--report", "tap=report.tap"),
+  stderr:   "error: Invalid value `tap=report.tap` for `--report`, expected `FORMAT=PATH`, where FORMAT is `junit`\n",
+  status:   EXIT_FAILURE,
+}
@@ -0,0 +1,156 @@
+use crate::common::*;
+
+test! {
+  name:     evaluate,
+  justfile: "
+    a := 'foo'
+    b := arch() + a
+  ",
+  args:     ("--safe", "--evaluate", "a"),
+  stdout:   "foo",
+}
+
+test! {
+  name:     backtick,
+  justfile: "
+    a := `echo foo`
+  ",
+  args:     ("--safe", "--evaluate"),
+  stderr:   "
+    error: Backticks may not be evaluated in safe mode
+      |
+    1 | a := `echo foo`
+      |      ^^^^^^^^^^
+  ",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     env_var,
+  justfile: "
+    a := env_var_or_default('FOO', 'bar')
+  ",
+  args:     ("--safe", "--evaluate"),
+  stderr:   "
+    error: Function `env_var_or_default` may not be called in safe mode
+      |
+    1 | a := env_var_or_default('FOO', 'bar')
+      |      ^^^^^^^^^^^^^^^^^^
+  ",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     retry,
+  justfile: "
+    a := retry('3', 'echo foo')
+  ",
+  args:     ("--safe", "--evaluate"),
+  stderr:   "
+    error: Function `retry` may not be called in safe mode
+      |
+    1 | a := retry('3', 'echo foo')
+      |      ^^^^^
+  ",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     filesystem,
+  justfile: "
+    a := sha256_file('justfile')
+  ",
+  args:     ("--safe", "--evaluate"),
+  stderr:   "
+    error: Function `sha256_file` may not be called in safe mode
+      |
+    1 | a := sha256_file('justfile')
+      |      ^^^^^^^^^^^
+  ",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     nondeterministic,
+  justfile: "
+    a := uuid()
+  ",
+  args:     ("--safe", "--evaluate"),
+  stderr:   "
+    error: Function `uuid` may not be called in safe mode
+      |
+    1 | a := uuid()
+      |      ^^^^
+  ",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     shell,
+  justfile: "
+    a := shell('echo $1', 'foo')
+  ",
+  args:     ("--safe", "--evaluate"),
+  stderr:   "
+    error: Function `shell` may not be called in safe mode
+      |
+    1 | a := shell('echo $1', 'foo')
+      |      ^^^^^
+  ",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     require,
+  justfile: "
+    a := require('sh')
+  ",
+  args:     ("--safe", "--evaluate"),
+  stderr:   "
+    error: Function `require` may not be called in safe mode
+      |
+    1 | a := require('sh')
+      |      ^^^^^^^
+  ",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     recipe,
+  justfile: "
+    foo:
+      echo foo
+  ",
+  args:     ("--safe"),
+  stderr:   "error: Recipe `foo` may not be run in safe mode\n",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     dry_run,
+  justfile: "
+    foo:
+      echo {{`echo bar`}}
+  ",
+  args:     ("--safe", "--dry-run"),
+  stderr:   "echo `echo bar`\n",
+}
+
+test! {
+  name:     command,
+  justfile: "
+    export A := 'foo'
+  ",
+  args:     ("--safe", "--command", "echo", "pwned"),
+  stderr:   "error: `--command` may not be used in safe mode\n",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     command_dry_run,
+  justfile: "
+    export A := 'foo'
+  ",
+  args:     ("--safe", "--dry-run", "--command", "echo", "pwned"),
+  stderr:   "echo pwned\n",
+}
@@ -145,3 +145,188 @@ fn single_upwards() {
 
   search_test(&path, &["../"]);
 }
+
+#[test]
+fn search_from() {
+  let tmp = tmptree! {
+    justfile: "default:\n\techo bad",
+    a: {
+      justfile: "default:\n\techo ok",
+      b: {},
+    },
+    c: {},
+  };
+
+  search_test(tmp.path().join("c"), &["--search-from", "../a/b"]);
+  search_test(tmp.path(), &["--search-from", "a/b", "default"]);
+}
+
+#[test]
+fn search_from_invocation_directory() {
+  let tmp = tmptree! {
+    a: {
+      justfile: "default:\n\t@echo {{invocation_directory()}}",
+    },
+    c: {},
+  };
+
+  let output = process::Command::new(executable_path("just"))
+    .current_dir(tmp.path().join("c"))
+    .args(&["--search-from", "../a"])
+    .output()
+    .expect("just invocation failed");
+
+  assert!(output.status.success());
+
+  assert_eq!(
+    path::Path::new(str::from_utf8(&output.stdout).unwrap().trim()),
+    tmp.path().join("c").canonicalize().unwrap()
+  );
+}
+
+/// Run just in `path` with `args`, and no global justfile, returning its
+/// standard error if it fails
+fn search_error<P: AsRef<path::Path>>(path: P, args: &[&str]) -> String {
+  let config_home = tempfile::tempdir().unwrap();
+
+  let output = process::Command::new(executable_path("just"))
+    .current_dir(path)
+    .env("XDG_CONFIG_HOME", config_home.path())
+    .args(args)
+    .output()
+    .expect("just invocation failed");
+
+  assert!(!output.status.success());
+
+  str::from_utf8(&output.stderr).unwrap().to_owned()
+}
+
+#[test]
+fn search_boundary_none() {
+  let tmp = tmptree! {
+    justfile: "default:\n\techo ok",
+    ".git": {},
+    worktree: {
+      ".git": "gitdir: ../.git/worktrees/worktree",
+      a: {},
+    },
+  };
+
+  search_test(tmp.path().join("worktree/a"), &[]);
+  search_test(tmp.path().join("worktree/a"), &[
+    "--search-boundary",
+    "none",
+  ]);
+}
+
+#[test]
+fn search_boundary_worktree() {
+  let tmp = tmptree! {
+    justfile: "default:\n\techo bad",
+    ".git": {},
+    worktree: {
+      ".git": "gitdir: ../.git/worktrees/worktree",
+      a: {},
+    },
+    submodule: {
+      ".git": "gitdir: ../.git/modules/submodule",
+      justfile: "default:\n\techo ok",
+      a: {},
+    },
+    a: {},
+  };
+
+  assert_eq!(
+    search_error(tmp.path().join("worktree/a"), &[
+      "--search-boundary",
+      "worktree"
+    ]),
+    "error: No justfile found\n"
+  );
+
+  search_test(tmp.path().join("submodule/a"), &[
+    "--search-boundary",
+    "worktree",
+  ]);
+}
+
+#[test]
+fn search_boundary_worktree_crosses_repository() {
+  let tmp = tmptree! {
+    justfile: "default:\n\techo ok",
+    repository: {
+      ".git": {},
+      a: {},
+    },
+  };
+
+  search_test(tmp.path().join("repository/a"), &[
+    "--search-boundary",
+    "worktree",
+  ]);
+}
+
+#[test]
+fn search_boundary_repository() {
+  let tmp = tmptree! {
+    justfile: "default:\n\techo bad",
+    repository: {
+      ".git": {},
+      a: {},
+    },
+  };
+
+  assert_eq!(
+    search_error(tmp.path().join("repository/a"), &[
+      "--search-boundary",
+      "repository"
+    ]),
+    "error: No justfile found\n"
+  );
+}
+
+#[test]
+fn search_boundary_environment_variable() {
+  let tmp = tmptree! {
+    justfile: "default:\n\techo bad",
+    repository: {
+      ".git": {},
+      a: {},
+    },
+  };
+
+  let config_home = tempfile::tempdir().unwrap();
+
+  let output = process::Command::new(executable_path("just"))
+    .current_dir(tmp.path().join("repository/a"))
+    .env("XDG_CONFIG_HOME", config_home.path())
+    .env("JUST_SEARCH_BOUNDARY", "repository")
+    .output()
+    .expect("just invocation failed");
+
+  assert_eq!(
+    str::from_utf8(&output.stderr).unwrap(),
+    "error: No justfile found\n"
+  );
+}
+
+#[test]
+fn search_boundary_search_from() {
+  let tmp = tmptree! {
+    justfile: "default:\n\techo bad",
+    repository: {
+      ".git": {},
+      a: {},
+    },
+  };
+
+  assert_eq!(
+    search_error(tmp.path(), &[
+      "--search-from",
+      "repository/a",
+      "--search-boundary",
+      "repository"
+    ]),
+    "error: No justfile found\n"
+  );
+}
@@ -0,0 +1,130 @@
+use crate::common::*;
+
+#[test]
+fn creates_dotenv_file() {
+  let tmp = tmptree! {
+    justfile: "",
+  };
+
+  let output = just(tmp.path(), &["--set-env", "FOO=bar", "BAZ=a b"]);
+
+  assert!(output.status.success());
+  assert_eq!(
+    str::from_utf8(&output.stderr).unwrap(),
+    format!(
+      "Set `FOO` and `BAZ` in `{}`\n",
+      tmp.path().join(".env").display()
+    )
+  );
+  assert_eq!(
+    fs::read_to_string(tmp.path().join(".env")).unwrap(),
+    "FOO=bar\nBAZ='a b'\n"
+  );
+  assert!(!tmp.path().join(".env.lock").exists());
+}
+
+#[test]
+fn preserves_comments_and_order() {
+  let tmp = tmptree! {
+    justfile: "",
+    ".env": "# database\nHOST=localhost\n\nPORT=5432 # default\nUSER=admin\n",
+  };
+
+  let output = just(tmp.path(), &[
+    "--quiet",
+    "--set-env",
+    "PORT=6543",
+    "NAME=app",
+  ]);
+
+  assert!(output.status.success());
+  assert_eq!(str::from_utf8(&output.stderr).unwrap(), "");
+  assert_eq!(
+    fs::read_to_string(tmp.path().join(".env")).unwrap(),
+    "# database\nHOST=localhost\n\nPORT=6543\nUSER=admin\nNAME=app\n"
+  );
+}
+
+#[test]
+fn values_are_loaded() {
+  let tmp = tmptree! {
+    justfile: "set dotenv-load\n\nfoo:\n  @echo \"$FOO\"",
+  };
+
+  let output = just(tmp.path(), &["--quiet", "--set-env", "FOO=it's $HOME"]);
+  assert!(output.status.success());
+
+  let output = just(tmp.path(), &["foo"]);
+  assert!(output.status.success());
+  assert_eq!(str::from_utf8(&output.stdout).unwrap(), "it's $HOME\n");
+}
+
+#[test]
+fn dotenv_file_option() {
+  let tmp = tmptree! {
+    justfile: "",
+    "config.env": "A=b\n",
+  };
+
+  let output = just(tmp.path(), &[
+    "--quiet",
+    "--dotenv-file",
+    "config.env",
+    "--set-env",
+    "C=d",
+  ]);
+
+  assert!(output.status.success());
+  assert_eq!(
+    fs::read_to_string(tmp.path().join("config.env")).unwrap(),
+    "A=b\nC=d\n"
+  );
+  assert!(!tmp.path().join(".env").exists());
+}
+
+#[test]
+fn dotenv_files_setting() {
+  let tmp = tmptree! {
+    justfile: "set dotenv-files := ['.env.defaults', '.env.local']",
+  };
+
+  let output = just(tmp.path(), &["--quiet", "--set-env", "FOO=bar"]);
+
+  assert!(output.status.success());
+  assert_eq!(
+    fs::read_to_string(tmp.path().join(".env.local")).unwrap(),
+    "FOO=bar\n"
+  );
+  assert!(!tmp.path().join(".env.defaults").exists());
+}
+
+#[test]
+fn held_lock_times_out() {
+  let tmp = tmptree! {
+    justfile: "",
+    ".env": "FOO=bar\n",
+    ".env.lock": "",
+  };
+
+  let output = just(tmp.path(), &["--set-env", "FOO=baz"]);
+
+  assert_eq!(output.status.code(), Some(EXIT_FAILURE));
+  assert!(str::from_utf8(&output.stderr)
+    .unwrap()
+    .contains("Timed out waiting for lock file"));
+  assert_eq!(
+    fs::read_to_string(tmp.path().join(".env")).unwrap(),
+    "FOO=bar\n"
+  );
+  assert!(tmp.path().join(".env.lock").exists());
+}
+
+test! {
+  name: invalid_argument,
+  justfile: "",
+  args: ("--set-env", "FOO"),
+  stderr: "
+    error: Invalid value `FOO` for `--set-env`, expected `KEY=VALUE`, where KEY consists of letters, digits, `_`, and `.`, and does not start with a digit
+  ",
+  status: EXIT_FAILURE,
+}
@@ -0,0 +1,145 @@
+use crate::common::*;
+
+test! {
+  name:     linewise_recipe,
+  justfile: "
+    foo:
+      @x=hello
+      echo $x
+  ",
+  args:     ("--shebang", "sh -eu", "foo"),
+  stdout:   "hello\n",
+}
+
+test! {
+  name:     alias,
+  justfile: "
+    foo:
+      x=hello
+      echo $x
+  ",
+  args:     ("--recipe-shell", "sh", "foo"),
+  stdout:   "hello\n",
+}
+
+test! {
+  name:     overrides_shebang_line,
+  justfile: "
+    foo:
+      #!/usr/bin/env nonexistent-interpreter
+      echo hello
+  ",
+  args:     ("--shebang", "sh", "foo"),
+  stdout:   "hello\n",
+}
+
+test! {
+  name:     interpreter_arguments,
+  justfile: "
+    foo:
+      echo hello
+  ",
+  args:     ("--shebang", "sh -x", "foo"),
+  stdout:   "hello\n",
+  stderr:   "+ echo hello\n",
+}
+
+test! {
+  name:     infallible_lines_are_stripped,
+  justfile: "
+    foo:
+      -echo hello
+  ",
+  args:     ("--shebang", "sh", "foo"),
+  stdout:   "hello\n",
+}
+
+test! {
+  name:     dependencies_are_unaffected,
+  justfile: "
+    foo: bar
+      x=foo
+      echo $x
+
+    bar:
+      echo bar
+  ",
+  args:     ("--shebang", "sh", "foo"),
+  stdout:   "bar\nfoo\n",
+  stderr:   "echo bar\n",
+}
+
+test! {
+  name:     line_numbers,
+  justfile: "
+    foo:
+      a
+
+      b
+  ",
+  args:     ("--shebang", "cat", "foo"),
+  stdout:   "
+    #!cat
+    a
+
+    b
+  ",
+}
+
+test! {
+  name:     failure,
+  justfile: "
+    foo:
+      false
+      echo unreachable
+  ",
+  args:     ("--shebang", "sh -e", "foo"),
+  stderr:   "error: Recipe `foo` failed with exit code 1\n",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     missing_interpreter,
+  justfile: "
+    foo:
+      echo hello
+  ",
+  args:     ("--shebang", "nonexistent-interpreter", "foo"),
+  stderr:   "error: Recipe `foo` with shebang `#!nonexistent-interpreter` execution error: No such file or directory (os error 2)\n",
+  status:   EXIT_FAILURE,
+}
+
+#[test]
+#[cfg(unix)]
+fn set_tempdir() {
+  let tmp = tmptree! {
+    justfile: "set tempdir := 'scripts'\n\nfoo:\n  #!/bin/sh\n  basename \"$(dirname \"$(dirname \"$0\")\")\"\n",
+    scripts: {},
+  };
+
+  let output = Command::new(executable_path("just"))
+    .current_dir(tmp.path())
+    .output()
+    .unwrap();
+
+  assert!(output.status.success());
+  assert_eq!(str::from_utf8(&output.stdout).unwrap(), "scripts\n");
+  assert_eq!(fs::read_dir(tmp.path().join("scripts")).unwrap().count(), 0);
+}
+
+#[test]
+fn set_tempdir_missing() {
+  let tmp = tmptree! {
+    justfile: "set tempdir := 'scripts'\n\nfoo:\n  #!/bin/sh\n  echo foo\n",
+  };
+
+  let output = Command::new(executable_path("just"))
+    .current_dir(tmp.path())
+    .output()
+    .unwrap();
+
+  assert!(!output.status.success());
+  assert!(str::from_utf8(&output.stderr)
+    .unwrap()
+    .starts_with("error: Recipe `foo` could not be run because of an IO error"));
+}
@@ -174,3 +174,49 @@ test! {
   stderr: "echo -n bar\r\necho foo\n",
   shell: false,
 }
+
+#[cfg(unix)]
+test! {
+  name: windows_shell_ignored,
+  justfile: "
+    set shell := ['echo', '-n']
+    set windows-shell := ['foo-bar-baz']
+
+    foo:
+      echo foo
+  ",
+  args: (),
+  stdout: "echo foo",
+  stderr: "echo foo\n",
+  shell: false,
+}
+
+#[cfg(unix)]
+test! {
+  name: windows_powershell_ignored,
+  justfile: "
+    set windows-powershell
+
+    foo:
+      echo foo
+  ",
+  args: (),
+  stdout: "foo\n",
+  stderr: "echo foo\n",
+}
+
+#[cfg(windows)]
+test! {
+  name: windows_shell_overrides_shell,
+  justfile: "
+    set shell := ['foo-bar-baz']
+    set windows-shell := ['echo', '-n']
+
+    foo:
+      echo foo
+  ",
+  args: (),
+  stdout: "-n echo foo\r\n",
+  stderr: "echo foo\n",
+  shell: false,
+}
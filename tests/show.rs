@@ -0,0 +1,142 @@
+use crate::common::*;
+
+const JUSTFILE: &str = "
+  alias b := build
+
+  build:
+    cargo build
+
+  test-unit:
+    cargo test --lib
+
+  test-integration: build
+    cargo test --test integration
+
+  _test-helper:
+    echo helper
+";
+
+test! {
+  name:     multiple,
+  justfile: JUSTFILE,
+  args:     ("--show", "test-unit", "build"),
+  stdout:   "
+    test-unit:
+        cargo test --lib
+
+    build:
+        cargo build
+  ",
+}
+
+test! {
+  name:     multiple_with_alias,
+  justfile: JUSTFILE,
+  args:     ("--show", "b", "test-unit"),
+  stdout:   "
+    alias b := build
+    build:
+        cargo build
+
+    test-unit:
+        cargo test --lib
+  ",
+}
+
+test! {
+  name:     glob,
+  justfile: JUSTFILE,
+  args:     ("--show", "test-*"),
+  stdout:   "
+    test-integration: build
+        cargo test --test integration
+
+    test-unit:
+        cargo test --lib
+  ",
+}
+
+test! {
+  name:     glob_unsorted,
+  justfile: JUSTFILE,
+  args:     ("--show", "test-*", "--unsorted"),
+  stdout:   "
+    test-unit:
+        cargo test --lib
+
+    test-integration: build
+        cargo test --test integration
+  ",
+}
+
+test! {
+  name:     glob_all,
+  justfile: JUSTFILE,
+  args:     ("--show", "*"),
+  stdout:   "
+    build:
+        cargo build
+
+    test-integration: build
+        cargo test --test integration
+
+    test-unit:
+        cargo test --lib
+  ",
+}
+
+test! {
+  name:     shown_once,
+  justfile: JUSTFILE,
+  args:     ("--show", "build", "*", "build"),
+  stdout:   "
+    build:
+        cargo build
+
+    test-integration: build
+        cargo test --test integration
+
+    test-unit:
+        cargo test --lib
+  ",
+}
+
+test! {
+  name:     private_by_name,
+  justfile: JUSTFILE,
+  args:     ("--show", "_test-helper", "test-unit"),
+  stdout:   "
+    _test-helper:
+        echo helper
+
+    test-unit:
+        cargo test --lib
+  ",
+}
+
+test! {
+  name:     glob_no_matches,
+  justfile: JUSTFILE,
+  args:     ("--show", "build", "deploy-*"),
+  stderr:   "Justfile contains no recipes matching `deploy-*`.\n",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     missing_prints_nothing,
+  justfile: JUSTFILE,
+  args:     ("--show", "build", "tset-unit"),
+  stderr:   "
+    Justfile does not contain recipe `tset-unit`.
+    Did you mean `test-unit`?
+  ",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     overrides,
+  justfile: JUSTFILE,
+  args:     ("--show", "build", "foo=bar"),
+  stderr:   "error: `--show` used with unexpected overrides: `foo=bar`\n",
+  status:   EXIT_FAILURE,
+}
@@ -0,0 +1,92 @@
+use crate::common::*;
+
+const JUSTFILE: &str = "
+build: setup
+  sleep 0.1
+
+setup:
+
+fail:
+  exit 1
+";
+
+/// Run just in `tmp` with `args`, recording statistics in `tmp/stats`
+fn just_with_stats(tmp: &Path, args: &[&str]) -> (bool, String, String) {
+  outcome(
+    just_command(tmp)
+      .env("JUST_STATS_FILE", tmp.join("stats"))
+      .args(args)
+      .output()
+      .unwrap(),
+  )
+}
+
+#[test]
+fn records_runs() {
+  let tmp = tmptree! {
+    justfile: JUSTFILE,
+  };
+
+  assert!(just_with_stats(tmp.path(), &["build"]).0);
+  assert!(just_with_stats(tmp.path(), &["setup"]).0);
+  assert!(!just_with_stats(tmp.path(), &["fail"]).0);
+  assert!(just_with_stats(tmp.path(), &["--dry-run", "setup"]).0);
+
+  let (success, stdout, _) = just_with_stats(tmp.path(), &["--stats"]);
+
+  assert!(success);
+
+  let lines = stdout.lines().collect::<Vec<&str>>();
+
+  assert_eq!(lines.len(), 3);
+  assert_eq!(lines[0], "Recipe   Runs   Average");
+  assert!(lines[1].starts_with("setup       2    0.0"));
+  assert!(lines[2].starts_with("build       1    0.1"));
+}
+
+#[test]
+fn stats_file_flag() {
+  let tmp = tmptree! {
+    justfile: JUSTFILE,
+  };
+
+  let output = just(tmp.path(), &["--stats-file", "other", "setup"]);
+
+  assert!(output.status.success());
+
+  assert!(tmp.path().join("other").is_file());
+  assert!(!tmp.path().join("stats").exists());
+}
+
+#[test]
+fn no_stats_file() {
+  let tmp = tmptree! {
+    justfile: JUSTFILE,
+  };
+
+  let output = just_command(tmp.path())
+    .env_remove("JUST_STATS_FILE")
+    .arg("--stats")
+    .output()
+    .unwrap();
+
+  assert!(!output.status.success());
+
+  assert_eq!(
+    String::from_utf8(output.stderr).unwrap(),
+    "`--stats` requires a statistics file, given with `--stats-file` or $JUST_STATS_FILE\n"
+  );
+}
+
+#[test]
+fn stats_file_missing() {
+  let tmp = tmptree! {
+    justfile: JUSTFILE,
+  };
+
+  let (success, stdout, stderr) = just_with_stats(tmp.path(), &["--stats"]);
+
+  assert!(!success);
+  assert_eq!(stdout, "");
+  assert!(stderr.starts_with("Failed to read statistics file"));
+}
@@ -0,0 +1,63 @@
+use crate::common::*;
+
+test! {
+  name:     justfile,
+  justfile: "
+    foo:
+      echo file
+  ",
+  args:     ("--justfile", "-", "foo"),
+  stdin:    "
+foo:
+  echo stdin
+",
+  stdout:   "stdin\n",
+  stderr:   "echo stdin\n",
+}
+
+test! {
+  name:     justfile_directory,
+  justfile: "",
+  args:     ("--justfile", "-"),
+  stdin:    "
+foo:
+  echo {{ if justfile_directory() == invocation_directory() { 'same' } else { 'different' } }}
+",
+  stdout:   "same\n",
+  stderr:   "echo same\n",
+}
+
+test! {
+  name:     working_directory,
+  justfile: "",
+  args:     ("--working-directory", "..", "--justfile", "-"),
+  stdin:    "
+foo:
+  echo {{ if justfile_directory() == invocation_directory() { 'same' } else { 'different' } }}
+",
+  stdout:   "different\n",
+  stderr:   "echo different\n",
+}
+
+test! {
+  name:     list,
+  justfile: "",
+  args:     ("--justfile", "-", "--list"),
+  stdin:    "
+foo:
+bar:
+",
+  stdout:   "
+    Available recipes:
+        bar
+        foo
+  ",
+}
+
+test! {
+  name:     edit,
+  justfile: "",
+  args:     ("--justfile", "-", "--edit"),
+  stderr:   "error: `--edit` may not be used with a justfile read from standard input\n",
+  status:   EXIT_FAILURE,
+}
@@ -0,0 +1,114 @@
+use crate::common::*;
+
+test! {
+  name:     unknown_recipe_alias,
+  justfile: "
+    alias bld := build
+
+    build:
+  ",
+  args:     ("blb"),
+  stderr:   "error: Justfile does not contain recipe `blb`.\nDid you mean `bld`, an alias for `build`?\n",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     unknown_recipe_override,
+  justfile: "
+    version := '0.1.0'
+
+    build:
+  ",
+  args:     ("version", "1.0.0"),
+  stderr:   "
+    error: Justfile does not contain recipes `version` or `1.0.0`.
+    Did you mean to override variable `version` with `version=VALUE`?
+  ",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     unknown_recipe_prefers_recipes,
+  justfile: "
+    versions := '0.1.0'
+
+    version:
+  ",
+  args:     ("versio"),
+  stderr:   "error: Justfile does not contain recipe `versio`.\nDid you mean `version`?\n",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     unknown_override,
+  justfile: "
+    version := '0.1.0'
+
+    build:
+  ",
+  args:     ("verison=1.0.0"),
+  stderr:   "
+    error: Variable `verison` overridden on the command line but not present in justfile
+    Did you mean `version`?
+  ",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     evaluate_unknown_variable,
+  justfile: "
+    version := '0.1.0'
+  ",
+  args:     ("--evaluate", "verison"),
+  stderr:   "error: Justfile does not contain variable `verison`.\nDid you mean `version`?\n",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     undefined_variable,
+  justfile: "
+    version := '0.1.0'
+
+    build:
+      echo {{verison}}
+  ",
+  stderr:   "
+    error: Variable `verison` not defined
+    Did you mean `version`?
+      |
+    4 |   echo {{verison}}
+      |          ^^^^^^^
+  ",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     undefined_variable_function,
+  justfile: "
+    build:
+      echo {{arch}}
+  ",
+  stderr:   "
+    error: Variable `arch` not defined
+    Did you mean `arch()`?
+      |
+    2 |   echo {{arch}}
+      |          ^^^^
+  ",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     unknown_function,
+  justfile: "
+    home := env_vra('HOME')
+  ",
+  stderr:   "
+    error: Call to unknown function `env_vra`
+    Did you mean `env_var()`?
+      |
+    1 | home := env_vra('HOME')
+      |         ^^^^^^^
+  ",
+  status:   EXIT_FAILURE,
+}
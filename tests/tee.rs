@@ -0,0 +1,195 @@
+use crate::common::*;
+
+#[test]
+fn attribute() {
+  let tmp = tmptree! {
+    justfile: "
+[tee('logs/build.log')]
+build:
+  @echo out
+  @echo err >&2
+",
+  };
+
+  let output = just(tmp.path(), &["build"]);
+
+  assert!(output.status.success());
+  assert_eq!(str::from_utf8(&output.stdout).unwrap(), "out\n");
+  assert_eq!(str::from_utf8(&output.stderr).unwrap(), "err\n");
+  assert_eq!(
+    fs::read_to_string(tmp.path().join("logs/build.log")).unwrap(),
+    "out\nerr\n"
+  );
+}
+
+#[test]
+fn attribute_truncates() {
+  let tmp = tmptree! {
+    justfile: "
+[tee('build.log')]
+build:
+  @echo out
+",
+    "build.log": "old\n",
+  };
+
+  assert!(just(tmp.path(), &["build"]).status.success());
+
+  assert_eq!(
+    fs::read_to_string(tmp.path().join("build.log")).unwrap(),
+    "out\n"
+  );
+}
+
+#[test]
+fn attribute_shebang() {
+  let tmp = tmptree! {
+    justfile: "
+[tee('build.log')]
+build:
+  #!/usr/bin/env sh
+  echo out
+",
+  };
+
+  let output = just(tmp.path(), &["build"]);
+
+  assert!(output.status.success());
+  assert_eq!(str::from_utf8(&output.stdout).unwrap(), "out\n");
+  assert_eq!(
+    fs::read_to_string(tmp.path().join("build.log")).unwrap(),
+    "out\n"
+  );
+}
+
+#[test]
+fn attribute_failure() {
+  let tmp = tmptree! {
+    justfile: "
+[tee('build.log')]
+build:
+  @echo out
+  @exit 3
+",
+  };
+
+  let output = just(tmp.path(), &["build"]);
+
+  assert_eq!(output.status.code(), Some(3));
+  assert_eq!(
+    fs::read_to_string(tmp.path().join("build.log")).unwrap(),
+    "out\n"
+  );
+}
+
+#[test]
+fn attribute_with_filter() {
+  let tmp = tmptree! {
+    justfile: "
+[tee('build.log'), filter('tr a-z A-Z')]
+build:
+  @echo out
+",
+  };
+
+  let output = just(tmp.path(), &["build"]);
+
+  assert!(output.status.success());
+  assert_eq!(str::from_utf8(&output.stdout).unwrap(), "OUT\n");
+  assert_eq!(
+    fs::read_to_string(tmp.path().join("build.log")).unwrap(),
+    "out\n"
+  );
+}
+
+#[test]
+fn attribute_quiet() {
+  let tmp = tmptree! {
+    justfile: "
+[tee('build.log')]
+build:
+  echo out
+",
+  };
+
+  let output = just(tmp.path(), &["--quiet", "build"]);
+
+  assert!(output.status.success());
+  assert_eq!(str::from_utf8(&output.stdout).unwrap(), "");
+  assert_eq!(str::from_utf8(&output.stderr).unwrap(), "");
+  assert_eq!(
+    fs::read_to_string(tmp.path().join("build.log")).unwrap(),
+    "out\n"
+  );
+}
+
+#[test]
+fn attribute_dry_run() {
+  let tmp = tmptree! {
+    justfile: "
+[tee('build.log')]
+build:
+  echo out
+",
+  };
+
+  assert!(just(tmp.path(), &["--dry-run", "build"]).status.success());
+  assert!(!tmp.path().join("build.log").exists());
+}
+
+#[test]
+fn directory() {
+  let tmp = tmptree! {
+    justfile: "
+build: test
+  @echo build
+
+test:
+  @echo test
+",
+  };
+
+  let output = just(tmp.path(), &["--tee", "logs", "build"]);
+
+  assert!(output.status.success());
+  assert_eq!(str::from_utf8(&output.stdout).unwrap(), "test\nbuild\n");
+
+  let mut logs = fs::read_dir(tmp.path().join("logs"))
+    .unwrap()
+    .map(|entry| entry.unwrap().path())
+    .collect::<Vec<PathBuf>>();
+
+  logs.sort();
+
+  assert_eq!(logs.len(), 2);
+
+  for (log, recipe) in logs.iter().zip(&["build", "test"]) {
+    let name = log.file_name().unwrap().to_str().unwrap();
+    let timestamp = name
+      .strip_prefix(&format!("{}-", recipe))
+      .and_then(|rest| rest.strip_suffix(".log"))
+      .unwrap();
+    assert_eq!(timestamp.len(), "20210101T000000".len(), "{}", name);
+    assert_eq!(fs::read_to_string(log).unwrap(), format!("{}\n", recipe));
+  }
+}
+
+#[test]
+fn open_failure() {
+  let tmp = tmptree! {
+    justfile: "
+[tee('build.log/nested')]
+build:
+  @echo out
+",
+    "build.log": "",
+  };
+
+  let output = just(tmp.path(), &["build"]);
+
+  assert!(!output.status.success());
+  assert!(str::from_utf8(&output.stderr)
+    .unwrap()
+    .starts_with("error: Failed to open log file `"));
+  assert_eq!(str::from_utf8(&output.stdout).unwrap(), "");
+}
@@ -0,0 +1,93 @@
+use crate::common::*;
+
+test! {
+  name: flag,
+  justfile: "
+    foo:
+      echo foo
+      @echo bar
+  ",
+  args: ("--timestamps", "--timestamp-format", "time"),
+  stdout: "foo\nbar\n",
+  stderr: "[time] echo foo\n",
+}
+
+test! {
+  name: setting,
+  justfile: "
+    set timestamps
+
+    foo:
+      echo foo
+  ",
+  args: ("--timestamp-format", "%%"),
+  stdout: "foo\n",
+  stderr: "[%] echo foo\n",
+}
+
+test! {
+  name: disabled_by_default,
+  justfile: "
+    foo:
+      echo foo
+  ",
+  args: ("--timestamp-format", "time"),
+  stdout: "foo\n",
+  stderr: "echo foo\n",
+}
+
+test! {
+  name: dry_run,
+  justfile: "
+    foo:
+      #!/bin/sh
+      echo foo
+  ",
+  args: ("--timestamps", "--timestamp-format", "time", "--dry-run"),
+  stderr: "[time] #!/bin/sh\n[time] echo foo\n",
+}
+
+test! {
+  name: invalid_format,
+  justfile: "
+    foo:
+      echo foo
+  ",
+  args: ("--timestamps", "--timestamp-format", "%Q"),
+  stderr: "error: Invalid `--timestamp-format` `%Q`: invalid format specifier `%Q`\n",
+  status: EXIT_FAILURE,
+}
+
+#[test]
+fn default_format() {
+  let tmp = tmptree! {
+    justfile: "foo:\n  echo foo",
+  };
+
+  let output = Command::new(executable_path("just"))
+    .current_dir(tmp.path())
+    .arg("--timestamps")
+    .output()
+    .unwrap();
+
+  assert!(output.status.success());
+
+  let stderr = str::from_utf8(&output.stderr).unwrap();
+  let (timestamp, line) = stderr.split_at(11);
+
+  assert_eq!(line, "echo foo\n");
+  assert!(
+    timestamp.starts_with('[')
+      && timestamp.ends_with("] ")
+      && timestamp[1..9]
+        .chars()
+        .enumerate()
+        .all(|(i, c)| if i % 3 == 2 {
+          c == ':'
+        } else {
+          c.is_ascii_digit()
+        }),
+    "unexpected timestamp: {:?}",
+    timestamp
+  );
+}
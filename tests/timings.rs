@@ -0,0 +1,79 @@
+use crate::common::*;
+
+/// Run just in `tmp` with `args`, returning its exit status and stderr, with
+/// durations and their padding replaced by ` N`
+fn timings(tmp: &Path, args: &[&str]) -> (Option<i32>, String) {
+  let output = just(tmp, args);
+
+  let stderr = regex::Regex::new(r" +\d+\.\d{3}s")
+    .unwrap()
+    .replace_all(str::from_utf8(&output.stderr).unwrap(), " N")
+    .into_owned();
+
+  (output.status.code(), stderr)
+}
+
+#[test]
+fn recipes_and_dependencies() {
+  let tmp = tmptree! {
+    justfile: "build: setup\n  @echo build\n\nsetup:\n  @echo setup\n\ntest name:\n  @echo {{name}}",
+  };
+
+  let (status, stderr) = timings(tmp.path(), &["--timings", "build", "test", "unit"]);
+
+  assert_eq!(status, Some(EXIT_SUCCESS));
+  assert_eq!(
+    stderr,
+    "\
+Recipe     Duration Status
+setup N      0
+build N      0
+test unit N      0
+Total N
+"
+  );
+}
+
+#[test]
+fn failure() {
+  let tmp = tmptree! {
+    justfile: "build:\n  @exit 3",
+  };
+
+  let (status, stderr) = timings(tmp.path(), &["--timings"]);
+
+  assert_eq!(status, Some(3));
+  assert_eq!(
+    stderr,
+    "\
+Recipe  Duration Status
+build N      3
+Total N
+error: Recipe `build` failed on line 2 with exit code 3
+"
+  );
+}
+
+#[test]
+fn disabled_by_default() {
+  let tmp = tmptree! {
+    justfile: "build:\n  @echo build",
+  };
+
+  let (status, stderr) = timings(tmp.path(), &[]);
+
+  assert_eq!(status, Some(EXIT_SUCCESS));
+  assert_eq!(stderr, "");
+}
+
+#[test]
+fn dry_run() {
+  let tmp = tmptree! {
+    justfile: "build:\n  @echo build",
+  };
+
+  let (status, stderr) = timings(tmp.path(), &["--timings", "--dry-run"]);
+
+  assert_eq!(status, Some(EXIT_SUCCESS));
+  assert_eq!(stderr, "echo build\n");
+}
@@ -0,0 +1,111 @@
+use crate::common::*;
+
+test! {
+  name:     error,
+  justfile: "
+    set unknown-recipe := 'error'
+
+    build:
+  ",
+  args:     ("bulid"),
+  stderr:   "error: Justfile does not contain recipe `bulid`.\nDid you mean `build`?\n",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     default,
+  justfile: "
+    set unknown-recipe := 'default'
+
+    run +args:
+      @echo {{args}}
+
+    build:
+      @echo build
+  ",
+  args:     ("lint", "--fix"),
+  stdout:   "lint --fix\n",
+}
+
+test! {
+  name:     default_known_recipe,
+  justfile: "
+    set unknown-recipe := 'default'
+
+    run +args:
+      @echo {{args}}
+
+    build:
+      @echo build
+  ",
+  args:     ("build"),
+  stdout:   "build\n",
+}
+
+test! {
+  name:     default_without_parameters,
+  justfile: "
+    set unknown-recipe := 'default'
+
+    run:
+
+    build:
+  ",
+  args:     ("lint"),
+  stderr:   "error: Justfile does not contain recipe `lint`.\n",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     suggest_run_accepted,
+  justfile: "
+    set unknown-recipe := 'suggest-run'
+
+    build:
+      @echo build
+  ",
+  args:     ("bulid"),
+  stdin:    "y\n",
+  stdout:   "build\n",
+  stderr:   "Justfile does not contain recipe `bulid`. Run `build` instead? (y/N) ",
+}
+
+test! {
+  name:     suggest_run_accepted_yes,
+  justfile: "
+    set unknown-recipe := 'suggest-run'
+
+    build:
+      @echo build
+  ",
+  args:     ("--yes", "bulid"),
+  stdout:   "build\n",
+}
+
+test! {
+  name:     suggest_run_declined,
+  justfile: "
+    set unknown-recipe := 'suggest-run'
+
+    build:
+      @echo build
+  ",
+  args:     ("bulid"),
+  stdin:    "n\n",
+  stderr:   "Justfile does not contain recipe `bulid`. Run `build` instead? (y/N) \
+             error: Justfile does not contain recipe `bulid`.\n\
+             Did you mean `build`?\n",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     suggest_run_no_suggestion,
+  justfile: "
+    set unknown-recipe := 'suggest-run'
+
+    build:
+  ",
+  args:     ("xyzzy"),
+  stderr:   "error: Justfile does not contain recipe `xyzzy`.\n",
+  status:   EXIT_FAILURE,
+}
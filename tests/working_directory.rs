@@ -184,3 +184,54 @@ fn search_dir_parent() -> Result<(), Box<dyn Error>> {
 
   Ok(())
 }
+
+/// Test that recipes with the `[no-cd]` attribute run in the invocation
+/// directory, and that `invocation_directory()` still works
+#[test]
+fn no_cd_attribute() -> Result<(), Box<dyn Error>> {
+  let tmp = tmptree! {
+    justfile: "
+[no-cd]
+linewise:
+  cat data
+  echo {{invocation_directory()}}
+
+[no-cd]
+shebang:
+  #!/usr/bin/env sh
+  cat data
+
+cd:
+  cat data
+",
+    data: "root\n",
+    sub: {
+      data: "sub\n",
+    },
+  };
+
+  let sub = tmp.path().join("sub");
+
+  let run = |recipe: &str| -> Result<String, Box<dyn Error>> {
+    let output = Command::new(executable_path("just"))
+      .current_dir(&sub)
+      .arg(recipe)
+      .output()?;
+
+    if !output.status.success() {
+      eprintln!("{:?}", String::from_utf8_lossy(&output.stderr));
+      panic!();
+    }
+
+    Ok(String::from_utf8(output.stdout)?)
+  };
+
+  assert_eq!(
+    run("linewise")?,
+    format!("sub\n{}\n", sub.canonicalize()?.display())
+  );
+  assert_eq!(run("shebang")?, "sub\n");
+  assert_eq!(run("cd")?, "root\n");
+
+  Ok(())
+}